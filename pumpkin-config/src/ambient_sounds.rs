@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct AmbientSoundsConfig {
+    /// Should the server play the ambient sounds of blocks like nether portals and campfires?
+    /// Vanilla clients already play these on their own, so this is mostly useful for custom ambient sounds
+    pub enabled: bool,
+    /// How many random blocks around every player are checked for an ambient sound each tick
+    pub blocks_per_player: u32,
+}
+
+impl Default for AmbientSoundsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            // The same as Vanilla clients
+            blocks_per_player: 667,
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct AntiXrayConfig {
+    /// Should blocks hidden in stone be sent as stone, so x-ray cheats can't find them?
+    /// Worlds can turn it on and off on their own, this is the default for all of them
+    pub enabled: bool,
+    /// The blocks to hide, they are only hidden while no side of them can be seen
+    pub hidden_blocks: Vec<String>,
+}
+
+impl Default for AntiXrayConfig {
+    fn default() -> Self {
+        let hidden_blocks = [
+            "coal_ore",
+            "deepslate_coal_ore",
+            "iron_ore",
+            "deepslate_iron_ore",
+            "copper_ore",
+            "deepslate_copper_ore",
+            "gold_ore",
+            "deepslate_gold_ore",
+            "redstone_ore",
+            "deepslate_redstone_ore",
+            "emerald_ore",
+            "deepslate_emerald_ore",
+            "lapis_ore",
+            "deepslate_lapis_ore",
+            "diamond_ore",
+            "deepslate_diamond_ore",
+            "nether_gold_ore",
+            "nether_quartz_ore",
+            "ancient_debris",
+        ];
+        Self {
+            enabled: false,
+            hidden_blocks: hidden_blocks.into_iter().map(String::from).collect(),
+        }
+    }
+}
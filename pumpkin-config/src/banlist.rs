@@ -0,0 +1,73 @@
+use std::net::IpAddr;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct BannedPlayerEntry {
+    pub uuid: Uuid,
+    pub name: String,
+    /// Unix timestamp in seconds of when the ban was created
+    pub created: u64,
+    pub source: String,
+    /// Unix timestamp in seconds of when the ban expires, `None` bans forever
+    pub expires: Option<u64>,
+    pub reason: String,
+}
+
+impl BannedPlayerEntry {
+    pub fn new(
+        uuid: Uuid,
+        name: String,
+        created: u64,
+        source: String,
+        expires: Option<u64>,
+        reason: String,
+    ) -> Self {
+        Self {
+            uuid,
+            name,
+            created,
+            source,
+            expires,
+            reason,
+        }
+    }
+
+    pub fn is_expired(&self, now: u64) -> bool {
+        self.expires.is_some_and(|expires| expires <= now)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct BannedIpEntry {
+    pub ip: IpAddr,
+    /// Unix timestamp in seconds of when the ban was created
+    pub created: u64,
+    pub source: String,
+    /// Unix timestamp in seconds of when the ban expires, `None` bans forever
+    pub expires: Option<u64>,
+    pub reason: String,
+}
+
+impl BannedIpEntry {
+    pub fn new(
+        ip: IpAddr,
+        created: u64,
+        source: String,
+        expires: Option<u64>,
+        reason: String,
+    ) -> Self {
+        Self {
+            ip,
+            created,
+            source,
+            expires,
+            reason,
+        }
+    }
+
+    pub fn is_expired(&self, now: u64) -> bool {
+        self.expires.is_some_and(|expires| expires <= now)
+    }
+}
@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct ChatConfig {
+    /// Who receives chat messages
+    pub scope: ChatScope,
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum ChatScope {
+    /// Every player on the server, in all worlds
+    Global,
+    /// Only players within `radius` blocks of the sender
+    Local { radius: f64 },
+}
+
+impl Default for ChatConfig {
+    fn default() -> Self {
+        Self {
+            scope: ChatScope::Global,
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct ChunkCacheConfig {
+    /// How many ticks chunks stay loaded after the last player stopped watching them,
+    /// so players stepping out of a chunk and back in don't load it again
+    pub unload_grace_ticks: u64,
+    /// How many chunks can be loaded at most, 0 disables the limit. Above it chunks nobody watches get unloaded
+    /// before their grace period is over, the ones unwatched the longest first. Watched and spawn chunks always stay loaded
+    pub max_resident_chunks: usize,
+    /// How many entities can keep the chunk they are in loaded at once, e.g. patrolling bosses
+    pub max_chunk_loading_entities: usize,
+}
+
+impl Default for ChunkCacheConfig {
+    fn default() -> Self {
+        Self {
+            unload_grace_ticks: 100,
+            max_resident_chunks: 0,
+            max_chunk_loading_entities: 16,
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct ChunkGenerationConfig {
+    /// How many chunks can be generated at the same time, 0 disables the limit.
+    /// Further chunks are queued, nearer chunks first
+    pub max_concurrent_generations: usize,
+    /// How many chunks can be loaded from disk or generated at the same time, 0 disables the limit.
+    /// Further chunks are queued, nearer chunks first, so one player requesting many chunks can't hold up everyone else
+    pub max_concurrent_loads: usize,
+    /// How long looking up a block waits for its chunk to be loaded or generated, in milliseconds
+    pub block_lookup_timeout_ms: u64,
+    /// How long joining players wait for the chunks around them to be sent before the rest of the world gets sent,
+    /// in milliseconds. 0 to not wait
+    pub join_chunks_timeout_ms: u64,
+    /// How many blocks of a chunk can change in one tick before the whole chunk is sent again instead of every block,
+    /// e.g. when trees or structures populate a chunk players already got. 0 always sends every block on its own
+    pub max_block_updates_per_tick: u32,
+}
+
+impl Default for ChunkGenerationConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrent_generations: 8,
+            max_concurrent_loads: 32,
+            block_lookup_timeout_ms: 30_000,
+            join_chunks_timeout_ms: 0,
+            max_block_updates_per_tick: 64,
+        }
+    }
+}
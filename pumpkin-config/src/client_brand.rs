@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct ClientBrandConfig {
+    /// Should the brand of joining clients be logged, like "vanilla" or "fabric"?
+    pub log: bool,
+    /// Clients whose brand contains one of these, ignoring case, get kicked. E.g. for known cheat clients,
+    /// keep in mind that clients can send any brand they want
+    pub blocked_brands: Vec<String>,
+    /// The message clients with a blocked brand get kicked with
+    pub kick_message: String,
+}
+
+impl Default for ClientBrandConfig {
+    fn default() -> Self {
+        Self {
+            log: true,
+            blocked_brands: Vec::new(),
+            kick_message: "Your client is not allowed on this server".to_string(),
+        }
+    }
+}
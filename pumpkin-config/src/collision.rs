@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct CollisionConfig {
+    /// Should overlapping entities push each other apart?
+    pub enabled: bool,
+    /// How strong entities push each other, Vanilla uses 0.05
+    pub push_strength: f64,
+    /// The maximum horizontal velocity an entity can get from pushes in a single tick
+    pub max_push: f64,
+}
+
+impl Default for CollisionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            push_strength: 0.05,
+            max_push: 0.2,
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use pumpkin_core::PermissionLvl;
 use serde::{Deserialize, Serialize};
 
@@ -10,6 +12,11 @@ pub struct CommandsConfig {
     pub log_console: bool, // TODO: commands...
     /// The op permission level of everyone that is not in the ops file
     pub default_op_level: PermissionLvl,
+    /// How many seconds a player has to wait before using a command again, by command name. Aliases share the
+    /// cooldown of the command they point to
+    pub cooldowns: HashMap<String, u64>,
+    /// Players with at least this op permission level are never on cooldown
+    pub cooldown_bypass_level: PermissionLvl,
 }
 
 impl Default for CommandsConfig {
@@ -18,6 +25,8 @@ impl Default for CommandsConfig {
             use_console: true,
             log_console: true,
             default_op_level: PermissionLvl::Zero,
+            cooldowns: HashMap::new(),
+            cooldown_bypass_level: PermissionLvl::Two,
         }
     }
 }
@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct DeathConfig {
+    /// Should the death message of a player be shown to everyone in their world?
+    pub broadcast_messages: bool,
+    /// The message shown on the death screen and in chat, `{player}` is replaced with the name of the player.
+    /// Leave empty to use the translated Vanilla message of how the player died
+    pub message: String,
+    /// Should players respawn right away instead of seeing the death screen, like the `doImmediateRespawn` gamerule?
+    /// Worlds can turn it on and off on their own, this is the default for all of them
+    pub immediate_respawn: bool,
+}
+
+impl Default for DeathConfig {
+    fn default() -> Self {
+        Self {
+            broadcast_messages: true,
+            message: String::new(),
+            immediate_respawn: false,
+        }
+    }
+}
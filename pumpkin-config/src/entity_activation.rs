@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+/// Mobs further away from every player than their activation range don't run their AI, which saves a lot of CPU on big servers.
+///
+/// All ranges are in blocks, 0 keeps the mobs of the category always active
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct EntityActivationConfig {
+    /// Disabled by default, as Vanilla always ticks every mob in loaded chunks
+    pub enabled: bool,
+    pub monsters: u32,
+    pub animals: u32,
+    pub water: u32,
+    pub villagers: u32,
+    pub flying: u32,
+    /// Everything else, e.g. golems
+    pub misc: u32,
+}
+
+impl Default for EntityActivationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            monsters: 32,
+            animals: 32,
+            water: 16,
+            villagers: 32,
+            flying: 48,
+            misc: 16,
+        }
+    }
+}
@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct EntityLimitConfig {
+    /// Should players be stopped from spawning more entities once they reach the limit?
+    /// Protects the server from players spamming spawn eggs or projectiles
+    pub enabled: bool,
+    /// How many entities spawned by a player may exist at the same time
+    pub max_entities_per_player: u32,
+}
+
+impl Default for EntityLimitConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_entities_per_player: 200,
+        }
+    }
+}
@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct ExperienceConfig {
+    /// Should mined ores and killed mobs drop experience orbs? Like the `doTileDrops` and `doMobLoot` gamerules
+    pub drop_orbs: bool,
+}
+
+impl Default for ExperienceConfig {
+    fn default() -> Self {
+        Self { drop_orbs: true }
+    }
+}
@@ -16,19 +16,55 @@ pub mod networking;
 
 pub mod resource_pack;
 
+pub use ambient_sounds::AmbientSoundsConfig;
+pub use anti_xray::AntiXrayConfig;
+pub use chat::{ChatConfig, ChatScope};
+pub use chunk_cache::ChunkCacheConfig;
+pub use chunk_generation::ChunkGenerationConfig;
+pub use client_brand::ClientBrandConfig;
+pub use collision::CollisionConfig;
 pub use commands::CommandsConfig;
+pub use death::DeathConfig;
+pub use entity_activation::EntityActivationConfig;
+pub use entity_limit::EntityLimitConfig;
+pub use experience::ExperienceConfig;
+pub use lighting::LightingConfig;
+pub use lightning::LightningConfig;
+pub use movement::MovementConfig;
 pub use networking::auth::AuthenticationConfig;
 pub use networking::compression::CompressionConfig;
 pub use networking::lan_broadcast::LANBroadcastConfig;
 pub use networking::rcon::RCONConfig;
 pub use pvp::PVPConfig;
+pub use rate_limit::RateLimitConfig;
 pub use server_links::ServerLinksConfig;
+pub use snow::SnowConfig;
+pub use velocity::VelocityConfig;
 
+mod ambient_sounds;
+mod anti_xray;
+mod chat;
+mod chunk_cache;
+mod chunk_generation;
+mod client_brand;
+mod collision;
 mod commands;
+mod death;
+mod entity_activation;
+mod entity_limit;
+mod experience;
+mod lighting;
+mod lightning;
 
+pub mod banlist;
+mod movement;
 pub mod op;
 mod pvp;
+mod rate_limit;
 mod server_links;
+mod snow;
+mod velocity;
+pub mod whitelist;
 
 use networking::NetworkingConfig;
 use resource_pack::ResourcePackConfig;
@@ -53,7 +89,24 @@ pub struct AdvancedConfiguration {
     pub networking: NetworkingConfig,
     pub commands: CommandsConfig,
     pub pvp: PVPConfig,
+    pub chat: ChatConfig,
+    pub death: DeathConfig,
+    pub experience: ExperienceConfig,
+    pub collision: CollisionConfig,
+    pub rate_limit: RateLimitConfig,
+    pub movement: MovementConfig,
+    pub chunk_generation: ChunkGenerationConfig,
+    pub chunk_cache: ChunkCacheConfig,
+    pub lighting: LightingConfig,
+    pub ambient_sounds: AmbientSoundsConfig,
     pub server_links: ServerLinksConfig,
+    pub entity_activation: EntityActivationConfig,
+    pub anti_xray: AntiXrayConfig,
+    pub entity_limit: EntityLimitConfig,
+    pub lightning: LightningConfig,
+    pub snow: SnowConfig,
+    pub client_brand: ClientBrandConfig,
+    pub velocity: VelocityConfig,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -65,6 +118,14 @@ pub struct BasicConfiguration {
     pub seed: String,
     /// The maximum number of players allowed on the server. Specifying `0` disables the limit.
     pub max_players: u32,
+    /// How many of the `max_players` slots are kept free for operators.
+    pub reserved_slots: u32,
+    /// Whether only whitelisted players are allowed to join.
+    pub whitelist_enabled: bool,
+    /// The message shown to players which are not whitelisted.
+    pub whitelist_message: String,
+    /// Whether operators can join even if they are not whitelisted.
+    pub whitelist_ops_bypass: bool,
     /// The maximum view distance for players.
     pub view_distance: NonZeroU8,
     /// The maximum simulated view distance.
@@ -77,6 +138,8 @@ pub struct BasicConfiguration {
     pub allow_nether: bool,
     /// Whether the server is in hardcore mode.
     pub hardcore: bool,
+    /// Whether the debug screen hides coordinates and other details from players.
+    pub reduced_debug_info: bool,
     /// Whether online mode is enabled. Requires valid Minecraft accounts.
     pub online_mode: bool,
     /// Whether packet encryption is enabled. Required when online mode is enabled.
@@ -86,6 +149,14 @@ pub struct BasicConfiguration {
     pub tps: f32,
     /// The default game mode for players.
     pub default_gamemode: GameMode,
+    /// Whether players always get the default game mode when joining, instead of keeping their own.
+    pub force_gamemode: bool,
+    /// How many blocks around the world spawn new players get scattered. `0` spawns everyone on the same block.
+    pub spawn_radius: u32,
+    /// How many chunks around the world spawn are kept loaded even without players. Specifying `0` disables it.
+    pub spawn_chunk_radius: u8,
+    /// How many ticks between automatic world saves. Specifying `0` disables autosaving.
+    pub autosave_interval_ticks: u64,
     /// Whether to remove IPs from logs or not
     pub scrub_ips: bool,
     /// Whether to use a server favicon
@@ -100,17 +171,26 @@ impl Default for BasicConfiguration {
             server_address: SocketAddr::new(Ipv4Addr::new(0, 0, 0, 0).into(), 25565),
             seed: "".to_string(),
             max_players: 100000,
+            reserved_slots: 0,
+            whitelist_enabled: false,
+            whitelist_message: "You are not whitelisted on this server!".to_string(),
+            whitelist_ops_bypass: true,
             view_distance: NonZeroU8::new(10).unwrap(),
             simulation_distance: NonZeroU8::new(10).unwrap(),
             default_difficulty: Difficulty::Normal,
             op_permission_level: PermissionLvl::Four,
             allow_nether: true,
             hardcore: false,
+            reduced_debug_info: false,
             online_mode: true,
             encryption: true,
             motd: "A Blazing fast Pumpkin Server!".to_string(),
             tps: 20.0,
             default_gamemode: GameMode::Survival,
+            force_gamemode: false,
+            spawn_radius: 10,
+            spawn_chunk_radius: 2,
+            autosave_interval_ticks: 6000,
             scrub_ips: true,
             use_favicon: true,
             favicon_path: "icon.png".to_string(),
@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct LightingConfig {
+    /// Should the server spread the light of blocks which emit light, e.g. lit furnaces and redstone lamps,
+    /// when they are placed or turned on and off? Clients always calculate the light they show on their own
+    pub block_light: bool,
+}
+
+impl Default for LightingConfig {
+    fn default() -> Self {
+        Self { block_light: true }
+    }
+}
@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct LightningConfig {
+    /// Should lightning strike randomly near players during thunderstorms?
+    pub enabled: bool,
+    /// Should lightning set the block it strikes on fire? Like Vanilla, it never does on Peaceful and Easy
+    pub fire: bool,
+    /// The damage entities close to a strike take
+    pub damage: f32,
+}
+
+impl Default for LightningConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            fire: true,
+            damage: 5.0,
+        }
+    }
+}
@@ -9,6 +9,8 @@ pub struct LoggingConfig {
     pub threads: bool,
     pub color: bool,
     pub timestamp: bool,
+    /// Warn with a breakdown of the tick phases when a world tick takes longer than this many milliseconds, `0` disables the warning
+    pub slow_tick_threshold_ms: u64,
 }
 
 impl Default for LoggingConfig {
@@ -20,6 +22,7 @@ impl Default for LoggingConfig {
             threads: true,
             color: true,
             timestamp: true,
+            slow_tick_threshold_ms: 50,
         }
     }
 }
@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct MovementConfig {
+    /// Should we kick players who float in the air for too long without being allowed to fly?
+    /// Disabled by default, as we don't check for blocks around the player yet, so climbing and swimming would count as floating
+    pub kick_flying: bool,
+    /// How many movements a player can float in the air before getting kicked, Vanilla allows 80 ticks
+    pub max_floating_ticks: u32,
+    /// For how many ticks after a teleport players are exempt from flight checks, as they may still be in the air
+    pub teleport_grace_ticks: u32,
+    /// Should joining players float in place until the chunk they stand in was sent?
+    /// Otherwise they can fall through freshly generated terrain which didn't reach them yet
+    pub hold_until_chunk_sent: bool,
+    /// How long players float at most while waiting for their chunk, in milliseconds
+    pub chunk_hold_timeout_ms: u64,
+    /// Should we move players back who moved further than they can in one movement?
+    pub check_speed: bool,
+    /// How many blocks players can walk in one movement, like Vanilla's "moved too quickly" check.
+    /// Sprinting allows 30% more, sneaking 70% less and gliding with an elytra a lot more
+    pub max_speed: f64,
+}
+
+impl Default for MovementConfig {
+    fn default() -> Self {
+        Self {
+            kick_flying: false,
+            max_floating_ticks: 80,
+            teleport_grace_ticks: 20,
+            hold_until_chunk_sent: true,
+            chunk_hold_timeout_ms: 10_000,
+            check_speed: true,
+            max_speed: 10.0,
+        }
+    }
+}
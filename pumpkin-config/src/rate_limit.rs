@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct RateLimitConfig {
+    /// Should we drop actions of players who send too many of them?
+    pub enabled: bool,
+    /// How many block interactions (breaking, placing, using) a player can do in a single tick
+    pub max_block_interactions_per_tick: u32,
+    /// How much chat spam a player can build up before messages are dropped.
+    /// Every chat message adds 20 and every tick removes 1, like Vanilla does
+    pub chat_spam_threshold: u32,
+    /// How many dropped actions a player can build up before getting kicked, 0 disables kicking.
+    /// Every tick removes 1 again
+    pub kick_threshold: u32,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_block_interactions_per_tick: 10,
+            chat_spam_threshold: 200,
+            kick_threshold: 200,
+        }
+    }
+}
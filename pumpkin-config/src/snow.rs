@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct SnowConfig {
+    /// Should snow form on the ground and water freeze in cold biomes?
+    pub enabled: bool,
+    /// How many snow layers can pile up during snowfall, like the `snowAccumulationHeight` gamerule
+    pub max_layers: u8,
+}
+
+impl Default for SnowConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_layers: 1,
+        }
+    }
+}
@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct VelocityConfig {
+    /// The fastest entities can be pushed, in blocks per tick. Faster velocities, e.g. from bugs or exploits,
+    /// get slowed down to this speed. Clients can't be sent more than 3.9 along each axis anyway
+    pub max_velocity: f64,
+}
+
+impl Default for VelocityConfig {
+    fn default() -> Self {
+        Self { max_velocity: 10.0 }
+    }
+}
@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct WhitelistEntry {
+    pub uuid: Uuid,
+    pub name: String,
+}
+
+impl WhitelistEntry {
+    pub fn new(uuid: Uuid, name: String) -> Self {
+        Self { uuid, name }
+    }
+}
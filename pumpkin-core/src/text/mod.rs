@@ -42,6 +42,21 @@ impl TextComponent {
         }
     }
 
+    /// Text the client translates into its language, `with` fills in the placeholders of the translation
+    pub fn translate<K>(key: K, with: Vec<TextComponent>) -> Self
+    where
+        K: Into<Cow<'static, str>>,
+    {
+        Self {
+            content: TextContent::Translate {
+                translate: key.into(),
+                with,
+            },
+            style: Style::default(),
+            extra: vec![],
+        }
+    }
+
     pub fn add_child(mut self, child: TextComponent) -> Self {
         self.extra.push(child);
         self
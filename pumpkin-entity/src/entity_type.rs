@@ -1,5 +1,7 @@
+use pumpkin_macros::entity_type_from_id;
+
 // TODO make this dynamic
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 #[repr(i32)]
 pub enum EntityType {
     AcaciaBoat = 0,
@@ -152,3 +154,12 @@ pub enum EntityType {
     Player = 147,
     FishingBobber = 148,
 }
+
+impl EntityType {
+    /// The entity type with the id from the entity registry, `None` if there is none.
+    /// Players aren't in our registry dump, so their id gives `None` as well
+    #[must_use]
+    pub const fn from_id(id: u16) -> Option<Self> {
+        entity_type_from_id!(id)
+    }
+}
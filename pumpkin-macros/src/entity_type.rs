@@ -0,0 +1,49 @@
+use std::{collections::HashMap, sync::LazyLock};
+
+use proc_macro::TokenStream;
+use proc_macro2::{Ident, Span};
+use quote::quote;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct Entity {
+    id: u16,
+}
+
+static ENTITIES: LazyLock<HashMap<String, Entity>> = LazyLock::new(|| {
+    serde_json::from_str(include_str!("../../assets/entities.json"))
+        .expect("Could not parse entities.json registry.")
+});
+
+/// Turns a registry name like `zombified_piglin` into its `EntityType` variant `ZombifiedPiglin`
+fn variant_name(name: &str) -> String {
+    name.split('_')
+        .map(|part| {
+            let mut chars = part.chars();
+            chars.next().map_or_else(String::new, |first| {
+                first.to_uppercase().chain(chars).collect()
+            })
+        })
+        .collect()
+}
+
+/// A match of the given id on all entity types in the registry, evaluating to `Option<EntityType>`
+pub(crate) fn entity_type_from_id_impl(item: TokenStream) -> TokenStream {
+    let id: proc_macro2::TokenStream = item.into();
+
+    let mut entities: Vec<_> = ENTITIES.iter().collect();
+    entities.sort_by_key(|(_, entity)| entity.id);
+    let arms = entities.into_iter().map(|(name, entity)| {
+        let entity_id = entity.id;
+        let variant = Ident::new(&variant_name(name), Span::call_site());
+        quote! { #entity_id => Some(Self::#variant), }
+    });
+
+    quote! {
+        match #id {
+            #(#arms)*
+            _ => None,
+        }
+    }
+    .into()
+}
@@ -90,3 +90,9 @@ mod block_state;
 pub fn block_state(item: TokenStream) -> TokenStream {
     block_state::block_state_impl(item)
 }
+
+mod entity_type;
+#[proc_macro]
+pub fn entity_type_from_id(item: TokenStream) -> TokenStream {
+    entity_type::entity_type_from_id_impl(item)
+}
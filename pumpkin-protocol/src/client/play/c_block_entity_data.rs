@@ -0,0 +1,33 @@
+use bytes::BufMut;
+use pumpkin_core::math::position::WorldPosition;
+use pumpkin_macros::client_packet;
+use pumpkin_nbt::{compound::NbtCompound, COMPOUND_ID};
+
+use crate::{bytebuf::ByteBufMut, ClientPacket, VarInt};
+
+#[client_packet("play:block_entity_data")]
+pub struct CBlockEntityData<'a> {
+    location: WorldPosition,
+    block_entity_type: VarInt,
+    nbt: &'a NbtCompound,
+}
+
+impl<'a> CBlockEntityData<'a> {
+    pub fn new(location: WorldPosition, block_entity_type: VarInt, nbt: &'a NbtCompound) -> Self {
+        Self {
+            location,
+            block_entity_type,
+            nbt,
+        }
+    }
+}
+
+impl ClientPacket for CBlockEntityData<'_> {
+    fn write(&self, bytebuf: &mut impl BufMut) {
+        bytebuf.put_i64(self.location.as_long());
+        bytebuf.put_var_int(&self.block_entity_type);
+        // Network NBT has no name for the root compound
+        bytebuf.put_u8(COMPOUND_ID);
+        bytebuf.put_slice(&self.nbt.serialize_content());
+    }
+}
@@ -33,3 +33,30 @@ impl<T> Metadata<T> {
         Self { index, typ, value }
     }
 }
+
+/// Several metadata entries of an entity in a single packet
+#[derive(Serialize)]
+#[client_packet("play:set_entity_data")]
+pub struct CSetEntityMetadataBatch<'a> {
+    entity_id: VarInt,
+    metadata: &'a [Metadata<MetadataValue>],
+    end: u8,
+}
+
+impl<'a> CSetEntityMetadataBatch<'a> {
+    pub fn new(entity_id: VarInt, metadata: &'a [Metadata<MetadataValue>]) -> Self {
+        Self {
+            entity_id,
+            metadata,
+            end: 255,
+        }
+    }
+}
+
+/// The value of a metadata entry in a [`CSetEntityMetadataBatch`], entries of different types can be mixed
+#[derive(Serialize, Clone)]
+#[serde(untagged)]
+pub enum MetadataValue {
+    Byte(i8),
+    VarInt(VarInt),
+}
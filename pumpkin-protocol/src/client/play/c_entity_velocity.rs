@@ -3,6 +3,15 @@ use serde::Serialize;
 
 use crate::VarInt;
 
+/// The fastest velocity along each axis the packets can hold, as they send it in 1/8000 blocks per tick in a short
+pub const MAX_PACKET_VELOCITY: f64 = 3.9;
+
+/// Converts the velocity in blocks per tick to the unit of the packets, clamped so that it can't wrap around
+#[must_use]
+pub fn encode_velocity(velocity: f64) -> i16 {
+    (velocity.clamp(-MAX_PACKET_VELOCITY, MAX_PACKET_VELOCITY) * 8000.0) as i16
+}
+
 #[derive(Serialize)]
 #[client_packet("play:set_entity_motion")]
 pub struct CEntityVelocity<'a> {
@@ -16,9 +25,9 @@ impl<'a> CEntityVelocity<'a> {
     pub fn new(entity_id: &'a VarInt, velocity_x: f64, velocity_y: f64, velocity_z: f64) -> Self {
         Self {
             entity_id,
-            velocity_x: (velocity_x.clamp(-3.9, 3.9) * 8000.0) as i16,
-            velocity_y: (velocity_y.clamp(-3.9, 3.9) * 8000.0) as i16,
-            velocity_z: (velocity_z.clamp(-3.9, 3.9) * 8000.0) as i16,
+            velocity_x: encode_velocity(velocity_x),
+            velocity_y: encode_velocity(velocity_y),
+            velocity_z: encode_velocity(velocity_z),
         }
     }
 }
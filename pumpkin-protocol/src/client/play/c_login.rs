@@ -25,8 +25,11 @@ pub struct CLogin<'a> {
     game_mode: u8,
     previous_gamemode: i8,
     debug: bool,
+    /// Superflat worlds have their void fog and horizon at the bottom of the world instead of at sea level
     is_flat: bool,
+    /// Where the player died last, used by recovery compasses
     death_dimension_name: Option<(Identifier, WorldPosition)>,
+    /// Ticks until the player can use a portal again
     portal_cooldown: VarInt,
     sealevel: VarInt,
     enforce_secure_chat: bool,
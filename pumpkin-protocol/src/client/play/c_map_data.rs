@@ -0,0 +1,52 @@
+use bytes::BufMut;
+use pumpkin_macros::client_packet;
+
+use crate::{bytebuf::ByteBufMut, ClientPacket, VarInt};
+
+/// How many pixels a map is wide and high
+pub const MAP_SIZE: usize = 128;
+
+/// The image of a map, always the whole map at once.
+/// TODO: Icons and partial updates
+#[client_packet("play:map_item_data")]
+pub struct CMapData<'a> {
+    map_id: VarInt,
+    scale: i8,
+    locked: bool,
+    /// The color of every pixel row by row, `None` to leave the image as it is
+    colors: Option<&'a [u8]>,
+}
+
+impl<'a> CMapData<'a> {
+    pub fn new(map_id: VarInt, scale: i8, locked: bool, colors: Option<&'a [u8]>) -> Self {
+        Self {
+            map_id,
+            scale,
+            locked,
+            colors,
+        }
+    }
+}
+
+impl ClientPacket for CMapData<'_> {
+    fn write(&self, bytebuf: &mut impl BufMut) {
+        bytebuf.put_var_int(&self.map_id);
+        bytebuf.put_i8(self.scale);
+        bytebuf.put_bool(self.locked);
+        // No icons
+        bytebuf.put_bool(false);
+        match self.colors {
+            Some(colors) => {
+                // Columns, rows and the corner the colors start at
+                bytebuf.put_u8(MAP_SIZE as u8);
+                bytebuf.put_u8(MAP_SIZE as u8);
+                bytebuf.put_u8(0);
+                bytebuf.put_u8(0);
+                bytebuf.put_var_int(&VarInt(colors.len() as i32));
+                bytebuf.put_slice(colors);
+            }
+            // No columns means there are no colors
+            None => bytebuf.put_u8(0),
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use bytes::BufMut;
+use pumpkin_core::math::vector3::Vector3;
+use pumpkin_macros::client_packet;
+
+use crate::{
+    bytebuf::ByteBufMut,
+    codec::{var_long::VarLong, Codec},
+    ClientPacket,
+};
+
+/// Updates multiple blocks within a single chunk section at once
+#[client_packet("play:section_blocks_update")]
+pub struct CMultiBlockUpdate<'a> {
+    /// The position of the chunk section, in sections
+    section: Vector3<i32>,
+    /// The position inside of the section (0-15) and the new block state id of each block
+    blocks: &'a [(Vector3<u8>, u16)],
+}
+
+impl<'a> CMultiBlockUpdate<'a> {
+    pub fn new(section: Vector3<i32>, blocks: &'a [(Vector3<u8>, u16)]) -> Self {
+        Self { section, blocks }
+    }
+}
+
+impl ClientPacket for CMultiBlockUpdate<'_> {
+    fn write(&self, bytebuf: &mut impl BufMut) {
+        bytebuf.put_i64(
+            ((i64::from(self.section.x) & 0x3F_FFFF) << 42)
+                | ((i64::from(self.section.z) & 0x3F_FFFF) << 20)
+                | (i64::from(self.section.y) & 0xF_FFFF),
+        );
+        bytebuf.put_list::<(Vector3<u8>, u16)>(self.blocks, |p, (position, state_id)| {
+            let block = (i64::from(*state_id) << 12)
+                | (i64::from(position.x) << 8)
+                | (i64::from(position.z) << 4)
+                | i64::from(position.y);
+            VarLong(block).encode(p);
+        });
+    }
+}
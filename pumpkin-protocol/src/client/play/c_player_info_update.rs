@@ -42,7 +42,7 @@ impl ClientPacket for CPlayerInfoUpdate<'_> {
                     PlayerAction::UpdateListed(listed) => p.put_bool(*listed),
                     PlayerAction::UpdateLatency(_) => todo!(),
                     PlayerAction::UpdateDisplayName(_) => todo!(),
-                    PlayerAction::UpdateListOrder => todo!(),
+                    PlayerAction::UpdateListOrder(order) => p.put_var_int(order),
                 }
             }
         });
@@ -0,0 +1,17 @@
+use pumpkin_macros::client_packet;
+use serde::Serialize;
+
+use crate::codec::identifier::Identifier;
+
+#[derive(Serialize)]
+#[client_packet("play:custom_payload")]
+pub struct CPlayPluginMessage<'a> {
+    channel: &'a Identifier,
+    data: &'a [u8],
+}
+
+impl<'a> CPlayPluginMessage<'a> {
+    pub fn new(channel: &'a Identifier, data: &'a [u8]) -> Self {
+        Self { channel, data }
+    }
+}
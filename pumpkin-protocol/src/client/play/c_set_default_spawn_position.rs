@@ -0,0 +1,18 @@
+use pumpkin_core::math::position::WorldPosition;
+
+use pumpkin_macros::client_packet;
+use serde::Serialize;
+
+/// Sets where the compass of the client points to
+#[derive(Serialize)]
+#[client_packet("play:set_default_spawn_position")]
+pub struct CSetDefaultSpawnPosition {
+    location: WorldPosition,
+    angle: f32,
+}
+
+impl CSetDefaultSpawnPosition {
+    pub fn new(location: WorldPosition, angle: f32) -> Self {
+        Self { location, angle }
+    }
+}
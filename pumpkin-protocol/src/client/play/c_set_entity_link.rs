@@ -0,0 +1,20 @@
+use pumpkin_macros::client_packet;
+use serde::Serialize;
+
+/// Attaches a leash from one entity to another
+#[derive(Serialize)]
+#[client_packet("play:set_entity_link")]
+pub struct CSetEntityLink {
+    attached_entity_id: i32,
+    /// `-1` to remove the leash
+    holding_entity_id: i32,
+}
+
+impl CSetEntityLink {
+    pub fn new(attached_entity_id: i32, holding_entity_id: Option<i32>) -> Self {
+        Self {
+            attached_entity_id,
+            holding_entity_id: holding_entity_id.unwrap_or(-1),
+        }
+    }
+}
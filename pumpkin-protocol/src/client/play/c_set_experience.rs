@@ -0,0 +1,23 @@
+use pumpkin_macros::client_packet;
+use serde::Serialize;
+
+use crate::VarInt;
+
+#[derive(Serialize)]
+#[client_packet("play:set_experience")]
+pub struct CSetExperience {
+    /// The progress towards the next level, between `0.0` and `1.0`
+    experience_bar: f32,
+    level: VarInt,
+    total_experience: VarInt,
+}
+
+impl CSetExperience {
+    pub fn new(experience_bar: f32, level: VarInt, total_experience: VarInt) -> Self {
+        Self {
+            experience_bar,
+            level,
+            total_experience,
+        }
+    }
+}
@@ -3,6 +3,8 @@ use serde::Serialize;
 
 use crate::VarInt;
 
+use super::encode_velocity;
+
 #[derive(Serialize)]
 #[client_packet("play:add_entity")]
 pub struct CSpawnEntity {
@@ -50,9 +52,9 @@ impl CSpawnEntity {
             yaw: (yaw * 256.0 / 360.0).floor() as u8,
             head_yaw: (head_yaw * 256.0 / 360.0).floor() as u8,
             data,
-            velocity_x: (velocity_x.clamp(-3.9, 3.9) * 8000.0) as i16,
-            velocity_y: (velocity_y.clamp(-3.9, 3.9) * 8000.0) as i16,
-            velocity_z: (velocity_z.clamp(-3.9, 3.9) * 8000.0) as i16,
+            velocity_x: encode_velocity(f64::from(velocity_x)),
+            velocity_y: encode_velocity(f64::from(velocity_y)),
+            velocity_z: encode_velocity(f64::from(velocity_z)),
         }
     }
 }
@@ -0,0 +1,28 @@
+use pumpkin_core::math::vector3::Vector3;
+use pumpkin_macros::client_packet;
+use serde::Serialize;
+
+use crate::VarInt;
+
+#[derive(Serialize)]
+#[client_packet("play:add_experience_orb")]
+pub struct CSpawnExperienceOrb {
+    entity_id: VarInt,
+    x: f64,
+    y: f64,
+    z: f64,
+    /// How much experience the orb gives, the client only uses it to pick the size of the orb
+    count: i16,
+}
+
+impl CSpawnExperienceOrb {
+    pub fn new(entity_id: VarInt, position: Vector3<f64>, count: i16) -> Self {
+        Self {
+            entity_id,
+            x: position.x,
+            y: position.y,
+            z: position.z,
+            count,
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use pumpkin_macros::client_packet;
+use serde::Serialize;
+
+use crate::VarInt;
+
+/// Plays the animation of an entity flying into the one collecting it, used for items and experience orbs.
+/// The collected entity still has to be removed
+#[derive(Serialize)]
+#[client_packet("play:take_item_entity")]
+pub struct CTakeItemEntity {
+    collected_entity_id: VarInt,
+    collector_entity_id: VarInt,
+    item_count: VarInt,
+}
+
+impl CTakeItemEntity {
+    pub fn new(
+        collected_entity_id: VarInt,
+        collector_entity_id: VarInt,
+        item_count: VarInt,
+    ) -> Self {
+        Self {
+            collected_entity_id,
+            collector_entity_id,
+            item_count,
+        }
+    }
+}
@@ -2,6 +2,7 @@ mod bossevent_action;
 mod c_acknowledge_block;
 mod c_actionbar;
 mod c_block_destroy_stage;
+mod c_block_entity_data;
 mod c_block_event;
 mod c_block_update;
 mod c_boss_event;
@@ -28,6 +29,8 @@ mod c_initialize_world_border;
 mod c_keep_alive;
 mod c_level_event;
 mod c_login;
+mod c_map_data;
+mod c_multi_block_update;
 mod c_open_screen;
 mod c_particle;
 mod c_ping_response;
@@ -37,8 +40,10 @@ mod c_player_chat_message;
 mod c_player_info_update;
 mod c_player_position;
 mod c_player_remove;
+mod c_plugin_message;
 mod c_remove_entities;
 mod c_reset_score;
+mod c_resource_pack_push;
 mod c_respawn;
 mod c_server_links;
 mod c_set_border_center;
@@ -49,15 +54,20 @@ mod c_set_border_warning_distance;
 mod c_set_container_content;
 mod c_set_container_property;
 mod c_set_container_slot;
+mod c_set_default_spawn_position;
+mod c_set_entity_link;
+mod c_set_experience;
 mod c_set_health;
 mod c_set_held_item;
 mod c_set_time;
 mod c_set_title;
 mod c_sound_effect;
 mod c_spawn_entity;
+mod c_spawn_experience_orb;
 mod c_store_cookie;
 mod c_subtitle;
 mod c_system_chat_message;
+mod c_take_item_entity;
 mod c_teleport_entity;
 mod c_transfer;
 mod c_unload_chunk;
@@ -73,6 +83,7 @@ pub use bossevent_action::*;
 pub use c_acknowledge_block::*;
 pub use c_actionbar::*;
 pub use c_block_destroy_stage::*;
+pub use c_block_entity_data::*;
 pub use c_block_event::*;
 pub use c_block_update::*;
 pub use c_boss_event::*;
@@ -99,6 +110,8 @@ pub use c_initialize_world_border::*;
 pub use c_keep_alive::*;
 pub use c_level_event::*;
 pub use c_login::*;
+pub use c_map_data::*;
+pub use c_multi_block_update::*;
 pub use c_open_screen::*;
 pub use c_particle::*;
 pub use c_ping_response::*;
@@ -108,8 +121,10 @@ pub use c_player_chat_message::*;
 pub use c_player_info_update::*;
 pub use c_player_position::*;
 pub use c_player_remove::*;
+pub use c_plugin_message::*;
 pub use c_remove_entities::*;
 pub use c_reset_score::*;
+pub use c_resource_pack_push::*;
 pub use c_respawn::*;
 pub use c_server_links::*;
 pub use c_set_border_center::*;
@@ -120,15 +135,20 @@ pub use c_set_border_warning_distance::*;
 pub use c_set_container_content::*;
 pub use c_set_container_property::*;
 pub use c_set_container_slot::*;
+pub use c_set_default_spawn_position::*;
+pub use c_set_entity_link::*;
+pub use c_set_experience::*;
 pub use c_set_health::*;
 pub use c_set_held_item::*;
 pub use c_set_time::*;
 pub use c_set_title::*;
 pub use c_sound_effect::*;
 pub use c_spawn_entity::*;
+pub use c_spawn_experience_orb::*;
 pub use c_store_cookie::*;
 pub use c_subtitle::*;
 pub use c_system_chat_message::*;
+pub use c_take_item_entity::*;
 pub use c_teleport_entity::*;
 pub use c_transfer::*;
 pub use c_unload_chunk::*;
@@ -12,5 +12,6 @@ pub enum PlayerAction<'a> {
     UpdateListed(bool),
     UpdateLatency(u8),
     UpdateDisplayName(u8),
-    UpdateListOrder,
+    /// Players with a higher order are listed first
+    UpdateListOrder(VarInt),
 }
@@ -1,5 +1,5 @@
 use aes::cipher::{generic_array::GenericArray, BlockEncryptMut, BlockSizeUser, KeyIvInit};
-use bytes::{BufMut, BytesMut};
+use bytes::{BufMut, Bytes, BytesMut};
 use thiserror::Error;
 
 use libdeflater::{CompressionLvl, Compressor};
@@ -59,6 +59,20 @@ impl PacketEncoder {
         VarInt(P::PACKET_ID).encode(&mut self.buf);
         // Now write the packet into an empty buffer
         packet.write(&mut self.buf);
+        self.frame_packet(start_len)
+    }
+
+    /// Appends a packet which was already serialized by [`serialize_packet`], compressing it with this encoder's threshold.
+    ///
+    /// This allows to serialize a packet once and send it to many connections
+    pub fn append_serialized(&mut self, data: &[u8]) -> Result<(), PacketEncodeError> {
+        let start_len = self.buf.len();
+        self.buf.extend_from_slice(data);
+        self.frame_packet(start_len)
+    }
+
+    /// Adds the length prefixes to the packet ID and data starting at `start_len` and compresses them when needed
+    fn frame_packet(&mut self, start_len: usize) -> Result<(), PacketEncodeError> {
         let data_len = self.buf.len() - start_len;
 
         if let Some((compressor, compression_threshold)) = &mut self.compression {
@@ -199,6 +213,14 @@ impl PacketEncoder {
     }
 }
 
+/// Writes the packet ID and data without any framing, see [`PacketEncoder::append_serialized`]
+pub fn serialize_packet<P: ClientPacket>(packet: &P) -> Bytes {
+    let mut buf = BytesMut::new();
+    VarInt(P::PACKET_ID).encode(&mut buf);
+    packet.write(&mut buf);
+    buf.freeze()
+}
+
 #[derive(Error, Debug)]
 #[error("Invalid compression Level")]
 pub struct CompressionLevelError;
@@ -593,4 +615,23 @@ mod tests {
 
         assert_eq!(buffer, expected_payload);
     }
+
+    /// Test that a serialized packet gets the same framing as a directly appended one
+    #[test]
+    fn test_append_serialized_matches_append_packet() {
+        let json = "A".repeat(100);
+        let packet = CStatusResponse::new(&json);
+        let compression = Some((CompressionThreshold(10), CompressionLevel(6)));
+
+        let mut encoder = PacketEncoder::default();
+        encoder.set_compression(compression).unwrap();
+        encoder
+            .append_serialized(&serialize_packet(&packet))
+            .expect("Failed to append packet");
+
+        assert_eq!(
+            encoder.take(),
+            build_packet_with_encoder(&packet, compression, None)
+        );
+    }
 }
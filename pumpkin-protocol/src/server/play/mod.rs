@@ -20,6 +20,8 @@ mod s_player_input;
 mod s_player_position;
 mod s_player_position_rotation;
 mod s_player_rotation;
+mod s_plugin_message;
+mod s_resource_pack_response;
 mod s_set_creative_slot;
 mod s_set_held_item;
 mod s_swing_arm;
@@ -48,6 +50,8 @@ pub use s_player_input::*;
 pub use s_player_position::*;
 pub use s_player_position_rotation::*;
 pub use s_player_rotation::*;
+pub use s_plugin_message::*;
+pub use s_resource_pack_response::*;
 pub use s_set_creative_slot::*;
 pub use s_set_held_item::*;
 pub use s_swing_arm::*;
@@ -0,0 +1,24 @@
+use bytes::Buf;
+use pumpkin_macros::server_packet;
+
+use crate::{
+    bytebuf::{ByteBuf, ReadingError},
+    codec::identifier::Identifier,
+    ServerPacket,
+};
+const MAX_PAYLOAD_SIZE: usize = 32767;
+
+#[server_packet("play:custom_payload")]
+pub struct SPlayPluginMessage {
+    pub channel: Identifier,
+    pub data: bytes::Bytes,
+}
+
+impl ServerPacket for SPlayPluginMessage {
+    fn read(bytebuf: &mut impl Buf) -> Result<Self, ReadingError> {
+        Ok(Self {
+            channel: bytebuf.try_get_identifer()?,
+            data: bytebuf.try_copy_to_bytes_len(bytebuf.remaining(), MAX_PAYLOAD_SIZE)?,
+        })
+    }
+}
@@ -0,0 +1,65 @@
+use bytes::Buf;
+use pumpkin_macros::server_packet;
+
+use crate::{
+    bytebuf::{ByteBuf, ReadingError},
+    ServerPacket, VarInt,
+};
+
+#[server_packet("play:resource_pack")]
+pub struct SResourcePackResponse {
+    pub uuid: uuid::Uuid,
+    pub result: VarInt,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ResourcePackStatus {
+    SuccessfullyDownloaded = 0,
+    Declined,
+    FailedDownload,
+    Accepted,
+    Downloaded,
+    InvalidUrl,
+    FailedToReload,
+    Discarded,
+}
+
+impl ResourcePackStatus {
+    /// Whether the client ended up without the resource pack
+    #[must_use]
+    pub const fn is_failure(self) -> bool {
+        matches!(
+            self,
+            Self::Declined | Self::FailedDownload | Self::InvalidUrl | Self::FailedToReload
+        )
+    }
+}
+
+pub struct InvalidResourcePackStatus;
+
+impl TryFrom<i32> for ResourcePackStatus {
+    type Error = InvalidResourcePackStatus;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::SuccessfullyDownloaded),
+            1 => Ok(Self::Declined),
+            2 => Ok(Self::FailedDownload),
+            3 => Ok(Self::Accepted),
+            4 => Ok(Self::Downloaded),
+            5 => Ok(Self::InvalidUrl),
+            6 => Ok(Self::FailedToReload),
+            7 => Ok(Self::Discarded),
+            _ => Err(InvalidResourcePackStatus),
+        }
+    }
+}
+
+impl ServerPacket for SResourcePackResponse {
+    fn read(bytebuf: &mut impl Buf) -> Result<Self, ReadingError> {
+        Ok(Self {
+            uuid: bytebuf.try_get_uuid()?,
+            result: bytebuf.try_get_var_int()?,
+        })
+    }
+}
@@ -62,7 +62,7 @@ pub struct SyncedRegistry {
     instrument: IndexMap<String, Instrument>,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(u8)]
 pub enum DimensionType {
     Overworld,
@@ -86,6 +86,14 @@ impl DimensionType {
             Self::TheNether => Identifier::vanilla("the_nether"),
         }
     }
+
+    /// How many Overworld blocks one block in this dimension corresponds to, used to scale coordinates when travelling through portals
+    pub const fn coordinate_scale(&self) -> f64 {
+        match self {
+            Self::TheNether => 8.0,
+            Self::Overworld | Self::OverworldCaves | Self::TheEnd => 1.0,
+        }
+    }
 }
 
 impl Registry {
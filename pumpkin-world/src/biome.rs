@@ -11,6 +11,17 @@ pub enum Biome {
     // TODO list all Biomes
 }
 
+impl Biome {
+    /// The base temperature of the biome, like Vanilla. Below `0.15` it snows instead of rains and water freezes
+    #[must_use]
+    pub const fn temperature(self) -> f32 {
+        match self {
+            Self::Plains => 0.8,
+            Self::SnowyTiga => -0.5,
+        }
+    }
+}
+
 #[derive(Clone)]
 #[enum_dispatch(BiomeSupplierImpl)]
 pub enum BiomeSupplier {
@@ -1,86 +1,228 @@
 use std::collections::HashMap;
-use std::sync::LazyLock;
+use std::sync::{Arc, LazyLock};
 
+use arc_swap::ArcSwap;
 use serde::Deserialize;
 
-pub static BLOCKS: LazyLock<TopLevel> = LazyLock::new(|| {
-    serde_json::from_str(include_str!("../../../assets/blocks.json"))
-        .expect("Could not parse blocks.json registry.")
-});
+use super::block_sound::BlockSoundGroup;
 
-pub static BLOCKS_BY_ID: LazyLock<HashMap<u16, Block>> = LazyLock::new(|| {
-    let mut map = HashMap::new();
-    for block in &BLOCKS.blocks {
-        map.insert(block.id, block.clone());
-    }
-    map
+/// The block registry every lookup goes through, swapped by [`reload_blocks`].
+///
+/// Lookups hand out their own references to blocks, so a reload never invalidates a block someone is still holding
+static REGISTRY: LazyLock<ArcSwap<BlockRegistry>> = LazyLock::new(|| {
+    let registry = BlockRegistry::from_json(include_str!("../../../assets/blocks.json"))
+        .expect("Could not parse blocks.json registry.");
+    ArcSwap::from_pointee(registry)
 });
 
-pub static BLOCK_ID_BY_REGISTRY_ID: LazyLock<HashMap<String, u16>> = LazyLock::new(|| {
-    let mut map = HashMap::new();
-    for block in &BLOCKS.blocks {
-        map.insert(block.name.clone(), block.id);
-    }
-    map
-});
+/// All blocks and the lookup tables built from them
+pub struct BlockRegistry {
+    pub blocks: Vec<Arc<Block>>,
+    /// The index of the block in `blocks`
+    block_index_by_id: HashMap<u16, usize>,
+    block_id_by_registry_id: HashMap<String, u16>,
+    block_id_by_item_id: HashMap<u16, u16>,
+    state_index_by_state_id: HashMap<u16, u16>,
+    /// The index of the block in `blocks` and of the state in the block, indexed by state id.
+    ///
+    /// State ids are dense, so this is a plain array lookup instead of hashing
+    state_locations: Vec<Option<(usize, usize)>>,
+    /// The properties of every state which get queried in hot loops, indexed by state id
+    state_flags: Vec<StateFlags>,
+}
+
+impl BlockRegistry {
+    fn from_json(json: &str) -> serde_json::Result<Self> {
+        let top_level: TopLevel = serde_json::from_str(json)?;
+        let blocks: Vec<Arc<Block>> = top_level
+            .blocks
+            .into_iter()
+            .map(|mut block| {
+                block.sound_group = BlockSoundGroup::of_block(&block.name);
+                Arc::new(block)
+            })
+            .collect();
 
-pub static BLOCK_ID_BY_STATE_ID: LazyLock<HashMap<u16, u16>> = LazyLock::new(|| {
-    let mut map = HashMap::new();
-    for block in &BLOCKS.blocks {
-        for state in &block.states {
-            map.insert(state.id, block.id);
+        let mut block_index_by_id = HashMap::new();
+        let mut block_id_by_registry_id = HashMap::new();
+        let mut block_id_by_item_id = HashMap::new();
+        let mut state_index_by_state_id = HashMap::new();
+        let mut state_locations = Vec::new();
+        for (block_index, block) in blocks.iter().enumerate() {
+            block_index_by_id.insert(block.id, block_index);
+            block_id_by_registry_id.insert(block.name.clone(), block.id);
+            block_id_by_item_id.insert(block.item_id, block.id);
+            for (state_index, state) in block.states.iter().enumerate() {
+                state_index_by_state_id.insert(state.id, state_index as u16);
+                let id = state.id as usize;
+                if state_locations.len() <= id {
+                    state_locations.resize(id + 1, None);
+                }
+                state_locations[id] = Some((block_index, state_index));
+            }
         }
-    }
-    map
-});
 
-pub static STATE_INDEX_BY_STATE_ID: LazyLock<HashMap<u16, u16>> = LazyLock::new(|| {
-    let mut map = HashMap::new();
-    for block in &BLOCKS.blocks {
-        for (index, state) in block.states.iter().enumerate() {
-            map.insert(state.id, index as u16);
+        let mut state_flags = vec![StateFlags::default(); state_locations.len()];
+        for block in &blocks {
+            for state in &block.states {
+                state_flags[state.id as usize] = StateFlags {
+                    air: state.air,
+                    solid: state.is_surface_solid(),
+                    luminance: state.luminance,
+                    opacity: state.opacity.map_or(0, |opacity| opacity.min(15) as u8),
+                };
+            }
         }
+
+        Ok(Self {
+            blocks,
+            block_index_by_id,
+            block_id_by_registry_id,
+            block_id_by_item_id,
+            state_index_by_state_id,
+            state_locations,
+            state_flags,
+        })
     }
-    map
-});
 
-pub static BLOCK_ID_BY_ITEM_ID: LazyLock<HashMap<u16, u16>> = LazyLock::new(|| {
-    let mut map = HashMap::new();
-    for block in &BLOCKS.blocks {
-        map.insert(block.item_id, block.id);
+    fn block_by_id(&self, id: u16) -> Option<&Arc<Block>> {
+        self.block_index_by_id
+            .get(&id)
+            .map(|index| &self.blocks[*index])
     }
-    map
-});
 
-pub fn get_block(registry_id: &str) -> Option<&Block> {
-    let id = BLOCK_ID_BY_REGISTRY_ID.get(&registry_id.replace("minecraft:", ""))?;
-    BLOCKS_BY_ID.get(id)
+    /// The block of the state and the state itself. Unlike [`get_block_and_state_by_state_id`] nothing gets copied,
+    /// for looking up many states at once
+    pub fn block_and_state(&self, id: u16) -> Option<(&Block, &State)> {
+        let (block_index, state_index) = (*self.state_locations.get(id as usize)?)?;
+        let block = &self.blocks[block_index];
+        Some((block, &block.states[state_index]))
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+struct StateFlags {
+    air: bool,
+    solid: bool,
+    luminance: u8,
+    opacity: u8,
+}
+
+/// The current block registry
+pub fn registry() -> Arc<BlockRegistry> {
+    REGISTRY.load_full()
+}
+
+/// Replaces the block registry with the blocks from a `blocks.json`, returns how many blocks were loaded.
+///
+/// Every runtime lookup in this module picks up the new blocks. Block states resolved at compile time
+/// with the `block_state!` macro and the registries sent to clients during configuration are not reloaded.
+/// Blocks that were looked up before the reload stay valid, they just don't change
+pub fn reload_blocks(json: &str) -> serde_json::Result<usize> {
+    let registry = BlockRegistry::from_json(json)?;
+    let count = registry.blocks.len();
+    REGISTRY.store(Arc::new(registry));
+    Ok(count)
+}
+
+pub fn get_block(registry_id: &str) -> Option<Arc<Block>> {
+    let registry = REGISTRY.load();
+    let id = registry
+        .block_id_by_registry_id
+        .get(&registry_id.replace("minecraft:", ""))?;
+    registry.block_by_id(*id).cloned()
+}
+
+pub fn get_block_by_id(id: u16) -> Option<Arc<Block>> {
+    REGISTRY.load().block_by_id(id).cloned()
+}
+
+pub fn get_state_by_state_id(id: u16) -> Option<State> {
+    REGISTRY
+        .load()
+        .block_and_state(id)
+        .map(|(_, state)| state.clone())
+}
+
+pub fn get_block_by_state_id(id: u16) -> Option<Arc<Block>> {
+    let registry = REGISTRY.load();
+    let (block_index, _) = (*registry.state_locations.get(id as usize)?)?;
+    Some(registry.blocks[block_index].clone())
+}
+
+pub fn get_block_and_state_by_state_id(id: u16) -> Option<(Arc<Block>, State)> {
+    let registry = REGISTRY.load();
+    let (block_index, state_index) = (*registry.state_locations.get(id as usize)?)?;
+    let block = &registry.blocks[block_index];
+    Some((block.clone(), block.states[state_index].clone()))
 }
 
-pub fn get_block_by_id<'a>(id: u16) -> Option<&'a Block> {
-    BLOCKS_BY_ID.get(&id)
+/// The index of the state in [`Block::states`]
+pub fn get_state_index(id: u16) -> Option<u16> {
+    REGISTRY.load().state_index_by_state_id.get(&id).copied()
 }
 
-pub fn get_state_by_state_id<'a>(id: u16) -> Option<&'a State> {
-    get_block_and_state_by_state_id(id).map(|(_, state)| state)
+/// Whether the state is air, unknown states are not
+pub fn state_is_air(id: u16) -> bool {
+    REGISTRY
+        .load()
+        .state_flags
+        .get(id as usize)
+        .is_some_and(|flags| flags.air)
 }
 
-pub fn get_block_by_state_id<'a>(id: u16) -> Option<&'a Block> {
-    let block_id = BLOCK_ID_BY_STATE_ID.get(&id)?;
-    BLOCKS_BY_ID.get(block_id)
+/// Whether entities collide with the state, unknown states are not solid
+pub fn state_is_solid(id: u16) -> bool {
+    REGISTRY
+        .load()
+        .state_flags
+        .get(id as usize)
+        .is_some_and(|flags| flags.solid)
 }
 
-pub fn get_block_and_state_by_state_id<'a>(id: u16) -> Option<(&'a Block, &'a State)> {
-    let block_id = BLOCK_ID_BY_STATE_ID.get(&id)?;
-    let block = BLOCKS_BY_ID.get(block_id)?;
-    let state_index = STATE_INDEX_BY_STATE_ID.get(&id)?;
-    let state = block.states.get(*state_index as usize)?;
-    Some((block, state))
+/// How much light the state emits, unknown states don't emit any
+pub fn state_luminance(id: u16) -> u8 {
+    REGISTRY
+        .load()
+        .state_flags
+        .get(id as usize)
+        .map_or(0, |flags| flags.luminance)
 }
 
-pub fn get_block_by_item<'a>(item_id: u16) -> Option<&'a Block> {
-    let block_id = BLOCK_ID_BY_ITEM_ID.get(&item_id)?;
-    BLOCKS_BY_ID.get(block_id)
+/// How much light passing through the state gets dimmed, `15` blocks light completely. Unknown states don't dim light
+pub fn state_opacity(id: u16) -> u8 {
+    REGISTRY
+        .load()
+        .state_flags
+        .get(id as usize)
+        .map_or(0, |flags| flags.opacity)
+}
+
+impl Block {
+    /// Returns the value of every property for the state at `state_index` in [`Block::states`].
+    ///
+    /// States are ordered like the combinations of all property values, with the last property changing the fastest
+    pub fn state_properties(&self, state_index: usize) -> Vec<(&str, &str)> {
+        let mut remaining = state_index;
+        let mut properties: Vec<(&str, &str)> = self
+            .properties
+            .iter()
+            .rev()
+            .map(|property| {
+                let value = &property.values[remaining % property.values.len()];
+                remaining /= property.values.len();
+                (property.name.as_str(), value.as_str())
+            })
+            .collect();
+        properties.reverse();
+        properties
+    }
+}
+
+pub fn get_block_by_item(item_id: u16) -> Option<Arc<Block>> {
+    let registry = REGISTRY.load();
+    let block_id = registry.block_id_by_item_id.get(&item_id)?;
+    registry.block_by_id(*block_id).cloned()
 }
 #[expect(dead_code)]
 #[derive(Deserialize, Clone, Debug)]
@@ -94,6 +236,9 @@ pub struct Block {
     pub id: u16,
     pub item_id: u16,
     pub hardness: f32,
+    /// Not part of blocks.json, see [`BlockSoundGroup::of_block`]
+    #[serde(skip)]
+    pub sound_group: BlockSoundGroup,
     pub wall_variant_id: Option<u16>,
     pub translation_key: String,
     pub name: String,
@@ -101,11 +246,10 @@ pub struct Block {
     pub default_state_id: u16,
     pub states: Vec<State>,
 }
-#[expect(dead_code)]
 #[derive(Deserialize, Clone, Debug)]
 pub struct Property {
-    name: String,
-    values: Vec<String>,
+    pub name: String,
+    pub values: Vec<String>,
 }
 #[derive(Deserialize, Clone, Debug)]
 pub struct State {
@@ -118,9 +262,87 @@ pub struct State {
     pub collision_shapes: Vec<u16>,
     pub block_entity_type: Option<u32>,
 }
+
+impl State {
+    /// Whether entities can stand on the state.
+    /// Air, fluids and plants like tall grass have no collision, so they are no surface
+    pub fn is_surface_solid(&self) -> bool {
+        !self.air && !self.collision_shapes.is_empty()
+    }
+}
+
 #[expect(dead_code)]
 #[derive(Deserialize, Clone, Debug)]
 struct Shape {
     min: [f32; 3],
     max: [f32; 3],
 }
+
+#[cfg(test)]
+mod test {
+    use super::{
+        get_block, get_block_and_state_by_state_id, registry, state_is_solid, state_luminance,
+        state_opacity,
+    };
+
+    #[test]
+    fn state_lookup() {
+        for block in &registry().blocks {
+            for state in &block.states {
+                let (found_block, found_state) =
+                    get_block_and_state_by_state_id(state.id).expect("Every state exists");
+                assert_eq!(found_block.id, block.id);
+                assert_eq!(found_state.id, state.id);
+            }
+        }
+    }
+
+    #[test]
+    fn fast_paths() {
+        let stone = get_block("minecraft:stone").unwrap();
+        let air = get_block("minecraft:air").unwrap();
+        let glowstone = get_block("minecraft:glowstone").unwrap();
+        assert!(state_is_solid(stone.default_state_id));
+        assert!(!state_is_solid(air.default_state_id));
+        assert_eq!(state_luminance(glowstone.default_state_id), 15);
+        assert_eq!(state_opacity(stone.default_state_id), 15);
+        assert_eq!(state_opacity(air.default_state_id), 0);
+        assert!(!state_is_solid(u16::MAX));
+    }
+
+    #[test]
+    fn surfaces() {
+        let surface = |name: &str| {
+            let block = get_block(name).unwrap();
+            get_block_and_state_by_state_id(block.default_state_id)
+                .unwrap()
+                .1
+                .is_surface_solid()
+        };
+        assert!(surface("minecraft:grass_block"));
+        assert!(surface("minecraft:oak_slab"));
+        assert!(!surface("minecraft:air"));
+        assert!(!surface("minecraft:water"));
+        assert!(!surface("minecraft:lava"));
+        assert!(!surface("minecraft:short_grass"));
+    }
+
+    #[test]
+    fn state_properties() {
+        let block = get_block("minecraft:oak_stairs").unwrap();
+        let default_index = block
+            .states
+            .iter()
+            .position(|state| state.id == block.default_state_id)
+            .unwrap();
+        assert_eq!(
+            block.state_properties(default_index),
+            vec![
+                ("facing", "north"),
+                ("half", "bottom"),
+                ("shape", "straight"),
+                ("waterlogged", "false")
+            ]
+        );
+    }
+}
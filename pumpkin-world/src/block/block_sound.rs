@@ -0,0 +1,313 @@
+use std::{collections::HashMap, sync::LazyLock};
+
+use pumpkin_macros::sound;
+use serde::Deserialize;
+
+/// The sound group of every block which doesn't sound like stone, by block name.
+///
+/// Our block registry dump has no sound groups, so they are kept in their own file which the extractor leaves alone
+static SOUND_GROUPS: LazyLock<HashMap<String, BlockSoundGroup>> = LazyLock::new(|| {
+    serde_json::from_str::<HashMap<BlockSoundGroup, Vec<String>>>(include_str!(
+        "../../../assets/block_sound_groups.json"
+    ))
+    .expect("Could not parse block_sound_groups.json.")
+    .into_iter()
+    .flat_map(|(group, blocks)| blocks.into_iter().map(move |block| (block, group)))
+    .collect()
+});
+
+/// The set of sounds a Block plays when broken, placed or hit, the `SoundType` of the block in vanilla.
+///
+/// Read from block_sound_groups.json, blocks which aren't listed there fall back to `Stone`
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum BlockSoundGroup {
+    #[default]
+    Stone,
+    Wood,
+    Gravel,
+    Grass,
+    LilyPad,
+    Metal,
+    Glass,
+    Wool,
+    Sand,
+    Snow,
+    PowderSnow,
+    Ladder,
+    Anvil,
+    SlimeBlock,
+    HoneyBlock,
+    WetGrass,
+    CoralBlock,
+    Bamboo,
+    BambooSapling,
+    Scaffolding,
+    SweetBerryBush,
+    Crop,
+    HardCrop,
+    Vine,
+    NetherWart,
+    Lantern,
+    Stem,
+    Nylium,
+    Fungus,
+    Roots,
+    Shroomlight,
+    WeepingVines,
+    TwistingVines,
+    SoulSand,
+    SoulSoil,
+    Basalt,
+    WartBlock,
+    Netherrack,
+    NetherBricks,
+    NetherSprouts,
+    NetherOre,
+    BoneBlock,
+    NetheriteBlock,
+    AncientDebris,
+    Lodestone,
+    Chain,
+    NetherGoldOre,
+    GildedBlackstone,
+    Candle,
+    Amethyst,
+    AmethystCluster,
+    SmallAmethystBud,
+    MediumAmethystBud,
+    LargeAmethystBud,
+    Tuff,
+    TuffBricks,
+    PolishedTuff,
+    Calcite,
+    DripstoneBlock,
+    PointedDripstone,
+    Copper,
+    CopperBulb,
+    CopperGrate,
+    CaveVines,
+    SporeBlossom,
+    Azalea,
+    FloweringAzalea,
+    MossCarpet,
+    PinkPetals,
+    Moss,
+    BigDripleaf,
+    SmallDripleaf,
+    RootedDirt,
+    HangingRoots,
+    AzaleaLeaves,
+    SculkSensor,
+    SculkCatalyst,
+    Sculk,
+    SculkVein,
+    SculkShrieker,
+    GlowLichen,
+    Deepslate,
+    DeepslateBricks,
+    DeepslateTiles,
+    PolishedDeepslate,
+    Froglight,
+    Frogspawn,
+    MangroveRoots,
+    MuddyMangroveRoots,
+    Mud,
+    MudBricks,
+    PackedMud,
+    HangingSign,
+    NetherWoodHangingSign,
+    BambooWoodHangingSign,
+    BambooWood,
+    NetherWood,
+    CherryWood,
+    CherrySapling,
+    CherryLeaves,
+    CherryWoodHangingSign,
+    ChiseledBookshelf,
+    SuspiciousSand,
+    SuspiciousGravel,
+    DecoratedPot,
+    TrialSpawner,
+    Sponge,
+    WetSponge,
+    Vault,
+    HeavyCore,
+    Cobweb,
+    Spawner,
+    CreakingHeart,
+    Resin,
+    ResinBricks,
+}
+
+impl BlockSoundGroup {
+    /// The sound group of the block with the registry name, without the `minecraft:` namespace
+    #[must_use]
+    pub fn of_block(name: &str) -> Self {
+        SOUND_GROUPS.get(name).copied().unwrap_or_default()
+    }
+
+    /// The sound id played when a block of this group is broken
+    #[must_use]
+    pub const fn break_sound(&self) -> u16 {
+        match self {
+            Self::Stone => sound!("block.stone.break"),
+            Self::Wood => sound!("block.wood.break"),
+            Self::Gravel => sound!("block.gravel.break"),
+            Self::Grass => sound!("block.grass.break"),
+            Self::LilyPad => sound!("block.big_dripleaf.break"),
+            Self::Metal => sound!("block.metal.break"),
+            Self::Glass => sound!("block.glass.break"),
+            Self::Wool => sound!("block.wool.break"),
+            Self::Sand => sound!("block.sand.break"),
+            Self::Snow => sound!("block.snow.break"),
+            Self::PowderSnow => sound!("block.powder_snow.break"),
+            Self::Ladder => sound!("block.ladder.break"),
+            Self::Anvil => sound!("block.anvil.break"),
+            Self::SlimeBlock => sound!("block.slime_block.break"),
+            Self::HoneyBlock => sound!("block.honey_block.break"),
+            Self::WetGrass => sound!("block.wet_grass.break"),
+            Self::CoralBlock => sound!("block.coral_block.break"),
+            Self::Bamboo => sound!("block.bamboo.break"),
+            Self::BambooSapling => sound!("block.bamboo_sapling.break"),
+            Self::Scaffolding => sound!("block.scaffolding.break"),
+            Self::SweetBerryBush => sound!("block.sweet_berry_bush.break"),
+            Self::Crop => sound!("block.crop.break"),
+            Self::HardCrop => sound!("block.wood.break"),
+            Self::Vine => sound!("block.vine.break"),
+            Self::NetherWart => sound!("block.nether_wart.break"),
+            Self::Lantern => sound!("block.lantern.break"),
+            Self::Stem => sound!("block.stem.break"),
+            Self::Nylium => sound!("block.nylium.break"),
+            Self::Fungus => sound!("block.fungus.break"),
+            Self::Roots => sound!("block.roots.break"),
+            Self::Shroomlight => sound!("block.shroomlight.break"),
+            Self::WeepingVines => sound!("block.weeping_vines.break"),
+            Self::TwistingVines => sound!("block.weeping_vines.break"),
+            Self::SoulSand => sound!("block.soul_sand.break"),
+            Self::SoulSoil => sound!("block.soul_soil.break"),
+            Self::Basalt => sound!("block.basalt.break"),
+            Self::WartBlock => sound!("block.wart_block.break"),
+            Self::Netherrack => sound!("block.netherrack.break"),
+            Self::NetherBricks => sound!("block.nether_bricks.break"),
+            Self::NetherSprouts => sound!("block.nether_sprouts.break"),
+            Self::NetherOre => sound!("block.nether_ore.break"),
+            Self::BoneBlock => sound!("block.bone_block.break"),
+            Self::NetheriteBlock => sound!("block.netherite_block.break"),
+            Self::AncientDebris => sound!("block.ancient_debris.break"),
+            Self::Lodestone => sound!("block.lodestone.break"),
+            Self::Chain => sound!("block.chain.break"),
+            Self::NetherGoldOre => sound!("block.nether_gold_ore.break"),
+            Self::GildedBlackstone => sound!("block.gilded_blackstone.break"),
+            Self::Candle => sound!("block.candle.break"),
+            Self::Amethyst => sound!("block.amethyst_block.break"),
+            Self::AmethystCluster => sound!("block.amethyst_cluster.break"),
+            Self::SmallAmethystBud => sound!("block.small_amethyst_bud.break"),
+            Self::MediumAmethystBud => sound!("block.medium_amethyst_bud.break"),
+            Self::LargeAmethystBud => sound!("block.large_amethyst_bud.break"),
+            Self::Tuff => sound!("block.tuff.break"),
+            Self::TuffBricks => sound!("block.tuff_bricks.break"),
+            Self::PolishedTuff => sound!("block.polished_tuff.break"),
+            Self::Calcite => sound!("block.calcite.break"),
+            Self::DripstoneBlock => sound!("block.dripstone_block.break"),
+            Self::PointedDripstone => sound!("block.pointed_dripstone.break"),
+            Self::Copper => sound!("block.copper.break"),
+            Self::CopperBulb => sound!("block.copper_bulb.break"),
+            Self::CopperGrate => sound!("block.copper_grate.break"),
+            Self::CaveVines => sound!("block.cave_vines.break"),
+            Self::SporeBlossom => sound!("block.spore_blossom.break"),
+            Self::Azalea => sound!("block.azalea.break"),
+            Self::FloweringAzalea => sound!("block.flowering_azalea.break"),
+            Self::MossCarpet => sound!("block.moss_carpet.break"),
+            Self::PinkPetals => sound!("block.pink_petals.break"),
+            Self::Moss => sound!("block.moss.break"),
+            Self::BigDripleaf => sound!("block.big_dripleaf.break"),
+            Self::SmallDripleaf => sound!("block.small_dripleaf.break"),
+            Self::RootedDirt => sound!("block.rooted_dirt.break"),
+            Self::HangingRoots => sound!("block.hanging_roots.break"),
+            Self::AzaleaLeaves => sound!("block.azalea_leaves.break"),
+            Self::SculkSensor => sound!("block.sculk_sensor.break"),
+            Self::SculkCatalyst => sound!("block.sculk_catalyst.break"),
+            Self::Sculk => sound!("block.sculk.break"),
+            Self::SculkVein => sound!("block.sculk_vein.break"),
+            Self::SculkShrieker => sound!("block.sculk_shrieker.break"),
+            Self::GlowLichen => sound!("block.sculk_vein.break"),
+            Self::Deepslate => sound!("block.deepslate.break"),
+            Self::DeepslateBricks => sound!("block.deepslate_bricks.break"),
+            Self::DeepslateTiles => sound!("block.deepslate_tiles.break"),
+            Self::PolishedDeepslate => sound!("block.polished_deepslate.break"),
+            Self::Froglight => sound!("block.froglight.break"),
+            Self::Frogspawn => sound!("block.frogspawn.break"),
+            Self::MangroveRoots => sound!("block.mangrove_roots.break"),
+            Self::MuddyMangroveRoots => sound!("block.muddy_mangrove_roots.break"),
+            Self::Mud => sound!("block.mud.break"),
+            Self::MudBricks => sound!("block.mud_bricks.break"),
+            Self::PackedMud => sound!("block.packed_mud.break"),
+            Self::HangingSign => sound!("block.hanging_sign.break"),
+            Self::NetherWoodHangingSign => sound!("block.nether_wood_hanging_sign.break"),
+            Self::BambooWoodHangingSign => sound!("block.bamboo_wood_hanging_sign.break"),
+            Self::BambooWood => sound!("block.bamboo_wood.break"),
+            Self::NetherWood => sound!("block.nether_wood.break"),
+            Self::CherryWood => sound!("block.cherry_wood.break"),
+            Self::CherrySapling => sound!("block.cherry_sapling.break"),
+            Self::CherryLeaves => sound!("block.cherry_leaves.break"),
+            Self::CherryWoodHangingSign => sound!("block.cherry_wood_hanging_sign.break"),
+            Self::ChiseledBookshelf => sound!("block.chiseled_bookshelf.break"),
+            Self::SuspiciousSand => sound!("block.suspicious_sand.break"),
+            Self::SuspiciousGravel => sound!("block.suspicious_gravel.break"),
+            Self::DecoratedPot => sound!("block.decorated_pot.break"),
+            Self::TrialSpawner => sound!("block.trial_spawner.break"),
+            Self::Sponge => sound!("block.sponge.break"),
+            Self::WetSponge => sound!("block.wet_sponge.break"),
+            Self::Vault => sound!("block.vault.break"),
+            Self::HeavyCore => sound!("block.heavy_core.break"),
+            Self::Cobweb => sound!("block.cobweb.break"),
+            Self::Spawner => sound!("block.spawner.break"),
+            Self::CreakingHeart => sound!("block.creaking_heart.break"),
+            Self::Resin => sound!("block.resin.break"),
+            Self::ResinBricks => sound!("block.resin_bricks.break"),
+        }
+    }
+
+    /// Base volume of the group, vanilla plays break sounds at `(volume + 1) / 2`
+    #[must_use]
+    pub const fn volume(&self) -> f32 {
+        match self {
+            Self::Anvil => 0.3,
+            _ => 1.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BlockSoundGroup, SOUND_GROUPS};
+    use crate::block::block_registry::get_block;
+
+    fn sound_group(name: &str) -> BlockSoundGroup {
+        get_block(name).unwrap().sound_group
+    }
+
+    #[test]
+    fn glass_and_wool() {
+        assert_eq!(sound_group("minecraft:glass"), BlockSoundGroup::Glass);
+        assert_eq!(
+            sound_group("minecraft:red_stained_glass_pane"),
+            BlockSoundGroup::Glass
+        );
+        assert_eq!(sound_group("minecraft:white_wool"), BlockSoundGroup::Wool);
+    }
+
+    #[test]
+    fn stone() {
+        assert_eq!(sound_group("minecraft:stone"), BlockSoundGroup::Stone);
+        assert_eq!(sound_group("minecraft:sandstone"), BlockSoundGroup::Stone);
+    }
+
+    #[test]
+    fn listed_blocks_exist() {
+        for name in SOUND_GROUPS.keys() {
+            assert!(get_block(name).is_some(), "{name} is not a block");
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use super::block_registry::{get_block, get_state_by_state_id};
+use super::block_registry::{get_block, state_is_air};
 
 #[derive(Clone, Copy, Debug, Eq)]
 pub struct BlockState {
@@ -33,7 +33,7 @@ impl BlockState {
 
     #[inline]
     pub fn is_air(&self) -> bool {
-        get_state_by_state_id(self.state_id).unwrap().air
+        state_is_air(self.state_id)
     }
 
     #[inline]
@@ -1,10 +1,13 @@
 pub mod block_registry;
+pub mod block_sound;
 pub mod block_state;
 
 use pumpkin_core::math::vector3::Vector3;
 
+pub use block_sound::BlockSoundGroup;
 pub use block_state::BlockState;
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum BlockFace {
     Bottom = 0,
     Top,
@@ -33,6 +36,27 @@ impl TryFrom<i32> for BlockFace {
 }
 
 impl BlockFace {
+    pub const ALL: [Self; 6] = [
+        Self::Bottom,
+        Self::Top,
+        Self::North,
+        Self::South,
+        Self::West,
+        Self::East,
+    ];
+
+    /// The face on the other side of the block
+    pub const fn opposite(self) -> Self {
+        match self {
+            Self::Bottom => Self::Top,
+            Self::Top => Self::Bottom,
+            Self::North => Self::South,
+            Self::South => Self::North,
+            Self::West => Self::East,
+            Self::East => Self::West,
+        }
+    }
+
     pub fn to_offset(&self) -> Vector3<i32> {
         match self {
             BlockFace::Bottom => (0, -1, 0),
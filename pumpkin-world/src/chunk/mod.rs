@@ -7,7 +7,10 @@ use std::ops::Index;
 use thiserror::Error;
 
 use crate::{
-    block::BlockState,
+    block::{
+        block_registry::{registry, state_is_solid, state_opacity},
+        BlockState,
+    },
     coordinates::{ChunkRelativeBlockCoordinates, Height},
     level::LevelFolder,
     WORLD_HEIGHT,
@@ -215,12 +218,107 @@ impl ChunkBlocks {
         index.y.get_absolute() as usize * CHUNK_AREA + *index.z as usize * 16 + *index.x as usize
     }
 
-    #[expect(dead_code)]
-    fn calculate_heightmap(&self) -> ChunkHeightmaps {
-        // figure out how LongArray is formatted
-        // figure out how to find out if block is motion blocking
-        todo!()
+    /// Returns every block state in the chunk, in the same order as the chunk data packet
+    pub fn iter_blocks(&self) -> impl Iterator<Item = u16> + '_ {
+        self.blocks.iter().copied()
     }
+
+    /// A copy of the blocks where every block `is_hidden` returns `true` for is replaced by the filler for its height,
+    /// which gets the index of the layer counted from the bottom of the world. Blocks next to a block which doesn't
+    /// block light completely stay visible, so players still see everything exposed to air or water.
+    ///
+    /// The blocks of neighboring chunks are unknown, so blocks at the sides of the chunk are never hidden.
+    /// Returns `None` if there is nothing to hide
+    pub fn obfuscated(
+        &self,
+        is_hidden: impl Fn(u16) -> bool,
+        filler: impl Fn(usize) -> u16,
+    ) -> Option<Self> {
+        let opaque = |index: usize| state_opacity(self.blocks[index]) == 15;
+        let mut obfuscated: Option<Self> = None;
+        for (index, &state_id) in self.blocks.iter().enumerate() {
+            if !is_hidden(state_id) {
+                continue;
+            }
+            let (x, z, y) = (index % 16, (index / 16) % 16, index / CHUNK_AREA);
+            if x == 0 || x == 15 || z == 0 || z == 15 || y == 0 || y == WORLD_HEIGHT - 1 {
+                continue;
+            }
+            let neighbors = [
+                index - 1,
+                index + 1,
+                index - 16,
+                index + 16,
+                index - CHUNK_AREA,
+                index + CHUNK_AREA,
+            ];
+            if !neighbors.into_iter().all(opaque) {
+                continue;
+            }
+            obfuscated
+                .get_or_insert_with(|| Self {
+                    blocks: self.blocks.clone(),
+                    heightmap: self.heightmap.clone(),
+                })
+                .blocks[index] = filler(y);
+        }
+        obfuscated
+    }
+
+    /// The first layer, counted from the bottom of the world, above the highest block in the column which sky light
+    /// doesn't pass through unchanged, like stone. Glass and air don't block the sky.
+    /// Returns 0 if the whole column sees the sky
+    pub fn sky_height(&self, x: u8, z: u8) -> usize {
+        // TODO: Use the heightmaps once setting blocks updates them
+        let column = usize::from(z) * 16 + usize::from(x);
+        (0..WORLD_HEIGHT)
+            .rev()
+            .find(|y| state_opacity(self.blocks[y * CHUNK_AREA + column]) > 0)
+            .map_or(0, |y| y + 1)
+    }
+
+    /// Computes the heightmaps from the blocks of the chunk
+    pub fn calculate_heightmap(&self) -> ChunkHeightmaps {
+        let mut motion_blocking = [0; CHUNK_AREA];
+        let mut world_surface = [0; CHUNK_AREA];
+        let registry = registry();
+        // Columns are ordered by z, then x, the same as the blocks in a layer
+        for column in 0..CHUNK_AREA {
+            for y in (0..WORLD_HEIGHT).rev() {
+                let Some((block, state)) =
+                    registry.block_and_state(self.blocks[y * CHUNK_AREA + column])
+                else {
+                    continue;
+                };
+                if state.air {
+                    continue;
+                }
+                // The height is the first air block above the highest block
+                if world_surface[column] == 0 {
+                    world_surface[column] = y as i64 + 1;
+                }
+                if state_is_solid(state.id) || block.name == "water" || block.name == "lava" {
+                    motion_blocking[column] = y as i64 + 1;
+                    break;
+                }
+            }
+        }
+        ChunkHeightmaps {
+            motion_blocking: pack_heightmap(&motion_blocking),
+            world_surface: pack_heightmap(&world_surface),
+        }
+    }
+}
+
+/// Packs the heights into longs, every height takes 9 bits (enough for the world height) and heights never span two longs
+fn pack_heightmap(heights: &[i64; CHUNK_AREA]) -> LongArray {
+    const BITS_PER_HEIGHT: usize = 9;
+    const HEIGHTS_PER_LONG: usize = 64 / BITS_PER_HEIGHT;
+    let mut longs = vec![0; CHUNK_AREA.div_ceil(HEIGHTS_PER_LONG)];
+    for (index, height) in heights.iter().enumerate() {
+        longs[index / HEIGHTS_PER_LONG] |= height << ((index % HEIGHTS_PER_LONG) * BITS_PER_HEIGHT);
+    }
+    LongArray::new(longs)
 }
 
 impl Index<ChunkRelativeBlockCoordinates> for ChunkBlocks {
@@ -326,3 +424,73 @@ pub enum ChunkParsingError {
     #[error("Error deserializing chunk: {0}")]
     ErrorDeserializingChunk(String),
 }
+
+#[cfg(test)]
+mod test {
+    use super::{ChunkBlocks, ChunkHeightmaps, CHUNK_AREA, CHUNK_VOLUME};
+    use crate::block::block_registry::get_block;
+
+    #[test]
+    fn heightmap() {
+        let mut blocks = ChunkBlocks::default();
+        assert_eq!(
+            blocks.calculate_heightmap().world_surface.to_vec(),
+            ChunkHeightmaps::default().world_surface.to_vec()
+        );
+
+        // A stone block at the bottom of the first column
+        blocks.blocks[0] = get_block("minecraft:stone").unwrap().default_state_id;
+        // A flower, which does not block motion, at the top of the second column
+        blocks.blocks[CHUNK_AREA * 10 + 1] = get_block("minecraft:poppy").unwrap().default_state_id;
+        let heightmap = blocks.calculate_heightmap();
+        assert_eq!(heightmap.world_surface[0], 1 | (11 << 9));
+        assert_eq!(heightmap.motion_blocking[0], 1);
+    }
+
+    #[test]
+    fn sky_height() {
+        let mut blocks = ChunkBlocks::default();
+        assert_eq!(blocks.sky_height(3, 4), 0);
+
+        let column = 4 * 16 + 3;
+        blocks.blocks[CHUNK_AREA * 5 + column] =
+            get_block("minecraft:stone").unwrap().default_state_id;
+        blocks.blocks[CHUNK_AREA * 9 + column] =
+            get_block("minecraft:glass").unwrap().default_state_id;
+        assert_eq!(blocks.sky_height(3, 4), 6);
+        // Other columns are not covered
+        assert_eq!(blocks.sky_height(4, 4), 0);
+    }
+
+    #[test]
+    fn obfuscation() {
+        let stone = get_block("minecraft:stone").unwrap().default_state_id;
+        let ore = get_block("minecraft:diamond_ore").unwrap().default_state_id;
+        let mut blocks = ChunkBlocks::default();
+        blocks.blocks = Box::new([stone; CHUNK_VOLUME]);
+        // Buried in stone
+        let buried = CHUNK_AREA * 5 + 16 * 5 + 5;
+        // Next to air
+        let exposed = CHUNK_AREA * 5 + 16 * 5 + 9;
+        // At the side of the chunk
+        let side = CHUNK_AREA * 5 + 16 * 5;
+        for index in [buried, exposed, side] {
+            blocks.blocks[index] = ore;
+        }
+        blocks.blocks[exposed + 1] = 0;
+
+        let obfuscated = blocks
+            .obfuscated(|state_id| state_id == ore, |_| stone)
+            .unwrap();
+        assert_eq!(obfuscated.blocks[buried], stone);
+        assert_eq!(obfuscated.blocks[exposed], ore);
+        assert_eq!(obfuscated.blocks[side], ore);
+        // The real blocks stay the same
+        assert_eq!(blocks.blocks[buried], ore);
+
+        blocks.blocks[buried] = stone;
+        assert!(blocks
+            .obfuscated(|state_id| state_id == ore, |_| stone)
+            .is_none());
+    }
+}
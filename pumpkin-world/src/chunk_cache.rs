@@ -0,0 +1,98 @@
+use std::collections::HashMap;
+
+use pumpkin_core::math::vector2::Vector2;
+
+/// How many chunks are in memory and how many got unloaded, see [`crate::level::Level::chunk_cache_stats`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ChunkCacheStats {
+    /// Chunks in memory, watched or not
+    pub resident: usize,
+    /// Chunks in memory nobody watches, they get unloaded once their grace period is over
+    pub unwatched: usize,
+    /// Chunks unloaded because nobody watched them anymore since the level was loaded
+    pub evicted: u64,
+}
+
+/// The loaded chunks nobody watches anymore, with the tick they lost their last watcher.
+///
+/// They stay loaded for a grace period, so a player stepping out of a chunk and back in doesn't load it again
+#[derive(Default)]
+pub(crate) struct UnwatchedChunks {
+    since: HashMap<Vector2<i32>, u64>,
+}
+
+impl UnwatchedChunks {
+    /// Keeps the tick if the chunk is already unwatched, so being unwatched again doesn't extend the grace period
+    pub fn insert(&mut self, chunk: Vector2<i32>, tick: u64) {
+        self.since.entry(chunk).or_insert(tick);
+    }
+
+    pub fn remove(&mut self, chunk: &Vector2<i32>) {
+        self.since.remove(chunk);
+    }
+
+    pub fn len(&self) -> usize {
+        self.since.len()
+    }
+
+    /// Removes and returns the chunks to unload: the ones whose grace period is over, and the ones unwatched the
+    /// longest until at most `max_resident` chunks are loaded. A `max_resident` of 0 disables the limit
+    pub fn evict(
+        &mut self,
+        tick: u64,
+        grace_ticks: u64,
+        resident: usize,
+        max_resident: usize,
+    ) -> Vec<Vector2<i32>> {
+        let over_limit = if max_resident == 0 {
+            0
+        } else {
+            resident.saturating_sub(max_resident)
+        };
+        let mut oldest_first: Vec<(u64, Vector2<i32>)> = self
+            .since
+            .iter()
+            .map(|(chunk, since)| (*since, *chunk))
+            .collect();
+        oldest_first.sort_unstable_by_key(|(since, chunk)| (*since, chunk.x, chunk.z));
+
+        let evicted: Vec<Vector2<i32>> = oldest_first
+            .into_iter()
+            .enumerate()
+            .take_while(|(index, (since, _))| {
+                *index < over_limit || tick.saturating_sub(*since) >= grace_ticks
+            })
+            .map(|(_, (_, chunk))| chunk)
+            .collect();
+        for chunk in &evicted {
+            self.since.remove(chunk);
+        }
+        evicted
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_core::math::vector2::Vector2;
+
+    use super::UnwatchedChunks;
+
+    #[test]
+    fn grace_period_and_limit() {
+        let mut unwatched = UnwatchedChunks::default();
+        unwatched.insert(Vector2::new(0, 0), 10);
+        unwatched.insert(Vector2::new(1, 0), 20);
+        unwatched.insert(Vector2::new(2, 0), 30);
+        // Unwatched again, still counts from the first time
+        unwatched.insert(Vector2::new(0, 0), 25);
+
+        assert!(unwatched.evict(29, 20, 100, 0).is_empty());
+        assert_eq!(unwatched.evict(30, 20, 100, 0), vec![Vector2::new(0, 0)]);
+        // One chunk too many, the one unwatched the longest goes first
+        assert_eq!(unwatched.evict(31, 20, 101, 100), vec![Vector2::new(1, 0)]);
+        assert_eq!(unwatched.len(), 1);
+
+        unwatched.remove(&Vector2::new(2, 0));
+        assert!(unwatched.evict(1000, 20, 200, 100).is_empty());
+    }
+}
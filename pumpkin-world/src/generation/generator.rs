@@ -14,6 +14,9 @@ pub trait GeneratorInit {
 
 pub trait WorldGenerator: Sync + Send {
     fn generate_chunk(&self, at: Vector2<i32>) -> ChunkData;
+
+    /// The biome the generator picks for the column
+    fn biome(&self, at: XZBlockCoordinates) -> Biome;
 }
 
 pub(crate) trait BiomeGenerator: Sync + Send {
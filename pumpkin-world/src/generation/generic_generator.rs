@@ -2,8 +2,11 @@ use noise::{NoiseFn, Perlin};
 use pumpkin_core::math::vector2::Vector2;
 
 use crate::{
+    biome::Biome,
     chunk::{ChunkBlocks, ChunkData},
-    coordinates::{ChunkRelativeBlockCoordinates, ChunkRelativeXZBlockCoordinates},
+    coordinates::{
+        ChunkRelativeBlockCoordinates, ChunkRelativeXZBlockCoordinates, XZBlockCoordinates,
+    },
     WORLD_LOWEST_Y,
 };
 
@@ -33,6 +36,10 @@ impl<B: BiomeGenerator + GeneratorInit, T: PerlinTerrainGenerator + GeneratorIni
 }
 
 impl<B: BiomeGenerator, T: PerlinTerrainGenerator> WorldGenerator for GenericGenerator<B, T> {
+    fn biome(&self, at: XZBlockCoordinates) -> Biome {
+        self.biome_generator.generate_biome(at)
+    }
+
     fn generate_chunk(&self, at: Vector2<i32>) -> ChunkData {
         let mut blocks = ChunkBlocks::default();
         self.terrain_generator.prepare_chunk(&at, &self.perlin);
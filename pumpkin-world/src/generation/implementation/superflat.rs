@@ -1,4 +1,5 @@
-use pumpkin_core::math::vector2::Vector2;
+use pumpkin_core::math::{vector2::Vector2, vector3::Vector3};
+use pumpkin_macros::block_state;
 
 use crate::{
     biome::Biome,
@@ -6,13 +7,13 @@ use crate::{
     coordinates::XZBlockCoordinates,
     generation::{
         generator::{BiomeGenerator, GeneratorInit, TerrainGenerator},
-        generic_generator::GenericGenerator,
         Seed,
     },
 };
 
-#[expect(dead_code)]
-pub type SuperflatGenerator = GenericGenerator<SuperflatBiomeGenerator, SuperflatTerrainGenerator>;
+use super::test::TestGenerator;
+
+pub type SuperflatGenerator = TestGenerator<SuperflatBiomeGenerator, SuperflatTerrainGenerator>;
 
 pub(crate) struct SuperflatBiomeGenerator {}
 
@@ -45,20 +46,14 @@ impl TerrainGenerator for SuperflatTerrainGenerator {
     fn generate_block(
         &self,
         _chunk_pos: &Vector2<i32>,
-        _at: pumpkin_core::math::vector3::Vector3<i32>,
+        at: Vector3<i32>,
         _biome: Biome,
     ) -> BlockState {
-        todo!()
-    }
-
-    /*
-    fn generate_block(&self, at: BlockCoordinates, _: Biome) -> BlockState {
-        match *at.y {
+        match at.y {
             -64 => block_state!("bedrock"),
             -63..=-62 => block_state!("dirt"),
             -61 => block_state!("grass_block"),
             _ => BlockState::AIR,
         }
     }
-    */
 }
@@ -39,6 +39,10 @@ impl<B: BiomeGenerator + GeneratorInit, T: TerrainGenerator + GeneratorInit> Gen
 }
 
 impl<B: BiomeGenerator, T: TerrainGenerator> WorldGenerator for TestGenerator<B, T> {
+    fn biome(&self, at: XZBlockCoordinates) -> Biome {
+        self.biome_generator.generate_biome(at)
+    }
+
     fn generate_chunk(&self, at: Vector2<i32>) -> ChunkData {
         let mut blocks = ChunkBlocks::default();
         self.terrain_generator.prepare_chunk(&at);
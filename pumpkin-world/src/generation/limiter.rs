@@ -0,0 +1,112 @@
+use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+    sync::{Condvar, Mutex},
+};
+
+/// Limits how many chunks get generated at the same time.
+///
+/// Waiting generations are queued by their priority, which is the position of the chunk in the request.
+/// Since every request is sorted by distance, the nearest chunks of all players get generated first
+pub struct GenerationLimiter {
+    state: Mutex<LimiterState>,
+    condvar: Condvar,
+}
+
+struct LimiterState {
+    /// How many generations can still be started, `None` if there is no limit
+    available: Option<usize>,
+    /// (priority, ticket) of all waiting generations, lower goes first
+    waiting: BinaryHeap<Reverse<(usize, u64)>>,
+    next_ticket: u64,
+}
+
+/// Allows to generate a chunk, gets released when dropped
+pub struct GenerationPermit<'a> {
+    limiter: &'a GenerationLimiter,
+}
+
+impl GenerationLimiter {
+    /// `max_concurrent` of 0 disables the limit
+    pub fn new(max_concurrent: usize) -> Self {
+        Self {
+            state: Mutex::new(LimiterState {
+                available: (max_concurrent != 0).then_some(max_concurrent),
+                waiting: BinaryHeap::new(),
+                next_ticket: 0,
+            }),
+            condvar: Condvar::new(),
+        }
+    }
+
+    /// Blocks until the generation is allowed to start
+    pub fn acquire(&self, priority: usize) -> GenerationPermit<'_> {
+        let mut state = self.state.lock().expect("Generation limiter was poisoned");
+        if state.available.is_none() {
+            return GenerationPermit { limiter: self };
+        }
+
+        let ticket = state.next_ticket;
+        state.next_ticket += 1;
+        state.waiting.push(Reverse((priority, ticket)));
+
+        let mut state = self
+            .condvar
+            .wait_while(state, |state| {
+                state.available == Some(0)
+                    || state.waiting.peek() != Some(&Reverse((priority, ticket)))
+            })
+            .expect("Generation limiter was poisoned");
+        state.waiting.pop();
+        if let Some(available) = state.available.as_mut() {
+            *available -= 1;
+        }
+        // The next waiting generation might be able to start as well
+        self.condvar.notify_all();
+        GenerationPermit { limiter: self }
+    }
+
+    /// How many generations are waiting to be started
+    pub fn queue_depth(&self) -> usize {
+        self.state
+            .lock()
+            .expect("Generation limiter was poisoned")
+            .waiting
+            .len()
+    }
+}
+
+impl Drop for GenerationPermit<'_> {
+    fn drop(&mut self) {
+        let mut state = self
+            .limiter
+            .state
+            .lock()
+            .expect("Generation limiter was poisoned");
+        if let Some(available) = state.available.as_mut() {
+            *available += 1;
+            self.limiter.condvar.notify_all();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::GenerationLimiter;
+
+    #[test]
+    fn permits_are_released() {
+        let limiter = GenerationLimiter::new(1);
+        drop(limiter.acquire(0));
+        drop(limiter.acquire(1));
+        assert_eq!(limiter.queue_depth(), 0);
+    }
+
+    #[test]
+    fn unlimited() {
+        let limiter = GenerationLimiter::new(0);
+        let _first = limiter.acquire(0);
+        let _second = limiter.acquire(0);
+        assert_eq!(limiter.queue_depth(), 0);
+    }
+}
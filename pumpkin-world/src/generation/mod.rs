@@ -8,6 +8,7 @@ mod generator;
 mod generic_generator;
 pub mod height_limit;
 mod implementation;
+mod limiter;
 pub mod noise;
 pub mod ore_sampler;
 mod positions;
@@ -17,8 +18,10 @@ mod seed;
 pub use generator::WorldGenerator;
 use implementation::{
     //overworld::biome::plains::PlainsGenerator,
+    superflat::SuperflatGenerator,
     test::{TestBiomeGenerator, TestGenerator, TestTerrainGenerator},
 };
+pub use limiter::GenerationLimiter;
 pub use seed::Seed;
 
 use generator::GeneratorInit;
@@ -29,6 +32,11 @@ pub fn get_world_gen(seed: Seed) -> Box<dyn WorldGenerator> {
     Box::new(TestGenerator::<TestBiomeGenerator, TestTerrainGenerator>::new(seed))
 }
 
+/// A superflat world, used by in-memory levels so tests know where the ground is
+pub(crate) fn get_flat_world_gen(seed: Seed) -> Box<dyn WorldGenerator> {
+    Box::new(SuperflatGenerator::new(seed))
+}
+
 pub mod section_coords {
     use num_traits::PrimInt;
 
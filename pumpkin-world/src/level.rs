@@ -1,21 +1,34 @@
-use std::{path::PathBuf, sync::Arc};
+use std::{
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
 
 use dashmap::{DashMap, Entry};
 use num_traits::Zero;
-use pumpkin_core::math::vector2::Vector2;
-use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
-use tokio::{
-    runtime::Handle,
-    sync::{mpsc, RwLock},
+use pumpkin_config::ADVANCED_CONFIG;
+use pumpkin_core::math::{vector2::Vector2, vector3::Vector3};
+use rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
+use tokio::sync::{
+    mpsc::{self, error::TrySendError},
+    RwLock,
 };
 
 use crate::{
+    biome::Biome,
     chunk::{
         anvil::AnvilChunkReader, ChunkData, ChunkParsingError, ChunkReader, ChunkReadingError,
     },
-    generation::{get_world_gen, Seed, WorldGenerator},
+    chunk_cache::{ChunkCacheStats, UnwatchedChunks},
+    coordinates::XZBlockCoordinates,
+    generation::{get_flat_world_gen, get_world_gen, GenerationLimiter, Seed, WorldGenerator},
     lock::{anvil::AnvilLevelLocker, LevelLocker},
-    world_info::{anvil::AnvilLevelInfo, LevelData, WorldInfoReader, WorldInfoWriter},
+    world_info::{
+        anvil::AnvilLevelInfo, LevelData, WorldInfoError, WorldInfoReader, WorldInfoWriter,
+    },
 };
 
 /// The `Level` module provides functionality for working with chunks within or outside a Minecraft world.
@@ -29,13 +42,29 @@ use crate::{
 /// For more details on world generation, refer to the `WorldGenerator` module.
 pub struct Level {
     pub seed: Seed,
-    pub level_info: LevelData,
+    /// Whether the level generates superflat terrain
+    flat: bool,
+    level_info: Mutex<LevelData>,
+    /// Whether the level info holds a world spawn, see [`Level::spawn`]
+    has_spawn: AtomicBool,
     world_info_writer: Arc<dyn WorldInfoWriter>,
     level_folder: LevelFolder,
     loaded_chunks: Arc<DashMap<Vector2<i32>, Arc<RwLock<ChunkData>>>>,
     chunk_watchers: Arc<DashMap<Vector2<i32>, usize>>,
+    /// Loaded chunks nobody watches, which get unloaded by [`Level::tick_chunk_cache`]
+    unwatched_chunks: Mutex<UnwatchedChunks>,
+    /// The chunks loaded or generated since the last call of [`Level::take_loaded_chunks`]
+    newly_loaded_chunks: Mutex<Vec<Vector2<i32>>>,
+    /// How often [`Level::tick_chunk_cache`] ran
+    chunk_cache_ticks: AtomicU64,
+    evicted_chunks: AtomicU64,
     chunk_reader: Arc<dyn ChunkReader>,
     world_gen: Arc<dyn WorldGenerator>,
+    generation_limiter: Arc<GenerationLimiter>,
+    /// Limits how many chunks are loaded or generated at the same time, nearer chunks first
+    load_limiter: Arc<GenerationLimiter>,
+    /// In-memory levels can't save their chunks, so they never unload them
+    in_memory: bool,
     // Gets unlocked when dropped
     // TODO: Make this a trait
     _locker: Arc<AnvilLevelLocker>,
@@ -64,44 +93,156 @@ impl Level {
         let locker = AnvilLevelLocker::look(&level_folder).expect("Failed to lock level");
 
         // TODO: Load info correctly based on world format type
-        let level_info = AnvilLevelInfo
-            .read_world_info(&level_folder)
-            .unwrap_or_default(); // TODO: Improve error handling
+        let level_info = AnvilLevelInfo.read_world_info(&level_folder);
+        // A level without info is new, so its spawn isn't known yet
+        let has_spawn = level_info.is_ok();
+        let level_info = level_info.unwrap_or_default(); // TODO: Improve error handling
         let seed = Seed(level_info.world_gen_settings.seed as u64);
         let world_gen = get_world_gen(seed).into();
 
         Self {
             seed,
+            flat: false,
             world_gen,
             world_info_writer: Arc::new(AnvilLevelInfo),
             level_folder,
             chunk_reader: Arc::new(AnvilChunkReader::new()),
             loaded_chunks: Arc::new(DashMap::new()),
             chunk_watchers: Arc::new(DashMap::new()),
-            level_info,
+            unwatched_chunks: Mutex::new(UnwatchedChunks::default()),
+            newly_loaded_chunks: Mutex::new(Vec::new()),
+            chunk_cache_ticks: AtomicU64::new(0),
+            evicted_chunks: AtomicU64::new(0),
+            level_info: Mutex::new(level_info),
+            has_spawn: AtomicBool::new(has_spawn),
+            generation_limiter: Arc::new(GenerationLimiter::new(
+                ADVANCED_CONFIG.chunk_generation.max_concurrent_generations,
+            )),
+            load_limiter: Arc::new(GenerationLimiter::new(
+                ADVANCED_CONFIG.chunk_generation.max_concurrent_loads,
+            )),
+            in_memory: false,
             _locker: Arc::new(locker),
         }
     }
 
+    /// A level which only lives in memory, nothing is read from or written to disk.
+    ///
+    /// All chunks are generated superflat and stay loaded, which makes it useful for tests
+    pub fn in_memory() -> Self {
+        let seed = Seed(0);
+        Self {
+            seed,
+            flat: true,
+            world_gen: get_flat_world_gen(seed).into(),
+            world_info_writer: Arc::new(InMemoryStorage),
+            level_folder: LevelFolder {
+                root_folder: PathBuf::new(),
+                region_folder: PathBuf::new(),
+            },
+            chunk_reader: Arc::new(InMemoryStorage),
+            loaded_chunks: Arc::new(DashMap::new()),
+            chunk_watchers: Arc::new(DashMap::new()),
+            unwatched_chunks: Mutex::new(UnwatchedChunks::default()),
+            newly_loaded_chunks: Mutex::new(Vec::new()),
+            chunk_cache_ticks: AtomicU64::new(0),
+            evicted_chunks: AtomicU64::new(0),
+            level_info: Mutex::new(LevelData::default()),
+            has_spawn: AtomicBool::new(false),
+            generation_limiter: Arc::new(GenerationLimiter::new(0)),
+            load_limiter: Arc::new(GenerationLimiter::new(0)),
+            in_memory: true,
+            _locker: Arc::new(AnvilLevelLocker::unlocked()),
+        }
+    }
+
+    /// Superflat worlds have their horizon at the bottom of the world instead of at sea level
+    pub const fn is_flat(&self) -> bool {
+        self.flat
+    }
+
     pub async fn save(&self) {
         log::info!("Saving level...");
-        // lets first save all chunks
-        for chunk in self.loaded_chunks.iter() {
-            let chunk = chunk.read().await;
-            self.clean_chunk(&chunk.position);
-        }
+        // lets first save all chunks, cleaning them removes them from the loaded chunks so we can't iterate those
+        let chunks: Vec<_> = self
+            .loaded_chunks
+            .iter()
+            .map(|chunk| *chunk.key())
+            .collect();
+        self.clean_chunks(&chunks);
         // then lets save the world info
+        self.save_info();
+    }
+
+    /// Writes the world info without touching the chunks, used for periodic saving
+    // TODO: Also write the loaded chunks once `Level::write_chunk` is implemented
+    pub fn save_info(&self) {
+        let level_info = self
+            .level_info
+            .lock()
+            .expect("Level info was poisoned")
+            .clone();
         self.world_info_writer
-            .write_world_info(self.level_info.clone(), &self.level_folder)
+            .write_world_info(level_info, &self.level_folder)
             .expect("Failed to save world info");
     }
 
+    /// The world spawn and the yaw players look at when spawning, `None` for a new level which has none yet
+    pub fn spawn(&self) -> Option<(Vector3<i32>, f32)> {
+        if !self.has_spawn.load(Ordering::Relaxed) {
+            return None;
+        }
+        let info = self.level_info.lock().expect("Level info was poisoned");
+        Some((
+            Vector3::new(info.spawn_x, info.spawn_y, info.spawn_z),
+            info.spawn_angle,
+        ))
+    }
+
+    /// Moves the world spawn, it gets saved with the world info
+    pub fn set_spawn(&self, position: Vector3<i32>, angle: f32) {
+        let mut info = self.level_info.lock().expect("Level info was poisoned");
+        info.spawn_x = position.x;
+        info.spawn_y = position.y;
+        info.spawn_z = position.z;
+        info.spawn_angle = angle;
+        self.has_spawn.store(true, Ordering::Relaxed);
+    }
+
     pub fn get_block() {}
 
+    /// How many chunks are currently waiting to be generated
+    pub fn chunk_generation_queue_depth(&self) -> usize {
+        self.generation_limiter.queue_depth()
+    }
+
+    /// The biome of the block column. Chunks don't store their biomes yet, so it is the biome the generator picks
+    pub fn biome(&self, at: XZBlockCoordinates) -> Biome {
+        self.world_gen.biome(at)
+    }
+
+    pub fn is_chunk_loaded(&self, chunk: &Vector2<i32>) -> bool {
+        self.loaded_chunks.contains_key(chunk)
+    }
+
+    /// The chunk if it is loaded, never loads or generates it
+    pub fn get_loaded_chunk(&self, chunk: &Vector2<i32>) -> Option<Arc<RwLock<ChunkData>>> {
+        self.loaded_chunks
+            .get(chunk)
+            .map(|chunk| chunk.value().clone())
+    }
+
     pub fn loaded_chunk_count(&self) -> usize {
         self.loaded_chunks.len()
     }
 
+    pub fn loaded_chunk_positions(&self) -> Vec<Vector2<i32>> {
+        self.loaded_chunks
+            .iter()
+            .map(|chunk| *chunk.key())
+            .collect()
+    }
+
     pub fn list_cached(&self) {
         for entry in self.loaded_chunks.iter() {
             log::debug!("In map: {:?}", entry.key());
@@ -118,6 +259,10 @@ impl Level {
     }
 
     pub fn mark_chunk_as_newly_watched(&self, chunk: Vector2<i32>) {
+        self.unwatched_chunks
+            .lock()
+            .expect("Unwatched chunks were poisoned")
+            .remove(&chunk);
         match self.chunk_watchers.entry(chunk) {
             Entry::Occupied(mut occupied) => {
                 let value = occupied.get_mut();
@@ -176,16 +321,93 @@ impl Level {
     }
 
     pub fn clean_chunk(&self, chunk: &Vector2<i32>) {
+        if self.in_memory {
+            return;
+        }
         if let Some(data) = self.loaded_chunks.remove(chunk) {
             self.write_chunk(data);
         }
     }
 
+    /// Unloads the chunks nobody watches after the grace period from the config.
+    /// Chunks which get watched again before are kept, so players going back and forth don't load them over and over
+    pub fn schedule_unload(&self, chunks: &[Vector2<i32>]) {
+        if self.in_memory {
+            return;
+        }
+        let tick = self.chunk_cache_ticks.load(Ordering::Relaxed);
+        let mut unwatched = self
+            .unwatched_chunks
+            .lock()
+            .expect("Unwatched chunks were poisoned");
+        for chunk in chunks {
+            if !self.is_chunk_watched(chunk) && self.is_chunk_loaded(chunk) {
+                unwatched.insert(*chunk, tick);
+            }
+        }
+    }
+
+    /// Unloads the unwatched chunks whose grace period is over, and the ones unwatched the longest while more chunks
+    /// than allowed are loaded. Should be called once every tick, returns the chunks which got unloaded
+    pub fn tick_chunk_cache(&self) -> Vec<Vector2<i32>> {
+        if self.in_memory {
+            return Vec::new();
+        }
+        let config = &ADVANCED_CONFIG.chunk_cache;
+        let tick = self.chunk_cache_ticks.fetch_add(1, Ordering::Relaxed) + 1;
+        let evicted = self
+            .unwatched_chunks
+            .lock()
+            .expect("Unwatched chunks were poisoned")
+            .evict(
+                tick,
+                config.unload_grace_ticks,
+                self.loaded_chunk_count(),
+                config.max_resident_chunks,
+            );
+        let mut unloaded = Vec::new();
+        for chunk in evicted {
+            // A player could have started watching it in the meantime
+            if !self.is_chunk_watched(&chunk) {
+                self.clean_chunk(&chunk);
+                self.evicted_chunks.fetch_add(1, Ordering::Relaxed);
+                unloaded.push(chunk);
+            }
+        }
+        unloaded
+    }
+
+    /// The chunks which got loaded or generated since the last call, e.g. to index what is in them
+    pub fn take_loaded_chunks(&self) -> Vec<Vector2<i32>> {
+        std::mem::take(
+            &mut *self
+                .newly_loaded_chunks
+                .lock()
+                .expect("Newly loaded chunks were poisoned"),
+        )
+    }
+
+    /// How many chunks are loaded and how many got unloaded, for monitoring
+    pub fn chunk_cache_stats(&self) -> ChunkCacheStats {
+        ChunkCacheStats {
+            resident: self.loaded_chunk_count(),
+            unwatched: self
+                .unwatched_chunks
+                .lock()
+                .expect("Unwatched chunks were poisoned")
+                .len(),
+            evicted: self.evicted_chunks.load(Ordering::Relaxed),
+        }
+    }
+
     pub fn is_chunk_watched(&self, chunk: &Vector2<i32>) -> bool {
         self.chunk_watchers.get(chunk).is_some()
     }
 
     pub fn clean_memory(&self, chunks_to_check: &[Vector2<i32>]) {
+        if self.in_memory {
+            return;
+        }
         chunks_to_check.iter().for_each(|chunk| {
             if let Some(entry) = self.chunk_watchers.get(chunk) {
                 if entry.value().is_zero() {
@@ -225,14 +447,23 @@ impl Level {
 
     /// Reads/Generates many chunks in a world
     /// Note: The order of the output chunks will almost never be in the same order as the order of input chunks
+    /// Chunks which can neither be loaded nor generated are left out
+    ///
+    /// Loading never waits for the receiver, so a slow receiver can't hold up the threads loading chunks for
+    /// everyone else. Returns the chunks which didn't fit into the channel, to be sent once it has room again
     pub fn fetch_chunks(
         &self,
         chunks: &[Vector2<i32>],
-        channel: mpsc::Sender<Arc<RwLock<ChunkData>>>,
-        rt: &Handle,
-    ) {
-        chunks.par_iter().for_each(|at| {
-            let channel = channel.clone();
+        channel: &mpsc::Sender<Arc<RwLock<ChunkData>>>,
+    ) -> Vec<Arc<RwLock<ChunkData>>> {
+        let backlog = Mutex::new(Vec::new());
+        // Requests are sorted by distance, so the index is used as the priority.
+        // This way the nearest chunks of every player are loaded first, even if someone else requested a lot of chunks
+        chunks.par_iter().enumerate().for_each(|(priority, at)| {
+            // Nobody is waiting for the chunks anymore, e.g. because the player left
+            if channel.is_closed() {
+                return;
+            }
             let loaded_chunks = self.loaded_chunks.clone();
             let chunk_reader = self.chunk_reader.clone();
             let level_info = self.level_folder.clone();
@@ -242,8 +473,11 @@ impl Level {
             let chunk = loaded_chunks
                 .get(&chunk_pos)
                 .map(|entry| entry.value().clone())
-                .unwrap_or_else(|| {
-                    let loaded_chunk =
+                .or_else(|| {
+                    // A corrupted region file or a bug in the generator must not take down the whole server,
+                    // the chunk is skipped instead so the receiver sees it as unavailable
+                    let loaded_chunk = panic::catch_unwind(AssertUnwindSafe(|| {
+                        let _permit = self.load_limiter.acquire(priority);
                         match Self::load_chunk_from_save(chunk_reader, &level_info, chunk_pos) {
                             Ok(chunk) => chunk,
                             Err(err) => {
@@ -256,26 +490,68 @@ impl Level {
                             }
                         }
                         .unwrap_or_else(|| {
+                            let _permit = self.generation_limiter.acquire(priority);
                             Arc::new(RwLock::new(world_gen.generate_chunk(chunk_pos)))
-                        });
+                        })
+                    }))
+                    .inspect_err(|_| {
+                        log::error!(
+                            "Failed to load or generate chunk {:?}, skipping it",
+                            chunk_pos
+                        );
+                    })
+                    .ok()?;
 
                     if let Some(data) = loaded_chunks.get(&chunk_pos) {
                         // Another thread populated in between the previous check and now
                         // We did work, but this is basically like a cache miss, not much we
                         // can do about it
-                        data.value().clone()
+                        Some(data.value().clone())
                     } else {
                         loaded_chunks.insert(chunk_pos, loaded_chunk.clone());
-                        loaded_chunk
+                        self.newly_loaded_chunks
+                            .lock()
+                            .expect("Newly loaded chunks were poisoned")
+                            .push(chunk_pos);
+                        Some(loaded_chunk)
                     }
                 });
+            let Some(chunk) = chunk else {
+                return;
+            };
 
-            rt.spawn(async move {
-                let _ = channel
-                    .send(chunk)
-                    .await
-                    .inspect_err(|err| log::error!("unable to send chunk to channel: {}", err));
-            });
+            match channel.try_send(chunk) {
+                // Nobody is waiting for the chunk anymore
+                Ok(()) | Err(TrySendError::Closed(_)) => {}
+                Err(TrySendError::Full(chunk)) => backlog
+                    .lock()
+                    .expect("Chunk backlog was poisoned")
+                    .push(chunk),
+            }
         });
+        backlog.into_inner().expect("Chunk backlog was poisoned")
+    }
+}
+
+/// Storage of an in-memory level, which has nothing saved
+struct InMemoryStorage;
+
+impl ChunkReader for InMemoryStorage {
+    fn read_chunk(
+        &self,
+        _save_file: &LevelFolder,
+        _at: &Vector2<i32>,
+    ) -> Result<ChunkData, ChunkReadingError> {
+        Err(ChunkReadingError::ChunkNotExist)
+    }
+}
+
+impl WorldInfoWriter for InMemoryStorage {
+    fn write_world_info(
+        &self,
+        _info: LevelData,
+        _level_folder: &LevelFolder,
+    ) -> Result<(), WorldInfoError> {
+        Ok(())
     }
 }
@@ -10,6 +10,7 @@ use pumpkin_core::math::vector2::Vector2;
 pub mod biome;
 pub mod block;
 pub mod chunk;
+pub mod chunk_cache;
 pub mod coordinates;
 pub mod cylindrical_chunk_iterator;
 pub mod dimension;
@@ -22,6 +23,13 @@ pub mod world_info;
 pub const WORLD_HEIGHT: usize = 384;
 pub const WORLD_LOWEST_Y: i16 = -64;
 pub const WORLD_MAX_Y: i16 = WORLD_HEIGHT as i16 - WORLD_LOWEST_Y.abs();
+
+/// Whether blocks can be at the y, the world goes from `WORLD_LOWEST_Y` up to just below `WORLD_MAX_Y`
+#[must_use]
+pub const fn is_in_world(y: i32) -> bool {
+    WORLD_LOWEST_Y as i32 <= y && y < WORLD_MAX_Y as i32
+}
+
 pub const DIRECT_PALETTE_BITS: u32 = 15;
 
 #[macro_export]
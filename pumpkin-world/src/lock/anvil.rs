@@ -12,6 +12,13 @@ const SESSION_LOCK_FILE_NAME: &str = "session.lock";
 
 const SNOWMAN: &[u8] = "☃".as_bytes();
 
+impl AnvilLevelLocker {
+    /// Doesn't lock anything, for levels which are never saved
+    pub const fn unlocked() -> Self {
+        Self { _lock: None }
+    }
+}
+
 impl LevelLocker<Self> for AnvilLevelLocker {
     fn look(folder: &crate::level::LevelFolder) -> Result<Self, LockError> {
         let file_path = folder.root_folder.join(SESSION_LOCK_FILE_NAME);
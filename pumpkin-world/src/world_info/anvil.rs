@@ -59,22 +59,27 @@ impl WorldInfoWriter for AnvilLevelInfo {
                 spawn_x: info.spawn_x,
                 spawn_y: info.spawn_y,
                 spawn_z: info.spawn_z,
+                spawn_angle: info.spawn_angle,
                 nbt_version: info.nbt_version,
                 version: info.version,
             },
         };
-        // convert it into nbt
-        let nbt = pumpkin_nbt::serializer::to_bytes_unnamed(&level).unwrap();
-        // now compress using GZip, TODO: im not sure about the to_vec, but writer is not implemented for BytesMut, see https://github.com/tokio-rs/bytes/pull/478
-        let mut encoder = GzEncoder::new(nbt.to_vec(), Compression::best());
-        let compressed_data = Vec::new();
-        encoder.write_all(&compressed_data)?;
-
-        // open file
+        // convert it into nbt, files have a named root tag unlike the network
+        let nbt = pumpkin_nbt::serializer::to_bytes(&level, String::new()).unwrap();
+        // now compress using GZip
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+        encoder.write_all(&nbt)?;
+        let compressed_data = encoder.finish()?;
+
+        // open file, a new level doesn't have one yet
         let path = level_folder.root_folder.join(LEVEL_DAT_FILE_NAME);
-        let mut world_info_file = OpenOptions::new().write(true).open(path)?;
+        let mut world_info_file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
         // write compressed data into file
-        world_info_file.write_all(&compressed_data).unwrap();
+        world_info_file.write_all(&compressed_data)?;
 
         Ok(())
     }
@@ -86,3 +91,38 @@ pub struct LevelDat {
     #[serde(rename = "Data")]
     pub data: LevelData,
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        level::LevelFolder,
+        world_info::{LevelData, WorldInfoReader, WorldInfoWriter},
+    };
+
+    use super::AnvilLevelInfo;
+
+    #[test]
+    fn spawn_round_trip() {
+        let root_folder =
+            std::env::temp_dir().join(format!("pumpkin-level-{}", std::process::id()));
+        std::fs::create_dir_all(&root_folder).unwrap();
+        let folder = LevelFolder {
+            region_folder: root_folder.join("region"),
+            root_folder,
+        };
+        let info = LevelData {
+            spawn_x: 12,
+            spawn_y: 70,
+            spawn_z: -5,
+            spawn_angle: 90.0,
+            ..Default::default()
+        };
+        AnvilLevelInfo.write_world_info(info, &folder).unwrap();
+        let read = AnvilLevelInfo.read_world_info(&folder);
+        std::fs::remove_dir_all(&folder.root_folder).unwrap();
+
+        let read = read.unwrap();
+        assert_eq!((read.spawn_x, read.spawn_y, read.spawn_z), (12, 70, -5));
+        assert!((read.spawn_angle - 90.0).abs() < f32::EPSILON);
+    }
+}
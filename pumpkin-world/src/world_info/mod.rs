@@ -40,6 +40,9 @@ pub struct LevelData {
     pub spawn_y: i32,
     // The Z coordinate of the world spawn.
     pub spawn_z: i32,
+    // The yaw players look at when spawning.
+    #[serde(default)]
+    pub spawn_angle: f32,
     #[serde(rename = "version")]
     // The NBT version of the level
     pub nbt_version: i32,
@@ -104,6 +107,7 @@ impl Default for LevelData {
             spawn_x: 0,
             spawn_y: 200,
             spawn_z: 0,
+            spawn_angle: 0.0,
             nbt_version: -1,
             version: Default::default(),
         }
@@ -122,7 +122,7 @@ impl ChestBlock {
                 .await;
         }
 
-        if let Some(e) = get_block("minecraft:chest").cloned() {
+        if let Some(e) = get_block("minecraft:chest") {
             server
                 .broadcast_packet_all(&CBlockAction::new(
                     &location,
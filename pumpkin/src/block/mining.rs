@@ -0,0 +1,619 @@
+use pumpkin_registry::{get_tag_values, TagCategory, TagType};
+use pumpkin_world::block::block_registry::get_block_by_state_id;
+
+/// The kinds of tools which mine some blocks faster, see [`block_requires_tool`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ToolType {
+    Pickaxe,
+    Axe,
+    Shovel,
+    Hoe,
+}
+
+impl ToolType {
+    const ALL: [Self; 4] = [Self::Pickaxe, Self::Axe, Self::Shovel, Self::Hoe];
+
+    /// The block tag of the blocks this kind of tool mines faster
+    const fn mineable_tag(self) -> &'static str {
+        match self {
+            Self::Pickaxe => "mineable/pickaxe",
+            Self::Axe => "mineable/axe",
+            Self::Shovel => "mineable/shovel",
+            Self::Hoe => "mineable/hoe",
+        }
+    }
+}
+
+/// What a tool is made of, deciding how fast it mines and which blocks drop something
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ToolMaterial {
+    Wood,
+    Stone,
+    Iron,
+    Diamond,
+    Gold,
+    Netherite,
+}
+
+impl ToolMaterial {
+    /// How many times faster than a hand the tool mines the blocks it is made for, like Vanilla
+    const fn speed(self) -> f32 {
+        match self {
+            Self::Wood => 2.0,
+            Self::Stone => 4.0,
+            Self::Iron => 6.0,
+            Self::Diamond => 8.0,
+            Self::Gold => 12.0,
+            Self::Netherite => 9.0,
+        }
+    }
+
+    /// The block tag of the blocks which need a better tool to drop something
+    const fn incorrect_tag(self) -> &'static str {
+        match self {
+            Self::Wood => "incorrect_for_wooden_tool",
+            Self::Stone => "incorrect_for_stone_tool",
+            Self::Iron => "incorrect_for_iron_tool",
+            Self::Diamond => "incorrect_for_diamond_tool",
+            Self::Gold => "incorrect_for_gold_tool",
+            Self::Netherite => "incorrect_for_netherite_tool",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Tool {
+    pub kind: ToolType,
+    pub material: ToolMaterial,
+}
+
+impl Tool {
+    /// The tool an item is, e.g. `diamond_pickaxe`. `None` for items which are no mining tool
+    #[must_use]
+    pub fn from_item_name(name: &str) -> Option<Self> {
+        let (material, kind) = name
+            .strip_prefix("minecraft:")
+            .unwrap_or(name)
+            .split_once('_')?;
+        let material = match material {
+            "wooden" => ToolMaterial::Wood,
+            "stone" => ToolMaterial::Stone,
+            "iron" => ToolMaterial::Iron,
+            "diamond" => ToolMaterial::Diamond,
+            "golden" => ToolMaterial::Gold,
+            "netherite" => ToolMaterial::Netherite,
+            _ => return None,
+        };
+        let kind = match kind {
+            "pickaxe" => ToolType::Pickaxe,
+            "axe" => ToolType::Axe,
+            "shovel" => ToolType::Shovel,
+            "hoe" => ToolType::Hoe,
+            _ => return None,
+        };
+        Some(Self { kind, material })
+    }
+}
+
+/// Whether the block is in the block tag, the tags are flattened so they only hold block names
+fn is_in_block_tag(tag: &str, block_name: &str) -> bool {
+    get_tag_values(TagCategory::Block, tag).is_some_and(|values| {
+        values
+            .iter()
+            .any(|value| matches!(value, TagType::Item(name) if name == block_name))
+    })
+}
+
+/// How long the block takes to mine, negative for blocks which can't be mined like bedrock
+#[must_use]
+pub fn block_hardness(state_id: u16) -> f32 {
+    get_block_by_state_id(state_id).map_or(-1.0, |block| block.hardness)
+}
+
+/// The kind of tool the block needs to drop something when mined, see [`REQUIRES_TOOL`]
+#[must_use]
+pub fn block_requires_tool(state_id: u16) -> Option<ToolType> {
+    let block = get_block_by_state_id(state_id)?;
+    if !REQUIRES_TOOL.contains(&block.name.as_str()) {
+        return None;
+    }
+    ToolType::ALL
+        .into_iter()
+        .find(|kind| is_in_block_tag(kind.mineable_tag(), &block.name))
+}
+
+/// Whether mining the block with the tool, or the hand for `None`, drops something
+#[must_use]
+pub fn can_harvest(state_id: u16, tool: Option<Tool>) -> bool {
+    let Some(required) = block_requires_tool(state_id) else {
+        return true;
+    };
+    let Some(block) = get_block_by_state_id(state_id) else {
+        return true;
+    };
+    tool.is_some_and(|tool| {
+        tool.kind == required && !is_in_block_tag(tool.material.incorrect_tag(), &block.name)
+    })
+}
+
+/// How much of the block gets mined every tick with the tool, or the hand for `None`, following Vanilla's formula.
+/// The block breaks once this adds up to 1, so blocks with 1 or more break instantly.
+///
+/// `efficiency` is the level of the Efficiency enchantment and `haste` the level of the Haste effect, 0 for none
+#[must_use]
+pub fn break_speed(state_id: u16, tool: Option<Tool>, efficiency: u8, haste: u8) -> f32 {
+    let hardness = block_hardness(state_id);
+    if hardness < 0.0 {
+        return 0.0;
+    }
+    let Some(block) = get_block_by_state_id(state_id) else {
+        return 0.0;
+    };
+    // Tools of the right kind mine faster even if their material is too weak for drops
+    let mut speed = tool
+        .filter(|tool| is_in_block_tag(tool.kind.mineable_tag(), &block.name))
+        .map_or(1.0, |tool| tool.material.speed());
+    if speed > 1.0 && efficiency > 0 {
+        speed += f32::from(efficiency).powi(2) + 1.0;
+    }
+    speed *= 0.2f32.mul_add(f32::from(haste), 1.0);
+    let divisor = if can_harvest(state_id, tool) {
+        30.0
+    } else {
+        100.0
+    };
+    speed / hardness / divisor
+}
+
+/// The blocks which only drop something when mined with the right tool, like Vanilla's `requiresCorrectToolForDrops`.
+/// The block registry doesn't have it, so it is listed here
+// TODO: Use the block registry once it has `requiresCorrectToolForDrops`
+const REQUIRES_TOOL: &[&str] = &[
+    "stone",
+    "granite",
+    "polished_granite",
+    "diorite",
+    "polished_diorite",
+    "andesite",
+    "polished_andesite",
+    "cobblestone",
+    "gold_ore",
+    "deepslate_gold_ore",
+    "iron_ore",
+    "deepslate_iron_ore",
+    "coal_ore",
+    "deepslate_coal_ore",
+    "nether_gold_ore",
+    "lapis_ore",
+    "deepslate_lapis_ore",
+    "lapis_block",
+    "dispenser",
+    "sandstone",
+    "chiseled_sandstone",
+    "cut_sandstone",
+    "gold_block",
+    "iron_block",
+    "bricks",
+    "mossy_cobblestone",
+    "obsidian",
+    "spawner",
+    "diamond_ore",
+    "deepslate_diamond_ore",
+    "diamond_block",
+    "furnace",
+    "cobblestone_stairs",
+    "stone_pressure_plate",
+    "iron_door",
+    "redstone_ore",
+    "deepslate_redstone_ore",
+    "netherrack",
+    "basalt",
+    "polished_basalt",
+    "stone_bricks",
+    "mossy_stone_bricks",
+    "cracked_stone_bricks",
+    "chiseled_stone_bricks",
+    "iron_bars",
+    "chain",
+    "brick_stairs",
+    "stone_brick_stairs",
+    "nether_bricks",
+    "nether_brick_fence",
+    "nether_brick_stairs",
+    "enchanting_table",
+    "brewing_stand",
+    "end_stone",
+    "sandstone_stairs",
+    "emerald_ore",
+    "deepslate_emerald_ore",
+    "ender_chest",
+    "emerald_block",
+    "light_weighted_pressure_plate",
+    "heavy_weighted_pressure_plate",
+    "redstone_block",
+    "nether_quartz_ore",
+    "hopper",
+    "quartz_block",
+    "chiseled_quartz_block",
+    "quartz_pillar",
+    "quartz_stairs",
+    "dropper",
+    "white_terracotta",
+    "orange_terracotta",
+    "magenta_terracotta",
+    "light_blue_terracotta",
+    "yellow_terracotta",
+    "lime_terracotta",
+    "pink_terracotta",
+    "gray_terracotta",
+    "light_gray_terracotta",
+    "cyan_terracotta",
+    "purple_terracotta",
+    "blue_terracotta",
+    "brown_terracotta",
+    "green_terracotta",
+    "red_terracotta",
+    "black_terracotta",
+    "iron_trapdoor",
+    "prismarine",
+    "prismarine_bricks",
+    "dark_prismarine",
+    "prismarine_stairs",
+    "prismarine_brick_stairs",
+    "dark_prismarine_stairs",
+    "prismarine_slab",
+    "prismarine_brick_slab",
+    "dark_prismarine_slab",
+    "terracotta",
+    "coal_block",
+    "red_sandstone",
+    "chiseled_red_sandstone",
+    "cut_red_sandstone",
+    "red_sandstone_stairs",
+    "stone_slab",
+    "smooth_stone_slab",
+    "sandstone_slab",
+    "cut_sandstone_slab",
+    "petrified_oak_slab",
+    "cobblestone_slab",
+    "brick_slab",
+    "stone_brick_slab",
+    "nether_brick_slab",
+    "quartz_slab",
+    "red_sandstone_slab",
+    "cut_red_sandstone_slab",
+    "purpur_slab",
+    "smooth_stone",
+    "smooth_sandstone",
+    "smooth_quartz",
+    "smooth_red_sandstone",
+    "purpur_block",
+    "purpur_pillar",
+    "purpur_stairs",
+    "end_stone_bricks",
+    "magma_block",
+    "red_nether_bricks",
+    "bone_block",
+    "observer",
+    "white_glazed_terracotta",
+    "orange_glazed_terracotta",
+    "magenta_glazed_terracotta",
+    "light_blue_glazed_terracotta",
+    "yellow_glazed_terracotta",
+    "lime_glazed_terracotta",
+    "pink_glazed_terracotta",
+    "gray_glazed_terracotta",
+    "light_gray_glazed_terracotta",
+    "cyan_glazed_terracotta",
+    "purple_glazed_terracotta",
+    "blue_glazed_terracotta",
+    "brown_glazed_terracotta",
+    "green_glazed_terracotta",
+    "red_glazed_terracotta",
+    "black_glazed_terracotta",
+    "white_concrete",
+    "orange_concrete",
+    "magenta_concrete",
+    "light_blue_concrete",
+    "yellow_concrete",
+    "lime_concrete",
+    "pink_concrete",
+    "gray_concrete",
+    "light_gray_concrete",
+    "cyan_concrete",
+    "purple_concrete",
+    "blue_concrete",
+    "brown_concrete",
+    "green_concrete",
+    "red_concrete",
+    "black_concrete",
+    "dead_tube_coral_block",
+    "dead_brain_coral_block",
+    "dead_bubble_coral_block",
+    "dead_fire_coral_block",
+    "dead_horn_coral_block",
+    "tube_coral_block",
+    "brain_coral_block",
+    "bubble_coral_block",
+    "fire_coral_block",
+    "horn_coral_block",
+    "dead_tube_coral",
+    "dead_brain_coral",
+    "dead_bubble_coral",
+    "dead_fire_coral",
+    "dead_horn_coral",
+    "dead_tube_coral_fan",
+    "dead_brain_coral_fan",
+    "dead_bubble_coral_fan",
+    "dead_fire_coral_fan",
+    "dead_horn_coral_fan",
+    "dead_tube_coral_wall_fan",
+    "dead_brain_coral_wall_fan",
+    "dead_bubble_coral_wall_fan",
+    "dead_fire_coral_wall_fan",
+    "dead_horn_coral_wall_fan",
+    "polished_granite_stairs",
+    "smooth_red_sandstone_stairs",
+    "mossy_stone_brick_stairs",
+    "polished_diorite_stairs",
+    "mossy_cobblestone_stairs",
+    "end_stone_brick_stairs",
+    "stone_stairs",
+    "smooth_sandstone_stairs",
+    "smooth_quartz_stairs",
+    "granite_stairs",
+    "andesite_stairs",
+    "red_nether_brick_stairs",
+    "polished_andesite_stairs",
+    "diorite_stairs",
+    "polished_granite_slab",
+    "smooth_red_sandstone_slab",
+    "mossy_stone_brick_slab",
+    "polished_diorite_slab",
+    "mossy_cobblestone_slab",
+    "end_stone_brick_slab",
+    "smooth_sandstone_slab",
+    "smooth_quartz_slab",
+    "granite_slab",
+    "andesite_slab",
+    "red_nether_brick_slab",
+    "polished_andesite_slab",
+    "diorite_slab",
+    "smoker",
+    "blast_furnace",
+    "grindstone",
+    "stonecutter",
+    "bell",
+    "lantern",
+    "soul_lantern",
+    "warped_nylium",
+    "crimson_nylium",
+    "netherite_block",
+    "ancient_debris",
+    "crying_obsidian",
+    "respawn_anchor",
+    "lodestone",
+    "blackstone",
+    "blackstone_stairs",
+    "blackstone_slab",
+    "polished_blackstone",
+    "polished_blackstone_bricks",
+    "cracked_polished_blackstone_bricks",
+    "chiseled_polished_blackstone",
+    "polished_blackstone_brick_slab",
+    "polished_blackstone_brick_stairs",
+    "gilded_blackstone",
+    "polished_blackstone_stairs",
+    "polished_blackstone_slab",
+    "polished_blackstone_pressure_plate",
+    "chiseled_nether_bricks",
+    "cracked_nether_bricks",
+    "quartz_bricks",
+    "tuff",
+    "calcite",
+    "oxidized_copper",
+    "weathered_copper",
+    "exposed_copper",
+    "copper_block",
+    "copper_ore",
+    "deepslate_copper_ore",
+    "oxidized_cut_copper",
+    "weathered_cut_copper",
+    "exposed_cut_copper",
+    "cut_copper",
+    "oxidized_cut_copper_stairs",
+    "weathered_cut_copper_stairs",
+    "exposed_cut_copper_stairs",
+    "cut_copper_stairs",
+    "oxidized_cut_copper_slab",
+    "weathered_cut_copper_slab",
+    "exposed_cut_copper_slab",
+    "cut_copper_slab",
+    "waxed_copper_block",
+    "waxed_weathered_copper",
+    "waxed_exposed_copper",
+    "waxed_oxidized_copper",
+    "waxed_oxidized_cut_copper",
+    "waxed_weathered_cut_copper",
+    "waxed_exposed_cut_copper",
+    "waxed_cut_copper",
+    "waxed_oxidized_cut_copper_stairs",
+    "waxed_weathered_cut_copper_stairs",
+    "waxed_exposed_cut_copper_stairs",
+    "waxed_cut_copper_stairs",
+    "waxed_oxidized_cut_copper_slab",
+    "waxed_weathered_cut_copper_slab",
+    "waxed_exposed_cut_copper_slab",
+    "waxed_cut_copper_slab",
+    "lightning_rod",
+    "dripstone_block",
+    "deepslate",
+    "cobbled_deepslate",
+    "cobbled_deepslate_stairs",
+    "cobbled_deepslate_slab",
+    "polished_deepslate",
+    "polished_deepslate_stairs",
+    "polished_deepslate_slab",
+    "deepslate_tiles",
+    "deepslate_tile_stairs",
+    "deepslate_tile_slab",
+    "deepslate_bricks",
+    "deepslate_brick_stairs",
+    "deepslate_brick_slab",
+    "chiseled_deepslate",
+    "cracked_deepslate_bricks",
+    "cracked_deepslate_tiles",
+    "smooth_basalt",
+    "raw_iron_block",
+    "raw_copper_block",
+    "raw_gold_block",
+    "amethyst_block",
+    "budding_amethyst",
+    "cobblestone_wall",
+    "mossy_cobblestone_wall",
+    "brick_wall",
+    "prismarine_wall",
+    "red_sandstone_wall",
+    "mossy_stone_brick_wall",
+    "granite_wall",
+    "stone_brick_wall",
+    "nether_brick_wall",
+    "andesite_wall",
+    "red_nether_brick_wall",
+    "sandstone_wall",
+    "end_stone_brick_wall",
+    "diorite_wall",
+    "blackstone_wall",
+    "polished_blackstone_brick_wall",
+    "polished_blackstone_wall",
+    "cobbled_deepslate_wall",
+    "polished_deepslate_wall",
+    "deepslate_tile_wall",
+    "deepslate_brick_wall",
+    "mud_brick_wall",
+    "tuff_wall",
+    "polished_tuff_wall",
+    "tuff_brick_wall",
+    "resin_brick_wall",
+    "anvil",
+    "chipped_anvil",
+    "damaged_anvil",
+    "cauldron",
+    "water_cauldron",
+    "lava_cauldron",
+    "powder_snow_cauldron",
+    "mud_bricks",
+    "mud_brick_stairs",
+    "mud_brick_slab",
+    "crafter",
+    "tuff_slab",
+    "tuff_stairs",
+    "chiseled_tuff",
+    "polished_tuff",
+    "polished_tuff_slab",
+    "polished_tuff_stairs",
+    "tuff_bricks",
+    "tuff_brick_slab",
+    "tuff_brick_stairs",
+    "chiseled_tuff_bricks",
+    "chiseled_copper",
+    "exposed_chiseled_copper",
+    "weathered_chiseled_copper",
+    "oxidized_chiseled_copper",
+    "waxed_chiseled_copper",
+    "waxed_exposed_chiseled_copper",
+    "waxed_weathered_chiseled_copper",
+    "waxed_oxidized_chiseled_copper",
+    "copper_grate",
+    "exposed_copper_grate",
+    "weathered_copper_grate",
+    "oxidized_copper_grate",
+    "waxed_copper_grate",
+    "waxed_exposed_copper_grate",
+    "waxed_weathered_copper_grate",
+    "waxed_oxidized_copper_grate",
+    "copper_bulb",
+    "exposed_copper_bulb",
+    "weathered_copper_bulb",
+    "oxidized_copper_bulb",
+    "waxed_copper_bulb",
+    "waxed_exposed_copper_bulb",
+    "waxed_weathered_copper_bulb",
+    "waxed_oxidized_copper_bulb",
+    "copper_door",
+    "exposed_copper_door",
+    "weathered_copper_door",
+    "oxidized_copper_door",
+    "waxed_copper_door",
+    "waxed_exposed_copper_door",
+    "waxed_weathered_copper_door",
+    "waxed_oxidized_copper_door",
+    "copper_trapdoor",
+    "exposed_copper_trapdoor",
+    "weathered_copper_trapdoor",
+    "oxidized_copper_trapdoor",
+    "waxed_copper_trapdoor",
+    "waxed_exposed_copper_trapdoor",
+    "waxed_weathered_copper_trapdoor",
+    "waxed_oxidized_copper_trapdoor",
+    "resin_bricks",
+    "resin_brick_slab",
+    "resin_brick_stairs",
+    "chiseled_resin_bricks",
+    "snow",
+    "snow_block",
+];
+
+#[cfg(test)]
+mod test {
+    use pumpkin_world::block::block_registry::get_block;
+
+    use super::{
+        block_hardness, block_requires_tool, break_speed, can_harvest, Tool, ToolMaterial, ToolType,
+    };
+
+    fn state(name: &str) -> u16 {
+        get_block(name).expect("The block exists").default_state_id
+    }
+
+    #[test]
+    fn tool_requirements() {
+        assert!((block_hardness(state("stone")) - 1.5).abs() < f32::EPSILON);
+        assert!(block_hardness(state("bedrock")) < 0.0);
+        assert_eq!(block_requires_tool(state("stone")), Some(ToolType::Pickaxe));
+        assert_eq!(block_requires_tool(state("dirt")), None);
+        assert_eq!(block_requires_tool(state("oak_log")), None);
+        assert_eq!(block_requires_tool(state("snow")), Some(ToolType::Shovel));
+        // Mined faster with a pickaxe, but they drop by hand
+        assert_eq!(block_requires_tool(state("rail")), None);
+        assert_eq!(block_requires_tool(state("piston")), None);
+        assert!(can_harvest(state("rail"), None));
+        assert!(can_harvest(state("piston"), None));
+
+        let iron_pickaxe = Tool::from_item_name("minecraft:iron_pickaxe");
+        let diamond_pickaxe = Tool::from_item_name("diamond_pickaxe");
+        assert!(!can_harvest(state("stone"), None));
+        assert!(can_harvest(state("obsidian"), diamond_pickaxe));
+        assert!(!can_harvest(state("obsidian"), iron_pickaxe));
+        assert!(can_harvest(state("dirt"), None));
+        assert_eq!(Tool::from_item_name("diamond_sword"), None);
+    }
+
+    #[test]
+    fn mining_speed() {
+        let stone = state("stone");
+        // By hand stone takes 7.5 seconds
+        assert!((break_speed(stone, None, 0, 0) - 1.0 / 150.0).abs() < 1e-6);
+        let wooden_pickaxe = Some(Tool {
+            kind: ToolType::Pickaxe,
+            material: ToolMaterial::Wood,
+        });
+        assert!((break_speed(stone, wooden_pickaxe, 0, 0) - 2.0 / 45.0).abs() < 1e-6);
+        // Efficiency II adds 5 and Haste II makes it 40% faster
+        assert!((break_speed(stone, wooden_pickaxe, 2, 2) - 7.0 * 1.4 / 45.0).abs() < 1e-6);
+        // An axe doesn't help with stone
+        let axe = Tool::from_item_name("diamond_axe");
+        assert!((break_speed(stone, axe, 5, 0) - 1.0 / 150.0).abs() < 1e-6);
+        assert!(break_speed(state("bedrock"), wooden_pickaxe, 5, 2).abs() < f32::EPSILON);
+    }
+}
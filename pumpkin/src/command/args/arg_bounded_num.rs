@@ -105,7 +105,6 @@ impl<T: ToFromNumber> BoundedNumArgumentConsumer<T> {
         self
     }
 
-    #[allow(unused)]
     pub(crate) const fn max(mut self, max_inclusive: T) -> Self {
         self.max_inclusive = Some(max_inclusive);
         self
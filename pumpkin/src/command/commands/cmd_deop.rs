@@ -1,14 +1,11 @@
-use crate::{
-    command::{
-        args::{arg_players::PlayersArgumentConsumer, Arg, ConsumedArgs},
-        tree::CommandTree,
-        tree_builder::argument,
-        CommandError, CommandExecutor, CommandSender,
-    },
-    data::{op_data::OPERATOR_CONFIG, SaveJSONConfiguration},
+use crate::command::{
+    args::{arg_players::PlayersArgumentConsumer, Arg, ConsumedArgs},
+    tree::CommandTree,
+    tree_builder::argument,
+    CommandError, CommandExecutor, CommandSender,
 };
 use async_trait::async_trait;
-use pumpkin_core::text::TextComponent;
+use pumpkin_core::text::{color::NamedColor, TextComponent};
 use CommandError::InvalidConsumption;
 
 const NAMES: [&str; 1] = ["deop"];
@@ -25,8 +22,6 @@ impl CommandExecutor for DeopExecutor {
         server: &crate::server::Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        let mut config = OPERATOR_CONFIG.write().await;
-
         let Some(Arg::Players(targets)) = args.get(&ARG_TARGET) else {
             return Err(InvalidConsumption(Some(ARG_TARGET.into())));
         };
@@ -34,26 +29,25 @@ impl CommandExecutor for DeopExecutor {
         // from the command tree, the command can only be executed with one player
         let player = &targets[0];
 
-        if let Some(op_index) = config
-            .ops
-            .iter()
-            .position(|o| o.uuid == player.gameprofile.id)
-        {
-            config.ops.remove(op_index);
-        }
-        config.save();
-
-        player
-            .set_permission_lvl(
-                pumpkin_core::PermissionLvl::Zero,
-                &server.command_dispatcher,
-            )
-            .await;
+        server.remove_op(player).await;
 
         let player_name = &player.gameprofile.name;
         let message = format!("Revoked {player_name}'s server operator status.");
-        let msg = TextComponent::text(message);
-        sender.send_message(msg).await;
+        // Like Vanilla, the other operators get told about it as well
+        let except: Vec<_> = sender
+            .as_player()
+            .map(|player| player.gameprofile.id)
+            .into_iter()
+            .collect();
+        server
+            .broadcast_op_message(
+                &TextComponent::text(format!("[{sender}: {message}]"))
+                    .color_named(NamedColor::Gray)
+                    .italic(),
+                &except,
+            )
+            .await;
+        sender.send_message(TextComponent::text(message)).await;
         player
             .send_system_message(&TextComponent::text("You are no longer a server operator."))
             .await;
@@ -7,6 +7,7 @@ use crate::command::args::{Arg, ConsumedArgs};
 use crate::command::tree::CommandTree;
 use crate::command::tree_builder::{argument, require};
 use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::entity::death::DeathCause;
 use CommandError::InvalidConsumption;
 
 const NAMES: [&str; 1] = ["kill"];
@@ -31,7 +32,7 @@ impl CommandExecutor for KillExecutor {
         let target_count = targets.len();
 
         for target in targets {
-            target.living_entity.kill().await;
+            target.kill(DeathCause::Kill).await;
         }
 
         let msg = if target_count == 1 {
@@ -58,7 +59,7 @@ impl CommandExecutor for KillSelfExecutor {
     ) -> Result<(), CommandError> {
         let target = sender.as_player().ok_or(CommandError::InvalidRequirement)?;
 
-        target.living_entity.kill().await;
+        target.kill(DeathCause::Kill).await;
 
         Ok(())
     }
@@ -1,15 +1,12 @@
-use crate::{
-    command::{
-        args::{arg_players::PlayersArgumentConsumer, Arg, ConsumedArgs},
-        tree::CommandTree,
-        tree_builder::argument,
-        CommandError, CommandExecutor, CommandSender,
-    },
-    data::{op_data::OPERATOR_CONFIG, SaveJSONConfiguration},
+use crate::command::{
+    args::{arg_players::PlayersArgumentConsumer, Arg, ConsumedArgs},
+    tree::CommandTree,
+    tree_builder::argument,
+    CommandError, CommandExecutor, CommandSender,
 };
 use async_trait::async_trait;
-use pumpkin_config::{op::Op, BASIC_CONFIG};
-use pumpkin_core::text::TextComponent;
+use pumpkin_config::BASIC_CONFIG;
+use pumpkin_core::text::{color::NamedColor, TextComponent};
 use CommandError::InvalidConsumption;
 
 const NAMES: [&str; 1] = ["op"];
@@ -26,8 +23,6 @@ impl CommandExecutor for OpExecutor {
         server: &crate::server::Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        let mut config = OPERATOR_CONFIG.write().await;
-
         let Some(Arg::Players(targets)) = args.get(&ARG_TARGET) else {
             return Err(InvalidConsumption(Some(ARG_TARGET.into())));
         };
@@ -39,32 +34,25 @@ impl CommandExecutor for OpExecutor {
             .op_permission_level
             .min(sender.permission_lvl());
 
-        if let Some(op) = config
-            .ops
-            .iter_mut()
-            .find(|o| o.uuid == player.gameprofile.id)
-        {
-            op.level = new_level;
-        } else {
-            let op_entry = Op::new(
-                player.gameprofile.id,
-                player.gameprofile.name.clone(),
-                new_level,
-                false,
-            );
-            config.ops.push(op_entry);
-        }
-
-        config.save();
-
-        player
-            .set_permission_lvl(new_level, &server.command_dispatcher)
-            .await;
+        server.set_op(player, new_level).await;
 
         let player_name = &player.gameprofile.name;
         let message = format!("Made {player_name} a server operator.");
-        let msg = TextComponent::text(message);
-        sender.send_message(msg).await;
+        // Like Vanilla, the other operators get told about it as well
+        let except: Vec<_> = sender
+            .as_player()
+            .map(|player| player.gameprofile.id)
+            .into_iter()
+            .collect();
+        server
+            .broadcast_op_message(
+                &TextComponent::text(format!("[{sender}: {message}]"))
+                    .color_named(NamedColor::Gray)
+                    .italic(),
+                &except,
+            )
+            .await;
+        sender.send_message(TextComponent::text(message)).await;
         player
             .send_system_message(&TextComponent::text("You are now a server operator."))
             .await;
@@ -1,6 +1,5 @@
 use async_trait::async_trait;
 use pumpkin_core::text::TextComponent;
-use pumpkin_protocol::client::play::CSystemChatMessage;
 
 use crate::command::{
     args::{arg_message::MsgArgConsumer, Arg, ConsumedArgs},
@@ -31,10 +30,7 @@ impl CommandExecutor for SayExecutor {
         };
 
         server
-            .broadcast_packet_all(&CSystemChatMessage::new(
-                &TextComponent::text(format!("[{sender}] {msg}")),
-                false,
-            ))
+            .broadcast_system_message(&TextComponent::text(format!("[{sender}] {msg}")))
             .await;
         Ok(())
     }
@@ -0,0 +1,79 @@
+use async_trait::async_trait;
+use pumpkin_core::math::{position::WorldPosition, vector3::Vector3};
+use pumpkin_core::text::TextComponent;
+
+use crate::command::args::arg_bounded_num::BoundedNumArgumentConsumer;
+use crate::command::args::arg_position_block::BlockPosArgumentConsumer;
+use crate::command::args::{ConsumedArgs, DefaultNameArgConsumer, FindArg, FindArgDefaultName};
+use crate::command::tree::CommandTree;
+use crate::command::tree_builder::{argument, argument_default_name};
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+
+const NAMES: [&str; 1] = ["setworldspawn"];
+
+const DESCRIPTION: &str = "Sets the world spawn.";
+
+const ARG_POSITION: &str = "position";
+
+fn angle_consumer() -> BoundedNumArgumentConsumer<f32> {
+    BoundedNumArgumentConsumer::new()
+        .min(-180.0)
+        .max(180.0)
+        .name("angle")
+}
+
+struct SetWorldSpawnExecutor;
+
+#[async_trait]
+impl CommandExecutor for SetWorldSpawnExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        // TODO: allow console to use the command (seed sender.world)
+        let world = sender.world().ok_or(CommandError::InvalidRequirement)?;
+        let position = match BlockPosArgumentConsumer::find_arg(args, ARG_POSITION) {
+            Ok(position) => position,
+            Err(_) => {
+                let position = sender.position().ok_or(CommandError::InvalidRequirement)?;
+                WorldPosition(Vector3::new(
+                    position.x.floor() as i32,
+                    position.y.floor() as i32,
+                    position.z.floor() as i32,
+                ))
+            }
+        };
+        // Like Vanilla, players face south without an angle
+        let angle = match angle_consumer().find_arg_default_name(args) {
+            Ok(Ok(angle)) => angle,
+            Ok(Err(())) => {
+                return Err(CommandError::GeneralCommandIssue(format!(
+                    "{} is out of bounds.",
+                    angle_consumer().default_name()
+                )))
+            }
+            Err(_) => 0.0,
+        };
+
+        world.set_default_spawn(position, angle).await;
+        sender
+            .send_message(TextComponent::text(format!(
+                "Set the world spawn point to {position} [{angle}]"
+            )))
+            .await;
+
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .with_child(
+            argument(ARG_POSITION, BlockPosArgumentConsumer)
+                .with_child(argument_default_name(angle_consumer()).execute(SetWorldSpawnExecutor))
+                .execute(SetWorldSpawnExecutor),
+        )
+        .execute(SetWorldSpawnExecutor)
+}
@@ -8,17 +8,14 @@ use crate::command::tree_builder::{argument_default_name, literal};
 use crate::command::{
     tree::CommandTree, CommandError, CommandExecutor, CommandSender, ConsumedArgs,
 };
+use crate::world::level_time::DayTime;
 
 const NAMES: [&str; 1] = ["time"];
 
 const DESCRIPTION: &str = "Query the world time.";
 
-// TODO: This should be either higher or not bounded
 fn arg_number() -> BoundedNumArgumentConsumer<i32> {
-    BoundedNumArgumentConsumer::new()
-        .name("time")
-        .min(0)
-        .max(24000)
+    BoundedNumArgumentConsumer::new().name("time").min(0)
 }
 
 #[derive(Clone, Copy)]
@@ -99,20 +96,17 @@ impl CommandExecutor for TimeChangeExecutor {
             .worlds
             .first()
             .expect("There should always be at least one world");
-        let mut level_time = world.level_time.lock().await;
 
         let msg = match mode {
             Mode::Add => {
                 // add
-                level_time.add_time(time_count.into());
-                level_time.send_time(world).await;
-                let curr_time = level_time.query_daytime();
+                world.add_time(time_count.into()).await;
+                let curr_time = world.level_time.lock().await.query_daytime();
                 format!("Added {time_count} time for result: {curr_time}")
             }
             Mode::Set => {
                 // set
-                level_time.set_time(time_count.into());
-                level_time.send_time(world).await;
+                world.set_time(time_count.unsigned_abs().into()).await;
                 format!("Changed time to: {time_count}")
             }
         };
@@ -122,6 +116,30 @@ impl CommandExecutor for TimeChangeExecutor {
     }
 }
 
+struct TimeSetPresetExecutor(DayTime);
+
+#[async_trait]
+impl CommandExecutor for TimeSetPresetExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &crate::server::Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let world = server
+            .worlds
+            .first()
+            .expect("There should always be at least one world");
+        let time = self.0 as u64;
+        world.set_time(time).await;
+
+        sender
+            .send_message(TextComponent::text(format!("Changed time to: {time}")))
+            .await;
+        Ok(())
+    }
+}
+
 pub fn init_command_tree() -> CommandTree {
     CommandTree::new(NAMES, DESCRIPTION)
         .with_child(
@@ -136,8 +154,13 @@ pub fn init_command_tree() -> CommandTree {
                 .with_child(literal("day").execute(TimeQueryExecutor(QueryMode::Day))),
         )
         .with_child(
-            literal("set").with_child(
-                argument_default_name(arg_number()).execute(TimeChangeExecutor(Mode::Set)),
-            ),
+            literal("set")
+                .with_child(literal("day").execute(TimeSetPresetExecutor(DayTime::Day)))
+                .with_child(literal("night").execute(TimeSetPresetExecutor(DayTime::Night)))
+                .with_child(literal("noon").execute(TimeSetPresetExecutor(DayTime::Noon)))
+                .with_child(literal("midnight").execute(TimeSetPresetExecutor(DayTime::Midnight)))
+                .with_child(
+                    argument_default_name(arg_number()).execute(TimeChangeExecutor(Mode::Set)),
+                ),
         )
 }
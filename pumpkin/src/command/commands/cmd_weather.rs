@@ -0,0 +1,47 @@
+use async_trait::async_trait;
+use pumpkin_core::text::TextComponent;
+
+use crate::command::tree_builder::literal;
+use crate::command::{
+    tree::CommandTree, CommandError, CommandExecutor, CommandSender, ConsumedArgs,
+};
+
+const NAMES: [&str; 1] = ["weather"];
+
+const DESCRIPTION: &str = "Sets the weather.";
+
+// TODO: Add rain once there is rain without thunder
+struct WeatherExecutor {
+    thundering: bool,
+}
+
+#[async_trait]
+impl CommandExecutor for WeatherExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &crate::server::Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        // Like Vanilla, the weather is the one of the Overworld
+        let world = server
+            .worlds
+            .first()
+            .expect("There should always be at least one world");
+        world.set_thundering(self.thundering).await;
+
+        let msg = if self.thundering {
+            "Set the weather to rain & thunder"
+        } else {
+            "Set the weather to clear"
+        };
+        sender.send_message(TextComponent::text(msg)).await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .with_child(literal("clear").execute(WeatherExecutor { thundering: false }))
+        .with_child(literal("thunder").execute(WeatherExecutor { thundering: true }))
+}
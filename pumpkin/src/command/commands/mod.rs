@@ -13,8 +13,10 @@ pub mod cmd_pumpkin;
 pub mod cmd_say;
 pub mod cmd_seed;
 pub mod cmd_setblock;
+pub mod cmd_setworldspawn;
 pub mod cmd_stop;
 pub mod cmd_teleport;
 pub mod cmd_time;
 pub mod cmd_transfer;
+pub mod cmd_weather;
 pub mod cmd_worldborder;
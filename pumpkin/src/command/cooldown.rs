@@ -0,0 +1,50 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+/// When a player last used each command with a cooldown, see [`crate::command::dispatcher::CommandDispatcher::dispatch`]
+#[derive(Default)]
+pub struct CommandCooldowns(HashMap<String, Instant>);
+
+impl CommandCooldowns {
+    /// How long the player still has to wait before using the command again, `None` if they can use it now
+    #[must_use]
+    pub fn remaining(&self, command: &str, cooldown: Duration, now: Instant) -> Option<Duration> {
+        let used = self.0.get(command)?;
+        cooldown
+            .checked_sub(now.saturating_duration_since(*used))
+            .filter(|remaining| !remaining.is_zero())
+    }
+
+    /// Starts the cooldown of the command
+    pub fn start(&mut self, command: &str, now: Instant) {
+        self.0.insert(command.to_string(), now);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, Instant};
+
+    use super::CommandCooldowns;
+
+    #[test]
+    fn cooldown_runs_out() {
+        let mut cooldowns = CommandCooldowns::default();
+        let now = Instant::now();
+        let cooldown = Duration::from_secs(10);
+        assert_eq!(cooldowns.remaining("spawn", cooldown, now), None);
+
+        cooldowns.start("spawn", now);
+        assert_eq!(
+            cooldowns.remaining("spawn", cooldown, now + Duration::from_secs(4)),
+            Some(Duration::from_secs(6))
+        );
+        assert_eq!(cooldowns.remaining("home", cooldown, now), None);
+        assert_eq!(
+            cooldowns.remaining("spawn", cooldown, now + Duration::from_secs(10)),
+            None
+        );
+    }
+}
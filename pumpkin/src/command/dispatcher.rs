@@ -9,10 +9,14 @@ use crate::command::dispatcher::CommandError::{
 };
 use crate::command::tree::{Command, CommandTree, NodeType, RawArgs};
 use crate::command::CommandSender;
+use crate::entity::player::Player;
 use crate::error::PumpkinError;
 use crate::server::Server;
+use pumpkin_config::ADVANCED_CONFIG;
 use pumpkin_core::text::color::{Color, NamedColor};
 use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 #[derive(Debug)]
 pub(crate) enum CommandError {
@@ -173,11 +177,34 @@ impl CommandDispatcher {
             return Err(PermissionDenied);
         }
 
+        let cooldown = self.player_cooldown(src, key);
+        if let Some((player, name, cooldown)) = &cooldown {
+            let remaining =
+                player
+                    .command_cooldowns
+                    .lock()
+                    .await
+                    .remaining(name, *cooldown, Instant::now());
+            if let Some(remaining) = remaining {
+                let seconds = remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0);
+                return Err(GeneralCommandIssue(format!(
+                    "Please wait {seconds} seconds before using /{key} again"
+                )));
+            }
+        }
+
         let tree = self.get_tree(key)?;
 
         // try paths until fitting path is found
         for path in tree.iter_paths() {
             if Self::try_is_fitting_path(src, server, &path, tree, &mut raw_args.clone()).await? {
+                if let Some((player, name, _)) = cooldown {
+                    player
+                        .command_cooldowns
+                        .lock()
+                        .await
+                        .start(name, Instant::now());
+                }
                 return Ok(());
             }
         }
@@ -206,6 +233,25 @@ impl CommandDispatcher {
         }
     }
 
+    /// The player, the name of the command aliases resolve to and its cooldown, if the command has a cooldown for the sender
+    fn player_cooldown<'b>(
+        &'b self,
+        src: &CommandSender,
+        key: &'b str,
+    ) -> Option<(Arc<Player>, &'b str, Duration)> {
+        let config = &ADVANCED_CONFIG.commands;
+        let player = src.as_player()?;
+        if player.permission_lvl.load() >= config.cooldown_bypass_level {
+            return None;
+        }
+        let name = match self.commands.get(key)? {
+            Command::Alias(target) => target.as_str(),
+            Command::Tree(_) => key,
+        };
+        let seconds = *config.cooldowns.get(name)?;
+        (seconds > 0).then(|| (player, name, Duration::from_secs(seconds)))
+    }
+
     pub(crate) fn get_permission_lvl(&self, key: &str) -> Option<PermissionLvl> {
         self.permissions.get(key).copied()
     }
@@ -11,7 +11,8 @@ use args::ConsumedArgs;
 use async_trait::async_trait;
 use commands::{
     cmd_clear, cmd_deop, cmd_fill, cmd_gamemode, cmd_give, cmd_help, cmd_kick, cmd_kill, cmd_list,
-    cmd_op, cmd_pumpkin, cmd_say, cmd_setblock, cmd_stop, cmd_teleport, cmd_time, cmd_worldborder,
+    cmd_op, cmd_pumpkin, cmd_say, cmd_setblock, cmd_setworldspawn, cmd_stop, cmd_teleport,
+    cmd_time, cmd_weather, cmd_worldborder,
 };
 use dispatcher::CommandError;
 use pumpkin_core::math::vector3::Vector3;
@@ -21,6 +22,7 @@ use pumpkin_core::text::TextComponent;
 pub mod args;
 pub mod client_cmd_suggestions;
 mod commands;
+pub mod cooldown;
 pub mod dispatcher;
 mod tree;
 mod tree_builder;
@@ -98,11 +100,11 @@ impl<'a> CommandSender<'a> {
     }
 
     #[must_use]
-    pub fn world(&self) -> Option<&World> {
+    pub fn world(&self) -> Option<Arc<World>> {
         match self {
             // TODO: maybe return first world when console
             CommandSender::Console | CommandSender::Rcon(..) => None,
-            CommandSender::Player(p) => Some(&p.living_entity.entity.world),
+            CommandSender::Player(p) => Some(p.world()),
         }
     }
 }
@@ -126,11 +128,13 @@ pub fn default_dispatcher() -> CommandDispatcher {
     dispatcher.register(cmd_list::init_command_tree(), PermissionLvl::Zero);
     dispatcher.register(cmd_clear::init_command_tree(), PermissionLvl::Two);
     dispatcher.register(cmd_setblock::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(cmd_setworldspawn::init_command_tree(), PermissionLvl::Two);
     dispatcher.register(cmd_seed::init_command_tree(), PermissionLvl::Two);
     dispatcher.register(cmd_transfer::init_command_tree(), PermissionLvl::Zero);
     dispatcher.register(cmd_fill::init_command_tree(), PermissionLvl::Two);
     dispatcher.register(cmd_op::init_command_tree(), PermissionLvl::Three);
     dispatcher.register(cmd_deop::init_command_tree(), PermissionLvl::Three);
+    dispatcher.register(cmd_weather::init_command_tree(), PermissionLvl::Two);
 
     dispatcher
 }
@@ -0,0 +1,152 @@
+use std::{
+    net::IpAddr,
+    path::Path,
+    sync::LazyLock,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use pumpkin_config::banlist::{BannedIpEntry, BannedPlayerEntry};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{LoadJSONConfiguration, SaveJSONConfiguration};
+
+pub static BANNED_PLAYER_LIST: LazyLock<tokio::sync::RwLock<BannedPlayerList>> =
+    LazyLock::new(|| tokio::sync::RwLock::new(BannedPlayerList::load()));
+
+pub static BANNED_IP_LIST: LazyLock<tokio::sync::RwLock<BannedIpList>> =
+    LazyLock::new(|| tokio::sync::RwLock::new(BannedIpList::load()));
+
+/// The current time as a Unix timestamp in seconds, used for ban expiry
+#[must_use]
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
+
+/// The disconnect message shown to banned players
+#[must_use]
+pub fn ban_message(reason: &str, expires: Option<u64>, ip_ban: bool) -> String {
+    let mut message = if ip_ban {
+        format!("Your IP address is banned from this server.\nReason: {reason}")
+    } else {
+        format!("You are banned from this server.\nReason: {reason}")
+    };
+    if let Some(expires) = expires {
+        let remaining = expires.saturating_sub(unix_now());
+        let days = remaining / 86400;
+        let hours = remaining % 86400 / 3600;
+        let minutes = remaining % 3600 / 60;
+        message.push_str(&format!(
+            "\nYour ban will be removed in {days}d {hours}h {minutes}m"
+        ));
+    }
+    message
+}
+
+#[derive(Deserialize, Serialize, Default)]
+#[serde(transparent)]
+pub struct BannedPlayerList {
+    pub banned_players: Vec<BannedPlayerEntry>,
+}
+
+impl BannedPlayerList {
+    /// Returns the active ban of the player, expired bans get pruned
+    pub fn get_entry(&mut self, uuid: &Uuid) -> Option<&BannedPlayerEntry> {
+        self.remove_expired();
+        self.banned_players.iter().find(|entry| entry.uuid == *uuid)
+    }
+
+    /// Bans the player, replacing an older ban of the same player, and saves the file
+    pub fn ban(&mut self, entry: BannedPlayerEntry) {
+        self.banned_players.retain(|old| old.uuid != entry.uuid);
+        self.banned_players.push(entry);
+        self.save();
+    }
+
+    /// Removes the ban and saves the file, returns `false` if the player was not banned
+    pub fn pardon(&mut self, uuid: &Uuid) -> bool {
+        let Some(index) = self
+            .banned_players
+            .iter()
+            .position(|entry| entry.uuid == *uuid)
+        else {
+            return false;
+        };
+        self.banned_players.remove(index);
+        self.save();
+        true
+    }
+
+    fn remove_expired(&mut self) {
+        let now = unix_now();
+        let len = self.banned_players.len();
+        self.banned_players.retain(|entry| !entry.is_expired(now));
+        if self.banned_players.len() != len {
+            self.save();
+        }
+    }
+}
+
+impl LoadJSONConfiguration for BannedPlayerList {
+    fn get_path() -> &'static Path {
+        Path::new("banned-players.json")
+    }
+    fn validate(&self) {
+        // TODO: Validate the banned players configuration
+    }
+}
+
+impl SaveJSONConfiguration for BannedPlayerList {}
+
+#[derive(Deserialize, Serialize, Default)]
+#[serde(transparent)]
+pub struct BannedIpList {
+    pub banned_ips: Vec<BannedIpEntry>,
+}
+
+impl BannedIpList {
+    /// Returns the active ban of the IP, expired bans get pruned
+    pub fn get_entry(&mut self, ip: &IpAddr) -> Option<&BannedIpEntry> {
+        self.remove_expired();
+        self.banned_ips.iter().find(|entry| entry.ip == *ip)
+    }
+
+    /// Bans the IP, replacing an older ban of the same IP, and saves the file
+    pub fn ban(&mut self, entry: BannedIpEntry) {
+        self.banned_ips.retain(|old| old.ip != entry.ip);
+        self.banned_ips.push(entry);
+        self.save();
+    }
+
+    /// Removes the ban and saves the file, returns `false` if the IP was not banned
+    pub fn pardon(&mut self, ip: &IpAddr) -> bool {
+        let Some(index) = self.banned_ips.iter().position(|entry| entry.ip == *ip) else {
+            return false;
+        };
+        self.banned_ips.remove(index);
+        self.save();
+        true
+    }
+
+    fn remove_expired(&mut self) {
+        let now = unix_now();
+        let len = self.banned_ips.len();
+        self.banned_ips.retain(|entry| !entry.is_expired(now));
+        if self.banned_ips.len() != len {
+            self.save();
+        }
+    }
+}
+
+impl LoadJSONConfiguration for BannedIpList {
+    fn get_path() -> &'static Path {
+        Path::new("banned-ips.json")
+    }
+    fn validate(&self) {
+        // TODO: Validate the banned ips configuration
+    }
+}
+
+impl SaveJSONConfiguration for BannedIpList {}
@@ -4,7 +4,10 @@ use serde::{Deserialize, Serialize};
 
 const DATA_FOLDER: &str = "data/";
 
+pub mod banlist_data;
 pub mod op_data;
+pub mod player_inventory_data;
+pub mod whitelist_data;
 
 pub trait LoadJSONConfiguration {
     #[must_use]
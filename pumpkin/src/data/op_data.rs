@@ -1,7 +1,9 @@
 use std::{path::Path, sync::LazyLock};
 
 use pumpkin_config::op;
+use pumpkin_core::PermissionLvl;
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
 use super::{LoadJSONConfiguration, SaveJSONConfiguration};
 
@@ -14,6 +16,45 @@ pub struct OperatorConfig {
     pub ops: Vec<op::Op>,
 }
 
+impl OperatorConfig {
+    /// Returns the permission level of the operator, or `None` if the player is not an operator
+    #[must_use]
+    pub fn get_level(&self, uuid: &Uuid) -> Option<PermissionLvl> {
+        self.ops
+            .iter()
+            .find(|op| op.uuid == *uuid)
+            .map(|op| op.level)
+    }
+
+    /// Whether the operator may join even if the server is full
+    #[must_use]
+    pub fn bypasses_player_limit(&self, uuid: &Uuid) -> bool {
+        self.ops
+            .iter()
+            .any(|op| op.uuid == *uuid && op.bypasses_player_limit)
+    }
+
+    /// Adds a new operator or updates the level of an existing one and saves the file
+    pub fn set_op(&mut self, uuid: Uuid, name: String, level: PermissionLvl) {
+        if let Some(op) = self.ops.iter_mut().find(|op| op.uuid == uuid) {
+            op.level = level;
+        } else {
+            self.ops.push(op::Op::new(uuid, name, level, false));
+        }
+        self.save();
+    }
+
+    /// Removes the operator and saves the file, returns `false` if the player was not an operator
+    pub fn remove_op(&mut self, uuid: &Uuid) -> bool {
+        let Some(index) = self.ops.iter().position(|op| op.uuid == *uuid) else {
+            return false;
+        };
+        self.ops.remove(index);
+        self.save();
+        true
+    }
+}
+
 impl LoadJSONConfiguration for OperatorConfig {
     fn get_path() -> &'static Path {
         Path::new("ops.json")
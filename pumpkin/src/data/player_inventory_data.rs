@@ -0,0 +1,139 @@
+use std::{env, fs, path::PathBuf};
+
+use pumpkin_inventory::player::PlayerInventory;
+use pumpkin_world::item::ItemStack;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::DATA_FOLDER;
+
+/// The folder inside of the data folder with one `<uuid>.json` file per player
+const PLAYER_DATA_FOLDER: &str = "playerdata";
+
+/// The first slot which gets saved, the crafting grid is not kept when leaving
+const FIRST_SAVED_SLOT: usize = 5;
+const LAST_SLOT: usize = 45;
+
+/// The inventory of a player, saved when they leave and loaded when they join again
+#[derive(Serialize, Deserialize, Default, Debug, PartialEq, Eq)]
+pub struct SavedInventory {
+    /// The selected hotbar slot, from 0 to 8
+    pub selected: usize,
+    pub items: Vec<SavedItem>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct SavedItem {
+    /// The slot in the player inventory window
+    pub slot: usize,
+    pub item_id: u16,
+    pub count: u8,
+}
+
+impl SavedInventory {
+    #[must_use]
+    pub fn from_inventory(inventory: &PlayerInventory) -> Self {
+        let items = inventory
+            .slots()
+            .into_iter()
+            .enumerate()
+            .skip(FIRST_SAVED_SLOT)
+            .filter_map(|(slot, item)| {
+                item.map(|item| SavedItem {
+                    slot,
+                    item_id: item.item_id,
+                    count: item.item_count,
+                })
+            })
+            .collect();
+        Self {
+            selected: inventory.get_selected() - 36,
+            items,
+        }
+    }
+
+    /// Puts the saved items into the inventory, invalid slots are skipped
+    pub fn apply(&self, inventory: &mut PlayerInventory) {
+        if self.selected < 9 {
+            inventory.set_selected(self.selected);
+        }
+        for item in &self.items {
+            if !(FIRST_SAVED_SLOT..=LAST_SLOT).contains(&item.slot) {
+                continue;
+            }
+            let stack = ItemStack::new(item.count, item.item_id);
+            if let Err(error) = inventory.set_slot(item.slot, Some(stack), true) {
+                log::warn!("Couldn't load item in slot {}: {error}", item.slot);
+            }
+        }
+    }
+
+    fn path(uuid: &Uuid) -> PathBuf {
+        env::current_dir()
+            .unwrap()
+            .join(DATA_FOLDER)
+            .join(PLAYER_DATA_FOLDER)
+            .join(format!("{uuid}.json"))
+    }
+
+    /// Returns `None` if the player has no saved inventory, e.g. because they joined for the first time
+    #[must_use]
+    pub fn load(uuid: &Uuid) -> Option<Self> {
+        let path = Self::path(uuid);
+        if !path.exists() {
+            return None;
+        }
+        let content = fs::read_to_string(&path)
+            .inspect_err(|err| log::error!("Couldn't read player inventory at {path:?}: {err}"))
+            .ok()?;
+        serde_json::from_str(&content)
+            .inspect_err(|err| log::error!("Couldn't parse player inventory at {path:?}: {err}"))
+            .ok()
+    }
+
+    pub fn save(&self, uuid: &Uuid) {
+        let path = Self::path(uuid);
+        if let Some(folder) = path.parent() {
+            if let Err(err) = fs::create_dir_all(folder) {
+                log::error!("Couldn't create player data folder {folder:?}: {err}");
+                return;
+            }
+        }
+        let content = match serde_json::to_string_pretty(self) {
+            Ok(content) => content,
+            Err(err) => {
+                log::error!("Couldn't serialize player inventory: {err}");
+                return;
+            }
+        };
+        if let Err(err) = fs::write(&path, content) {
+            log::error!("Couldn't save player inventory to {path:?}: {err}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_inventory::player::PlayerInventory;
+    use pumpkin_world::item::ItemStack;
+
+    use super::SavedInventory;
+
+    #[test]
+    fn inventory_round_trip() {
+        let mut inventory = PlayerInventory::new();
+        inventory.set_selected(3);
+        inventory
+            .set_slot(36, Some(ItemStack::new(16, 1)), true)
+            .unwrap();
+        inventory
+            .set_slot(45, Some(ItemStack::new(1, 2)), true)
+            .unwrap();
+
+        let saved = SavedInventory::from_inventory(&inventory);
+        let mut loaded = PlayerInventory::new();
+        saved.apply(&mut loaded);
+        assert_eq!(SavedInventory::from_inventory(&loaded), saved);
+        assert_eq!(loaded.get_selected(), 39);
+    }
+}
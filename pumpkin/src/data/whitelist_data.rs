@@ -0,0 +1,54 @@
+use std::{path::Path, sync::LazyLock};
+
+use pumpkin_config::whitelist::WhitelistEntry;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{LoadJSONConfiguration, SaveJSONConfiguration};
+
+pub static WHITELIST_CONFIG: LazyLock<tokio::sync::RwLock<WhitelistConfig>> =
+    LazyLock::new(|| tokio::sync::RwLock::new(WhitelistConfig::load()));
+
+#[derive(Deserialize, Serialize, Default)]
+#[serde(transparent)]
+pub struct WhitelistConfig {
+    pub whitelist: Vec<WhitelistEntry>,
+}
+
+impl WhitelistConfig {
+    #[must_use]
+    pub fn is_whitelisted(&self, uuid: &Uuid) -> bool {
+        self.whitelist.iter().any(|entry| entry.uuid == *uuid)
+    }
+
+    /// Adds the player to the whitelist and saves the file, returns `false` if the player was already whitelisted
+    pub fn add(&mut self, uuid: Uuid, name: String) -> bool {
+        if self.is_whitelisted(&uuid) {
+            return false;
+        }
+        self.whitelist.push(WhitelistEntry::new(uuid, name));
+        self.save();
+        true
+    }
+
+    /// Removes the player from the whitelist and saves the file, returns `false` if the player was not whitelisted
+    pub fn remove(&mut self, uuid: &Uuid) -> bool {
+        let Some(index) = self.whitelist.iter().position(|entry| entry.uuid == *uuid) else {
+            return false;
+        };
+        self.whitelist.remove(index);
+        self.save();
+        true
+    }
+}
+
+impl LoadJSONConfiguration for WhitelistConfig {
+    fn get_path() -> &'static Path {
+        Path::new("whitelist.json")
+    }
+    fn validate(&self) {
+        // TODO: Validate the whitelist configuration
+    }
+}
+
+impl SaveJSONConfiguration for WhitelistConfig {}
@@ -5,7 +5,7 @@ use tokio::sync::Mutex;
 
 use crate::entity::{mob::MobEntity, player::Player};
 
-use super::Goal;
+use super::{Goal, GoalControls};
 
 pub struct LookAtEntityGoal {
     // TODO: make this an entity
@@ -32,7 +32,7 @@ impl Goal for LookAtEntityGoal {
         *target = mob
             .living_entity
             .entity
-            .world
+            .world()
             .get_closest_player(mob.living_entity.entity.pos.load(), self.range)
             .await;
         target.is_some()
@@ -53,4 +53,8 @@ impl Goal for LookAtEntityGoal {
             mob.living_entity.entity.look_at(target_pos).await;
         }
     }
+
+    fn controls(&self) -> GoalControls {
+        GoalControls::LOOK
+    }
 }
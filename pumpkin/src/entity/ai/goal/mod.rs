@@ -1,16 +1,120 @@
+use std::sync::Arc;
+
 use async_trait::async_trait;
 
 use crate::entity::mob::MobEntity;
 
 pub mod look_at_entity;
 pub mod target_goal;
+pub mod wander;
+
+/// What a goal takes over while it runs, goals taking over the same can't run at the same time
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GoalControls {
+    pub movement: bool,
+    pub look: bool,
+}
+
+impl GoalControls {
+    pub const MOVEMENT: Self = Self {
+        movement: true,
+        look: false,
+    };
+    pub const LOOK: Self = Self {
+        movement: false,
+        look: true,
+    };
+
+    const fn overlaps(self, other: Self) -> bool {
+        (self.movement && other.movement) || (self.look && other.look)
+    }
+}
 
 #[async_trait]
 pub trait Goal: Send + Sync {
     /// How Should the Goal initially start?
     async fn can_start(&self, mob: &MobEntity) -> bool;
+    /// Called when the Goal starts running, right after [`Goal::can_start`] allowed it
+    async fn start(&self, _mob: &MobEntity) {}
     /// When its started, How it should Continue to run
     async fn should_continue(&self, mob: &MobEntity) -> bool;
     /// If the Goal is running, this gets called every tick
     async fn tick(&self, mob: &MobEntity);
+    /// Called when the Goal stops running, because it is done or a more important Goal took over
+    async fn stop(&self, _mob: &MobEntity) {}
+    /// What the Goal takes over while it runs, see [`GoalSelector`]
+    fn controls(&self) -> GoalControls;
+}
+
+struct PrioritizedGoal {
+    priority: u8,
+    goal: Arc<dyn Goal>,
+    running: bool,
+}
+
+/// The goals of a mob, like Vanilla a lower priority is more important.
+///
+/// Goals only run together if they take over different things, like walking and looking around.
+/// A goal which wants to start takes over from running goals which are less important
+#[derive(Default)]
+pub struct GoalSelector {
+    /// Sorted by priority
+    goals: Vec<PrioritizedGoal>,
+}
+
+impl GoalSelector {
+    /// Adds the goal, goals with the same priority are tried in the order they were added
+    pub fn add(&mut self, priority: u8, goal: Arc<dyn Goal>) {
+        let index = self
+            .goals
+            .partition_point(|other| other.priority <= priority);
+        self.goals.insert(
+            index,
+            PrioritizedGoal {
+                priority,
+                goal,
+                running: false,
+            },
+        );
+    }
+
+    pub async fn tick(&mut self, mob: &MobEntity) {
+        for entry in &mut self.goals {
+            if entry.running && !entry.goal.should_continue(mob).await {
+                entry.running = false;
+                entry.goal.stop(mob).await;
+            }
+        }
+
+        for index in 0..self.goals.len() {
+            let entry = &self.goals[index];
+            if entry.running {
+                continue;
+            }
+            let (priority, controls) = (entry.priority, entry.goal.controls());
+            // Running goals which are at least as important keep what they took over
+            let blocked = self.goals.iter().any(|other| {
+                other.running
+                    && other.priority <= priority
+                    && other.goal.controls().overlaps(controls)
+            });
+            if blocked || !self.goals[index].goal.can_start(mob).await {
+                continue;
+            }
+            for other in &mut self.goals {
+                if other.running && other.goal.controls().overlaps(controls) {
+                    other.running = false;
+                    other.goal.stop(mob).await;
+                }
+            }
+            self.goals[index].running = true;
+            self.goals[index].goal.start(mob).await;
+        }
+
+        for entry in &self.goals {
+            if entry.running {
+                entry.goal.tick(mob).await;
+            }
+        }
+    }
 }
@@ -1,15 +1,21 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use pumpkin_core::math::position::WorldPosition;
 use tokio::sync::Mutex;
 
-use crate::entity::{ai::path::NavigatorGoal, mob::MobEntity, player::Player};
+use crate::entity::{mob::MobEntity, player::Player};
 
-use super::Goal;
+use super::{Goal, GoalControls};
+
+/// How many blocks the search for a way to the target looks at, see [`crate::world::World::find_path`]
+const MAX_PATH_NODES: usize = 400;
 
 pub struct TargetGoal {
     // TODO: make this an entity
     target: Mutex<Option<Arc<Player>>>,
+    /// The block of the target the current path leads to
+    path_target: Mutex<Option<WorldPosition>>,
     range: f64,
 }
 
@@ -18,6 +24,7 @@ impl TargetGoal {
     pub fn new(range: f64) -> Self {
         Self {
             target: Mutex::new(None),
+            path_target: Mutex::new(None),
             range,
         }
     }
@@ -33,7 +40,7 @@ impl Goal for TargetGoal {
         *target = mob
             .living_entity
             .entity
-            .world
+            .world()
             .get_closest_player(mob.living_entity.entity.pos.load(), self.range)
             .await;
 
@@ -50,14 +57,33 @@ impl Goal for TargetGoal {
     }
     async fn tick(&self, mob: &MobEntity) {
         if let Some(target) = self.target.lock().await.as_ref() {
-            let mut navigator = mob.navigator.lock().await;
-            let target_player = target.living_entity.entity.pos.load();
+            let target_block = target.living_entity.entity.block_pos.load();
+            let mut path_target = self.path_target.lock().await;
+            // Only look for a new way once the target moved to another block
+            if *path_target == Some(target_block) {
+                return;
+            }
+            *path_target = Some(target_block);
 
-            navigator.set_progress(NavigatorGoal {
-                current_progress: mob.living_entity.entity.pos.load(),
-                destination: target_player,
-                speed: 0.1,
-            });
+            let entity = &mob.living_entity.entity;
+            let path = entity
+                .world()
+                .find_path(entity.block_pos.load(), target_block, MAX_PATH_NODES)
+                .await;
+            let mut navigator = mob.navigator.lock().await;
+            match path {
+                Some(path) => navigator.set_path(&path, 0.1),
+                None => navigator.cancel(),
+            }
         }
     }
+
+    async fn stop(&self, mob: &MobEntity) {
+        *self.path_target.lock().await = None;
+        mob.navigator.lock().await.cancel();
+    }
+
+    fn controls(&self) -> GoalControls {
+        GoalControls::MOVEMENT
+    }
 }
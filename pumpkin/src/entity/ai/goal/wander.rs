@@ -0,0 +1,89 @@
+use async_trait::async_trait;
+use pumpkin_core::math::{position::WorldPosition, vector2::Vector2, vector3::Vector3};
+use rand::Rng;
+use tokio::sync::Mutex;
+
+use crate::entity::mob::MobEntity;
+
+use super::{Goal, GoalControls};
+
+/// Like Vanilla, an idle mob starts wandering every 120 ticks on average
+const WANDER_CHANCE: u32 = 120;
+/// How many blocks away from the mob the destination can be, like Vanilla
+const HORIZONTAL_RANGE: i32 = 10;
+/// How many blocks higher or lower than the mob the destination can be, like Vanilla
+const VERTICAL_RANGE: i32 = 7;
+/// How many blocks the search for a way to the destination looks at, see [`crate::world::World::find_path`]
+const MAX_PATH_NODES: usize = 400;
+
+/// Walks to random places nearby every now and then
+pub struct WanderGoal {
+    speed: f64,
+    /// The way to where the mob walks to, found in [`Goal::can_start`]
+    path: Mutex<Option<Vec<WorldPosition>>>,
+}
+
+impl WanderGoal {
+    #[must_use]
+    pub fn new(speed: f64) -> Self {
+        Self {
+            speed,
+            path: Mutex::new(None),
+        }
+    }
+}
+
+#[async_trait]
+impl Goal for WanderGoal {
+    async fn can_start(&self, mob: &MobEntity) -> bool {
+        let entity = &mob.living_entity.entity;
+        let world = entity.world();
+        let block_pos = entity.block_pos.load().0;
+        let column = {
+            let mut rng = world.random.lock().await;
+            if rng.gen_range(0..WANDER_CHANCE) != 0 {
+                return false;
+            }
+            Vector2::new(
+                block_pos.x + rng.gen_range(-HORIZONTAL_RANGE..=HORIZONTAL_RANGE),
+                block_pos.z + rng.gen_range(-HORIZONTAL_RANGE..=HORIZONTAL_RANGE),
+            )
+        };
+        // Walk on top of the ground, not into it
+        let y = world.get_top_block(column).await + 1;
+        if (y - block_pos.y).abs() > VERTICAL_RANGE {
+            return false;
+        }
+        let destination = WorldPosition(Vector3::new(column.x, y, column.z));
+        let Some(path) = world
+            .find_path(entity.block_pos.load(), destination, MAX_PATH_NODES)
+            .await
+        else {
+            return false;
+        };
+        *self.path.lock().await = Some(path);
+        true
+    }
+
+    async fn start(&self, mob: &MobEntity) {
+        if let Some(path) = self.path.lock().await.take() {
+            mob.navigator.lock().await.set_path(&path, self.speed);
+        }
+    }
+
+    async fn should_continue(&self, mob: &MobEntity) -> bool {
+        !mob.navigator.lock().await.is_idle()
+    }
+
+    async fn tick(&self, _mob: &MobEntity) {
+        // The navigator walks to the destination
+    }
+
+    async fn stop(&self, mob: &MobEntity) {
+        mob.navigator.lock().await.cancel();
+    }
+
+    fn controls(&self) -> GoalControls {
+        GoalControls::MOVEMENT
+    }
+}
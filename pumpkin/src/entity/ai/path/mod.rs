@@ -1,107 +1,93 @@
-use pumpkin_core::math::vector3::Vector3;
+use std::collections::VecDeque;
+
+use pumpkin_core::math::{position::WorldPosition, vector3::Vector3};
 use pumpkin_protocol::client::play::CUpdateEntityPos;
 
 use crate::entity::living::LivingEntity;
 
+/// Walks a mob along a path, see [`crate::world::World::find_path`]
 #[derive(Default)]
 pub struct Navigator {
-    current_goal: Option<NavigatorGoal>,
+    current_path: Option<NavigatorPath>,
 }
 
-pub struct NavigatorGoal {
-    pub current_progress: Vector3<f64>,
-    pub destination: Vector3<f64>,
-    pub speed: f64,
+struct NavigatorPath {
+    /// Where the mob stands in each block of the path which is still ahead
+    waypoints: VecDeque<Vector3<f64>>,
+    /// How many blocks the mob walks each tick
+    speed: f64,
 }
 
 impl Navigator {
-    pub fn set_progress(&mut self, goal: NavigatorGoal) {
-        self.current_goal = Some(goal);
+    /// Walks through the blocks of a path from [`crate::world::World::find_path`]
+    pub fn set_path(&mut self, path: &[WorldPosition], speed: f64) {
+        let waypoints = path
+            .iter()
+            .map(|block| {
+                Vector3::new(
+                    f64::from(block.0.x) + 0.5,
+                    f64::from(block.0.y),
+                    f64::from(block.0.z) + 0.5,
+                )
+            })
+            .collect();
+        self.current_path = Some(NavigatorPath { waypoints, speed });
     }
 
     pub fn cancel(&mut self) {
-        self.current_goal = None;
+        self.current_path = None;
     }
 
-    pub async fn tick(&mut self, entity: &LivingEntity) {
-        if let Some(goal) = &mut self.current_goal {
-            // first lets check if we reached destination
-            if goal.current_progress == goal.destination {
-                // if yes, we are done here
-                self.current_goal = None;
-                return;
-            }
-
-            // A star algorithm
-            let mut best_move = Vector3::new(0.0, 0.0, 0.0);
-            let mut lowest_cost = f64::MAX;
-
-            for x in -1..=1 {
-                for z in -1..=1 {
-                    let x = f64::from(x);
-                    let z = f64::from(z);
-                    let potential_pos = Vector3::new(
-                        goal.current_progress.x + x,
-                        goal.current_progress.y,
-                        goal.current_progress.z + z,
-                    );
-                    let node = Node::new(potential_pos);
-                    let cost = node.get_expense(goal.destination);
-
-                    if cost < lowest_cost {
-                        lowest_cost = cost;
-                        best_move = Vector3::new(x, 0.0, z);
-                    }
-                }
-            }
+    /// Whether there is no destination to walk to
+    #[must_use]
+    pub const fn is_idle(&self) -> bool {
+        self.current_path.is_none()
+    }
 
-            // this is important, first this saves us many packets when we don't actually move, and secound this prevents division using zero
-            // when normalize
-            if best_move.x == 0.0 && best_move.z == 0.0 {
-                return;
+    pub async fn tick(&mut self, entity: &LivingEntity) {
+        let Some(path) = &mut self.current_path else {
+            return;
+        };
+        let pos = entity.entity.pos.load();
+        let mut new_pos = pos;
+        let mut step = path.speed;
+        // Walks past waypoints which are closer than one step
+        while let Some(waypoint) = path.waypoints.front() {
+            let remaining = waypoint.sub(&new_pos);
+            let distance = remaining.length();
+            if distance > step {
+                new_pos += remaining.normalize() * step;
+                break;
             }
-            // Update current progress based on the best move
-            goal.current_progress += best_move.normalize() * goal.speed;
-
-            // now lets move
-            entity.set_pos(goal.current_progress);
-            let pos = entity.entity.pos.load();
-            let last_pos = entity.last_pos.load();
-
-            entity
-                .entity
-                .world
-                .broadcast_packet_all(&CUpdateEntityPos::new(
-                    entity.entity.entity_id.into(),
-                    Vector3::new(
-                        pos.x.mul_add(4096.0, -(last_pos.x * 4096.0)) as i16,
-                        pos.y.mul_add(4096.0, -(last_pos.y * 4096.0)) as i16,
-                        pos.z.mul_add(4096.0, -(last_pos.z * 4096.0)) as i16,
-                    ),
-                    entity
-                        .entity
-                        .on_ground
-                        .load(std::sync::atomic::Ordering::Relaxed),
-                ))
-                .await;
+            new_pos = *waypoint;
+            step -= distance;
+            path.waypoints.pop_front();
         }
-    }
-}
-
-pub struct Node {
-    pub location: Vector3<f64>,
-}
+        if path.waypoints.is_empty() {
+            self.current_path = None;
+        }
+        // This saves us packets when we don't actually move
+        if new_pos == pos {
+            return;
+        }
+        entity.set_pos(new_pos);
+        let last_pos = entity.last_pos.load();
 
-impl Node {
-    #[must_use]
-    pub fn new(location: Vector3<f64>) -> Self {
-        Self { location }
-    }
-    /// How expensive is it to go to a location
-    ///
-    /// Returns a f64, Higher = More Expensive
-    #[must_use]
-    pub fn get_expense(&self, end: Vector3<f64>) -> f64 {
-        self.location.squared_distance_to_vec(end).sqrt()
+        entity
+            .entity
+            .world()
+            .broadcast_packet_all(&CUpdateEntityPos::new(
+                entity.entity.entity_id.into(),
+                Vector3::new(
+                    new_pos.x.mul_add(4096.0, -(last_pos.x * 4096.0)) as i16,
+                    new_pos.y.mul_add(4096.0, -(last_pos.y * 4096.0)) as i16,
+                    new_pos.z.mul_add(4096.0, -(last_pos.z * 4096.0)) as i16,
+                ),
+                entity
+                    .entity
+                    .on_ground
+                    .load(std::sync::atomic::Ordering::Relaxed),
+            ))
+            .await;
     }
 }
@@ -0,0 +1,46 @@
+use pumpkin_config::ADVANCED_CONFIG;
+use pumpkin_core::text::TextComponent;
+
+/// What killed a player, decides the death message
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DeathCause<'a> {
+    /// Hit the ground too hard
+    Fall,
+    /// Killed by another player, with the name of the killer
+    Player(&'a str),
+    /// Shot with an arrow by a player, with the name of the shooter
+    Arrow(&'a str),
+    /// Struck by lightning
+    Lightning,
+    /// Killed with the `/kill` command
+    Kill,
+    Generic,
+}
+
+impl DeathCause<'_> {
+    /// The Vanilla translation key of the death message
+    const fn translation_key(self) -> &'static str {
+        match self {
+            Self::Fall => "death.attack.fall",
+            Self::Player(_) => "death.attack.player",
+            Self::Arrow(_) => "death.attack.arrow",
+            Self::Lightning => "death.attack.lightningBolt",
+            Self::Kill => "death.attack.genericKill",
+            Self::Generic => "death.attack.generic",
+        }
+    }
+
+    /// The message shown on the death screen and in chat, the one from the config is used if there is one
+    #[must_use]
+    pub fn message(self, victim: &str) -> TextComponent {
+        let custom = &ADVANCED_CONFIG.death.message;
+        if !custom.is_empty() {
+            return TextComponent::text(custom.replace("{player}", victim));
+        }
+        let mut with = vec![TextComponent::text(victim.to_string())];
+        if let Self::Player(killer) | Self::Arrow(killer) = self {
+            with.push(TextComponent::text(killer.to_string()));
+        }
+        TextComponent::translate(self.translation_key(), with)
+    }
+}
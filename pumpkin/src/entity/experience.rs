@@ -0,0 +1,178 @@
+use std::ops::RangeInclusive;
+
+use pumpkin_entity::entity_type::EntityType;
+use rand::Rng;
+
+/// How many experience points it takes to get from the level to the next one, like Vanilla
+#[must_use]
+pub const fn points_to_next_level(level: i32) -> i32 {
+    if level >= 30 {
+        112 + (level - 30) * 9
+    } else if level >= 15 {
+        37 + (level - 15) * 5
+    } else {
+        7 + level * 2
+    }
+}
+
+/// The level reached with the total experience points and the progress towards the next level, between `0.0` and `1.0`
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn level_and_progress(total: i32) -> (i32, f32) {
+    let mut level = 0;
+    let mut remaining = total.max(0);
+    while remaining >= points_to_next_level(level) {
+        remaining -= points_to_next_level(level);
+        level += 1;
+    }
+    (level, remaining as f32 / points_to_next_level(level) as f32)
+}
+
+/// How much experience breaking the block drops, like Vanilla. `None` if it drops none
+fn block_experience_range(block_name: &str) -> Option<RangeInclusive<i32>> {
+    let range = match block_name.strip_prefix("deepslate_").unwrap_or(block_name) {
+        "coal_ore" => 0..=2,
+        "nether_gold_ore" => 0..=1,
+        "redstone_ore" => 1..=5,
+        "lapis_ore" | "nether_quartz_ore" => 2..=5,
+        "diamond_ore" | "emerald_ore" => 3..=7,
+        "sculk" => 1..=1,
+        "sculk_catalyst" | "sculk_sensor" | "calibrated_sculk_sensor" | "sculk_shrieker" => 5..=5,
+        "spawner" => 15..=43,
+        _ => return None,
+    };
+    Some(range)
+}
+
+/// The experience dropped when a player breaks the block, `0` for most blocks
+#[must_use]
+pub fn block_experience(block_name: &str, rng: &mut impl Rng) -> i32 {
+    // TODO: Drop nothing when mined with Silk Touch once we have enchantments
+    block_experience_range(block_name).map_or(0, |range| rng.gen_range(range))
+}
+
+/// How much experience killing a mob of the type drops, like Vanilla. `None` if it drops none
+const fn mob_experience_range(entity_type: &EntityType) -> Option<RangeInclusive<i32>> {
+    let range = match entity_type {
+        EntityType::Armadillo
+        | EntityType::Axolotl
+        | EntityType::Bee
+        | EntityType::Camel
+        | EntityType::Cat
+        | EntityType::Chicken
+        | EntityType::Cod
+        | EntityType::Cow
+        | EntityType::Dolphin
+        | EntityType::Donkey
+        | EntityType::Fox
+        | EntityType::Frog
+        | EntityType::GlowSquid
+        | EntityType::Goat
+        | EntityType::Horse
+        | EntityType::Llama
+        | EntityType::Mooshroom
+        | EntityType::Mule
+        | EntityType::Ocelot
+        | EntityType::Panda
+        | EntityType::Parrot
+        | EntityType::Pig
+        | EntityType::PolarBear
+        | EntityType::Pufferfish
+        | EntityType::Rabbit
+        | EntityType::Salmon
+        | EntityType::Sheep
+        | EntityType::SkeletonHorse
+        | EntityType::Sniffer
+        | EntityType::Squid
+        | EntityType::Strider
+        | EntityType::Tadpole
+        | EntityType::TraderLlama
+        | EntityType::TropicalFish
+        | EntityType::Turtle
+        | EntityType::Wolf
+        | EntityType::ZombieHorse => 1..=3,
+        // TODO: Slimes and magma cubes drop as much as their size, this is the smallest one
+        EntityType::Slime | EntityType::MagmaCube => 1..=1,
+        EntityType::Endermite | EntityType::Vex => 3..=3,
+        EntityType::Bogged
+        | EntityType::CaveSpider
+        | EntityType::Creeper
+        | EntityType::Drowned
+        | EntityType::Enderman
+        | EntityType::Ghast
+        | EntityType::Giant
+        | EntityType::Hoglin
+        | EntityType::Husk
+        | EntityType::Illusioner
+        | EntityType::Phantom
+        | EntityType::Piglin
+        | EntityType::Pillager
+        | EntityType::Shulker
+        | EntityType::Silverfish
+        | EntityType::Skeleton
+        | EntityType::Spider
+        | EntityType::Stray
+        | EntityType::Vindicator
+        | EntityType::Warden
+        | EntityType::Witch
+        | EntityType::WitherSkeleton
+        | EntityType::Zoglin
+        | EntityType::Zombie
+        | EntityType::ZombieVillager
+        | EntityType::ZombifiedPiglin => 5..=5,
+        EntityType::Blaze
+        | EntityType::Breeze
+        | EntityType::ElderGuardian
+        | EntityType::Evoker
+        | EntityType::Guardian => 10..=10,
+        EntityType::PiglinBrute | EntityType::Ravager => 20..=20,
+        EntityType::Wither => 50..=50,
+        // TODO: The first dragon killed in a world drops 12000
+        EntityType::EnderDragon => 500..=500,
+        _ => return None,
+    };
+    Some(range)
+}
+
+/// The experience dropped when a player kills a mob of the type, `0` for villagers, golems and other peaceful mobs
+#[must_use]
+pub fn mob_experience(entity_type: &EntityType, rng: &mut impl Rng) -> i32 {
+    // TODO: Baby mobs drop nothing once we know their age
+    mob_experience_range(entity_type).map_or(0, |range| rng.gen_range(range))
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_entity::entity_type::EntityType;
+
+    use super::{block_experience_range, level_and_progress, mob_experience_range};
+
+    #[test]
+    fn levels() {
+        assert_eq!(level_and_progress(0), (0, 0.0));
+        assert_eq!(level_and_progress(7), (1, 0.0));
+        // Vanilla needs 352 points for level 16, 1395 for level 30 and 1507 for level 31
+        assert_eq!(level_and_progress(352).0, 16);
+        assert_eq!(level_and_progress(1394).0, 29);
+        assert_eq!(level_and_progress(1395), (30, 0.0));
+        assert_eq!(level_and_progress(1507).0, 31);
+        assert_eq!(level_and_progress(1451), (30, 0.5));
+    }
+
+    #[test]
+    fn ores() {
+        assert_eq!(block_experience_range("deepslate_diamond_ore"), Some(3..=7));
+        assert_eq!(block_experience_range("iron_ore"), None);
+        assert_eq!(block_experience_range("stone"), None);
+    }
+
+    #[test]
+    fn mobs() {
+        assert_eq!(mob_experience_range(&EntityType::Cow), Some(1..=3));
+        assert_eq!(mob_experience_range(&EntityType::Zombie), Some(5..=5));
+        assert_eq!(mob_experience_range(&EntityType::Blaze), Some(10..=10));
+        assert_eq!(mob_experience_range(&EntityType::Wither), Some(50..=50));
+        assert_eq!(mob_experience_range(&EntityType::Villager), None);
+        assert_eq!(mob_experience_range(&EntityType::IronGolem), None);
+    }
+}
@@ -0,0 +1,82 @@
+use std::{collections::HashMap, sync::Arc};
+
+use async_trait::async_trait;
+use pumpkin_core::math::vector3::Vector3;
+use pumpkin_entity::entity_type::EntityType;
+
+use crate::{block::block_manager::BlockActionResult, server::Server};
+
+use super::{living::LivingEntity, player::Hand, player::Player, Entity};
+
+/// How a player clicked on an entity
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EntityInteraction {
+    /// Right click, e.g. trading with a villager
+    Interact(Hand),
+    /// Right click at a specific position relative to the entity, used by armor stands
+    InteractAt(Hand, Vector3<f32>),
+    /// Left click
+    Attack,
+}
+
+/// The entity a player interacted with
+pub enum InteractionTarget {
+    Player(Arc<Player>),
+    Living(Arc<LivingEntity>),
+}
+
+impl InteractionTarget {
+    #[must_use]
+    pub fn entity(&self) -> &Entity {
+        match self {
+            Self::Player(player) => &player.living_entity.entity,
+            Self::Living(living) => &living.entity,
+        }
+    }
+}
+
+#[async_trait]
+pub trait EntityInteractionHandler: Send + Sync {
+    /// Called when a player interacts with an entity of the registered type.
+    /// Return [`BlockActionResult::Consume`] to skip the default behaviour
+    async fn on_interact(
+        &self,
+        player: &Player,
+        target: &InteractionTarget,
+        interaction: EntityInteraction,
+        server: &Server,
+    ) -> BlockActionResult;
+}
+
+/// Saves and calls the interaction handlers of entity types
+#[derive(Default)]
+pub struct EntityInteractionManager {
+    handlers: HashMap<i32, Arc<dyn EntityInteractionHandler>>,
+}
+
+impl EntityInteractionManager {
+    /// Registers the handler for the entity type, replacing an older one
+    pub fn register(
+        &mut self,
+        entity_type: EntityType,
+        handler: Arc<dyn EntityInteractionHandler>,
+    ) {
+        self.handlers.insert(entity_type as i32, handler);
+    }
+
+    pub async fn on_interact(
+        &self,
+        player: &Player,
+        target: &InteractionTarget,
+        interaction: EntityInteraction,
+        server: &Server,
+    ) -> BlockActionResult {
+        let entity_type = target.entity().entity_type.clone() as i32;
+        if let Some(handler) = self.handlers.get(&entity_type) {
+            return handler
+                .on_interact(player, target, interaction, server)
+                .await;
+        }
+        BlockActionResult::Continue
+    }
+}
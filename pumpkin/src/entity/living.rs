@@ -1,7 +1,7 @@
-use std::sync::atomic::AtomicI32;
+use std::sync::atomic::{AtomicI32, AtomicU32};
 
 use crossbeam::atomic::AtomicCell;
-use pumpkin_core::math::vector3::Vector3;
+use pumpkin_core::math::{position::WorldPosition, vector3::Vector3};
 use pumpkin_entity::EntityId;
 use pumpkin_inventory::{Container, EmptyContainer};
 use pumpkin_protocol::client::play::{CDamageEvent, CEntityStatus, CSetEntityMetadata, Metadata};
@@ -30,6 +30,10 @@ where
     pub fall_distance: AtomicCell<f64>,
     /// Inventory if it exists on the entity
     pub inventory: Option<Mutex<C>>,
+    /// The bed the entity is sleeping in
+    pub sleeping_position: AtomicCell<Option<WorldPosition>>,
+    /// Ticks left of a riptide spin attack
+    pub spin_attack_ticks: AtomicU32,
 }
 impl LivingEntity {
     pub const fn new(entity: Entity) -> Self {
@@ -42,6 +46,8 @@ impl LivingEntity {
             fall_distance: AtomicCell::new(0.0),
             // This automatically gets inferred as Option::<EmptyContainer>::None
             inventory: None,
+            sleeping_position: AtomicCell::new(None),
+            spin_attack_ticks: AtomicU32::new(0),
         }
     }
 }
@@ -55,6 +61,8 @@ impl<C: Container> LivingEntity<C> {
             health: AtomicCell::new(20.0),
             fall_distance: AtomicCell::new(0.0),
             inventory: Some(Mutex::new(inventory)),
+            sleeping_position: AtomicCell::new(None),
+            spin_attack_ticks: AtomicU32::new(0),
         }
     }
 
@@ -67,6 +75,11 @@ impl<C: Container> LivingEntity<C> {
             self.time_until_regen
                 .fetch_sub(1, std::sync::atomic::Ordering::Relaxed);
         }
+        let _ = self.spin_attack_ticks.fetch_update(
+            std::sync::atomic::Ordering::Relaxed,
+            std::sync::atomic::Ordering::Relaxed,
+            |ticks| ticks.checked_sub(1),
+        );
     }
 
     pub fn set_pos(&self, position: Vector3<f64>) {
@@ -78,7 +91,7 @@ impl<C: Container> LivingEntity<C> {
         self.health.store(health);
         // tell everyone entities health changed
         self.entity
-            .world
+            .world()
             .broadcast_packet_all(&CSetEntityMetadata::new(
                 self.entity.entity_id.into(),
                 Metadata::new(9, 3.into(), health),
@@ -90,10 +103,18 @@ impl<C: Container> LivingEntity<C> {
         self.entity.entity_id
     }
 
+    /// Returns `true` if the damage killed the entity
     // TODO add damage_type enum
-    pub async fn damage(&self, amount: f32, damage_type: u8) {
+    pub async fn damage(&self, amount: f32, damage_type: u8) -> bool {
+        if self
+            .entity
+            .invulnerable
+            .load(std::sync::atomic::Ordering::Relaxed)
+        {
+            return false;
+        }
         self.entity
-            .world
+            .world()
             .broadcast_packet_all(&CDamageEvent::new(
                 self.entity.entity_id.into(),
                 damage_type.into(),
@@ -107,13 +128,22 @@ impl<C: Container> LivingEntity<C> {
 
         if new_health == 0.0 {
             self.kill().await;
+            true
         } else {
             self.set_health(new_health).await;
+            false
         }
     }
 
     /// Returns if the entity was damaged or not
     pub fn check_damage(&self, amount: f32) -> bool {
+        if self
+            .entity
+            .invulnerable
+            .load(std::sync::atomic::Ordering::Relaxed)
+        {
+            return false;
+        }
         let regen = self
             .time_until_regen
             .load(std::sync::atomic::Ordering::Relaxed);
@@ -133,7 +163,8 @@ impl<C: Container> LivingEntity<C> {
         amount > 0.0
     }
 
-    pub async fn update_fall_distance(&self, dont_damage: bool) {
+    /// Returns `true` if the fall damage killed the entity
+    pub async fn update_fall_distance(&self, dont_damage: bool) -> bool {
         let y = self.entity.pos.load().y;
         let last_y = self.last_pos.load().y;
         let grounded = self
@@ -147,22 +178,23 @@ impl<C: Container> LivingEntity<C> {
         if grounded {
             let fall_distance = self.fall_distance.swap(0.0);
             if dont_damage {
-                return;
+                return false;
             }
 
             let mut damage = (fall_distance - 3.0).max(0.0) as f32;
             damage = (damage * 2.0).round() / 2.0;
             if !self.check_damage(damage) {
-                return;
+                return false;
             }
 
-            self.damage(damage, 10).await; // Fall
+            return self.damage(damage, 10).await; // Fall
         } else if y_diff < 0.0 {
             self.fall_distance.store(0.0);
         } else {
             let fall_distance = self.fall_distance.load();
             self.fall_distance.store(fall_distance + y_diff);
         }
+        false
     }
 
     /// Kills the Entity
@@ -173,12 +205,12 @@ impl<C: Container> LivingEntity<C> {
 
         // Spawns death smoke particles
         self.entity
-            .world
+            .world()
             .broadcast_packet_all(&CEntityStatus::new(self.entity.entity_id, 60))
             .await;
         // Plays the death sound and death animation
         self.entity
-            .world
+            .world()
             .broadcast_packet_all(&CEntityStatus::new(self.entity.entity_id, 3))
             .await;
     }
@@ -0,0 +1,148 @@
+use pumpkin_config::EntityActivationConfig;
+use pumpkin_core::math::vector3::Vector3;
+use pumpkin_entity::entity_type::EntityType;
+
+/// Mobs of the same category share an activation range, see [`EntityActivationConfig`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ActivationCategory {
+    Monster,
+    Animal,
+    Water,
+    Villager,
+    Flying,
+    Misc,
+}
+
+impl ActivationCategory {
+    #[must_use]
+    pub const fn from_entity_type(entity_type: &EntityType) -> Self {
+        match entity_type {
+            EntityType::Bat
+            | EntityType::Allay
+            | EntityType::Bee
+            | EntityType::Blaze
+            | EntityType::Breeze
+            | EntityType::EnderDragon
+            | EntityType::Ghast
+            | EntityType::Parrot
+            | EntityType::Phantom
+            | EntityType::Vex
+            | EntityType::Wither => Self::Flying,
+            EntityType::Axolotl
+            | EntityType::Cod
+            | EntityType::Dolphin
+            | EntityType::ElderGuardian
+            | EntityType::GlowSquid
+            | EntityType::Guardian
+            | EntityType::Pufferfish
+            | EntityType::Salmon
+            | EntityType::Squid
+            | EntityType::Tadpole
+            | EntityType::TropicalFish => Self::Water,
+            EntityType::Villager | EntityType::WanderingTrader => Self::Villager,
+            EntityType::Armadillo
+            | EntityType::Camel
+            | EntityType::Cat
+            | EntityType::Chicken
+            | EntityType::Cow
+            | EntityType::Donkey
+            | EntityType::Fox
+            | EntityType::Frog
+            | EntityType::Goat
+            | EntityType::Horse
+            | EntityType::Llama
+            | EntityType::Mooshroom
+            | EntityType::Mule
+            | EntityType::Ocelot
+            | EntityType::Panda
+            | EntityType::Pig
+            | EntityType::PolarBear
+            | EntityType::Rabbit
+            | EntityType::Sheep
+            | EntityType::SkeletonHorse
+            | EntityType::Sniffer
+            | EntityType::Strider
+            | EntityType::TraderLlama
+            | EntityType::Turtle
+            | EntityType::Wolf
+            | EntityType::ZombieHorse => Self::Animal,
+            EntityType::Bogged
+            | EntityType::CaveSpider
+            | EntityType::Creaking
+            | EntityType::Creeper
+            | EntityType::Drowned
+            | EntityType::Enderman
+            | EntityType::Endermite
+            | EntityType::Evoker
+            | EntityType::Giant
+            | EntityType::Hoglin
+            | EntityType::Husk
+            | EntityType::Illusioner
+            | EntityType::MagmaCube
+            | EntityType::Piglin
+            | EntityType::PiglinBrute
+            | EntityType::Pillager
+            | EntityType::Ravager
+            | EntityType::Shulker
+            | EntityType::Silverfish
+            | EntityType::Skeleton
+            | EntityType::Slime
+            | EntityType::Spider
+            | EntityType::Stray
+            | EntityType::Vindicator
+            | EntityType::Warden
+            | EntityType::Witch
+            | EntityType::WitherSkeleton
+            | EntityType::Zoglin
+            | EntityType::Zombie
+            | EntityType::ZombieVillager
+            | EntityType::ZombifiedPiglin => Self::Monster,
+            _ => Self::Misc,
+        }
+    }
+
+    /// The activation range in blocks, 0 if the mobs are always active
+    #[must_use]
+    pub const fn range(self, config: &EntityActivationConfig) -> u32 {
+        match self {
+            Self::Monster => config.monsters,
+            Self::Animal => config.animals,
+            Self::Water => config.water,
+            Self::Villager => config.villagers,
+            Self::Flying => config.flying,
+            Self::Misc => config.misc,
+        }
+    }
+}
+
+/// Whether a mob at the position should run its AI this tick. Mobs become active again as soon as a player comes into range
+#[must_use]
+pub fn is_active(position: Vector3<f64>, range: u32, players: &[Vector3<f64>]) -> bool {
+    if range == 0 {
+        return true;
+    }
+    let range = f64::from(range);
+    players
+        .iter()
+        .any(|player| player.squared_distance_to_vec(position) <= range * range)
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_core::math::vector3::Vector3;
+
+    use super::is_active;
+
+    #[test]
+    fn activation_range() {
+        let mob = Vector3::new(0.0, 64.0, 0.0);
+        let near = Vector3::new(10.0, 64.0, 10.0);
+        let far = Vector3::new(100.0, 64.0, 0.0);
+
+        assert!(is_active(mob, 32, &[far, near]));
+        assert!(!is_active(mob, 32, &[far]));
+        assert!(!is_active(mob, 32, &[]));
+        // A range of 0 never freezes
+        assert!(is_active(mob, 0, &[]));
+    }
+}
@@ -9,32 +9,26 @@ use zombie::Zombie;
 use crate::{server::Server, world::World};
 
 use super::{
-    ai::{goal::Goal, path::Navigator},
+    ai::{
+        goal::{Goal, GoalSelector},
+        path::Navigator,
+    },
     living::LivingEntity,
 };
 
+pub mod activation;
 pub mod zombie;
 
 pub struct MobEntity {
     pub living_entity: Arc<LivingEntity>,
-    pub goals: Mutex<Vec<(Arc<dyn Goal>, bool)>>,
+    /// What the mob wants to do, see [`MobEntity::goal`]
+    pub goals: Mutex<GoalSelector>,
     pub navigator: Mutex<Navigator>,
 }
 
 impl MobEntity {
     pub async fn tick(&self) {
-        let mut goals = self.goals.lock().await;
-        for (goal, running) in goals.iter_mut() {
-            if *running {
-                if goal.should_continue(self).await {
-                    goal.tick(self).await;
-                } else {
-                    *running = false;
-                }
-            } else {
-                *running = goal.can_start(self).await;
-            }
-        }
+        self.goals.lock().await.tick(self).await;
         let mut navigator = self.navigator.lock().await;
         navigator.tick(&self.living_entity).await;
     }
@@ -54,7 +48,8 @@ pub async fn from_type(
 }
 
 impl MobEntity {
-    pub async fn goal<T: Goal + 'static>(&self, goal: T) {
-        self.goals.lock().await.push((Arc::new(goal), false));
+    /// Adds a goal to the mob, like Vanilla a lower priority is more important, see [`GoalSelector`]
+    pub async fn goal<T: Goal + 'static>(&self, priority: u8, goal: T) {
+        self.goals.lock().await.add(priority, Arc::new(goal));
     }
 }
@@ -5,7 +5,9 @@ use pumpkin_entity::entity_type::EntityType;
 use uuid::Uuid;
 
 use crate::{
-    entity::ai::goal::{look_at_entity::LookAtEntityGoal, target_goal::TargetGoal},
+    entity::ai::goal::{
+        look_at_entity::LookAtEntityGoal, target_goal::TargetGoal, wander::WanderGoal,
+    },
     server::Server,
     world::World,
 };
@@ -23,8 +25,10 @@ impl Zombie {
         let (zombie_entity, uuid) = server
             .add_mob_entity(EntityType::Zombie, position, world)
             .await;
-        zombie_entity.goal(LookAtEntityGoal::new(8.0)).await;
-        zombie_entity.goal(TargetGoal::new(16.0)).await;
+        // Same priorities as Vanilla
+        zombie_entity.goal(2, TargetGoal::new(16.0)).await;
+        zombie_entity.goal(7, WanderGoal::new(0.1)).await;
+        zombie_entity.goal(8, LookAtEntityGoal::new(8.0)).await;
         (zombie_entity, uuid)
     }
 }
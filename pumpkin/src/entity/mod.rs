@@ -1,7 +1,12 @@
 use core::f32;
-use std::sync::{atomic::AtomicBool, Arc};
+use std::sync::{
+    atomic::{AtomicBool, AtomicU8},
+    Arc,
+};
 
+use arc_swap::ArcSwap;
 use crossbeam::atomic::AtomicCell;
+use pumpkin_config::ADVANCED_CONFIG;
 use pumpkin_core::math::{
     boundingbox::{BoundingBox, BoundingBoxSize},
     get_section_cord,
@@ -10,20 +15,47 @@ use pumpkin_core::math::{
     vector3::Vector3,
     wrap_degrees,
 };
+use pumpkin_core::text::TextComponent;
 use pumpkin_entity::{entity_type::EntityType, pose::EntityPose, EntityId};
 use pumpkin_protocol::{
-    client::play::{CHeadRot, CSetEntityMetadata, CTeleportEntity, CUpdateEntityRot, Metadata},
+    client::play::{
+        CHeadRot, CSetEntityMetadata, CSetEntityMetadataBatch, CTeleportEntity, CUpdateEntityRot,
+        Metadata, MetadataValue, MAX_PACKET_VELOCITY,
+    },
     codec::var_int::VarInt,
 };
+use tokio::sync::Mutex;
 
-use crate::world::World;
+use crate::{net::Client, world::World};
 
 pub mod ai;
+pub mod death;
+pub mod experience;
+pub mod interaction;
 pub mod mob;
 
 pub mod living;
 pub mod player;
 
+/// Slows the velocity down to the maximum speed and along each axis to what the velocity packet can hold.
+/// Velocities which aren't finite, e.g. from dividing by zero, become zero
+fn limit_velocity(velocity: Vector3<f64>, max_speed: f64) -> Vector3<f64> {
+    if !(velocity.x.is_finite() && velocity.y.is_finite() && velocity.z.is_finite()) {
+        return Vector3::new(0.0, 0.0, 0.0);
+    }
+    let speed = velocity.length();
+    let velocity = if speed > max_speed {
+        velocity * (max_speed / speed)
+    } else {
+        velocity
+    };
+    Vector3::new(
+        velocity.x.clamp(-MAX_PACKET_VELOCITY, MAX_PACKET_VELOCITY),
+        velocity.y.clamp(-MAX_PACKET_VELOCITY, MAX_PACKET_VELOCITY),
+        velocity.z.clamp(-MAX_PACKET_VELOCITY, MAX_PACKET_VELOCITY),
+    )
+}
+
 /// Represents a not living Entity (e.g. Item, Egg, Snowball...)
 pub struct Entity {
     /// A unique identifier for the entity
@@ -32,8 +64,8 @@ pub struct Entity {
     pub entity_uuid: uuid::Uuid,
     /// The type of entity (e.g., player, zombie, item)
     pub entity_type: EntityType,
-    /// The world in which the entity exists.
-    pub world: Arc<World>,
+    /// The world in which the entity exists, see [`Entity::world`]
+    world: ArcSwap<World>,
     /// The entity's current position in the world
     pub pos: AtomicCell<Vector3<f64>>,
     /// The entity's position rounded to the nearest block coordinates
@@ -44,6 +76,8 @@ pub struct Entity {
     pub sneaking: AtomicBool,
     /// Indicates whether the entity is sprinting
     pub sprinting: AtomicBool,
+    /// Indicates whether the entity is swimming
+    pub swimming: AtomicBool,
     /// Indicates whether the entity is flying due to a fall
     pub fall_flying: AtomicBool,
     /// The entity's current velocity vector, aka Knockback
@@ -64,6 +98,16 @@ pub struct Entity {
     pub bounding_box: AtomicCell<BoundingBox>,
     ///The size (width and height) of the bounding box
     pub bounding_box_size: AtomicCell<BoundingBoxSize>,
+    /// The custom name shown above the entity, e.g. from a name tag
+    pub custom_name: Mutex<Option<TextComponent>>,
+    /// Whether the custom name is always shown, not only when looking at the entity
+    pub custom_name_visible: AtomicBool,
+    /// Whether the entity floats instead of falling, see [`Entity::set_no_gravity`]
+    pub no_gravity: AtomicBool,
+    /// Whether the entity never takes damage, e.g. for NPCs and holograms
+    pub invulnerable: AtomicBool,
+    /// The [`MetadataField`]s which changed since the last [`Entity::flush_metadata`]
+    dirty_metadata: AtomicU8,
 }
 
 impl Entity {
@@ -91,9 +135,10 @@ impl Entity {
             block_pos: AtomicCell::new(WorldPosition(Vector3::new(floor_x, floor_y, floor_z))),
             chunk_pos: AtomicCell::new(Vector2::new(floor_x, floor_z)),
             sneaking: AtomicBool::new(false),
-            world,
+            world: ArcSwap::new(world),
             // TODO: Load this from previous instance
             sprinting: AtomicBool::new(false),
+            swimming: AtomicBool::new(false),
             fall_flying: AtomicBool::new(false),
             yaw: AtomicCell::new(0.0),
             head_yaw: AtomicCell::new(0.0),
@@ -103,6 +148,11 @@ impl Entity {
             pose: AtomicCell::new(EntityPose::Standing),
             bounding_box,
             bounding_box_size,
+            custom_name: Mutex::new(None),
+            custom_name_visible: AtomicBool::new(false),
+            no_gravity: AtomicBool::new(false),
+            invulnerable: AtomicBool::new(false),
+            dirty_metadata: AtomicU8::new(0),
         }
     }
 
@@ -160,7 +210,7 @@ impl Entity {
         // TODO: do caching, only send packet when needed
         let yaw = (yaw * 256.0 / 360.0).rem_euclid(256.0);
         let pitch = (pitch * 256.0 / 360.0).rem_euclid(256.0);
-        self.world
+        self.world()
             .broadcast_packet_all(&CUpdateEntityRot::new(
                 self.entity_id.into(),
                 yaw as u8,
@@ -168,13 +218,13 @@ impl Entity {
                 self.on_ground.load(std::sync::atomic::Ordering::Relaxed),
             ))
             .await;
-        self.world
+        self.world()
             .broadcast_packet_all(&CHeadRot::new(self.entity_id.into(), yaw as u8))
             .await;
     }
 
     pub async fn teleport(&self, position: Vector3<f64>, yaw: f32, pitch: f32) {
-        self.world
+        self.world()
             .broadcast_packet_all(&CTeleportEntity::new(
                 self.entity_id.into(),
                 position,
@@ -197,9 +247,34 @@ impl Entity {
         self.pitch.store(pitch);
     }
 
+    /// Sets the velocity, limited to [`pumpkin_config::VelocityConfig::max_velocity`] and to what the velocity
+    /// packet can hold. Returns the velocity which was set
+    pub fn set_velocity(&self, velocity: Vector3<f64>) -> Vector3<f64> {
+        let limited = limit_velocity(velocity, ADVANCED_CONFIG.velocity.max_velocity);
+        if limited != velocity {
+            log::debug!(
+                "Limited the velocity {velocity:?} of entity {} to {limited:?}",
+                self.entity_id
+            );
+        }
+        self.velocity.store(limited);
+        limited
+    }
+
+    /// The world the entity is in right now
+    pub fn world(&self) -> Arc<World> {
+        self.world.load_full()
+    }
+
+    /// Moves the entity into another world, only the world decides which entities it ticks and shows,
+    /// see [`crate::server::WorldManager::transfer_player`]
+    pub fn set_world(&self, world: Arc<World>) {
+        self.world.store(world);
+    }
+
     /// Removes the Entity from their current World
     pub async fn remove(&self) {
-        self.world.remove_entity(self).await;
+        self.world().remove_entity(self).await;
     }
 
     /// Applies knockback to the entity, following vanilla Minecraft's mechanics.
@@ -216,7 +291,7 @@ impl Entity {
 
         let var8 = Vector3::new(x, 0.0, z).normalize() * strength;
         let velocity = self.velocity.load();
-        self.velocity.store(Vector3::new(
+        self.set_velocity(Vector3::new(
             velocity.x / 2.0 - var8.x,
             if self.on_ground.load(std::sync::atomic::Ordering::Relaxed) {
                 (velocity.y / 2.0 + strength).min(0.4)
@@ -227,57 +302,190 @@ impl Entity {
         ));
     }
 
-    pub async fn set_sneaking(&self, sneaking: bool) {
+    pub fn set_sneaking(&self, sneaking: bool) {
         assert!(self.sneaking.load(std::sync::atomic::Ordering::Relaxed) != sneaking);
         self.sneaking
             .store(sneaking, std::sync::atomic::Ordering::Relaxed);
-        self.set_flag(Flag::Sneaking, sneaking).await;
-        if sneaking {
-            self.set_pose(EntityPose::Crouching).await;
-        } else {
-            self.set_pose(EntityPose::Standing).await;
-        }
+        self.update_metadata(MetadataField::Flags);
     }
 
-    pub async fn set_sprinting(&self, sprinting: bool) {
+    pub fn set_sprinting(&self, sprinting: bool) {
         assert!(self.sprinting.load(std::sync::atomic::Ordering::Relaxed) != sprinting);
         self.sprinting
             .store(sprinting, std::sync::atomic::Ordering::Relaxed);
-        self.set_flag(Flag::Sprinting, sprinting).await;
+        self.update_metadata(MetadataField::Flags);
+    }
+
+    pub fn set_swimming(&self, swimming: bool) {
+        assert!(self.swimming.load(std::sync::atomic::Ordering::Relaxed) != swimming);
+        self.swimming
+            .store(swimming, std::sync::atomic::Ordering::Relaxed);
+        self.update_metadata(MetadataField::Flags);
     }
 
     pub fn check_fall_flying(&self) -> bool {
         !self.on_ground.load(std::sync::atomic::Ordering::Relaxed)
     }
 
-    pub async fn set_fall_flying(&self, fall_flying: bool) {
+    pub fn set_fall_flying(&self, fall_flying: bool) {
         assert!(self.fall_flying.load(std::sync::atomic::Ordering::Relaxed) != fall_flying);
         self.fall_flying
             .store(fall_flying, std::sync::atomic::Ordering::Relaxed);
-        self.set_flag(Flag::FallFlying, fall_flying).await;
+        self.update_metadata(MetadataField::Flags);
+    }
+
+    /// All flags of the entity, the client expects them together in a single byte
+    fn flags(&self) -> i8 {
+        let flags = [
+            (Flag::Sneaking, &self.sneaking),
+            (Flag::Sprinting, &self.sprinting),
+            (Flag::Swimming, &self.swimming),
+            (Flag::FallFlying, &self.fall_flying),
+        ];
+        flags
+            .into_iter()
+            .filter(|(_, value)| value.load(std::sync::atomic::Ordering::Relaxed))
+            .fold(0, |b, (flag, _)| b | (1 << flag as u8))
     }
 
-    async fn set_flag(&self, flag: Flag, value: bool) {
-        let index = flag as u8;
-        let mut b = 0i8;
-        if value {
-            b |= 1 << index;
-        } else {
-            b &= !(1 << index);
+    /// Marks the field as changed, it gets sent with all other changes of this tick by [`Entity::flush_metadata`]
+    pub fn update_metadata(&self, field: MetadataField) {
+        self.dirty_metadata
+            .fetch_or(field as u8, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    /// Sends all metadata fields which changed since the last flush in a single packet
+    pub async fn flush_metadata(&self) {
+        let dirty = self
+            .dirty_metadata
+            .swap(0, std::sync::atomic::Ordering::Relaxed);
+        if dirty == 0 {
+            return;
+        }
+        let mut metadata = Vec::new();
+        if dirty & MetadataField::Flags as u8 != 0 {
+            metadata.push(Metadata::new(
+                0,
+                0.into(),
+                MetadataValue::Byte(self.flags()),
+            ));
         }
-        let packet = CSetEntityMetadata::new(self.entity_id.into(), Metadata::new(0, 0.into(), b));
-        self.world.broadcast_packet_all(&packet).await;
+        if dirty & MetadataField::Pose as u8 != 0 {
+            let pose = self.pose.load() as i32;
+            metadata.push(Metadata::new(
+                6,
+                21.into(),
+                MetadataValue::VarInt(pose.into()),
+            ));
+        }
+        self.world()
+            .broadcast_packet_all(&CSetEntityMetadataBatch::new(
+                self.entity_id.into(),
+                &metadata,
+            ))
+            .await;
     }
 
-    pub async fn set_pose(&self, pose: EntityPose) {
-        self.pose.store(pose);
-        let pose = pose as i32;
-        let packet = CSetEntityMetadata::<VarInt>::new(
-            self.entity_id.into(),
-            Metadata::new(6, 21.into(), pose.into()),
-        );
-        self.world.broadcast_packet_all(&packet).await;
+    /// Sets or clears the custom name of the entity and sends it to all players who can see the entity
+    pub async fn set_custom_name(&self, name: Option<TextComponent>, visible: bool) {
+        let chunk_pos = self.chunk_pos.load();
+        self.world()
+            .broadcast_to_chunk_watchers(
+                chunk_pos,
+                &CSetEntityMetadata::new(self.entity_id.into(), Metadata::new(2, 6.into(), &name)),
+            )
+            .await;
+        self.world()
+            .broadcast_to_chunk_watchers(
+                chunk_pos,
+                &CSetEntityMetadata::new(
+                    self.entity_id.into(),
+                    Metadata::new(3, 8.into(), visible),
+                ),
+            )
+            .await;
+        *self.custom_name.lock().await = name;
+        self.custom_name_visible
+            .store(visible, std::sync::atomic::Ordering::Relaxed);
     }
+
+    /// Lets the entity float instead of falling and sends it to all players who can see the entity
+    pub async fn set_no_gravity(&self, no_gravity: bool) {
+        self.no_gravity
+            .store(no_gravity, std::sync::atomic::Ordering::Relaxed);
+        self.world()
+            .broadcast_to_chunk_watchers(
+                self.chunk_pos.load(),
+                &CSetEntityMetadata::new(
+                    self.entity_id.into(),
+                    Metadata::new(5, 8.into(), no_gravity),
+                ),
+            )
+            .await;
+    }
+
+    /// Sends the custom name to a client which just started seeing the entity
+    pub async fn send_custom_name(&self, client: &Client) {
+        let name = self.custom_name.lock().await;
+        if name.is_none() {
+            return;
+        }
+        client
+            .send_packet(&CSetEntityMetadata::new(
+                self.entity_id.into(),
+                Metadata::new(2, 6.into(), &*name),
+            ))
+            .await;
+        client
+            .send_packet(&CSetEntityMetadata::new(
+                self.entity_id.into(),
+                Metadata::new(
+                    3,
+                    8.into(),
+                    self.custom_name_visible
+                        .load(std::sync::atomic::Ordering::Relaxed),
+                ),
+            ))
+            .await;
+    }
+
+    /// Sends the flags, pose and gravity to a client which just started seeing the entity
+    pub async fn send_pose(&self, client: &Client) {
+        client
+            .send_packet(&CSetEntityMetadata::new(
+                self.entity_id.into(),
+                Metadata::new(0, 0.into(), self.flags()),
+            ))
+            .await;
+        client
+            .send_packet(&CSetEntityMetadata::<VarInt>::new(
+                self.entity_id.into(),
+                Metadata::new(6, 21.into(), (self.pose.load() as i32).into()),
+            ))
+            .await;
+        if self.no_gravity.load(std::sync::atomic::Ordering::Relaxed) {
+            client
+                .send_packet(&CSetEntityMetadata::new(
+                    self.entity_id.into(),
+                    Metadata::new(5, 8.into(), true),
+                ))
+                .await;
+        }
+    }
+
+    pub fn set_pose(&self, pose: EntityPose) {
+        if self.pose.swap(pose) != pose {
+            self.update_metadata(MetadataField::Pose);
+        }
+    }
+}
+
+/// The metadata fields whose changes get batched, see [`Entity::update_metadata`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum MetadataField {
+    Flags = 1 << 0,
+    Pose = 1 << 1,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -305,3 +513,28 @@ pub enum Flag {
     /// Indicates if the entity is flying due to a fall.
     FallFlying = 7,
 }
+
+#[cfg(test)]
+mod test {
+    use pumpkin_core::math::vector3::Vector3;
+
+    use super::limit_velocity;
+
+    #[test]
+    fn velocity_limits() {
+        let velocity = Vector3::new(1.0, 0.5, -1.0);
+        assert_eq!(limit_velocity(velocity, 10.0), velocity);
+        // Slowed down to the maximum speed, keeping the direction
+        let limited = limit_velocity(Vector3::new(0.0, 30.0, 40.0), 5.0);
+        assert!((limited.y - 3.0).abs() < 1e-9 && (limited.z - 4.0).abs() < 1e-9);
+        // Each axis fits into the velocity packet
+        assert_eq!(
+            limit_velocity(Vector3::new(-5.0, 0.0, 0.0), 10.0),
+            Vector3::new(-3.9, 0.0, 0.0)
+        );
+        assert_eq!(
+            limit_velocity(Vector3::new(f64::NAN, 1.0, f64::INFINITY), 10.0),
+            Vector3::new(0.0, 0.0, 0.0)
+        );
+    }
+}
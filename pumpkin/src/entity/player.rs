@@ -20,33 +20,38 @@ use pumpkin_core::{
     text::TextComponent,
     GameMode,
 };
-use pumpkin_entity::{entity_type::EntityType, EntityId};
+use pumpkin_entity::{entity_type::EntityType, pose::EntityPose, EntityId};
 use pumpkin_inventory::player::PlayerInventory;
 use pumpkin_macros::sound;
 use pumpkin_protocol::server::play::{
-    SCloseContainer, SCookieResponse as SPCookieResponse, SPlayPingRequest,
+    ResourcePackStatus, SCloseContainer, SCookieResponse as SPCookieResponse, SPlayPingRequest,
+    SPlayPluginMessage,
 };
 use pumpkin_protocol::{
     bytebuf::packet_id::Packet,
     client::play::{
-        CCombatDeath, CEntityStatus, CGameEvent, CHurtAnimation, CKeepAlive, CPlayDisconnect,
-        CPlayerAbilities, CPlayerInfoUpdate, CPlayerPosition, CSetHealth, CSystemChatMessage,
-        GameEvent, PlayerAction,
+        Animation, CActionBar, CChangeDifficulty, CCombatDeath, CEntityStatus, CGameEvent,
+        CHurtAnimation, CKeepAlive, CPlayerAbilities, CPlayerInfoUpdate, CPlayerPosition,
+        CResourcePackPush, CSetDefaultSpawnPosition, CSetExperience, CSetHealth,
+        CSystemChatMessage, GameEvent, PlayerAction,
     },
     server::play::{
         SChatCommand, SChatMessage, SClientCommand, SClientInformationPlay, SClientTickEnd,
         SCommandSuggestion, SConfirmTeleport, SInteract, SPickItemFromBlock, SPlayerAbilities,
         SPlayerAction, SPlayerCommand, SPlayerInput, SPlayerPosition, SPlayerPositionRotation,
-        SPlayerRotation, SSetCreativeSlot, SSetHeldItem, SSetPlayerGround, SSwingArm, SUseItem,
-        SUseItemOn,
+        SPlayerRotation, SResourcePackResponse, SSetCreativeSlot, SSetHeldItem, SSetPlayerGround,
+        SSwingArm, SUseItem, SUseItemOn,
     },
     RawPacket, ServerPacket, SoundCategory,
 };
-use pumpkin_protocol::{client::play::CUpdateTime, codec::var_int::VarInt};
 use pumpkin_protocol::{
-    client::play::{CSetEntityMetadata, Metadata},
+    client::play::{CEntityVelocity, CSetEntityMetadata, Metadata},
     server::play::{SClickContainer, SKeepAlive},
 };
+use pumpkin_protocol::{
+    client::play::{CSetHeldItem, CUpdateTime},
+    codec::var_int::VarInt,
+};
 use pumpkin_world::{
     cylindrical_chunk_iterator::Cylindrical,
     item::{
@@ -58,18 +63,37 @@ use tokio::sync::{Mutex, Notify, RwLock};
 
 use super::Entity;
 use crate::{
-    command::{client_cmd_suggestions, dispatcher::CommandDispatcher},
-    data::op_data::OPERATOR_CONFIG,
+    command::{client_cmd_suggestions, cooldown::CommandCooldowns, dispatcher::CommandDispatcher},
+    data::{op_data::OPERATOR_CONFIG, player_inventory_data::SavedInventory},
+    log_context::player_debug,
     net::{
         combat::{self, player_attack_sound, AttackType},
+        rate_limit::{RateLimitedAction, RateLimiter},
         Client, PlayerConfig,
     },
     server::Server,
-    world::World,
+    world::{SpawnPoint, World},
 };
 use crate::{error::PumpkinError, net::GameProfile};
 
-use super::living::LivingEntity;
+use super::{death::DeathCause, experience::level_and_progress, living::LivingEntity};
+
+/// The hitbox of a player in the pose, like Vanilla
+const fn pose_size(pose: EntityPose) -> BoundingBoxSize {
+    let (width, height) = match pose {
+        EntityPose::Sleeping | EntityPose::Dying => (0.2, 0.2),
+        EntityPose::FallFlying | EntityPose::Swimming | EntityPose::SpinAttack => (0.6, 0.6),
+        EntityPose::Crouching => (0.6, 1.5),
+        _ => (0.6, 1.8),
+    };
+    BoundingBoxSize { width, height }
+}
+
+/// How many ticks a player has to wait before using a portal again after changing dimensions, like Vanilla
+const PORTAL_COOLDOWN: u32 = 300;
+
+/// The permission level needed to chat with everyone while local chat is enabled
+const GLOBAL_CHAT_PERMISSION: PermissionLvl = PermissionLvl::Two;
 
 /// Represents a Minecraft player entity.
 ///
@@ -85,10 +109,14 @@ pub struct Player {
     pub config: Mutex<PlayerConfig>,
     /// The player's current gamemode (e.g., Survival, Creative, Adventure).
     pub gamemode: AtomicCell<GameMode>,
+    /// The gamemode the player had before entering a world with a forced gamemode, see [`World::enforce_gamemode`]
+    pub gamemode_before_forced: AtomicCell<Option<GameMode>>,
     /// The player's hunger level.
     pub food: AtomicI32,
     /// The player's food saturation level.
     pub food_saturation: AtomicCell<f32>,
+    /// The total experience points the player collected, see [`Player::add_experience`]
+    pub experience: AtomicI32,
     /// The ID of the currently open container (if any).
     pub open_container: AtomicCell<Option<u64>>,
     /// The item currently being held by the player.
@@ -120,10 +148,47 @@ pub struct Player {
     pub last_attacked_ticks: AtomicU32,
     /// The players op permission level
     pub permission_lvl: AtomicCell<PermissionLvl>,
+    /// Counts the players actions to drop them when the player is spamming
+    pub rate_limiter: RateLimiter,
+    /// When the player last used the commands with a cooldown
+    pub command_cooldowns: Mutex<CommandCooldowns>,
+    /// Ticks since the player got teleported, used to exempt them from flight checks for a while
+    pub ticks_since_teleport: AtomicU32,
+    /// The chunk the player floats in place for until it is sent, and since when, see [`Player::hold_until_chunk_sent`]
+    pub held_for_chunk: AtomicCell<Option<(Vector2<i32>, Instant)>>,
+    /// Whether the player is shown in the tab list, see [`World::set_listed`]
+    pub listed: AtomicBool,
+    /// Where the player is in the tab list, players with a higher order come first
+    pub list_order: AtomicI32,
+    /// How many movements in a row the player floated in the air
+    pub floating_ticks: AtomicU32,
+    /// Whether the player is charging a jump of the horse they ride
+    pub jumping_with_horse: AtomicBool,
+    /// The last status the client reported for the server resource pack, `None` if it did not respond yet
+    pub resource_pack_status: AtomicCell<Option<ResourcePackStatus>>,
+    /// Whether the debug screen hides coordinates and other details, see [`Player::set_reduced_debug_info`]
+    pub reduced_debug_info: AtomicBool,
+    /// Ticks until the player can use a portal again, set after changing dimensions
+    pub portal_cooldown: AtomicU32,
+    /// The bed the player respawns at and the way they face, `None` to respawn at the world spawn
+    pub respawn_point: AtomicCell<Option<SpawnPoint>>,
+    /// Whether the player wants to see the TPS on their action bar, see [`Server::set_tps_overlay`]
+    pub tps_overlay: AtomicBool,
+    /// Whether the chat messages of this operator reach everyone even with local chat, see [`Player::set_global_chat`]
+    pub global_chat: AtomicBool,
+    /// Whether the player is respawning right now, so they don't respawn twice, see [`Server::respawn_dead_player`]
+    pub respawning: AtomicBool,
     /// Tell tasks to stop if we are closing
     cancel_tasks: Notify,
 }
 
+/// An impossible watched section, so that `player_chunker::update_position` marks all chunks as watched as for a
+/// new join rather than a respawn.
+/// (We left shift by one so we can search around that chunk)
+fn no_watched_section() -> Cylindrical {
+    Cylindrical::new(Vector2::new(i32::MAX >> 1, i32::MAX >> 1), NonZeroU8::MIN)
+}
+
 impl Player {
     pub async fn new(
         client: Arc<Client>,
@@ -146,11 +211,9 @@ impl Player {
         );
 
         let gameprofile_clone = gameprofile.clone();
+        let reduced_debug_info = world.config.reduced_debug_info;
         let config = client.config.lock().await.clone().unwrap_or_default();
-        let bounding_box_size = BoundingBoxSize {
-            width: 0.6,
-            height: 1.8,
-        };
+        let bounding_box_size = pose_size(EntityPose::Standing);
 
         Self {
             living_entity: LivingEntity::new_with_container(
@@ -173,19 +236,15 @@ impl Player {
             // TODO: Load this from previous instance
             food: AtomicI32::new(20),
             food_saturation: AtomicCell::new(20.0),
+            experience: AtomicI32::new(0),
             current_block_destroy_stage: AtomicU8::new(0),
             open_container: AtomicCell::new(None),
             carried_item: AtomicCell::new(None),
             teleport_id_count: AtomicI32::new(0),
             abilities: Mutex::new(Abilities::default()),
             gamemode: AtomicCell::new(gamemode),
-            // We want this to be an impossible watched section so that `player_chunker::update_position`
-            // will mark chunks as watched for a new join rather than a respawn
-            // (We left shift by one so we can search around that chunk)
-            watched_section: AtomicCell::new(Cylindrical::new(
-                Vector2::new(i32::MAX >> 1, i32::MAX >> 1),
-                unsafe { NonZeroU8::new_unchecked(1) },
-            )),
+            gamemode_before_forced: AtomicCell::new(None),
+            watched_section: AtomicCell::new(no_watched_section()),
             wait_for_keep_alive: AtomicBool::new(false),
             keep_alive_id: AtomicI64::new(0),
             last_keep_alive_time: AtomicCell::new(std::time::Instant::now()),
@@ -193,16 +252,28 @@ impl Player {
             cancel_tasks: Notify::new(),
             // Minecraft has no why to change the default permission level of new players.
             // Minecrafts default permission level is 0
-            permission_lvl: OPERATOR_CONFIG
-                .read()
-                .await
-                .ops
-                .iter()
-                .find(|op| op.uuid == gameprofile_clone.id)
-                .map_or(
-                    AtomicCell::new(ADVANCED_CONFIG.commands.default_op_level),
-                    |op| AtomicCell::new(op.level),
-                ),
+            permission_lvl: AtomicCell::new(
+                OPERATOR_CONFIG
+                    .read()
+                    .await
+                    .get_level(&gameprofile_clone.id)
+                    .unwrap_or(ADVANCED_CONFIG.commands.default_op_level),
+            ),
+            rate_limiter: RateLimiter::default(),
+            command_cooldowns: Mutex::new(CommandCooldowns::default()),
+            resource_pack_status: AtomicCell::new(None),
+            reduced_debug_info: AtomicBool::new(reduced_debug_info),
+            ticks_since_teleport: AtomicU32::new(0),
+            portal_cooldown: AtomicU32::new(0),
+            respawn_point: AtomicCell::new(None),
+            tps_overlay: AtomicBool::new(false),
+            global_chat: AtomicBool::new(false),
+            respawning: AtomicBool::new(false),
+            held_for_chunk: AtomicCell::new(None),
+            listed: AtomicBool::new(true),
+            list_order: AtomicI32::new(0),
+            floating_ticks: AtomicU32::new(0),
+            jumping_with_horse: AtomicBool::new(false),
         }
     }
 
@@ -216,48 +287,53 @@ impl Player {
     /// Removes the Player out of the current World
     #[allow(unused_variables)]
     pub async fn remove(&self) {
-        let world = self.world();
+        let world = &self.world();
         self.cancel_tasks.notify_waiters();
 
         world.remove_player(self).await;
 
-        let cylindrical = self.watched_section.load();
-
-        // Radial chunks are all of the chunks the player is theoretically viewing
-        // Giving enough time, all of these chunks will be in memory
-        let radial_chunks = cylindrical.all_chunks_within();
-
-        log::debug!(
-            "Removing player {} ({}), unwatching {} chunks",
-            self.gameprofile.name,
+        let unwatched = self.unwatch_chunks(world);
+        player_debug!(
+            self,
+            "Removing player (client id {}), unwatched {} chunks",
             self.client.id,
-            radial_chunks.len()
+            unwatched
         );
 
-        // Decrement value of watched chunks
-        let chunks_to_clean = world.level.mark_chunks_as_not_watched(&radial_chunks);
-
-        // Remove chunks with no watchers from the cache
-        world.level.clean_chunks(&chunks_to_clean);
-        // Remove left over entries from all possiblily loaded chunks
-        world.level.clean_memory(&radial_chunks);
-
-        log::debug!(
-            "Removed player id {} ({}) ({} chunks remain cached)",
-            self.gameprofile.name,
-            self.client.id,
+        player_debug!(
+            self,
+            "Removed player ({} chunks remain cached)",
             self.world().level.loaded_chunk_count()
         );
 
         //self.world().level.list_cached();
     }
 
+    /// Stops watching the chunks around the player in the world, so the next chunk update sends all chunks
+    /// around them again, e.g. in the world they move to. Returns how many chunks were unwatched
+    pub fn unwatch_chunks(&self, world: &World) -> usize {
+        let cylindrical = self.watched_section.swap(no_watched_section());
+
+        // Radial chunks are all of the chunks the player is theoretically viewing
+        // Giving enough time, all of these chunks will be in memory
+        let radial_chunks = world
+            .chunk_selector
+            .select_chunks(cylindrical.center, cylindrical.view_distance);
+
+        // Decrement value of watched chunks
+        world.level.mark_chunks_as_not_watched(&radial_chunks);
+
+        // Unload chunks with no watchers once their grace period is over, including left over entries from all
+        // possibly loaded chunks
+        world.level.schedule_unload(&radial_chunks);
+        radial_chunks.len()
+    }
+
     pub async fn attack(&self, victim: &Arc<Self>) {
-        let world = self.world();
+        let world = &self.world();
         let victim_entity = &victim.living_entity.entity;
         let attacker_entity = &self.living_entity.entity;
         let config = &ADVANCED_CONFIG.pvp;
-
         let inventory = self.inventory().lock().await;
         let item_slot = inventory.held_item();
 
@@ -329,10 +405,15 @@ impl Player {
             damage *= 1.5;
         }
 
-        victim
+        let killed = victim
             .living_entity
             .damage(damage as f32, 34) // PlayerAttack
             .await;
+        if killed {
+            victim
+                .on_death(DeathCause::Player(&self.gameprofile.name))
+                .await;
+        }
 
         let mut knockback_strength = 1.0;
         match attack_type {
@@ -351,11 +432,24 @@ impl Player {
         if config.hurt_animation {
             let entity_id = VarInt(victim_entity.entity_id);
             world
-                .broadcast_packet_all(&CHurtAnimation::new(&entity_id, attacker_entity.yaw.load()))
+                .broadcast_to_chunk_watchers(
+                    victim_entity.chunk_pos.load(),
+                    &CHurtAnimation::new(&entity_id, attacker_entity.yaw.load()),
+                )
                 .await;
         }
 
-        if config.swing {}
+        if matches!(attack_type, AttackType::Critical) {
+            world
+                .send_entity_animation(victim_entity.entity_id, Animation::CriticalEffect)
+                .await;
+        }
+
+        if config.swing {
+            world
+                .send_entity_animation(attacker_entity.entity_id, Animation::SwingMainArm)
+                .await;
+        }
     }
 
     pub async fn await_cancel(&self) {
@@ -373,8 +467,27 @@ impl Player {
         let now = Instant::now();
         self.last_attacked_ticks
             .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        self.ticks_since_teleport
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        // Count down without wrapping below 0
+        let _ = self.portal_cooldown.fetch_update(
+            std::sync::atomic::Ordering::Relaxed,
+            std::sync::atomic::Ordering::Relaxed,
+            |cooldown| cooldown.checked_sub(1),
+        );
+
+        // The chunk may never arrive, e.g. when it failed to generate
+        if let Some((_, since)) = self.held_for_chunk.load() {
+            let timeout = Duration::from_millis(ADVANCED_CONFIG.movement.chunk_hold_timeout_ms);
+            if now.duration_since(since) >= timeout {
+                self.release_chunk_hold().await;
+            }
+        }
 
         self.living_entity.tick();
+        self.slow_down();
+        self.update_swimming().await;
+        self.update_pose().await;
 
         if now.duration_since(self.last_keep_alive_time.load()) >= Duration::from_secs(15) {
             // We never got a response from our last keep alive we send
@@ -411,8 +524,48 @@ impl Player {
         self.living_entity.entity.entity_id
     }
 
-    pub const fn world(&self) -> &Arc<World> {
-        &self.living_entity.entity.world
+    /// The world the player is in right now, players change worlds e.g. when respawning in another dimension
+    pub fn world(&self) -> Arc<World> {
+        self.living_entity.entity.world()
+    }
+
+    /// Lets the player float in place until the chunk they are in was sent, so they can't fall through terrain their
+    /// client doesn't have yet. Only the client is told they fly, their abilities stay the same
+    pub async fn hold_until_chunk_sent(&self) {
+        if !ADVANCED_CONFIG.movement.hold_until_chunk_sent {
+            return;
+        }
+        let chunk = self.living_entity.entity.chunk_pos.load();
+        self.held_for_chunk.store(Some((chunk, Instant::now())));
+        let abilities = self.abilities.lock().await;
+        // Flying without any speed, so the player neither falls nor moves
+        let mut flags = 2 | 4;
+        if abilities.invulnerable {
+            flags |= 1;
+        }
+        if abilities.creative {
+            flags |= 8;
+        }
+        self.client
+            .send_packet(&CPlayerAbilities::new(flags, 0.0, abilities.walk_speed_fov))
+            .await;
+    }
+
+    /// Releases a player held by [`Player::hold_until_chunk_sent`] once the chunk they are waiting for was sent
+    pub async fn on_chunk_sent(&self, chunk: Vector2<i32>) {
+        if self
+            .held_for_chunk
+            .load()
+            .is_some_and(|(held_for, _)| held_for == chunk)
+        {
+            self.release_chunk_hold().await;
+        }
+    }
+
+    async fn release_chunk_hold(&self) {
+        if self.held_for_chunk.swap(None).is_some() {
+            self.send_abilities_update().await;
+        }
     }
 
     /// Updates the current abilities the Player has
@@ -441,6 +594,217 @@ impl Player {
             .await;
     }
 
+    /// Loads the saved inventory of the player and sends it together with the selected slot.
+    /// Players joining for the first time keep their empty inventory
+    pub async fn load_inventory(&self) {
+        if let Some(saved) = SavedInventory::load(&self.gameprofile.id) {
+            saved.apply(&mut *self.inventory().lock().await);
+        }
+        self.set_container_content(None).await;
+        let selected = self.inventory().lock().await.get_selected() - 36;
+        self.client
+            .send_packet(&CSetHeldItem::new(selected as i8))
+            .await;
+    }
+
+    /// Saves the inventory, so the player gets it back when joining again
+    pub async fn save_inventory(&self) {
+        SavedInventory::from_inventory(&*self.inventory().lock().await).save(&self.gameprofile.id);
+    }
+
+    /// Hides or shows coordinates and other details on the debug screen of the player
+    pub async fn set_reduced_debug_info(&self, reduced_debug_info: bool) {
+        self.reduced_debug_info
+            .store(reduced_debug_info, std::sync::atomic::Ordering::Relaxed);
+        self.send_reduced_debug_info().await;
+    }
+
+    /// Syncs the reduced debug info with the client
+    pub async fn send_reduced_debug_info(&self) {
+        let status = if self
+            .reduced_debug_info
+            .load(std::sync::atomic::Ordering::Relaxed)
+        {
+            22
+        } else {
+            23
+        };
+        self.client
+            .send_packet(&CEntityStatus::new(self.entity_id(), status))
+            .await;
+    }
+
+    /// Players start swimming when sprinting under water and keep swimming as long as they sprint in water
+    async fn update_swimming(&self) {
+        let entity = &self.living_entity.entity;
+        let swimming = entity.swimming.load(std::sync::atomic::Ordering::Relaxed);
+        let sprinting = entity.sprinting.load(std::sync::atomic::Ordering::Relaxed);
+        let should_swim = sprinting
+            && if swimming {
+                self.is_in_water(entity.block_pos.load()).await
+            } else {
+                let pos = entity.pos.load();
+                let eyes = WorldPosition(Vector3::new(
+                    pos.x.floor() as i32,
+                    (pos.y + f64::from(entity.standing_eye_height)).floor() as i32,
+                    pos.z.floor() as i32,
+                ));
+                self.is_in_water(eyes).await
+            };
+        if should_swim != swimming {
+            entity.set_swimming(should_swim);
+        }
+    }
+
+    async fn is_in_water(&self, position: WorldPosition) -> bool {
+        self.world()
+            .get_block(position)
+            .await
+            .is_ok_and(|block| block.name == "water")
+    }
+
+    /// The pose the player should be in right now, the first matching state wins
+    async fn desired_pose(&self) -> EntityPose {
+        let living = &self.living_entity;
+        let entity = &living.entity;
+        if living.health.load() <= 0.0 {
+            EntityPose::Dying
+        } else if living.sleeping_position.load().is_some() {
+            EntityPose::Sleeping
+        } else if entity.swimming.load(std::sync::atomic::Ordering::Relaxed) {
+            EntityPose::Swimming
+        } else if entity
+            .fall_flying
+            .load(std::sync::atomic::Ordering::Relaxed)
+        {
+            EntityPose::FallFlying
+        } else if living
+            .spin_attack_ticks
+            .load(std::sync::atomic::Ordering::Relaxed)
+            > 0
+        {
+            EntityPose::SpinAttack
+        } else if entity.sneaking.load(std::sync::atomic::Ordering::Relaxed)
+            && !self.abilities.lock().await.flying
+        {
+            EntityPose::Crouching
+        } else {
+            EntityPose::Standing
+        }
+    }
+
+    /// Updates the pose and hitbox of the player and shows the new pose to everyone
+    // TODO: Crawl when the player does not fit into a block space standing up
+    async fn update_pose(&self) {
+        let pose = self.desired_pose().await;
+        let entity = &self.living_entity.entity;
+        if entity.pose.load() == pose {
+            return;
+        }
+        let size = pose_size(pose);
+        let pos = entity.pos.load();
+        entity.bounding_box_size.store(size);
+        entity
+            .bounding_box
+            .store(BoundingBox::new_from_pos(pos.x, pos.y, pos.z, &size));
+        entity.set_pose(pose);
+    }
+
+    /// The respawn point of the player, or the world spawn if they don't have one
+    pub async fn spawn_point(&self) -> SpawnPoint {
+        match self.respawn_point.load() {
+            Some(respawn_point) => respawn_point,
+            None => self.world().default_spawn().await,
+        }
+    }
+
+    /// Points the compass of the player to their respawn point, or the world spawn if they don't have one
+    pub async fn send_compass_target(&self) {
+        let spawn = self.spawn_point().await;
+        self.client
+            .send_packet(&CSetDefaultSpawnPosition::new(spawn.position, spawn.angle))
+            .await;
+    }
+
+    /// Whether the gamemode lets the player break and place blocks, using blocks like doors is always allowed
+    #[must_use]
+    pub fn may_modify_blocks(&self) -> bool {
+        match self.gamemode.load() {
+            // TODO: Allow held items with the `can_break` and `can_place_on` components once items have components
+            GameMode::Adventure | GameMode::Spectator => false,
+            GameMode::Survival | GameMode::Creative | GameMode::Undefined => true,
+        }
+    }
+
+    /// Lets an operator's chat messages reach every player even when local chat is enabled.
+    ///
+    /// Returns `false` if the player may not enable it
+    pub fn set_global_chat(&self, enabled: bool) -> bool {
+        if enabled && self.permission_lvl.load() < GLOBAL_CHAT_PERMISSION {
+            return false;
+        }
+        self.global_chat
+            .store(enabled, std::sync::atomic::Ordering::Relaxed);
+        true
+    }
+
+    /// Pushes the player, e.g. for jump pads or explosions. Pushes add up with the velocity the player still has.
+    ///
+    /// The client moves the player itself, so only the player's own client is told about the new velocity
+    pub async fn apply_velocity(&self, velocity: Vector3<f64>) {
+        let entity = &self.living_entity.entity;
+        let velocity = entity.set_velocity(entity.velocity.load().add(&velocity));
+        let entity_id = VarInt(entity.entity_id);
+        self.client
+            .send_packet(&CEntityVelocity::new(
+                &entity_id, velocity.x, velocity.y, velocity.z,
+            ))
+            .await;
+    }
+
+    /// Follows the client slowing down the player after a push, so we know when it's over
+    fn slow_down(&self) {
+        let entity = &self.living_entity.entity;
+        let velocity = entity.velocity.load();
+        if velocity.length_squared() == 0.0 {
+            return;
+        }
+        // Same drag and gravity as Vanilla, ignoring the slipperiness of blocks
+        let gravity = if entity.no_gravity.load(std::sync::atomic::Ordering::Relaxed) {
+            0.0
+        } else {
+            0.08
+        };
+        let velocity = if entity.on_ground.load(std::sync::atomic::Ordering::Relaxed) {
+            velocity.multiply(0.546, 0.0, 0.546)
+        } else {
+            Vector3::new(
+                velocity.x * 0.91,
+                (velocity.y - gravity) * 0.98,
+                velocity.z * 0.91,
+            )
+        };
+        // Falling is tracked by the flight checks, only keep the velocity while it still pushes the player
+        if velocity.y <= 0.0 && velocity.x.hypot(velocity.z) < 0.003 {
+            entity.velocity.store(Vector3::new(0.0, 0.0, 0.0));
+        } else {
+            entity.velocity.store(velocity);
+        }
+    }
+
+    /// Keeps the player from using a portal for a while, so they don't travel right back after arriving
+    pub fn start_portal_cooldown(&self) {
+        self.portal_cooldown
+            .store(PORTAL_COOLDOWN, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    #[must_use]
+    pub fn is_on_portal_cooldown(&self) -> bool {
+        self.portal_cooldown
+            .load(std::sync::atomic::Ordering::Relaxed)
+            > 0
+    }
+
     /// syncs the players permission level with the client
     pub async fn send_permission_lvl_update(&self) {
         self.client
@@ -474,10 +838,38 @@ impl Player {
             .await;
     }
 
+    /// Teleports the player using an entity teleport, yaw and pitch in degrees
+    pub async fn teleport(&self, position: Vector3<f64>, yaw: f32, pitch: f32) {
+        self.ticks_since_teleport
+            .store(0, std::sync::atomic::Ordering::Relaxed);
+        self.living_entity
+            .entity
+            .teleport(position, yaw, pitch)
+            .await;
+    }
+
+    /// Sends the difficulty of the world to just the player.
+    pub async fn send_difficulty(&self, world: &World) {
+        self.client
+            .send_packet(&CChangeDifficulty::new(
+                world.config.difficulty as u8,
+                false,
+            ))
+            .await;
+    }
+
     /// Yaw and Pitch in degrees
     /// Rarly used, For example when waking up player from bed or first time spawn. Otherwise entity teleport is used
     /// Player should respond with the `SConfirmTeleport` packet
     pub async fn request_teleport(&self, position: Vector3<f64>, yaw: f32, pitch: f32) {
+        self.ticks_since_teleport
+            .store(0, std::sync::atomic::Ordering::Relaxed);
+        self.send_teleport(position, yaw, pitch).await;
+    }
+
+    /// Like [`Player::request_teleport`], but without the grace period the movement checks give after teleports.
+    /// Used when the server moves the player back itself
+    pub async fn send_teleport(&self, position: Vector3<f64>, yaw: f32, pitch: f32) {
         // this is the ultra special magic code used to create the teleport id
         // This returns the old value
         // This operation wraps around on overflow.
@@ -522,31 +914,69 @@ impl Player {
         }) < d * d
     }
 
-    /// Kicks the Client with a reason depending on the connection state
+    pub fn entity_interaction_range(&self) -> f64 {
+        if self.gamemode.load() == GameMode::Creative {
+            5.0
+        } else {
+            3.0
+        }
+    }
+
+    pub fn can_interact_with_entity(&self, entity: &Entity, additional_range: f64) -> bool {
+        let d = self.entity_interaction_range() + additional_range;
+        let entity_pos = self.living_entity.entity.pos.load();
+        let standing_eye_height = self.living_entity.entity.standing_eye_height;
+        entity.bounding_box.load().squared_magnitude(Vector3 {
+            x: entity_pos.x,
+            y: entity_pos.y + f64::from(standing_eye_height),
+            z: entity_pos.z,
+        }) < d * d
+    }
+
+    /// Counts the action for rate limiting, returns `false` if the action should be dropped.
+    /// Players which keep on spamming get kicked
+    pub async fn check_rate_limit(&self, action: RateLimitedAction) -> bool {
+        if self.rate_limiter.try_action(action) {
+            return true;
+        }
+        player_debug!(self, "Exceeded the rate limit for {action:?}, dropping it");
+        if self.rate_limiter.should_kick() {
+            self.kick(TextComponent::text("Kicked for spamming")).await;
+        }
+        false
+    }
+
+    /// The brand the player's client sent while joining, like "vanilla" or "fabric"
+    pub async fn client_brand(&self) -> Option<String> {
+        self.client.brand.lock().await.clone()
+    }
+
+    /// Kicks the player with the reason shown on their disconnect screen, see [`World::disconnect_player`]
     pub async fn kick(&self, reason: TextComponent) {
-        if self
-            .client
-            .closed
-            .load(std::sync::atomic::Ordering::Relaxed)
-        {
-            log::debug!(
-                "Tried to kick id {} but connection is closed!",
-                self.client.id
-            );
+        self.world().disconnect_player(self, reason).await;
+    }
+
+    /// Prompts the client to download the server resource pack, if one is configured
+    pub async fn send_resource_pack(&self) {
+        let resource_config = &ADVANCED_CONFIG.resource_pack;
+        if !resource_config.enabled {
             return;
         }
 
+        self.resource_pack_status.store(None);
         self.client
-            .try_send_packet(&CPlayDisconnect::new(&reason))
-            .await
-            .unwrap_or_else(|_| self.client.close());
-        log::info!(
-            "Kicked Player {} ({}) for {}",
-            self.gameprofile.name,
-            self.client.id,
-            reason.to_pretty_console()
-        );
-        self.client.close();
+            .send_packet(&CResourcePackPush::new(
+                resource_pack_uuid(),
+                &resource_config.resource_pack_url,
+                &resource_config.resource_pack_sha1,
+                resource_config.force,
+                if resource_config.prompt_message.is_empty() {
+                    None
+                } else {
+                    Some(TextComponent::text(&resource_config.prompt_message))
+                },
+            ))
+            .await;
     }
 
     pub async fn set_health(&self, health: f32, food: i32, food_saturation: f32) {
@@ -558,15 +988,40 @@ impl Player {
             .await;
     }
 
-    pub async fn kill(&self) {
+    /// Gives the player experience points, their level goes up once they have enough
+    pub async fn add_experience(&self, points: i32) {
+        let (Ok(previous) | Err(previous)) = self.experience.fetch_update(
+            std::sync::atomic::Ordering::Relaxed,
+            std::sync::atomic::Ordering::Relaxed,
+            |total| Some(total.saturating_add(points)),
+        );
+        let total = previous.saturating_add(points);
+        self.send_experience(total).await;
+    }
+
+    async fn send_experience(&self, total: i32) {
+        let (level, progress) = level_and_progress(total);
+        self.client
+            .send_packet(&CSetExperience::new(progress, level.into(), total.into()))
+            .await;
+    }
+
+    pub async fn kill(&self, cause: DeathCause<'_>) {
         self.living_entity.kill().await;
+        self.on_death(cause).await;
+    }
 
+    /// Shows the player the death screen and tells the world how they died.
+    ///
+    /// In hardcore the client shows no respawn button, as it knows about hardcore since joining
+    pub async fn on_death(&self, cause: DeathCause<'_>) {
+        let message = cause.message(&self.gameprofile.name);
         self.client
-            .send_packet(&CCombatDeath::new(
-                self.entity_id().into(),
-                &TextComponent::text("noob"),
-            ))
+            .send_packet(&CCombatDeath::new(self.entity_id().into(), &message))
             .await;
+        if ADVANCED_CONFIG.death.broadcast_messages {
+            self.world().broadcast_system_message(&message).await;
+        }
     }
 
     pub async fn set_gamemode(&self, gamemode: GameMode) {
@@ -603,7 +1058,7 @@ impl Player {
         self.send_abilities_update().await;
         self.living_entity
             .entity
-            .world
+            .world()
             .broadcast_packet_all(&CPlayerInfoUpdate::new(
                 0x04,
                 &[pumpkin_protocol::client::play::Player {
@@ -624,7 +1079,7 @@ impl Player {
     /// Send skin layers and used hand to all players
     pub async fn update_client_information(&self) {
         let config = self.config.lock().await;
-        let world = self.world();
+        let world = &self.world();
         world
             .broadcast_packet_all(&CSetEntityMetadata::new(
                 self.entity_id().into(),
@@ -644,6 +1099,10 @@ impl Player {
             .send_packet(&CSystemChatMessage::new(text, false))
             .await;
     }
+
+    pub async fn send_actionbar(&self, text: &TextComponent) {
+        self.client.send_packet(&CActionBar::new(text)).await;
+    }
 }
 
 impl Player {
@@ -694,21 +1153,23 @@ impl Player {
                     .await;
             }
             SChatMessage::PACKET_ID => {
-                self.handle_chat_message(SChatMessage::read(bytebuf)?).await;
+                self.handle_chat_message(SChatMessage::read(bytebuf)?, server)
+                    .await;
             }
             SClientInformationPlay::PACKET_ID => {
                 self.handle_client_information(SClientInformationPlay::read(bytebuf)?)
                     .await;
             }
             SClientCommand::PACKET_ID => {
-                self.handle_client_status(SClientCommand::read(bytebuf)?)
+                self.handle_client_status(SClientCommand::read(bytebuf)?, server)
                     .await;
             }
             SPlayerInput::PACKET_ID => {
                 // TODO
             }
             SInteract::PACKET_ID => {
-                self.handle_interact(SInteract::read(bytebuf)?).await;
+                self.handle_interact(SInteract::read(bytebuf)?, server)
+                    .await;
             }
             SKeepAlive::PACKET_ID => {
                 self.handle_keep_alive(SKeepAlive::read(bytebuf)?).await;
@@ -773,6 +1234,10 @@ impl Player {
                 self.handle_command_suggestion(SCommandSuggestion::read(bytebuf)?, server)
                     .await;
             }
+            SPlayPluginMessage::PACKET_ID => {
+                self.handle_plugin_message(SPlayPluginMessage::read(bytebuf)?, server)
+                    .await;
+            }
             SPCookieResponse::PACKET_ID => {
                 self.handle_cookie_response(SPCookieResponse::read(bytebuf)?);
             }
@@ -780,6 +1245,10 @@ impl Player {
                 self.handle_close_container(server, SCloseContainer::read(bytebuf)?)
                     .await;
             }
+            SResourcePackResponse::PACKET_ID => {
+                self.handle_resource_pack_response(SResourcePackResponse::read(bytebuf)?)
+                    .await;
+            }
             _ => {
                 log::warn!("Failed to handle player packet id {}", packet.id.0);
                 // TODO: We give an error if all play packets are implemented
@@ -790,6 +1259,15 @@ impl Player {
     }
 }
 
+/// The server resource pack always uses the same id, so clients can tell it's the same pack
+#[must_use]
+pub fn resource_pack_uuid() -> uuid::Uuid {
+    uuid::Uuid::new_v3(
+        &uuid::Uuid::NAMESPACE_DNS,
+        ADVANCED_CONFIG.resource_pack.resource_pack_url.as_bytes(),
+    )
+}
+
 /// Represents a player's abilities and special powers.
 ///
 /// This struct contains information about the player's current abilities, such as flight, invulnerability, and creative mode.
@@ -0,0 +1,65 @@
+//! Tags log lines with the player they are about, so the activity of a single player can be filtered out of the logs.
+//!
+//! Use the `player_*` macros instead of the `log` macros when logging something a player did or something which happened to a player:
+//! `player_debug!(player, "Sending chunks")` logs `[Alex/069a79f4-44e9-4726-a5be-fca90e38aaf5] Sending chunks`
+
+use std::fmt::{self, Display};
+
+use uuid::Uuid;
+
+use crate::entity::player::Player;
+
+/// The player a log line is about
+pub struct PlayerLogContext<'a> {
+    pub name: &'a str,
+    pub uuid: Uuid,
+}
+
+impl Display for PlayerLogContext<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}/{}]", self.name, self.uuid)
+    }
+}
+
+impl Player {
+    #[must_use]
+    pub fn log_context(&self) -> PlayerLogContext<'_> {
+        PlayerLogContext {
+            name: &self.gameprofile.name,
+            uuid: self.gameprofile.id,
+        }
+    }
+}
+
+/// Logs at the level, prefixed by the [`PlayerLogContext`] of the player
+macro_rules! player_log {
+    ($level:expr, $player:expr, $($arg:tt)+) => {
+        log::log!($level, "{} {}", $player.log_context(), format_args!($($arg)+))
+    };
+}
+
+macro_rules! player_error {
+    ($player:expr, $($arg:tt)+) => {
+        $crate::log_context::player_log!(log::Level::Error, $player, $($arg)+)
+    };
+}
+
+macro_rules! player_warn {
+    ($player:expr, $($arg:tt)+) => {
+        $crate::log_context::player_log!(log::Level::Warn, $player, $($arg)+)
+    };
+}
+
+macro_rules! player_info {
+    ($player:expr, $($arg:tt)+) => {
+        $crate::log_context::player_log!(log::Level::Info, $player, $($arg)+)
+    };
+}
+
+macro_rules! player_debug {
+    ($player:expr, $($arg:tt)+) => {
+        $crate::log_context::player_log!(log::Level::Debug, $player, $($arg)+)
+    };
+}
+
+pub(crate) use {player_debug, player_error, player_info, player_log, player_warn};
@@ -58,6 +58,7 @@ pub mod command;
 pub mod data;
 pub mod entity;
 pub mod error;
+pub mod log_context;
 pub mod net;
 pub mod server;
 pub mod world;
@@ -146,12 +147,6 @@ async fn main() {
     log::info!("Report Issues on https://github.com/Pumpkin-MC/Pumpkin/issues");
     log::info!("Join our Discord for community support https://discord.com/invite/wT8XjrjKkf");
 
-    tokio::spawn(async {
-        setup_sighandler()
-            .await
-            .expect("Unable to setup signal handlers");
-    });
-
     // Setup the TCP server socket.
     let listener = tokio::net::TcpListener::bind(BASIC_CONFIG.server_address)
         .await
@@ -167,6 +162,15 @@ async fn main() {
     let server = Arc::new(Server::new());
     let mut ticker = Ticker::new(BASIC_CONFIG.tps);
 
+    {
+        let server = server.clone();
+        tokio::spawn(async move {
+            setup_sighandler(server)
+                .await
+                .expect("Unable to setup signal handlers");
+        });
+    }
+
     log::info!("Started Server took {}ms", time.elapsed().as_millis());
     log::info!("You now can connect to the server, Listening on {}", addr);
 
@@ -251,27 +255,29 @@ async fn main() {
                 }
                 log::debug!("Cleaning up player for id {}", id);
                 player.remove().await;
-                server.remove_player().await;
+                server.remove_player(&player).await;
             }
         });
     }
 }
 
-fn handle_interrupt() {
+async fn handle_interrupt(server: &Server) {
     log::warn!(
         "{}",
         TextComponent::text("Received interrupt signal; stopping server...")
             .color_named(NamedColor::Red)
             .to_pretty_console()
     );
+    // Make sure nothing since the last autosave gets lost
+    server.save().await;
     std::process::exit(0);
 }
 
 // Non-UNIX Ctrl-C handling
 #[cfg(not(unix))]
-async fn setup_sighandler() -> io::Result<()> {
+async fn setup_sighandler(server: Arc<Server>) -> io::Result<()> {
     if ctrl_c().await.is_ok() {
-        handle_interrupt();
+        handle_interrupt(&server).await;
     }
 
     Ok(())
@@ -279,17 +285,17 @@ async fn setup_sighandler() -> io::Result<()> {
 
 // Unix signal handling
 #[cfg(unix)]
-async fn setup_sighandler() -> io::Result<()> {
+async fn setup_sighandler(server: Arc<Server>) -> io::Result<()> {
     if signal(SignalKind::interrupt())?.recv().await.is_some() {
-        handle_interrupt();
+        handle_interrupt(&server).await;
     }
 
     if signal(SignalKind::hangup())?.recv().await.is_some() {
-        handle_interrupt();
+        handle_interrupt(&server).await;
     }
 
     if signal(SignalKind::terminate())?.recv().await.is_some() {
-        handle_interrupt();
+        handle_interrupt(&server).await;
     }
 
     Ok(())
@@ -488,7 +488,7 @@ impl Player {
         let players = self
             .living_entity
             .entity
-            .world
+            .world()
             .current_players
             .lock()
             .await
@@ -1,5 +1,5 @@
 use std::{
-    collections::VecDeque,
+    collections::{HashSet, VecDeque},
     net::SocketAddr,
     num::NonZeroU8,
     sync::{
@@ -19,6 +19,7 @@ use pumpkin_core::{text::TextComponent, ProfileAction};
 use pumpkin_protocol::{
     bytebuf::{packet_id::Packet, ReadingError},
     client::{config::CConfigDisconnect, login::CLoginDisconnect, play::CPlayDisconnect},
+    codec::identifier::Identifier,
     packet_decoder::PacketDecoder,
     packet_encoder::{PacketEncodeError, PacketEncoder},
     server::{
@@ -51,6 +52,7 @@ pub mod lan_broadcast;
 mod packet;
 mod proxy;
 pub mod query;
+pub mod rate_limit;
 pub mod rcon;
 
 #[derive(Deserialize, Clone, Debug)]
@@ -120,6 +122,8 @@ pub struct Client {
     pub config: Mutex<Option<PlayerConfig>>,
     /// The client's brand or modpack information, Optional.
     pub brand: Mutex<Option<String>>,
+    /// The plugin channels the client listens on, see [`Server::send_plugin_message`]
+    pub registered_channels: Mutex<HashSet<Identifier>>,
     /// The minecraft protocol version used by the client.
     pub protocol_version: AtomicI32,
     /// The Address used to connect to the Server, Send in the Handshake
@@ -153,6 +157,7 @@ impl Client {
             gameprofile: Mutex::new(None),
             config: Mutex::new(None),
             brand: Mutex::new(None),
+            registered_channels: Mutex::new(HashSet::new()),
             server_address: Mutex::new(String::new()),
             address: Mutex::new(address),
             connection_state: AtomicCell::new(ConnectionState::HandShake),
@@ -268,6 +273,26 @@ impl Client {
         */
     }
 
+    /// Sends a packet which was already serialized by [`pumpkin_protocol::packet_encoder::serialize_packet`], used to send the same packet to many clients.
+    ///
+    /// The packet still gets compressed using the client's compression threshold
+    pub async fn send_serialized(&self, data: &[u8]) {
+        if self.closed.load(std::sync::atomic::Ordering::Relaxed) {
+            return;
+        }
+
+        let mut enc = self.enc.lock().await;
+        if let Err(error) = enc.append_serialized(data) {
+            self.kick(&error.to_string()).await;
+            return;
+        }
+
+        let mut writer = self.connection_writer.lock().await;
+        if let Err(error) = writer.write_all(&enc.take()).await {
+            log::debug!("Unable to write to connection: {}", error.to_string());
+        }
+    }
+
     /// Sends a clientbound packet to the connected client.
     ///
     /// # Arguments
@@ -452,7 +477,7 @@ impl Client {
                     .await;
             }
             SLoginPluginResponse::PACKET_ID => {
-                self.handle_plugin_response(SLoginPluginResponse::read(bytebuf)?)
+                self.handle_plugin_response(server, SLoginPluginResponse::read(bytebuf)?)
                     .await;
             }
             SLoginAcknowledged::PACKET_ID => {
@@ -5,7 +5,6 @@ use crate::{
     net::{Client, PlayerConfig},
     server::Server,
 };
-use core::str;
 use pumpkin_protocol::{
     client::config::{CFinishConfig, CRegistryData},
     codec::var_int::VarInt,
@@ -50,16 +49,17 @@ impl Client {
 
     pub async fn handle_plugin_message(&self, plugin_message: SPluginMessage) {
         log::debug!("Handling plugin message");
-        if plugin_message
-            .channel
-            .to_string()
-            .starts_with("minecraft:brand")
+        if self
+            .handle_channel_registration(&plugin_message.channel, &plugin_message.data)
+            .await
         {
-            log::debug!("got a client brand");
-            match str::from_utf8(&plugin_message.data) {
-                Ok(brand) => *self.brand.lock().await = Some(brand.to_string()),
-                Err(e) => self.kick(&e.to_string()).await,
-            }
+            return;
+        }
+        if let Some(Err(reason)) = self
+            .handle_brand(&plugin_message.channel, &plugin_message.data)
+            .await
+        {
+            self.kick(&reason).await;
         }
     }
 
@@ -1,19 +1,23 @@
 use std::sync::LazyLock;
 
-use pumpkin_config::{ADVANCED_CONFIG, BASIC_CONFIG};
+use pumpkin_config::{networking::proxy::VelocityConfig, ADVANCED_CONFIG, BASIC_CONFIG};
 use pumpkin_core::text::TextComponent;
 use pumpkin_protocol::{
     client::{
-        config::{CConfigAddResourcePack, CConfigServerLinks, CKnownPacks},
+        config::{CConfigServerLinks, CKnownPacks},
         login::{CLoginSuccess, CSetCompression},
     },
     codec::var_int::VarInt,
     server::login::{SEncryptionResponse, SLoginCookieResponse, SLoginPluginResponse, SLoginStart},
     ConnectionState, KnownPack, Label, Link, LinkType,
 };
-use uuid::Uuid;
 
 use crate::{
+    data::{
+        banlist_data::{ban_message, BANNED_IP_LIST, BANNED_PLAYER_LIST},
+        op_data::OPERATOR_CONFIG,
+        whitelist_data::WHITELIST_CONFIG,
+    },
     net::{
         authentication::{self, AuthError},
         offline_uuid,
@@ -84,20 +88,38 @@ static LINKS: LazyLock<Vec<Link>> = LazyLock::new(|| {
     links
 });
 
+/// Which player slots a joining player may use
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SlotAccess {
+    Normal,
+    /// Operators can also use the reserved slots
+    Reserved,
+    /// Operators which bypass the player limit can always join
+    Unlimited,
+}
+
+/// Checks if there is no slot left for the joining player. A `max_players` of zero disables the limit
+fn is_server_full(
+    player_count: usize,
+    max_players: u32,
+    reserved_slots: u32,
+    access: SlotAccess,
+) -> bool {
+    if max_players == 0 {
+        return false;
+    }
+    let slots = match access {
+        SlotAccess::Normal => max_players.saturating_sub(reserved_slots),
+        SlotAccess::Reserved => max_players,
+        SlotAccess::Unlimited => return false,
+    };
+    player_count >= slots as usize
+}
+
 impl Client {
     pub async fn handle_login_start(&self, server: &Server, login_start: SLoginStart) {
         log::debug!("login start");
 
-        // Don't allow new logons when server is full.
-        // If max players is set to zero, then there is no max player count enforced.
-        // TODO: If client is an operator or otherwise suitable elevated permissions, allow client to bypass this requirement.
-        let max_players = BASIC_CONFIG.max_players;
-        if max_players > 0 && server.get_player_count().await >= max_players as usize {
-            self.kick("The server is currently full, please try again later")
-                .await;
-            return;
-        }
-
         if !is_valid_player_name(&login_start.name) {
             self.kick("Invalid characters in username").await;
             return;
@@ -119,7 +141,7 @@ impl Client {
                 {
                     Ok((_ip, profile)) => {
                         // self.address.lock() = ip;
-                        self.finish_login(&profile).await;
+                        self.finish_login(server, &profile).await;
                         *gameprofile = Some(profile);
                     }
                     Err(error) => self.kick(&error.to_string()).await,
@@ -149,7 +171,7 @@ impl Client {
                 if ADVANCED_CONFIG.networking.packet_compression.enabled {
                     self.enable_compression().await;
                 }
-                self.finish_login(&profile).await;
+                self.finish_login(server, &profile).await;
             }
 
             *gameprofile = Some(profile);
@@ -208,7 +230,7 @@ impl Client {
         if ADVANCED_CONFIG.networking.packet_compression.enabled {
             self.enable_compression().await;
         }
-        self.finish_login(profile).await;
+        self.finish_login(server, profile).await;
     }
 
     async fn enable_compression(&self) {
@@ -222,7 +244,64 @@ impl Client {
         self.set_compression(Some(compression)).await;
     }
 
-    async fn finish_login(&self, profile: &GameProfile) {
+    async fn finish_login(&self, server: &Server, profile: &GameProfile) {
+        let ban = BANNED_PLAYER_LIST
+            .write()
+            .await
+            .get_entry(&profile.id)
+            .map(|entry| ban_message(&entry.reason, entry.expires, false));
+        if let Some(message) = ban {
+            self.kick(&message).await;
+            return;
+        }
+        let ip = self.address.lock().await.ip();
+        let ip_ban = BANNED_IP_LIST
+            .write()
+            .await
+            .get_entry(&ip)
+            .map(|entry| ban_message(&entry.reason, entry.expires, true));
+        if let Some(message) = ip_ban {
+            self.kick(&message).await;
+            return;
+        }
+
+        if BASIC_CONFIG.whitelist_enabled
+            && !WHITELIST_CONFIG.read().await.is_whitelisted(&profile.id)
+        {
+            let bypass = BASIC_CONFIG.whitelist_ops_bypass
+                && OPERATOR_CONFIG
+                    .read()
+                    .await
+                    .get_level(&profile.id)
+                    .is_some();
+            if !bypass {
+                self.kick(&BASIC_CONFIG.whitelist_message).await;
+                return;
+            }
+        }
+
+        // Don't allow new logons when server is full
+        let slot_access = {
+            let operators = OPERATOR_CONFIG.read().await;
+            if operators.bypasses_player_limit(&profile.id) {
+                SlotAccess::Unlimited
+            } else if operators.get_level(&profile.id).is_some() {
+                SlotAccess::Reserved
+            } else {
+                SlotAccess::Normal
+            }
+        };
+        if is_server_full(
+            server.get_player_count().await,
+            BASIC_CONFIG.max_players,
+            BASIC_CONFIG.reserved_slots,
+            slot_access,
+        ) {
+            self.kick("The server is currently full, please try again later")
+                .await;
+            return;
+        }
+
         let packet = CLoginSuccess::new(&profile.id, &profile.name, &profile.properties);
         self.send_packet(&packet).await;
     }
@@ -285,23 +364,36 @@ impl Client {
         packet.payload_length.unwrap_or(VarInt::from(0)).0
     );
     }
-    pub async fn handle_plugin_response(&self, plugin_response: SLoginPluginResponse) {
+    pub async fn handle_plugin_response(
+        &self,
+        server: &Server,
+        plugin_response: SLoginPluginResponse,
+    ) {
         log::debug!("Handling plugin");
         let velocity_config = &ADVANCED_CONFIG.networking.proxy.velocity;
         if velocity_config.enabled {
-            let mut address = self.address.lock().await;
-            match velocity::receive_velocity_plugin_response(
-                address.port(),
-                velocity_config,
-                plugin_response,
-            ) {
-                Ok((profile, new_address)) => {
-                    self.finish_login(&profile).await;
-                    *self.gameprofile.lock().await = Some(profile);
-                    *address = new_address;
-                }
-                Err(error) => self.kick(&error.to_string()).await,
+            self.handle_velocity_response(server, velocity_config, plugin_response)
+                .await;
+        }
+    }
+
+    /// Finishes the login with the profile and address of the player Velocity forwarded
+    async fn handle_velocity_response(
+        &self,
+        server: &Server,
+        velocity_config: &VelocityConfig,
+        plugin_response: SLoginPluginResponse,
+    ) {
+        // Finishing the login locks the address again, so don't hold the lock here
+        let port = self.address.lock().await.port();
+        match velocity::receive_velocity_plugin_response(port, velocity_config, plugin_response) {
+            Ok((profile, address)) => {
+                // IP bans apply to the player, not the proxy
+                *self.address.lock().await = address;
+                self.finish_login(server, &profile).await;
+                *self.gameprofile.lock().await = Some(profile);
             }
+            Err(error) => self.kick(&error.to_string()).await,
         }
     }
 
@@ -310,26 +402,6 @@ impl Client {
         self.connection_state.store(ConnectionState::Config);
         self.send_packet(&server.get_branding()).await;
 
-        let resource_config = &ADVANCED_CONFIG.resource_pack;
-        if resource_config.enabled {
-            let resource_pack = CConfigAddResourcePack::new(
-                Uuid::new_v3(
-                    &uuid::Uuid::NAMESPACE_DNS,
-                    resource_config.resource_pack_url.as_bytes(),
-                ),
-                &resource_config.resource_pack_url,
-                &resource_config.resource_pack_sha1,
-                resource_config.force,
-                if resource_config.prompt_message.is_empty() {
-                    None
-                } else {
-                    Some(TextComponent::text(&resource_config.prompt_message))
-                },
-            );
-
-            self.send_packet(&resource_pack).await;
-        }
-
         if ADVANCED_CONFIG.server_links.enabled {
             self.send_packet(&CConfigServerLinks::new(
                 &VarInt(LINKS.len() as i32),
@@ -348,3 +420,98 @@ impl Client {
         log::debug!("login acknowledged");
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::{net::SocketAddr, time::Duration};
+
+    use bytes::{BufMut, BytesMut};
+    use hmac::{Hmac, Mac};
+    use pumpkin_config::networking::proxy::VelocityConfig;
+    use pumpkin_protocol::{
+        bytebuf::ByteBufMut, codec::var_int::VarInt, server::login::SLoginPluginResponse,
+        ConnectionState,
+    };
+    use sha2::Sha256;
+    use tokio::{
+        io::AsyncReadExt,
+        net::{TcpListener, TcpStream},
+    };
+    use uuid::Uuid;
+
+    use crate::{net::Client, server::Server};
+
+    use super::{is_server_full, SlotAccess};
+
+    /// The player info Velocity sends, signed with the secret
+    fn forwarded_player_info(secret: &str, address: &str, id: Uuid, name: &str) -> BytesMut {
+        let mut info = BytesMut::new();
+        info.put_var_int(&VarInt(1));
+        info.put_string(address);
+        info.put_uuid(&id);
+        info.put_string(name);
+        info.put_var_int(&VarInt(0));
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(&info);
+        let mut data = BytesMut::new();
+        data.put_slice(&mac.finalize().into_bytes());
+        data.put_slice(&info);
+        data
+    }
+
+    #[tokio::test]
+    async fn velocity_login_finishes() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let mut proxy = TcpStream::connect(listener.local_addr().unwrap())
+            .await
+            .unwrap();
+        let (connection, proxy_address) = listener.accept().await.unwrap();
+        let client = Client::new(connection, proxy_address, 0);
+        client.connection_state.store(ConnectionState::Login);
+        let server = Server::test_server();
+
+        let config = VelocityConfig {
+            enabled: true,
+            secret: "secret".to_string(),
+        };
+        let id = Uuid::from_u128(7);
+        let response = SLoginPluginResponse {
+            message_id: VarInt(0),
+            data: Some(forwarded_player_info(&config.secret, "203.0.113.7", id, "Steve").freeze()),
+        };
+        tokio::time::timeout(
+            Duration::from_secs(5),
+            client.handle_velocity_response(&server, &config, response),
+        )
+        .await
+        .expect("The login doesn't lock up");
+
+        let forwarded: SocketAddr =
+            SocketAddr::new("203.0.113.7".parse().unwrap(), proxy_address.port());
+        assert_eq!(*client.address.lock().await, forwarded);
+        assert_eq!(
+            client
+                .gameprofile
+                .lock()
+                .await
+                .as_ref()
+                .map(|profile| profile.id),
+            Some(id)
+        );
+        assert!(!client.closed.load(std::sync::atomic::Ordering::Relaxed));
+        // The login success packet reached the proxy
+        let mut buf = [0; 64];
+        assert!(proxy.read(&mut buf).await.unwrap() > 0);
+    }
+
+    #[test]
+    fn reserved_slots() {
+        assert!(!is_server_full(7, 10, 2, SlotAccess::Normal));
+        assert!(is_server_full(8, 10, 2, SlotAccess::Normal));
+        assert!(!is_server_full(8, 10, 2, SlotAccess::Reserved));
+        assert!(is_server_full(10, 10, 2, SlotAccess::Reserved));
+        assert!(!is_server_full(10, 10, 2, SlotAccess::Unlimited));
+        // No limit
+        assert!(!is_server_full(10, 0, 2, SlotAccess::Normal));
+    }
+}
@@ -2,20 +2,23 @@ use std::num::NonZeroU8;
 use std::sync::Arc;
 
 use crate::block::block_manager::BlockActionResult;
+use crate::entity::interaction::EntityInteraction;
 use crate::entity::mob;
+use crate::log_context::{player_debug, player_warn};
+use crate::net::rate_limit::RateLimitedAction;
 use crate::net::PlayerConfig;
 use crate::{
     command::CommandSender,
-    entity::player::{ChatMode, Hand, Player},
+    entity::player::{resource_pack_uuid, ChatMode, Hand, Player},
     error::PumpkinError,
     server::Server,
-    world::player_chunker,
+    world::{player_chunker, region::RegionAction},
 };
-use pumpkin_config::ADVANCED_CONFIG;
+use pumpkin_config::{ChatScope, ADVANCED_CONFIG};
 use pumpkin_core::math::{boundingbox::BoundingBox, position::WorldPosition};
 use pumpkin_core::{
     math::{vector3::Vector3, wrap_degrees},
-    text::TextComponent,
+    text::{color::NamedColor, TextComponent},
     GameMode,
 };
 use pumpkin_entity::entity_type::EntityType;
@@ -24,15 +27,18 @@ use pumpkin_inventory::InventoryError;
 use pumpkin_protocol::client::play::{CSetContainerSlot, CSetHeldItem, CSpawnEntity};
 use pumpkin_protocol::codec::slot::Slot;
 use pumpkin_protocol::codec::var_int::VarInt;
-use pumpkin_protocol::server::play::SCookieResponse as SPCookieResponse;
+use pumpkin_protocol::server::play::{
+    ResourcePackStatus, SCookieResponse as SPCookieResponse, SPlayPluginMessage,
+    SResourcePackResponse,
+};
 use pumpkin_protocol::{
     client::play::CCommandSuggestions,
     server::play::{SCloseContainer, SCommandSuggestion, SKeepAlive, SSetPlayerGround, SUseItem},
 };
 use pumpkin_protocol::{
     client::play::{
-        Animation, CAcknowledgeBlockChange, CEntityAnimation, CHeadRot, CPingResponse,
-        CPlayerChatMessage, CUpdateEntityPos, CUpdateEntityPosRot, CUpdateEntityRot, FilterType,
+        Animation, CAcknowledgeBlockChange, CDisguisedChatMessage, CHeadRot, CPingResponse,
+        CUpdateEntityPos, CUpdateEntityPosRot, CUpdateEntityRot,
     },
     server::play::{
         Action, ActionType, SChatCommand, SChatMessage, SClientCommand, SClientInformationPlay,
@@ -51,11 +57,19 @@ use pumpkin_world::{
 };
 use thiserror::Error;
 
+/// Sprinting players are 30% faster, like Vanilla
+const SPRINTING_SPEED: f64 = 1.3;
+/// Sneaking players move at 30% of their speed, like Vanilla
+const SNEAKING_SPEED: f64 = 0.3;
+/// The `minecraft:chat` chat type, which is the first in the chat type registry. Chat types are sent as their index plus one
+const CHAT_TYPE: VarInt = VarInt(1);
+
 #[derive(Debug, Error)]
 pub enum BlockPlacingError {
     BlockOutOfReach,
     InvalidBlockFace,
     BlockOutOfWorld,
+    BlockOutsideBorder,
     InventoryInvalid,
 }
 
@@ -68,23 +82,24 @@ impl std::fmt::Display for BlockPlacingError {
 impl PumpkinError for BlockPlacingError {
     fn is_kick(&self) -> bool {
         match self {
-            Self::BlockOutOfReach | Self::BlockOutOfWorld => false,
+            Self::BlockOutOfReach | Self::BlockOutOfWorld | Self::BlockOutsideBorder => false,
             Self::InvalidBlockFace | Self::InventoryInvalid => true,
         }
     }
 
     fn severity(&self) -> log::Level {
         match self {
-            Self::BlockOutOfReach | Self::BlockOutOfWorld | Self::InvalidBlockFace => {
-                log::Level::Warn
-            }
+            Self::BlockOutOfReach
+            | Self::BlockOutOfWorld
+            | Self::BlockOutsideBorder
+            | Self::InvalidBlockFace => log::Level::Warn,
             Self::InventoryInvalid => log::Level::Error,
         }
     }
 
     fn client_kick_reason(&self) -> Option<String> {
         match self {
-            Self::BlockOutOfReach | Self::BlockOutOfWorld => None,
+            Self::BlockOutOfReach | Self::BlockOutOfWorld | Self::BlockOutsideBorder => None,
             Self::InvalidBlockFace => Some("Invalid block face".into()),
             Self::InventoryInvalid => Some("Held item invalid".into()),
         }
@@ -121,6 +136,89 @@ impl Player {
         pos.clamp(-2.0E7, 2.0E7)
     }
 
+    /// Counts how long the player floats in the air and kicks them when it's too long, like Vanilla does when flying is not allowed.
+    /// Returns `false` if the player got kicked
+    async fn validate_flight(&self, last_y: f64, y: f64, on_ground: bool) -> bool {
+        let config = &ADVANCED_CONFIG.movement;
+        if !config.kick_flying {
+            return true;
+        }
+        // Falling players are fine, as are players who got pushed upwards
+        if on_ground
+            || y - last_y < -0.031_25
+            || self.living_entity.entity.velocity.load().y > 0.0
+            || self.world().is_flight_exempt(self).await
+        {
+            self.floating_ticks
+                .store(0, std::sync::atomic::Ordering::Relaxed);
+            return true;
+        }
+        // TODO: Vanilla only counts ticks without any blocks around the player, so climbing and swimming don't count
+        let floating_ticks = self
+            .floating_ticks
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed)
+            + 1;
+        if floating_ticks > config.max_floating_ticks {
+            self.kick(TextComponent::text("Flying is not enabled on this server"))
+                .await;
+            return false;
+        }
+        true
+    }
+
+    /// How many blocks the player may move horizontally in one movement, sprinting is faster and sneaking slower.
+    /// Like Vanilla, players gliding with an elytra may move `sqrt(3)` times as far
+    fn max_movement(&self) -> f64 {
+        let entity = &self.living_entity.entity;
+        let max_speed = ADVANCED_CONFIG.movement.max_speed;
+        if entity
+            .fall_flying
+            .load(std::sync::atomic::Ordering::Relaxed)
+        {
+            return max_speed * 3f64.sqrt();
+        }
+        let mut max_movement = max_speed;
+        if entity.sprinting.load(std::sync::atomic::Ordering::Relaxed) {
+            max_movement *= SPRINTING_SPEED;
+        }
+        if entity.sneaking.load(std::sync::atomic::Ordering::Relaxed) {
+            max_movement *= SNEAKING_SPEED;
+        }
+        max_movement
+    }
+
+    /// Whether the client still has to confirm a teleport, like Vanilla its movement is ignored until then,
+    /// as it may have been sent before the client knew about the teleport
+    async fn is_awaiting_teleport(&self) -> bool {
+        self.awaiting_teleport.lock().await.is_some()
+    }
+
+    /// Moves the player back when they moved further than [`Player::max_movement`], on top of the velocity the server gave them.
+    /// Only the horizontal movement counts, falling and jumping are up to [`Player::validate_flight`].
+    /// Returns `false` if the player got moved back
+    async fn validate_speed(&self, last_pos: Vector3<f64>, pos: Vector3<f64>) -> bool {
+        if !ADVANCED_CONFIG.movement.check_speed
+            || self.gamemode.load() == GameMode::Spectator
+            || self
+                .ticks_since_teleport
+                .load(std::sync::atomic::Ordering::Relaxed)
+                < ADVANCED_CONFIG.movement.teleport_grace_ticks
+        {
+            return true;
+        }
+        let velocity = self.living_entity.entity.velocity.load();
+        let moved = (pos.x - last_pos.x).hypot(pos.z - last_pos.z);
+        if moved - velocity.x.hypot(velocity.z) <= self.max_movement() {
+            return true;
+        }
+        player_warn!(self, "Moved too quickly, {moved:.2} blocks in one movement");
+        let entity = &self.living_entity.entity;
+        // No grace for the move back, otherwise the player could just send the same position again
+        self.send_teleport(last_pos, entity.yaw.load(), entity.pitch.load())
+            .await;
+        false
+    }
+
     pub async fn handle_position(self: &Arc<Self>, packet: SPlayerPosition) {
         // y = feet Y
         let position = packet.position;
@@ -133,6 +231,9 @@ impl Player {
             Self::clamp_vertical(position.y),
             Self::clamp_horizontal(position.z),
         );
+        if self.is_awaiting_teleport().await {
+            return;
+        }
         let entity = &self.living_entity.entity;
         self.living_entity.set_pos(position);
 
@@ -143,22 +244,17 @@ impl Player {
             .on_ground
             .store(packet.ground, std::sync::atomic::Ordering::Relaxed);
 
+        if !self.validate_flight(last_pos.y, pos.y, packet.ground).await {
+            return;
+        }
+        if !self.validate_speed(last_pos, pos).await {
+            return;
+        }
+
         let entity_id = entity.entity_id;
         let Vector3 { x, y, z } = pos;
-        let world = &entity.world;
+        let world = &entity.world();
 
-        // let delta = Vector3::new(x - lastx, y - lasty, z - lastz);
-        // let velocity = self.velocity;
-
-        // // Player is falling down fast, we should account for that
-        // let max_speed = if self.fall_flying { 300.0 } else { 100.0 };
-
-        // teleport when more than 8 blocks (i guess 8 blocks)
-        // TODO: REPLACE * 2.0 by movement packets. see vanilla for details
-        // if delta.length_squared() - velocity.length_squared() > max_speed * 2.0 {
-        //     self.teleport(x, y, z, self.entity.yaw, self.entity.pitch);
-        //     return;
-        // }
         // send new position to all other players
         world
             .broadcast_packet_except(
@@ -194,6 +290,9 @@ impl Player {
             Self::clamp_vertical(position.y),
             Self::clamp_horizontal(position.z),
         );
+        if self.is_awaiting_teleport().await {
+            return;
+        }
         let entity = &self.living_entity.entity;
         self.living_entity.set_pos(position);
 
@@ -204,6 +303,13 @@ impl Player {
             .on_ground
             .store(packet.ground, std::sync::atomic::Ordering::Relaxed);
 
+        if !self.validate_flight(last_pos.y, pos.y, packet.ground).await {
+            return;
+        }
+        if !self.validate_speed(last_pos, pos).await {
+            return;
+        }
+
         entity.set_rotation(
             wrap_degrees(packet.yaw) % 360.0,
             wrap_degrees(packet.pitch).clamp(-90.0, 90.0) % 360.0,
@@ -215,20 +321,8 @@ impl Player {
         let yaw = (entity.yaw.load() * 256.0 / 360.0).rem_euclid(256.0);
         let pitch = (entity.pitch.load() * 256.0 / 360.0).rem_euclid(256.0);
         // let head_yaw = (entity.head_yaw * 256.0 / 360.0).floor();
-        let world = &entity.world;
-
-        // let delta = Vector3::new(x - lastx, y - lasty, z - lastz);
-        // let velocity = self.velocity;
-
-        // // Player is falling down fast, we should account for that
-        // let max_speed = if self.fall_flying { 300.0 } else { 100.0 };
+        let world = &entity.world();
 
-        // // teleport when more than 8 blocks (i guess 8 blocks)
-        // // TODO: REPLACE * 2.0 by movement packets. see vanilla for details
-        // if delta.length_squared() - velocity.length_squared() > max_speed * 2.0 {
-        //     self.teleport(x, y, z, yaw, pitch);
-        //     return;
-        // }
         // send new position to all other players
 
         world
@@ -261,6 +355,9 @@ impl Player {
             self.kick(TextComponent::text("Invalid rotation")).await;
             return;
         }
+        if self.is_awaiting_teleport().await {
+            return;
+        }
         let entity = &self.living_entity.entity;
         entity
             .on_ground
@@ -275,7 +372,7 @@ impl Player {
         let pitch = (entity.pitch.load() * 256.0 / 360.0).rem_euclid(256.0);
         // let head_yaw = modulus(entity.head_yaw * 256.0 / 360.0, 256.0);
 
-        let world = &entity.world;
+        let world = &entity.world();
         let packet =
             CUpdateEntityRot::new(entity_id.into(), yaw as u8, pitch as u8, rotation.ground);
         world
@@ -421,27 +518,34 @@ impl Player {
             match action {
                 pumpkin_protocol::server::play::Action::StartSneaking => {
                     if !entity.sneaking.load(std::sync::atomic::Ordering::Relaxed) {
-                        entity.set_sneaking(true).await;
+                        entity.set_sneaking(true);
                     }
                 }
                 pumpkin_protocol::server::play::Action::StopSneaking => {
                     if entity.sneaking.load(std::sync::atomic::Ordering::Relaxed) {
-                        entity.set_sneaking(false).await;
+                        entity.set_sneaking(false);
                     }
                 }
                 pumpkin_protocol::server::play::Action::StartSprinting => {
                     if !entity.sprinting.load(std::sync::atomic::Ordering::Relaxed) {
-                        entity.set_sprinting(true).await;
+                        entity.set_sprinting(true);
                     }
                 }
                 pumpkin_protocol::server::play::Action::StopSprinting => {
                     if entity.sprinting.load(std::sync::atomic::Ordering::Relaxed) {
-                        entity.set_sprinting(false).await;
+                        entity.set_sprinting(false);
                     }
                 }
+                pumpkin_protocol::server::play::Action::StartHorseJump => {
+                    // TODO: Make the horse jump with the boost once players can ride horses
+                    self.jumping_with_horse
+                        .store(true, std::sync::atomic::Ordering::Relaxed);
+                }
+                pumpkin_protocol::server::play::Action::StopHorseJump => {
+                    self.jumping_with_horse
+                        .store(false, std::sync::atomic::Ordering::Relaxed);
+                }
                 pumpkin_protocol::server::play::Action::LeaveBed
-                | pumpkin_protocol::server::play::Action::StartHorseJump
-                | pumpkin_protocol::server::play::Action::StopHorseJump
                 | pumpkin_protocol::server::play::Action::OpenVehicleInventory => {
                     log::debug!("todo");
                 }
@@ -452,7 +556,7 @@ impl Player {
                         .load(std::sync::atomic::Ordering::Relaxed)
                         != fall_flying
                     {
-                        entity.set_fall_flying(fall_flying).await;
+                        entity.set_fall_flying(fall_flying);
                     }
                 } // TODO
             }
@@ -481,17 +585,17 @@ impl Player {
             Hand::Right => animation,
         };
 
-        let id = self.entity_id();
-        let world = self.world();
-        world
-            .broadcast_packet_except(
-                &[self.gameprofile.id],
-                &CEntityAnimation::new(id.into(), animation as u8),
-            )
+        self.world()
+            .send_entity_animation(self.entity_id(), animation)
             .await;
     }
 
-    pub async fn handle_chat_message(&self, chat_message: SChatMessage) {
+    /// Relays the chat message of the player with the disguised chat packet instead of the player chat packet.
+    ///
+    /// We don't verify chat signatures or keep track of the signed message chain, so clients would mark relayed player
+    /// chat as "modified" or "not secure". Disguised chat is shown like normal player chat with the `minecraft:chat`
+    /// decoration, but without the signature, so there are no such warnings
+    pub async fn handle_chat_message(&self, chat_message: SChatMessage, server: &Server) {
         let message = chat_message.message;
         if message.len() > 256 {
             self.kick(TextComponent::text("Oversized message")).await;
@@ -504,37 +608,40 @@ impl Player {
             return;
         }
 
+        if !self.check_rate_limit(RateLimitedAction::Chat).await {
+            self.send_system_message(
+                &TextComponent::text("You are sending messages too fast")
+                    .color_named(NamedColor::Red),
+            )
+            .await;
+            return;
+        }
+
         let gameprofile = &self.gameprofile;
         log::info!("<chat>{}: {}", gameprofile.name, message);
 
         let entity = &self.living_entity.entity;
-        let world = &entity.world;
-        world
-            .broadcast_packet_all(&CPlayerChatMessage::new(
-                gameprofile.id,
-                1.into(),
-                chat_message.signature.as_deref(),
-                &message,
-                chat_message.timestamp,
-                chat_message.salt,
-                &[],
-                Some(TextComponent::text(message.clone())),
-                FilterType::PassThrough,
-                1.into(),
-                TextComponent::text(gameprofile.name.clone()),
-                None,
-            ))
-            .await;
-
-        /* server.broadcast_packet(
-            self,
-            &CDisguisedChatMessage::new(
-                TextComponent::from(message.clone()),
-                VarInt(0),
-               gameprofile.name.clone().into(),
-                None,
-            ),
-        ) */
+        let world = &entity.world();
+        let content = TextComponent::text(message);
+        let sender_name = TextComponent::text(gameprofile.name.clone());
+        let packet = CDisguisedChatMessage::new(&content, CHAT_TYPE, &sender_name, None);
+        match ADVANCED_CONFIG.chat.scope {
+            ChatScope::Local { radius }
+                if !self.global_chat.load(std::sync::atomic::Ordering::Relaxed) =>
+            {
+                let nearby = world.get_nearby_players(entity.pos.load(), radius).await;
+                // The sender always sees their own message
+                if !nearby.contains_key(&gameprofile.id) {
+                    self.client.send_packet(&packet).await;
+                }
+                for player in nearby.values() {
+                    player.client.send_packet(&packet).await;
+                }
+            }
+            ChatScope::Local { .. } | ChatScope::Global => {
+                server.broadcast_packet_all(&packet).await;
+            }
+        }
     }
 
     pub async fn handle_client_information(
@@ -553,28 +660,12 @@ impl Player {
                 return;
             }
 
-            let (update_skin, update_watched) = {
+            let old_view_distance = player_chunker::get_view_distance(self).await;
+            let update_skin = {
                 let mut config = self.config.lock().await;
                 let update_skin = config.main_hand != main_hand
                     || config.skin_parts != client_information.skin_parts;
 
-                let old_view_distance = config.view_distance;
-
-                let update_watched =
-                    if old_view_distance.get() == client_information.view_distance as u8 {
-                        false
-                    } else {
-                        log::debug!(
-                            "Player {} ({}) updated render distance: {} -> {}.",
-                            self.gameprofile.name,
-                            self.client.id,
-                            old_view_distance,
-                            client_information.view_distance
-                        );
-
-                        true
-                    };
-
                 *config = PlayerConfig {
                     locale: client_information.locale,
                     // A Negative view distance would be impossible and make no sense right ?, Mojang: Lets make is signed :D
@@ -588,10 +679,17 @@ impl Player {
                     text_filtering: client_information.text_filtering,
                     server_listing: client_information.server_listing,
                 };
-                (update_skin, update_watched)
+                update_skin
             };
 
-            if update_watched {
+            // Only send and unload chunks if the distance they get sent in changed, not if the client asked for more
+            // than the server allows again
+            let view_distance = player_chunker::get_view_distance(self).await;
+            if view_distance != old_view_distance {
+                player_debug!(
+                    self,
+                    "Updated view distance: {old_view_distance} -> {view_distance}"
+                );
                 player_chunker::update_position(self).await;
             }
 
@@ -609,15 +707,13 @@ impl Player {
         }
     }
 
-    pub async fn handle_client_status(self: &Arc<Self>, client_status: SClientCommand) {
+    pub async fn handle_client_status(
+        self: &Arc<Self>,
+        client_status: SClientCommand,
+        server: &Server,
+    ) {
         match client_status.action_id.0 {
-            0 => {
-                if self.living_entity.health.load() > 0.0 {
-                    return;
-                }
-                self.world().respawn_player(self, false).await;
-                // TODO: hardcore set spectator
-            }
+            0 => server.respawn_dead_player(self).await,
             1 => {
                 // request stats
                 log::debug!("todo");
@@ -629,74 +725,52 @@ impl Player {
         };
     }
 
-    pub async fn handle_interact(&self, interact: SInteract) {
+    pub async fn handle_interact(&self, interact: SInteract, server: &Server) {
         let sneaking = interact.sneaking;
         let entity = &self.living_entity.entity;
         if entity.sneaking.load(std::sync::atomic::Ordering::Relaxed) != sneaking {
-            entity.set_sneaking(sneaking).await;
+            entity.set_sneaking(sneaking);
         }
         let Ok(action) = ActionType::try_from(interact.typ.0) else {
             self.kick(TextComponent::text("Invalid action type")).await;
             return;
         };
 
-        match action {
-            ActionType::Attack => {
-                let entity_id = interact.entity_id;
-                let config = &ADVANCED_CONFIG.pvp;
-                // TODO: do validation and stuff
-                if !config.enabled {
-                    return;
-                }
-
-                let world = &entity.world;
-                let player_victim = world.get_player_by_entityid(entity_id.0).await;
-                let entity_victim = world.get_living_entity_by_entityid(entity_id.0).await;
-                if let Some(player_victim) = player_victim {
-                    if player_victim.living_entity.health.load() <= 0.0 {
-                        // you can trigger this from a non-modded / innocent client client,
-                        // so we shouldn't kick the player
-                        return;
-                    }
-                    self.attack(&player_victim).await;
-                } else if let Some(entity_victim) = entity_victim {
-                    if entity_victim.health.load() <= 0.0 {
-                        return;
-                    }
-                    entity_victim.kill().await;
-                    world.clone().remove_mob_entity(entity_victim).await;
-                    // TODO: block entities should be checked here (signs)
-                } else {
-                    log::error!(
-                        "Player id {} interacted with entity id {} which was not found.",
-                        self.entity_id(),
-                        entity_id.0
-                    );
-                    self.kick(TextComponent::text("Interacted with invalid entity id"))
-                        .await;
-                    return;
-                };
-
-                if entity_id.0 == self.entity_id() {
-                    // this, however, can't be triggered from a non-modded client.
-                    self.kick(TextComponent::text("You can't attack yourself"))
-                        .await;
+        let interaction = if action == ActionType::Attack {
+            EntityInteraction::Attack
+        } else {
+            let Some(Ok(hand)) = interact.hand.map(|hand| Hand::try_from(hand.0)) else {
+                self.kick(TextComponent::text("Invalid hand")).await;
+                return;
+            };
+            match (action, interact.target_position) {
+                (ActionType::InteractAt, Some(position)) => {
+                    EntityInteraction::InteractAt(hand, position)
                 }
+                _ => EntityInteraction::Interact(hand),
             }
-            ActionType::Interact | ActionType::InteractAt => {
-                log::debug!("todo");
-            }
-        }
+        };
+
+        entity
+            .world()
+            .interact_entity(self, interact.entity_id.0, interaction, server)
+            .await;
     }
 
     pub async fn handle_player_action(&self, player_action: SPlayerAction, server: &Server) {
         match Status::try_from(player_action.status.0) {
             Ok(status) => match status {
                 Status::StartedDigging => {
+                    if !self
+                        .check_rate_limit(RateLimitedAction::BlockInteraction)
+                        .await
+                    {
+                        return;
+                    }
                     if !self.can_interact_with_block_at(&player_action.location, 1.0) {
-                        log::warn!(
-                            "Player {0} tried to interact with block out of reach at {1}",
-                            self.gameprofile.name,
+                        player_warn!(
+                            self,
+                            "Tried to interact with block out of reach at {}",
                             player_action.location
                         );
                         return;
@@ -707,7 +781,17 @@ impl Player {
                         let location = player_action.location;
                         // Block break & block break sound
                         let entity = &self.living_entity.entity;
-                        let world = &entity.world;
+                        let world = &entity.world();
+                        if !world.is_within_border(location).await
+                            || !world.may_build_at(self, &location).await
+                        {
+                            // The client already removed the block, put it back
+                            let _ = world.reset_fake_block(self, location).await;
+                            self.client
+                                .send_packet(&CAcknowledgeBlockChange::new(player_action.sequence))
+                                .await;
+                            return;
+                        }
                         let block = world.get_block(location).await;
 
                         world.break_block(location, Some(self)).await;
@@ -715,16 +799,16 @@ impl Player {
                         if let Ok(block) = block {
                             server
                                 .block_manager
-                                .on_broken(block, self, location, server)
+                                .on_broken(&block, self, location, server)
                                 .await;
                         }
                     }
                 }
                 Status::CancelledDigging => {
                     if !self.can_interact_with_block_at(&player_action.location, 1.0) {
-                        log::warn!(
-                            "Player {0} tried to interact with block out of reach at {1}",
-                            self.gameprofile.name,
+                        player_warn!(
+                            self,
+                            "Tried to interact with block out of reach at {}",
                             player_action.location
                         );
                         return;
@@ -734,27 +818,42 @@ impl Player {
                 }
                 Status::FinishedDigging => {
                     // TODO: do validation
+                    if !self
+                        .check_rate_limit(RateLimitedAction::BlockInteraction)
+                        .await
+                    {
+                        return;
+                    }
                     let location = player_action.location;
                     if !self.can_interact_with_block_at(&location, 1.0) {
-                        log::warn!(
-                            "Player {0} tried to interact with block out of reach at {1}",
-                            self.gameprofile.name,
+                        player_warn!(
+                            self,
+                            "Tried to interact with block out of reach at {}",
                             player_action.location
                         );
                         return;
                     }
                     // Block break & block break sound
                     let entity = &self.living_entity.entity;
-                    let world = &entity.world;
-                    let block = world.get_block(location).await;
+                    let world = &entity.world();
+                    if self.may_modify_blocks()
+                        && world.is_within_border(location).await
+                        && world.may_build_at(self, &location).await
+                    {
+                        let block = world.get_block(location).await;
 
-                    world.break_block(location, Some(self)).await;
+                        world.break_block(location, Some(self)).await;
 
-                    if let Ok(block) = block {
-                        server
-                            .block_manager
-                            .on_broken(block, self, location, server)
-                            .await;
+                        if let Ok(block) = block {
+                            server
+                                .block_manager
+                                .on_broken(&block, self, location, server)
+                                .await;
+                            world.drop_block_experience(server, location, block).await;
+                        }
+                    } else {
+                        // The client already removed the block, put it back
+                        let _ = world.reset_fake_block(self, location).await;
                     }
                 }
                 Status::DropItemStack
@@ -810,6 +909,13 @@ impl Player {
         use_item_on: SUseItemOn,
         server: &Arc<Server>,
     ) -> Result<(), Box<dyn PumpkinError>> {
+        if !self
+            .check_rate_limit(RateLimitedAction::BlockInteraction)
+            .await
+        {
+            return Ok(());
+        }
+
         let location = use_item_on.location;
         let mut should_try_decrement = false;
 
@@ -821,19 +927,26 @@ impl Player {
         if let Ok(face) = BlockFace::try_from(use_item_on.face.0) {
             let mut inventory = self.inventory().lock().await;
             let entity = &self.living_entity.entity;
-            let world = &entity.world;
+            let world = &entity.world();
             let slot_id = inventory.get_selected();
-            let cursor_pos = use_item_on.cursor_pos;
             let mut state_id = inventory.state_id;
             let item_slot = inventory.held_item_mut();
 
+            // Clicking a fence ties the leashes the player holds to it
+            if world
+                .tie_held_leashes_to_fence(server, self.entity_id(), location)
+                .await
+            {
+                return Ok(());
+            }
+
             if let Some(item_stack) = item_slot {
                 // check if block is interactive
                 if let Some(item) = get_item_by_id(item_stack.item_id) {
                     if let Ok(block) = world.get_block(location).await {
                         let result = server
                             .block_manager
-                            .on_use_with_item(block, self, location, item, server)
+                            .on_use_with_item(&block, self, location, item, server)
                             .await;
                         match result {
                             BlockActionResult::Continue => {}
@@ -847,13 +960,19 @@ impl Player {
                 // check if item is a block, Because Not every item can be placed :D
                 if let Some(block) = get_block_by_item(item_stack.item_id) {
                     should_try_decrement = self
-                        .run_is_block_place(block.clone(), server, use_item_on, location, &face)
+                        .run_is_block_place(
+                            Block::clone(&block),
+                            server,
+                            use_item_on,
+                            location,
+                            &face,
+                        )
                         .await?;
                 }
                 // check if item is a spawn egg
                 if let Some(item_t) = get_spawn_egg(item_stack.item_id) {
                     should_try_decrement = self
-                        .run_is_spawn_egg(item_t, server, location, cursor_pos, &face)
+                        .run_is_spawn_egg(item_t, server, location, &face)
                         .await?;
                 };
 
@@ -984,6 +1103,45 @@ impl Player {
         self.client.send_packet(&response).await;
     }
 
+    pub async fn handle_plugin_message(
+        self: &Arc<Self>,
+        packet: SPlayPluginMessage,
+        server: &Server,
+    ) {
+        if self
+            .client
+            .handle_channel_registration(&packet.channel, &packet.data)
+            .await
+        {
+            return;
+        }
+        match self
+            .client
+            .handle_brand(&packet.channel, &packet.data)
+            .await
+        {
+            Some(Err(reason)) => {
+                self.kick(TextComponent::text(reason)).await;
+                return;
+            }
+            Some(Ok(())) => return,
+            None => {}
+        }
+        let handler = server.plugin_channels.read().await.handler(&packet.channel);
+        match handler {
+            Some(handler) => {
+                handler
+                    .on_message(self, &packet.channel, &packet.data, server)
+                    .await;
+            }
+            None => player_debug!(
+                self,
+                "Dropped plugin message on {}, which has no handler",
+                packet.channel
+            ),
+        }
+    }
+
     pub fn handle_cookie_response(&self, packet: SPCookieResponse) {
         // TODO: allow plugins to access this
         log::debug!(
@@ -994,52 +1152,111 @@ impl Player {
         );
     }
 
+    pub async fn handle_resource_pack_response(&self, packet: SResourcePackResponse) {
+        if packet.uuid != resource_pack_uuid() {
+            player_debug!(
+                self,
+                "Responded to an unknown resource pack {}",
+                packet.uuid
+            );
+            return;
+        }
+        let Ok(status) = ResourcePackStatus::try_from(packet.result.0) else {
+            self.kick(TextComponent::text("Invalid resource pack status"))
+                .await;
+            return;
+        };
+        player_debug!(self, "Resource pack status: {status:?}");
+        self.resource_pack_status.store(Some(status));
+
+        if ADVANCED_CONFIG.resource_pack.force && status.is_failure() {
+            self.kick(TextComponent::text(
+                "You must accept the server resource pack to play on this server",
+            ))
+            .await;
+        }
+    }
+
+    /// Spawns the mob of the spawn egg on the clicked face, or makes the clicked spawner spawn it.
+    /// Returns whether the egg got used up
     async fn run_is_spawn_egg(
         &self,
         item_t: String,
         server: &Server,
         location: WorldPosition,
-        cursor_pos: Vector3<f32>,
         face: &BlockFace,
     ) -> Result<bool, Box<dyn PumpkinError>> {
         // checks if spawn egg has a corresponding entity name
-        if let Some(spawn_item_name) = get_entity_id(&item_t) {
-            let head_yaw = 10.0;
-            let world_pos = WorldPosition(location.0 + face.to_offset());
-            let pos = Vector3::new(
-                f64::from(world_pos.0.x),
-                f64::from(world_pos.0.y),
-                f64::from(world_pos.0.z),
-            );
-
-            // TODO: this should not be hardcoded
-            let (mob, uuid) = mob::from_type(EntityType::Zombie, server, pos, self.world()).await;
+        let Some(entity_id) = get_entity_id(&item_t) else {
+            // TODO: maybe include additional error types
+            return Ok(false);
+        };
+        let Some(entity_type) = EntityType::from_id(*entity_id) else {
+            return Ok(false);
+        };
+        if self.gamemode.load() == GameMode::Spectator {
+            return Ok(false);
+        }
+        let world = &self.world();
 
-            let opposite_yaw = self.living_entity.entity.yaw.load() + 180.0;
-            server
-                .broadcast_packet_all(&CSpawnEntity::new(
-                    VarInt(mob.living_entity.entity.entity_id),
-                    uuid,
-                    VarInt((*spawn_item_name).into()),
-                    pos.x + f64::from(cursor_pos.x),
-                    pos.y,
-                    pos.z + f64::from(cursor_pos.z),
-                    10.0,
-                    head_yaw,
-                    opposite_yaw,
-                    0.into(),
-                    0.0,
-                    0.0,
-                    0.0,
-                ))
-                .await;
+        // Using the egg on a spawner changes what it spawns instead
+        if world
+            .get_block(location)
+            .await
+            .is_ok_and(|block| block.name == "spawner")
+        {
+            if !self.may_modify_blocks() || !world.may_build_at(self, &location).await {
+                return Ok(false);
+            }
+            return Ok(world.set_spawner_entity(location, &item_t).await);
+        }
 
-            // TODO: send/configure additional commands/data based on type of entity (horse, slime, etc)
+        // Mobs spawn inside of blocks like grass, like Vanilla
+        let world_pos = if world.get_block_state(location).await?.replaceable {
+            location
         } else {
-            // TODO: maybe include additional error types
-            return Ok(false);
+            WorldPosition(location.0 + face.to_offset())
         };
+        if !world.is_within_border(world_pos).await
+            || !world
+                .region_allows(&world_pos, RegionAction::MobSpawning)
+                .await
+            || !world.may_spawn_entity_for(self).await
+        {
+            return Ok(false);
+        }
+        let pos = Vector3::new(
+            f64::from(world_pos.0.x) + 0.5,
+            f64::from(world_pos.0.y),
+            f64::from(world_pos.0.z) + 0.5,
+        );
+
+        let (mob, uuid) = mob::from_type(entity_type, server, pos, world).await;
+        world
+            .attribute_entity(self, mob.living_entity.entity.entity_id)
+            .await;
+        // The mob faces the player
+        let yaw = self.living_entity.entity.yaw.load() + 180.0;
+        mob.living_entity.entity.set_rotation(yaw, 0.0);
+        server
+            .broadcast_packet_all(&CSpawnEntity::new(
+                VarInt(mob.living_entity.entity.entity_id),
+                uuid,
+                VarInt((*entity_id).into()),
+                pos.x,
+                pos.y,
+                pos.z,
+                0.0,
+                yaw,
+                yaw,
+                0.into(),
+                0.0,
+                0.0,
+                0.0,
+            ))
+            .await;
 
+        // TODO: send/configure additional commands/data based on type of entity (horse, slime, etc)
         Ok(true)
     }
 
@@ -1052,7 +1269,7 @@ impl Player {
         face: &BlockFace,
     ) -> Result<bool, Box<dyn PumpkinError>> {
         let entity = &self.living_entity.entity;
-        let world = &entity.world;
+        let world = &entity.world();
 
         let clicked_world_pos = WorldPosition(location.0);
         let clicked_block_state = world.get_block_state(clicked_world_pos).await?;
@@ -1070,6 +1287,24 @@ impl Player {
             world_pos
         };
 
+        if !self.may_modify_blocks() || !world.may_build_at(self, &world_pos).await {
+            // The client already placed the block, remove it again
+            let _ = world.reset_fake_block(self, world_pos).await;
+            self.client
+                .send_packet(&CAcknowledgeBlockChange::new(use_item_on.sequence))
+                .await;
+            return Ok(false);
+        }
+
+        if !world.is_within_border(world_pos).await {
+            // The client already placed the block, remove it again
+            let _ = world.reset_fake_block(self, world_pos).await;
+            self.client
+                .send_packet(&CAcknowledgeBlockChange::new(use_item_on.sequence))
+                .await;
+            return Err(BlockPlacingError::BlockOutsideBorder.into());
+        }
+
         //check max world build height
         if world_pos.0.y > 319 {
             self.client
@@ -0,0 +1,69 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use pumpkin_config::ADVANCED_CONFIG;
+
+/// How much a single chat message adds to the chat spam counter
+const CHAT_SPAM_INCREMENT: u32 = 20;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RateLimitedAction {
+    /// Breaking, placing or using a block
+    BlockInteraction,
+    /// Sending a chat message
+    Chat,
+}
+
+/// Counts the actions of a single player to protect the server against clients spamming legitimate looking packets.
+///
+/// The counters get decreased in [`RateLimiter::tick`], which is called every world tick.
+#[derive(Default)]
+pub struct RateLimiter {
+    block_interactions: AtomicU32,
+    chat_spam: AtomicU32,
+    violations: AtomicU32,
+}
+
+impl RateLimiter {
+    /// Counts the action, returns `false` if the player exceeded the limit and the action should be dropped
+    pub fn try_action(&self, action: RateLimitedAction) -> bool {
+        let config = &ADVANCED_CONFIG.rate_limit;
+        if !config.enabled {
+            return true;
+        }
+
+        let allowed = match action {
+            RateLimitedAction::BlockInteraction => {
+                self.block_interactions.fetch_add(1, Ordering::Relaxed)
+                    < config.max_block_interactions_per_tick
+            }
+            RateLimitedAction::Chat => {
+                self.chat_spam
+                    .fetch_add(CHAT_SPAM_INCREMENT, Ordering::Relaxed)
+                    < config.chat_spam_threshold
+            }
+        };
+        if !allowed {
+            self.violations.fetch_add(1, Ordering::Relaxed);
+        }
+        allowed
+    }
+
+    /// Returns `true` if the player dropped so many actions that they should be kicked
+    pub fn should_kick(&self) -> bool {
+        let kick_threshold = ADVANCED_CONFIG.rate_limit.kick_threshold;
+        kick_threshold != 0 && self.violations.load(Ordering::Relaxed) >= kick_threshold
+    }
+
+    /// Resets the per tick counters and lets the spam counters decay
+    pub fn tick(&self) {
+        self.block_interactions.store(0, Ordering::Relaxed);
+        let decay = |count: u32| Some(count.saturating_sub(1));
+        // The closures always return Some, so these can't fail
+        let _ = self
+            .chat_spam
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, decay);
+        let _ = self
+            .violations
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, decay);
+    }
+}
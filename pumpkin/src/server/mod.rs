@@ -1,24 +1,29 @@
 use connection_cache::{CachedBranding, CachedStatus};
 use crossbeam::atomic::AtomicCell;
 use key_store::KeyStore;
-use pumpkin_config::BASIC_CONFIG;
+use plugin_channel::PluginChannelManager;
+use pumpkin_config::banlist::{BannedIpEntry, BannedPlayerEntry};
+use pumpkin_config::{ADVANCED_CONFIG, BASIC_CONFIG};
 use pumpkin_core::math::boundingbox::{BoundingBox, BoundingBoxSize};
 use pumpkin_core::math::position::WorldPosition;
-use pumpkin_core::math::vector2::Vector2;
 use pumpkin_core::math::vector3::Vector3;
-use pumpkin_core::GameMode;
+use pumpkin_core::text::TextComponent;
+use pumpkin_core::{GameMode, PermissionLvl};
 use pumpkin_entity::entity_type::EntityType;
 use pumpkin_entity::EntityId;
 use pumpkin_inventory::drag_handler::DragHandler;
 use pumpkin_inventory::{Container, OpenContainer};
 use pumpkin_protocol::client::login::CEncryptionRequest;
-use pumpkin_protocol::{client::config::CPluginMessage, ClientPacket};
+use pumpkin_protocol::{
+    client::config::CPluginMessage, packet_encoder::serialize_packet, ClientPacket,
+};
 use pumpkin_registry::{DimensionType, Registry};
-use pumpkin_world::block::block_registry::Block;
+use pumpkin_world::block::block_registry::{self, Block};
 use pumpkin_world::dimension::Dimension;
 use pumpkin_world::entity::entity_registry::get_entity_by_id;
 use rand::prelude::SliceRandom;
 use std::collections::HashMap;
+use std::net::IpAddr;
 use std::sync::atomic::AtomicU32;
 use std::{
     sync::{
@@ -27,12 +32,19 @@ use std::{
     },
     time::Duration,
 };
+use thiserror::Error;
 use tokio::sync::{Mutex, RwLock};
+use tps_overlay::TpsOverlay;
 use uuid::Uuid;
 
 use crate::block::block_manager::BlockManager;
 use crate::block::default_block_manager;
+use crate::data::banlist_data::{ban_message, unix_now, BANNED_IP_LIST, BANNED_PLAYER_LIST};
+use crate::data::op_data::OPERATOR_CONFIG;
+use crate::data::whitelist_data::WHITELIST_CONFIG;
+use crate::entity::ai::goal::GoalSelector;
 use crate::entity::ai::path::Navigator;
+use crate::entity::interaction::EntityInteractionManager;
 use crate::entity::living::LivingEntity;
 use crate::entity::mob::MobEntity;
 use crate::entity::Entity;
@@ -47,10 +59,29 @@ use crate::{
 
 mod connection_cache;
 mod key_store;
+pub mod plugin_channel;
 pub mod ticker;
+mod tps_overlay;
+mod world_manager;
+
+pub use world_manager::WorldManager;
 
 pub const CURRENT_MC_VERSION: &str = "1.21.4";
 
+/// The reason used when banning without giving one, same as Vanilla
+const DEFAULT_BAN_REASON: &str = "Banned by an operator.";
+
+/// Where [`Server::reload_registries`] reads the blocks from
+const BLOCK_REGISTRY_PATH: &str = "data/blocks.json";
+
+#[derive(Error, Debug)]
+pub enum ReloadRegistriesError {
+    #[error("Failed to read {BLOCK_REGISTRY_PATH}: {0}")]
+    Read(#[from] std::io::Error),
+    #[error("Failed to parse {BLOCK_REGISTRY_PATH}: {0}")]
+    Parse(#[from] serde_json::Error),
+}
+
 /// Represents a Minecraft server instance.
 pub struct Server {
     /// Handles cryptographic keys for secure communication.
@@ -63,8 +94,12 @@ pub struct Server {
     pub command_dispatcher: RwLock<CommandDispatcher>,
     /// Saves and calls blocks blocks
     pub block_manager: Arc<BlockManager>,
+    /// Saves and calls the entity interaction handlers
+    pub entity_interaction_manager: Arc<EntityInteractionManager>,
+    /// Saves and calls the handlers of inbound plugin messages
+    pub plugin_channels: RwLock<PluginChannelManager>,
     /// Manages multiple worlds within the server.
-    pub worlds: Vec<Arc<World>>,
+    pub worlds: WorldManager,
     // All the dimensions that exists on the server,
     pub dimensions: Vec<DimensionType>,
     /// Caches game registries for efficient access.
@@ -81,12 +116,34 @@ pub struct Server {
     pub auth_client: Option<reqwest::Client>,
     /// The server's custom bossbars
     pub bossbars: Mutex<CustomBossbars>,
+    /// Sends the TPS to the operators who want to see it
+    tps_overlay: TpsOverlay,
 }
 
 impl Server {
     #[allow(clippy::new_without_default)]
     #[must_use]
     pub fn new() -> Self {
+        let world = World::load(
+            Dimension::OverWorld.into_level(
+                // TODO: load form config
+                "./world".parse().unwrap(),
+            ),
+            DimensionType::Overworld,
+        );
+
+        world.force_load_spawn_chunks();
+
+        Self::with_world(world)
+    }
+
+    /// A server with only a [`World::test_world`], nothing is read from or written to the world folder
+    #[cfg(test)]
+    pub fn test_server() -> Self {
+        Self::with_world(World::test_world())
+    }
+
+    fn with_world(world: World) -> Self {
         let auth_client = BASIC_CONFIG.online_mode.then(|| {
             reqwest::Client::builder()
                 .timeout(Duration::from_millis(5000))
@@ -97,21 +154,6 @@ impl Server {
         // First register default command, after that plugins can put in their own
         let command_dispatcher = RwLock::new(default_dispatcher());
 
-        let world = World::load(
-            Dimension::OverWorld.into_level(
-                // TODO: load form config
-                "./world".parse().unwrap(),
-            ),
-            DimensionType::Overworld,
-        );
-
-        // Spawn chunks are never unloaded
-        for x in -1..=1 {
-            for z in -1..=1 {
-                world.level.mark_chunk_as_newly_watched(Vector2::new(x, z));
-            }
-        }
-
         Self {
             cached_registry: Registry::get_synced(),
             open_containers: RwLock::new(HashMap::new()),
@@ -119,7 +161,7 @@ impl Server {
             // 0 is invalid
             entity_id: 2.into(),
             container_id: 0.into(),
-            worlds: vec![Arc::new(world)],
+            worlds: WorldManager::new(vec![Arc::new(world)]),
             dimensions: vec![
                 DimensionType::Overworld,
                 DimensionType::OverworldCaves,
@@ -128,11 +170,14 @@ impl Server {
             ],
             command_dispatcher,
             block_manager: default_block_manager(),
+            entity_interaction_manager: Arc::new(EntityInteractionManager::default()),
+            plugin_channels: RwLock::new(PluginChannelManager::default()),
             auth_client,
             key_store: KeyStore::new(),
             server_listing: Mutex::new(CachedStatus::new()),
             server_branding: CachedBranding::new(),
             bossbars: Mutex::new(CustomBossbars::new()),
+            tps_overlay: TpsOverlay::default(),
         }
     }
 
@@ -163,18 +208,17 @@ impl Server {
     /// You still have to spawn the Player in the World to make then to let them Join and make them Visible
     pub async fn add_player(&self, client: Arc<Client>) -> (Arc<Player>, Arc<World>) {
         let entity_id = self.new_entity_id();
-        let gamemode = match BASIC_CONFIG.default_gamemode {
-            GameMode::Undefined => GameMode::Survival,
-            game_mode => game_mode,
-        };
         // Basically the default world
         // TODO: select default from config
-        let world = &self.worlds[0];
+        let world = self.worlds.default_world();
+        let gamemode = world.config.default_gamemode;
 
         let player = Arc::new(Player::new(client, world.clone(), entity_id, gamemode).await);
-        world
-            .add_player(player.gameprofile.id, player.clone())
-            .await;
+        // Nothing was sent to the player yet, so the gamemode can change without telling them
+        if let Some(forced) = world.forced_gamemode(&player) {
+            player.gamemode.store(forced);
+        }
+        self.worlds.add_player(world, player.clone()).await;
         // TODO: Config if we want increase online
         if let Some(config) = player.client.config.lock().await.as_ref() {
             // TODO: Config so we can also just ignore this hehe
@@ -186,7 +230,8 @@ impl Server {
         (player, world.clone())
     }
 
-    pub async fn remove_player(&self) {
+    pub async fn remove_player(&self, player: &Player) {
+        self.worlds.remove_player(player).await;
         // TODO: Config if we want decrease online
         self.server_listing.lock().await.remove_player();
     }
@@ -197,6 +242,21 @@ impl Server {
         }
     }
 
+    /// Reloads the block registry from `data/blocks.json` and sends all loaded chunks again, without restarting.
+    ///
+    /// Only the runtime block registry is hot-reloadable, see [`block_registry::reload_blocks`].
+    /// Returns how many blocks were loaded, the old registry stays in place if the file can't be loaded
+    pub async fn reload_registries(&self) -> Result<usize, ReloadRegistriesError> {
+        let json = std::fs::read_to_string(BLOCK_REGISTRY_PATH)?;
+        let blocks = block_registry::reload_blocks(&json)?;
+        let mut chunks = 0;
+        for world in &self.worlds {
+            chunks += world.resend_loaded_chunks().await;
+        }
+        log::info!("Reloaded {blocks} blocks and resent {chunks} chunks");
+        Ok(blocks)
+    }
+
     pub async fn add_mob_entity(
         &self,
         entity_type: EntityType,
@@ -207,7 +267,7 @@ impl Server {
 
         let mob = Arc::new(MobEntity {
             living_entity,
-            goals: Mutex::new(vec![]),
+            goals: Mutex::new(GoalSelector::default()),
             navigator: Mutex::new(Navigator::default()),
         });
         world.add_mob_entity(uuid, mob.clone()).await;
@@ -318,6 +378,7 @@ impl Server {
     /// Broadcasts a packet to all players in all worlds.
     ///
     /// This function sends the specified packet to every connected player in every world managed by the server.
+    /// Use [`Server::broadcast_to_dimension`] or [`World::broadcast_packet_all`] to only reach the players of one world.
     ///
     /// # Arguments
     ///
@@ -326,11 +387,25 @@ impl Server {
     where
         P: ClientPacket,
     {
-        for world in &self.worlds {
-            world.broadcast_packet_all(packet).await;
+        let data = serialize_packet(packet);
+        for (player, _) in self.all_players().await {
+            player.client.send_serialized(&data).await;
         }
     }
 
+    /// Broadcasts a packet only to the players in the world of the dimension, e.g. for announcements in the End.
+    /// Returns false if the server has no world for the dimension
+    pub async fn broadcast_to_dimension<P>(&self, dimension: DimensionType, packet: &P) -> bool
+    where
+        P: ClientPacket,
+    {
+        let Some(world) = self.worlds.get(dimension) else {
+            return false;
+        };
+        world.broadcast_packet_all(packet).await;
+        true
+    }
+
     /// Searches for a player by their username across all worlds.
     ///
     /// This function iterates through each world managed by the server and attempts to find a player with the specified username.
@@ -352,17 +427,42 @@ impl Server {
         None
     }
 
-    /// Returns all players from all worlds.
+    /// Returns all players from all worlds, see [`Server::all_players`]
     pub async fn get_all_players(&self) -> Vec<Arc<Player>> {
-        let mut players = Vec::<Arc<Player>>::new();
+        self.all_players()
+            .await
+            .into_iter()
+            .map(|(player, _)| player)
+            .collect()
+    }
 
-        for world in &self.worlds {
-            for (_, player) in world.current_players.lock().await.iter() {
-                players.push(player.clone());
+    /// Returns all players from all worlds together with the world they are in. Global features like global chat
+    /// and `/list` use this, a player moving between worlds is only in it once
+    pub async fn all_players(&self) -> Vec<(Arc<Player>, Arc<World>)> {
+        self.worlds.all_players().await
+    }
+
+    /// Sends the system message to every player in every world and logs it to the console
+    pub async fn broadcast_system_message(&self, message: &TextComponent) {
+        log::info!("{}", message.to_pretty_console());
+        for (player, world) in self.all_players().await {
+            if !world.is_broadcast_suppressed() {
+                player.send_system_message(message).await;
             }
         }
+    }
 
-        players
+    /// Logs the message to the console and sends it to every operator in every world except the `except` players,
+    /// even while broadcasts are suppressed
+    pub async fn broadcast_op_message(&self, message: &TextComponent, except: &[Uuid]) {
+        log::info!("{}", message.to_pretty_console());
+        for (player, _) in self.all_players().await {
+            if player.permission_lvl.load() > PermissionLvl::Zero
+                && !except.contains(&player.gameprofile.id)
+            {
+                player.send_system_message(message).await;
+            }
+        }
     }
 
     /// Returns a random player from any of the worlds or None if all worlds are empty.
@@ -393,6 +493,119 @@ impl Server {
         None
     }
 
+    /// Returns the permission level of a player, falling back to the default level for players that aren't operators.
+    pub async fn permission_level(&self, uuid: &uuid::Uuid) -> PermissionLvl {
+        OPERATOR_CONFIG
+            .read()
+            .await
+            .get_level(uuid)
+            .unwrap_or(ADVANCED_CONFIG.commands.default_op_level)
+    }
+
+    /// Makes the player an operator with the given permission level, saves it to `ops.json` and syncs the player.
+    pub async fn set_op(&self, player: &Arc<Player>, level: PermissionLvl) {
+        OPERATOR_CONFIG.write().await.set_op(
+            player.gameprofile.id,
+            player.gameprofile.name.clone(),
+            level,
+        );
+        player
+            .set_permission_lvl(level, &self.command_dispatcher)
+            .await;
+    }
+
+    /// Revokes the operator status of the player, saves it to `ops.json` and syncs the player.
+    /// The player gets the default level of players who aren't operators, see [`Server::permission_level`]
+    pub async fn remove_op(&self, player: &Arc<Player>) {
+        OPERATOR_CONFIG
+            .write()
+            .await
+            .remove_op(&player.gameprofile.id);
+        let level = self.permission_level(&player.gameprofile.id).await;
+        player
+            .set_permission_lvl(level, &self.command_dispatcher)
+            .await;
+    }
+
+    /// Checks if the player is on the whitelist, this does not check if the whitelist is enabled.
+    pub async fn is_whitelisted(&self, uuid: &uuid::Uuid) -> bool {
+        WHITELIST_CONFIG.read().await.is_whitelisted(uuid)
+    }
+
+    /// Adds the player to the whitelist and saves it to `whitelist.json`, returns `false` if the player was already whitelisted.
+    pub async fn whitelist_add(&self, uuid: uuid::Uuid, name: String) -> bool {
+        WHITELIST_CONFIG.write().await.add(uuid, name)
+    }
+
+    /// Removes the player from the whitelist and saves it to `whitelist.json`, returns `false` if the player was not whitelisted.
+    pub async fn whitelist_remove(&self, uuid: &uuid::Uuid) -> bool {
+        WHITELIST_CONFIG.write().await.remove(uuid)
+    }
+
+    /// Bans the player until the `expires` Unix timestamp or forever and saves it to `banned-players.json`.
+    /// If the player is online, they get kicked
+    pub async fn ban_player(&self, uuid: uuid::Uuid, reason: Option<String>, expires: Option<u64>) {
+        let reason = reason.unwrap_or_else(|| DEFAULT_BAN_REASON.to_string());
+        let player = self.get_player_by_uuid(uuid).await;
+        let name = player
+            .as_ref()
+            .map(|player| player.gameprofile.name.clone())
+            .unwrap_or_default();
+        BANNED_PLAYER_LIST.write().await.ban(BannedPlayerEntry::new(
+            uuid,
+            name,
+            unix_now(),
+            "Server".to_string(),
+            expires,
+            reason.clone(),
+        ));
+        if let Some(player) = player {
+            player
+                .kick(TextComponent::text(ban_message(&reason, expires, false)))
+                .await;
+        }
+    }
+
+    /// Removes the ban of the player, returns `false` if the player was not banned.
+    pub async fn pardon(&self, uuid: &uuid::Uuid) -> bool {
+        BANNED_PLAYER_LIST.write().await.pardon(uuid)
+    }
+
+    /// Checks if the player has an active ban, expired bans are ignored and pruned.
+    pub async fn is_banned(&self, uuid: &uuid::Uuid) -> bool {
+        BANNED_PLAYER_LIST.write().await.get_entry(uuid).is_some()
+    }
+
+    /// Bans the IP until the `expires` Unix timestamp or forever and saves it to `banned-ips.json`.
+    /// All online players with this IP get kicked
+    pub async fn ban_ip(&self, ip: IpAddr, reason: Option<String>, expires: Option<u64>) {
+        let reason = reason.unwrap_or_else(|| DEFAULT_BAN_REASON.to_string());
+        BANNED_IP_LIST.write().await.ban(BannedIpEntry::new(
+            ip,
+            unix_now(),
+            "Server".to_string(),
+            expires,
+            reason.clone(),
+        ));
+        for player in self.get_all_players().await {
+            if player.client.address.lock().await.ip() == ip {
+                player
+                    .kick(TextComponent::text(ban_message(&reason, expires, true)))
+                    .await;
+            }
+        }
+    }
+
+    /// Removes the ban of the IP, returns `false` if the IP was not banned.
+    pub async fn pardon_ip(&self, ip: &IpAddr) -> bool {
+        BANNED_IP_LIST.write().await.pardon(ip)
+    }
+
+    /// Checks if the IP has an active ban, expired bans are ignored and pruned.
+    pub async fn is_ip_banned(&self, ip: &IpAddr) -> bool {
+        BANNED_IP_LIST.write().await.get_entry(ip).is_some()
+    }
+
     /// Counts the total number of players across all worlds.
     ///
     /// This function iterates through each world and sums up the number of players currently connected to that world.
@@ -456,9 +669,28 @@ impl Server {
         self.key_store.get_digest(secret)
     }
 
+    /// Respawns the player if they are dead, players who are alive or respawning right now already respawned
+    pub async fn respawn_dead_player(&self, player: &Arc<Player>) {
+        if player.living_entity.health.load() > 0.0
+            || player
+                .respawning
+                .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+                .is_err()
+        {
+            return;
+        }
+        self.worlds.respawn_player(player, false).await;
+        // Hardcore players can only watch after dying
+        if BASIC_CONFIG.hardcore && player.gamemode.load() != GameMode::Spectator {
+            player.set_gamemode(GameMode::Spectator).await;
+        }
+        player.respawning.store(false, Ordering::Release);
+    }
+
     async fn tick(&self) {
         for world in &self.worlds {
-            world.tick().await;
+            world.tick_once(self).await;
         }
+        self.tps_overlay.tick(self).await;
     }
 }
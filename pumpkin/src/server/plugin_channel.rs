@@ -0,0 +1,294 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+use async_trait::async_trait;
+use pumpkin_config::ADVANCED_CONFIG;
+use pumpkin_protocol::{
+    bytebuf::ByteBuf, client::play::CPlayPluginMessage, codec::identifier::Identifier,
+};
+
+use crate::{entity::player::Player, log_context::player_debug, net::Client};
+
+use super::Server;
+
+/// Clients announce the channels they listen on with this channel, the channel names are separated by a NUL byte
+const REGISTER_CHANNEL: &str = "minecraft:register";
+/// Clients stop listening on channels with this channel, same format as [`REGISTER_CHANNEL`]
+const UNREGISTER_CHANNEL: &str = "minecraft:unregister";
+/// Clients send their brand on this channel while joining, as a string prefixed with its length
+const BRAND_CHANNEL: &str = "minecraft:brand";
+/// A client can't listen on more channels than this, like on Bukkit servers
+const MAX_REGISTERED_CHANNELS: usize = 128;
+/// Longer channel names get skipped when registering, like on Bukkit servers
+const MAX_CHANNEL_NAME_LENGTH: usize = 64;
+
+/// The channel `BungeeCord` proxies listen on, messages sent on it never reach the client
+#[must_use]
+pub fn bungeecord_channel() -> Identifier {
+    Identifier {
+        namespace: "bungeecord".to_string(),
+        path: "main".to_string(),
+    }
+}
+
+#[async_trait]
+pub trait PluginMessageHandler: Send + Sync {
+    /// Called when a player sends a message on the registered channel
+    async fn on_message(
+        &self,
+        player: &Arc<Player>,
+        channel: &Identifier,
+        data: &[u8],
+        server: &Server,
+    );
+}
+
+/// Saves and calls the handlers of plugin channels, see [`Server::send_plugin_message`] for the other direction
+#[derive(Default)]
+pub struct PluginChannelManager {
+    handlers: HashMap<Identifier, Arc<dyn PluginMessageHandler>>,
+}
+
+impl PluginChannelManager {
+    /// Registers the handler for the channel, replacing an older one
+    pub fn register(&mut self, channel: Identifier, handler: Arc<dyn PluginMessageHandler>) {
+        self.handlers.insert(channel, handler);
+    }
+
+    pub fn unregister(&mut self, channel: &Identifier) {
+        self.handlers.remove(channel);
+    }
+
+    /// The handler of the channel, messages on channels without one get dropped
+    #[must_use]
+    pub fn handler(&self, channel: &Identifier) -> Option<Arc<dyn PluginMessageHandler>> {
+        self.handlers.get(channel).cloned()
+    }
+}
+
+/// The channel names of a `minecraft:register` or `minecraft:unregister` message, invalid and too long names are skipped
+fn parse_channels(data: &[u8]) -> Vec<Identifier> {
+    data.split(|byte| *byte == 0)
+        .filter(|name| name.len() <= MAX_CHANNEL_NAME_LENGTH)
+        .filter_map(|name| std::str::from_utf8(name).ok())
+        .filter_map(|name| name.split_once(':'))
+        .map(|(namespace, path)| Identifier {
+            namespace: namespace.to_string(),
+            path: path.to_string(),
+        })
+        .collect()
+}
+
+/// Adds the channels until the client listens on [`MAX_REGISTERED_CHANNELS`], returns how many got skipped
+fn register_channels(registered: &mut HashSet<Identifier>, channels: Vec<Identifier>) -> usize {
+    let mut skipped = 0;
+    for channel in channels {
+        if registered.len() < MAX_REGISTERED_CHANNELS || registered.contains(&channel) {
+            registered.insert(channel);
+        } else {
+            skipped += 1;
+        }
+    }
+    skipped
+}
+
+/// Whether the brand contains one of the blocked brands, ignoring case
+fn is_blocked_brand(brand: &str, blocked_brands: &[String]) -> bool {
+    let brand = brand.to_lowercase();
+    blocked_brands
+        .iter()
+        .any(|blocked| brand.contains(&blocked.to_lowercase()))
+}
+
+/// Writes the string like Java's `DataOutput::writeUTF`, which `BungeeCord` reads the messages with.
+/// Strings only made of `\0` free characters are the same in Java's modified UTF-8 as in UTF-8.
+/// Longer strings than the length prefix allows are cut off at the last character that fits
+fn put_java_utf(data: &mut Vec<u8>, string: &str) {
+    let mut end = string.len().min(u16::MAX as usize);
+    while !string.is_char_boundary(end) {
+        end -= 1;
+    }
+    data.extend_from_slice(&(end as u16).to_be_bytes());
+    data.extend_from_slice(&string.as_bytes()[..end]);
+}
+
+/// The `BungeeCord` message moving the player to another server of the proxy
+fn bungeecord_connect_message(server_name: &str) -> Vec<u8> {
+    let mut data = Vec::new();
+    put_java_utf(&mut data, "Connect");
+    put_java_utf(&mut data, server_name);
+    data
+}
+
+impl Client {
+    /// Keeps track of the channels the client listens on, from the `minecraft:register` and `minecraft:unregister`
+    /// messages it sends. Returns false if the message was on another channel
+    pub async fn handle_channel_registration(&self, channel: &Identifier, data: &[u8]) -> bool {
+        let channel = channel.to_string();
+        let mut registered = self.registered_channels.lock().await;
+        if channel == REGISTER_CHANNEL {
+            let skipped = register_channels(&mut registered, parse_channels(data));
+            if skipped > 0 {
+                log::debug!(
+                    "Client id {} registered too many plugin channels, skipped {skipped}",
+                    self.id
+                );
+            }
+        } else if channel == UNREGISTER_CHANNEL {
+            for channel in parse_channels(data) {
+                registered.remove(&channel);
+            }
+        } else {
+            return false;
+        }
+        true
+    }
+
+    /// Saves the brand the client sends on the `minecraft:brand` channel, see [`pumpkin_config::ClientBrandConfig`].
+    /// Returns `None` if the message was on another channel, and the reason to kick the client for if the brand is
+    /// blocked or invalid. The caller kicks, as players have to be kicked through [`Player::kick`]
+    pub async fn handle_brand(
+        &self,
+        channel: &Identifier,
+        mut data: &[u8],
+    ) -> Option<Result<(), String>> {
+        if channel.to_string() != BRAND_CHANNEL {
+            return None;
+        }
+        let brand = match data.try_get_string() {
+            Ok(brand) => brand,
+            Err(error) => return Some(Err(error.to_string())),
+        };
+        let config = &ADVANCED_CONFIG.client_brand;
+        if config.log {
+            log::info!("Client id {} uses the brand {brand}", self.id);
+        }
+        let blocked = is_blocked_brand(&brand, &config.blocked_brands);
+        *self.brand.lock().await = Some(brand);
+        if blocked {
+            return Some(Err(config.kick_message.clone()));
+        }
+        Some(Ok(()))
+    }
+}
+
+impl Server {
+    /// Sends the message to the player on the channel. The message is dropped if the player's client did not register
+    /// the channel, except for the `BungeeCord` channel while `BungeeCord` forwarding is on, which the proxy takes.
+    /// Returns if the message was sent
+    pub async fn send_plugin_message(
+        &self,
+        player: &Player,
+        channel: &Identifier,
+        data: &[u8],
+    ) -> bool {
+        let proxy = &ADVANCED_CONFIG.networking.proxy;
+        let to_proxy =
+            proxy.enabled && proxy.bungeecord.enabled && *channel == bungeecord_channel();
+        if !to_proxy
+            && !player
+                .client
+                .registered_channels
+                .lock()
+                .await
+                .contains(channel)
+        {
+            player_debug!(
+                player,
+                "Dropped plugin message on {channel}, which the client did not register"
+            );
+            return false;
+        }
+        player
+            .client
+            .send_packet(&CPlayPluginMessage::new(channel, data))
+            .await;
+        true
+    }
+
+    /// Asks the `BungeeCord` proxy to move the player to another of its servers.
+    /// Returns false if `BungeeCord` forwarding is off, as nobody would get the message
+    pub async fn bungeecord_connect(&self, player: &Player, server_name: &str) -> bool {
+        self.send_plugin_message(
+            player,
+            &bungeecord_channel(),
+            &bungeecord_connect_message(server_name),
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use pumpkin_protocol::codec::identifier::Identifier;
+
+    use super::{
+        bungeecord_connect_message, is_blocked_brand, parse_channels, put_java_utf,
+        register_channels, MAX_REGISTERED_CHANNELS,
+    };
+
+    #[test]
+    fn register_message() {
+        assert_eq!(
+            parse_channels(b"minecraft:brand\0invalid\0mymod:sync"),
+            vec![
+                Identifier::vanilla("brand"),
+                Identifier {
+                    namespace: "mymod".to_string(),
+                    path: "sync".to_string(),
+                }
+            ]
+        );
+        assert!(parse_channels(b"").is_empty());
+        let long = format!("mymod:{}", "a".repeat(100));
+        assert!(parse_channels(long.as_bytes()).is_empty());
+    }
+
+    #[test]
+    fn register_limit() {
+        let channel = |index: usize| Identifier {
+            namespace: "mymod".to_string(),
+            path: index.to_string(),
+        };
+        let mut registered = HashSet::new();
+        let skipped = register_channels(
+            &mut registered,
+            (0..MAX_REGISTERED_CHANNELS + 10).map(channel).collect(),
+        );
+        assert_eq!(skipped, 10);
+        assert_eq!(registered.len(), MAX_REGISTERED_CHANNELS);
+        // Registering a channel again still works
+        assert_eq!(register_channels(&mut registered, vec![channel(0)]), 0);
+    }
+
+    #[test]
+    fn blocked_brands() {
+        let blocked = vec!["wurst".to_string(), "Meteor".to_string()];
+        assert!(is_blocked_brand("Wurst Client", &blocked));
+        assert!(is_blocked_brand("meteor-client", &blocked));
+        assert!(!is_blocked_brand("vanilla", &blocked));
+        assert!(!is_blocked_brand("fabric", &[]));
+    }
+
+    #[test]
+    fn connect_message() {
+        assert_eq!(
+            bungeecord_connect_message("lobby"),
+            b"\0\x07Connect\0\x05lobby".to_vec()
+        );
+    }
+
+    #[test]
+    fn utf_cut_at_character() {
+        // 'é' takes 2 bytes, so the last one doesn't fit anymore
+        let string = "é".repeat(40_000);
+        let mut data = Vec::new();
+        put_java_utf(&mut data, &string);
+        assert_eq!(&data[..2], &65_534u16.to_be_bytes());
+        assert!(std::str::from_utf8(&data[2..]).is_ok());
+    }
+}
@@ -0,0 +1,103 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use pumpkin_config::BASIC_CONFIG;
+use pumpkin_core::{
+    text::{color::NamedColor, TextComponent},
+    PermissionLvl,
+};
+
+use crate::entity::player::Player;
+
+use super::Server;
+
+/// The permission level players need to see the overlay
+const REQUIRED_PERMISSION: PermissionLvl = PermissionLvl::Two;
+/// The overlay is updated once a second
+const UPDATE_INTERVAL_TICKS: u64 = 20;
+
+/// Shows the TPS and MSPT on the action bar of operators who turned it on, see [`Server::set_tps_overlay`]
+#[derive(Default)]
+pub struct TpsOverlay {
+    ticks: AtomicU64,
+}
+
+impl TpsOverlay {
+    pub async fn tick(&self, server: &Server) {
+        if self.ticks.fetch_add(1, Ordering::Relaxed) % UPDATE_INTERVAL_TICKS != 0 {
+            return;
+        }
+
+        // A server tick ticks every world
+        let mut mspt = 0.0;
+        for world in &server.worlds {
+            mspt += world.average_tick_timings().await.total().as_secs_f32() * 1000.0;
+        }
+        let target = BASIC_CONFIG.tps;
+        // The ticker waits for the rest of the tick, so we only fall behind when ticks take too long
+        let tps = if mspt > 0.0 {
+            (1000.0 / mspt).min(target)
+        } else {
+            target
+        };
+        let text = overlay_text(tps, mspt, target);
+
+        for world in &server.worlds {
+            for player in world.current_players.lock().await.values() {
+                if player.tps_overlay.load(Ordering::Relaxed)
+                    && player.permission_lvl.load() >= REQUIRED_PERMISSION
+                {
+                    player.send_actionbar(&text).await;
+                }
+            }
+        }
+    }
+}
+
+/// The color of the TPS, green when we keep up with the target and red when we fall far behind
+fn tps_color(tps: f32, target: f32) -> NamedColor {
+    let ratio = tps / target;
+    if ratio >= 0.9 {
+        NamedColor::Green
+    } else if ratio >= 0.75 {
+        NamedColor::Yellow
+    } else {
+        NamedColor::Red
+    }
+}
+
+fn overlay_text(tps: f32, mspt: f32, target: f32) -> TextComponent {
+    TextComponent::text("TPS: ")
+        .add_child(TextComponent::text(format!("{tps:.1}")).color_named(tps_color(tps, target)))
+        .add_child(TextComponent::text(format!(" MSPT: {mspt:.1}")))
+}
+
+impl Server {
+    /// Turns the TPS overlay on the action bar of the player on or off.
+    /// Returns `false` if the player does not have the permission to see it
+    pub async fn set_tps_overlay(&self, player: &Player, enabled: bool) -> bool {
+        if enabled && player.permission_lvl.load() < REQUIRED_PERMISSION {
+            return false;
+        }
+        let was_enabled = player.tps_overlay.swap(enabled, Ordering::Relaxed);
+        if was_enabled && !enabled {
+            // Don't leave the last update on the action bar
+            player.send_actionbar(&TextComponent::text("")).await;
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_core::text::color::NamedColor;
+
+    use super::tps_color;
+
+    #[test]
+    fn color_thresholds() {
+        assert_eq!(tps_color(20.0, 20.0), NamedColor::Green);
+        assert_eq!(tps_color(18.0, 20.0), NamedColor::Green);
+        assert_eq!(tps_color(16.0, 20.0), NamedColor::Yellow);
+        assert_eq!(tps_color(10.0, 20.0), NamedColor::Red);
+    }
+}
@@ -0,0 +1,304 @@
+use std::{collections::HashMap, sync::Arc};
+
+use pumpkin_core::math::vector3::Vector3;
+use pumpkin_registry::DimensionType;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use crate::{
+    entity::player::Player,
+    world::{portal::scale_portal_position, World},
+};
+
+/// Holds all worlds of the server and makes sure every player is in exactly one of them
+pub struct WorldManager {
+    worlds: Vec<Arc<World>>,
+    player_worlds: Mutex<PlayerWorlds>,
+}
+
+/// The dimension of the world every player is in
+#[derive(Default)]
+struct PlayerWorlds(HashMap<Uuid, DimensionType>);
+
+impl PlayerWorlds {
+    /// Moves the player into the dimension, returns the dimension they were in before
+    fn enter(&mut self, uuid: Uuid, dimension: DimensionType) -> Option<DimensionType> {
+        self.0.insert(uuid, dimension)
+    }
+
+    fn leave(&mut self, uuid: &Uuid) -> Option<DimensionType> {
+        self.0.remove(uuid)
+    }
+
+    fn get(&self, uuid: &Uuid) -> Option<DimensionType> {
+        self.0.get(uuid).copied()
+    }
+}
+
+impl WorldManager {
+    /// The first world is the default world new players join
+    #[must_use]
+    pub fn new(worlds: Vec<Arc<World>>) -> Self {
+        assert!(!worlds.is_empty(), "There must be at least one world");
+        Self {
+            worlds,
+            player_worlds: Mutex::new(PlayerWorlds::default()),
+        }
+    }
+
+    /// The world new players join
+    #[must_use]
+    pub fn default_world(&self) -> &Arc<World> {
+        &self.worlds[0]
+    }
+
+    #[must_use]
+    pub fn first(&self) -> Option<&Arc<World>> {
+        self.worlds.first()
+    }
+
+    /// Returns the world of the dimension, if it is loaded
+    #[must_use]
+    pub fn get(&self, dimension: DimensionType) -> Option<&Arc<World>> {
+        self.worlds
+            .iter()
+            .find(|world| world.dimension_type == dimension)
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Arc<World>> {
+        self.worlds.iter()
+    }
+
+    /// Returns the world the player is currently in
+    pub async fn world_of(&self, uuid: &Uuid) -> Option<Arc<World>> {
+        let dimension = self.player_worlds.lock().await.get(uuid)?;
+        self.get(dimension).cloned()
+    }
+
+    /// Every online player together with the world they are in. Players being moved between worlds are only in
+    /// the list once, in the world the manager has them in
+    pub async fn all_players(&self) -> Vec<(Arc<Player>, Arc<World>)> {
+        // Moving players holds this lock, so all worlds are seen in the same state
+        let player_worlds = self.player_worlds.lock().await;
+        let mut players = Vec::new();
+        for world in &self.worlds {
+            for (uuid, player) in world.current_players.lock().await.iter() {
+                if player_worlds.get(uuid) == Some(world.dimension_type) {
+                    players.push((player.clone(), world.clone()));
+                }
+            }
+        }
+        players
+    }
+
+    /// Adds the player to the world, removing them from the world they were in before
+    pub async fn add_player(&self, world: &Arc<World>, player: Arc<Player>) {
+        let uuid = player.gameprofile.id;
+        // Keep the lock until the player is in the new world, so concurrent moves can't put them into two worlds
+        let mut player_worlds = self.player_worlds.lock().await;
+        let previous = player_worlds.enter(uuid, world.dimension_type);
+        if previous == Some(world.dimension_type) {
+            return;
+        }
+        if let Some(previous) = previous.and_then(|dimension| self.get(dimension)) {
+            previous.remove_player(&player).await;
+            previous.hide_ranged_bossbars(&player).await;
+            // The chunks of the new world get sent from scratch
+            player.unwatch_chunks(previous);
+        }
+        player.living_entity.entity.set_world(world.clone());
+        world.add_player(uuid, player).await;
+    }
+
+    /// Removes the player from whatever world they are in
+    pub async fn remove_player(&self, player: &Player) {
+        let mut player_worlds = self.player_worlds.lock().await;
+        if let Some(world) = player_worlds
+            .leave(&player.gameprofile.id)
+            .and_then(|dimension| self.get(dimension))
+        {
+            world.remove_player(player).await;
+        }
+    }
+
+    /// Moves the player into the world of the dimension, returns `false` if that world is not loaded
+    pub async fn transfer_player(&self, player: &Arc<Player>, dimension: DimensionType) -> bool {
+        let Some(world) = self.get(dimension) else {
+            return false;
+        };
+        let previous = self.world_of(&player.gameprofile.id).await;
+        self.add_player(world, player.clone()).await;
+        if let Some(previous) = previous.filter(|previous| !Arc::ptr_eq(previous, world)) {
+            previous.restore_gamemode(player).await;
+        }
+        world.enforce_gamemode(player).await;
+        player.start_portal_cooldown();
+        true
+    }
+
+    /// Sends the player through the nether portal they are standing in, between the Overworld and the Nether.
+    /// Returns `false` if they are on portal cooldown or the other world is not loaded
+    pub async fn travel_through_portal(&self, player: &Arc<Player>) -> bool {
+        if player.is_on_portal_cooldown() {
+            return false;
+        }
+        let Some(world) = self.world_of(&player.gameprofile.id).await else {
+            return false;
+        };
+        let to = match world.dimension_type {
+            DimensionType::Overworld => DimensionType::TheNether,
+            DimensionType::TheNether => DimensionType::Overworld,
+            DimensionType::OverworldCaves | DimensionType::TheEnd => return false,
+        };
+        let Some(destination) = self.get(to) else {
+            return false;
+        };
+        let scaled = scale_portal_position(
+            player.living_entity.entity.pos.load(),
+            world.dimension_type,
+            to,
+        );
+        let portal = destination.find_or_create_portal(scaled).await;
+        // Also starts the portal cooldown, which the respawn packet tells the client about
+        self.transfer_player(player, to).await;
+        let arrival = Vector3::new(
+            f64::from(portal.0.x) + 0.5,
+            f64::from(portal.0.y),
+            f64::from(portal.0.z) + 0.5,
+        );
+        destination
+            .respawn_player(player, world.dimension_type, true, Some(arrival))
+            .await;
+        true
+    }
+
+    /// Respawns the player in the world their current world sends them to, see [`crate::world::world_config::WorldConfig::respawn_dimension`].
+    /// Players stay in their world if the respawn world is not loaded.
+    ///
+    /// `alive` keeps their data, like when leaving the End through the exit portal
+    pub async fn respawn_player(&self, player: &Arc<Player>, alive: bool) {
+        let Some(world) = self.world_of(&player.gameprofile.id).await else {
+            return;
+        };
+        let respawn_world = world
+            .config
+            .respawn_dimension
+            .filter(|dimension| *dimension != world.dimension_type)
+            .and_then(|dimension| self.get(dimension));
+        match respawn_world {
+            Some(respawn_world) => {
+                self.transfer_player(player, respawn_world.dimension_type)
+                    .await;
+                respawn_world
+                    .respawn_player(player, world.dimension_type, alive, None)
+                    .await;
+            }
+            None => {
+                world
+                    .respawn_player(player, world.dimension_type, alive, None)
+                    .await;
+            }
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a WorldManager {
+    type Item = &'a Arc<World>;
+    type IntoIter = std::slice::Iter<'a, Arc<World>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use pumpkin_core::GameMode;
+    use pumpkin_registry::DimensionType;
+    use tokio::net::{TcpListener, TcpStream};
+    use uuid::Uuid;
+
+    use crate::{entity::player::Player, net::Client, world::World};
+
+    use super::{PlayerWorlds, WorldManager};
+
+    #[test]
+    fn transfer_between_two_worlds() {
+        let mut player_worlds = PlayerWorlds::default();
+        let player = Uuid::new_v4();
+
+        assert_eq!(player_worlds.enter(player, DimensionType::Overworld), None);
+        assert_eq!(
+            player_worlds.enter(player, DimensionType::TheNether),
+            Some(DimensionType::Overworld)
+        );
+        assert_eq!(player_worlds.get(&player), Some(DimensionType::TheNether));
+
+        assert_eq!(player_worlds.leave(&player), Some(DimensionType::TheNether));
+        assert_eq!(player_worlds.get(&player), None);
+    }
+
+    #[tokio::test]
+    async fn transfer_player_between_worlds() {
+        let overworld = Arc::new(World::test_world());
+        let mut nether = World::test_world();
+        nether.dimension_type = DimensionType::TheNether;
+        let nether = Arc::new(nether);
+        let manager = WorldManager::new(vec![overworld.clone(), nether.clone()]);
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let _connection = TcpStream::connect(listener.local_addr().unwrap())
+            .await
+            .unwrap();
+        let (stream, address) = listener.accept().await.unwrap();
+        let client = Arc::new(Client::new(stream, address, 0));
+        let player = Arc::new(Player::new(client, overworld.clone(), 1, GameMode::Survival).await);
+        let uuid = player.gameprofile.id;
+        manager.add_player(&overworld, player.clone()).await;
+
+        assert!(
+            manager
+                .transfer_player(&player, DimensionType::TheNether)
+                .await
+        );
+        assert!(Arc::ptr_eq(&player.world(), &nether));
+        assert!(manager
+            .world_of(&uuid)
+            .await
+            .is_some_and(|world| Arc::ptr_eq(&world, &nether)));
+        assert!(!overworld.current_players.lock().await.contains_key(&uuid));
+        assert!(nether.current_players.lock().await.contains_key(&uuid));
+        assert_eq!(manager.all_players().await.len(), 1);
+
+        // The End is not loaded, so the player stays
+        assert!(
+            !manager
+                .transfer_player(&player, DimensionType::TheEnd)
+                .await
+        );
+        assert!(Arc::ptr_eq(&player.world(), &nether));
+    }
+
+    #[tokio::test]
+    async fn no_portal_travel_on_cooldown() {
+        let overworld = Arc::new(World::test_world());
+        let mut nether = World::test_world();
+        nether.dimension_type = DimensionType::TheNether;
+        let manager = WorldManager::new(vec![overworld.clone(), Arc::new(nether)]);
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let _connection = TcpStream::connect(listener.local_addr().unwrap())
+            .await
+            .unwrap();
+        let (stream, address) = listener.accept().await.unwrap();
+        let client = Arc::new(Client::new(stream, address, 0));
+        let player = Arc::new(Player::new(client, overworld.clone(), 1, GameMode::Survival).await);
+        manager.add_player(&overworld, player.clone()).await;
+
+        player.start_portal_cooldown();
+        assert!(!manager.travel_through_portal(&player).await);
+        assert!(Arc::ptr_eq(&player.world(), &overworld));
+    }
+}
@@ -0,0 +1,140 @@
+use std::collections::{HashMap, HashSet};
+
+use pumpkin_config::BASIC_CONFIG;
+use pumpkin_core::math::vector2::Vector2;
+
+use super::World;
+
+/// Why a chunk needs to be ticked, a chunk stays active until none of them is left.
+///
+/// None of these systems exist yet, so no chunk is active for now
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum ChunkActivity {
+    /// Blocks waiting for a scheduled update, e.g. redstone repeaters
+    ScheduledUpdates = 1,
+    /// Block entities which do something every tick, e.g. furnaces and hoppers
+    TickingBlockEntities = 2,
+    /// Fluids which are still flowing
+    Fluids = 4,
+}
+
+/// The chunks with something going on, by the activities going on in them, see [`World::set_chunk_activity`]
+#[derive(Default)]
+pub struct ActiveChunks(HashMap<Vector2<i32>, u8>);
+
+impl ActiveChunks {
+    /// Returns true if the chunk joined or left the active chunks
+    fn set(&mut self, chunk: Vector2<i32>, activity: ChunkActivity, active: bool) -> bool {
+        let before = self.0.get(&chunk).copied().unwrap_or(0);
+        let mut after = before & !(activity as u8);
+        if active {
+            after |= activity as u8;
+        }
+        if after == 0 {
+            self.0.remove(&chunk);
+        } else {
+            self.0.insert(chunk, after);
+        }
+        (before == 0) != (after == 0)
+    }
+
+    fn is_active(&self, chunk: &Vector2<i32>, activity: ChunkActivity) -> bool {
+        self.0
+            .get(chunk)
+            .is_some_and(|activities| activities & activity as u8 != 0)
+    }
+}
+
+impl World {
+    /// Marks the chunk as having the activity going on or not anymore, so it gets ticked even without players nearby,
+    /// see [`World::ticked_chunks`]
+    // TODO: Call this from scheduled updates, ticking block entities and fluids once they exist
+    pub async fn set_chunk_activity(
+        &self,
+        chunk: Vector2<i32>,
+        activity: ChunkActivity,
+        active: bool,
+    ) {
+        if self.active_chunks.lock().await.set(chunk, activity, active) {
+            log::trace!(
+                "Chunk {} {} {} the active chunks",
+                chunk.x,
+                chunk.z,
+                if active { "joined" } else { "left" }
+            );
+        }
+    }
+
+    /// Whether the activity is going on in the chunk
+    pub async fn has_chunk_activity(&self, chunk: &Vector2<i32>, activity: ChunkActivity) -> bool {
+        self.active_chunks.lock().await.is_active(chunk, activity)
+    }
+
+    /// How many chunks have something going on, for diagnostics
+    pub async fn active_chunk_count(&self) -> usize {
+        self.active_chunks.lock().await.0.len()
+    }
+
+    /// The loaded chunks block ticking has to visit this tick: the chunks within the simulation distance of a player,
+    /// which get random ticks, and the active chunks. Chunks far away from players are skipped
+    pub async fn ticked_chunks(&self) -> Vec<Vector2<i32>> {
+        let mut chunks: HashSet<Vector2<i32>> =
+            self.active_chunks.lock().await.0.keys().copied().collect();
+        let distance = i32::from(BASIC_CONFIG.simulation_distance.get());
+        let occupied: Vec<Vector2<i32>> = self
+            .player_chunks
+            .lock()
+            .await
+            .occupied_chunks()
+            .map(|(chunk, _)| *chunk)
+            .collect();
+        for center in occupied {
+            for x in -distance..=distance {
+                for z in -distance..=distance {
+                    chunks.insert(Vector2::new(center.x + x, center.z + z));
+                }
+            }
+        }
+        chunks.retain(|chunk| self.level.is_chunk_loaded(chunk));
+        // Always visit the chunks in the same order, so a seeded world ticks the same way every time
+        let mut chunks: Vec<Vector2<i32>> = chunks.into_iter().collect();
+        chunks.sort_unstable_by_key(|chunk| (chunk.x, chunk.z));
+        chunks
+    }
+
+    /// Forgets the activity of unloaded chunks, nothing saves it so it would be stale once the chunk is loaded again
+    pub(super) async fn tick_active_chunks(&self) {
+        self.active_chunks
+            .lock()
+            .await
+            .0
+            .retain(|chunk, _| self.level.is_chunk_loaded(chunk));
+        // TODO: Run scheduled updates, fluids and block entities in `World::ticked_chunks` once we have them
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_core::math::vector2::Vector2;
+
+    use super::{ActiveChunks, ChunkActivity};
+
+    #[test]
+    fn chunks_join_and_leave() {
+        let mut chunks = ActiveChunks::default();
+        let chunk = Vector2::new(3, -2);
+        assert!(chunks.set(chunk, ChunkActivity::Fluids, true));
+        assert!(!chunks.set(chunk, ChunkActivity::ScheduledUpdates, true));
+        assert!(chunks.is_active(&chunk, ChunkActivity::Fluids));
+        assert!(!chunks.is_active(&chunk, ChunkActivity::TickingBlockEntities));
+
+        // Still has scheduled updates
+        assert!(!chunks.set(chunk, ChunkActivity::Fluids, false));
+        assert_eq!(chunks.0.len(), 1);
+        assert!(chunks.set(chunk, ChunkActivity::ScheduledUpdates, false));
+        assert!(chunks.0.is_empty());
+        // Going quiet twice changes nothing
+        assert!(!chunks.set(chunk, ChunkActivity::ScheduledUpdates, false));
+    }
+}
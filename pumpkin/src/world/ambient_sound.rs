@@ -0,0 +1,214 @@
+use std::{collections::HashMap, ops::RangeInclusive};
+
+use pumpkin_config::ADVANCED_CONFIG;
+use pumpkin_core::math::{position::WorldPosition, vector2::Vector2, vector3::Vector3};
+use pumpkin_macros::sound;
+use pumpkin_protocol::SoundCategory;
+use pumpkin_world::{
+    block::block_registry::{get_block, get_block_and_state_by_state_id, Block},
+    coordinates::ChunkRelativeBlockCoordinates,
+    is_in_world,
+};
+use rand::Rng;
+
+use super::World;
+
+/// How far away from players blocks are picked to play their ambient sound
+const SAMPLE_RADIUS: i32 = 16;
+
+/// A sound a block plays every now and then
+#[derive(Clone, Debug, PartialEq)]
+pub struct AmbientSound {
+    pub sound_id: u16,
+    /// The chance the sound plays when the block gets picked, between `0.0` and `1.0`
+    pub chance: f32,
+    pub volume: RangeInclusive<f32>,
+    pub pitch: RangeInclusive<f32>,
+    /// Only play the sound while the block emits light, e.g. while a campfire is lit
+    pub requires_light: bool,
+}
+
+/// The ambient sounds of block types, see [`World::set_ambient_sound`]
+pub struct AmbientSounds(HashMap<u16, AmbientSound>);
+
+impl Default for AmbientSounds {
+    /// The same sounds and chances as Vanilla
+    fn default() -> Self {
+        let mut sounds = HashMap::new();
+        let mut insert = |block: &str, sound: AmbientSound| {
+            if let Some(block) = get_block(block) {
+                sounds.insert(block.id, sound);
+            }
+        };
+        insert(
+            "nether_portal",
+            AmbientSound {
+                sound_id: sound!("block.portal.ambient"),
+                chance: 0.01,
+                volume: 0.5..=0.5,
+                pitch: 0.8..=1.2,
+                requires_light: false,
+            },
+        );
+        let crackle = AmbientSound {
+            sound_id: sound!("block.campfire.crackle"),
+            chance: 0.1,
+            volume: 0.5..=1.0,
+            pitch: 0.6..=1.3,
+            requires_light: true,
+        };
+        insert("campfire", crackle.clone());
+        insert("soul_campfire", crackle);
+        Self(sounds)
+    }
+}
+
+/// A random block around a player, with the random numbers to decide if and how its sound plays
+struct Sample {
+    position: WorldPosition,
+    roll: f32,
+    volume: f32,
+    pitch: f32,
+}
+
+impl Sample {
+    /// `None` if the picked block is outside of the world, e.g. for players close to the bottom
+    fn random(center: Vector3<i32>, rng: &mut impl Rng) -> Option<Self> {
+        let mut offset = || rng.gen_range(-SAMPLE_RADIUS..=SAMPLE_RADIUS);
+        let position = WorldPosition(center.add(&Vector3::new(offset(), offset(), offset())));
+        if !is_in_world(position.0.y) {
+            return None;
+        }
+        Some(Self {
+            position,
+            roll: rng.gen(),
+            volume: rng.gen(),
+            pitch: rng.gen(),
+        })
+    }
+}
+
+/// Picks a value in the range, `fraction` is between `0.0` and `1.0`
+fn lerp(range: &RangeInclusive<f32>, fraction: f32) -> f32 {
+    (range.end() - range.start()).mul_add(fraction, *range.start())
+}
+
+impl World {
+    /// Lets blocks of the type play the ambient sound, or stops them with `None`
+    pub async fn set_ambient_sound(&self, block: &Block, sound: Option<AmbientSound>) {
+        let mut sounds = self.ambient_sounds.write().await;
+        match sound {
+            Some(sound) => sounds.0.insert(block.id, sound),
+            None => sounds.0.remove(&block.id),
+        };
+    }
+
+    /// Picks random blocks in loaded chunks around every player and plays their ambient sound to the players close by.
+    /// This is only cosmetic, so chunks which are not loaded are skipped instead of being loaded
+    pub(super) async fn tick_ambient_sounds(&self) {
+        let config = &ADVANCED_CONFIG.ambient_sounds;
+        if !config.enabled || self.ambient_sounds.read().await.0.is_empty() {
+            return;
+        }
+        let centers: Vec<Vector3<i32>> = self
+            .current_players
+            .lock()
+            .await
+            .values()
+            .map(|player| player.living_entity.entity.block_pos.load().0)
+            .collect();
+
+        // Pick everything up front, so every chunk is only locked once
+        let mut samples: HashMap<Vector2<i32>, Vec<Sample>> = HashMap::new();
+        {
+            let mut rng = self.random.lock().await;
+            for center in centers {
+                for _ in 0..config.blocks_per_player {
+                    let Some(sample) = Sample::random(center, &mut *rng) else {
+                        continue;
+                    };
+                    let (chunk, _) = sample.position.chunk_and_chunk_relative_position();
+                    samples.entry(chunk).or_default().push(sample);
+                }
+            }
+        }
+
+        let mut heard = Vec::new();
+        for (chunk_pos, samples) in samples {
+            let Some(chunk) = self.level.get_loaded_chunk(&chunk_pos) else {
+                continue;
+            };
+            let chunk = chunk.read().await;
+            let sounds = self.ambient_sounds.read().await;
+            for sample in samples {
+                let (_, relative) = sample.position.chunk_and_chunk_relative_position();
+                let Some((block, state)) = chunk
+                    .blocks
+                    .get_block(ChunkRelativeBlockCoordinates::from(relative))
+                    .and_then(get_block_and_state_by_state_id)
+                else {
+                    continue;
+                };
+                let Some(sound) = sounds.0.get(&block.id) else {
+                    continue;
+                };
+                if sample.roll >= sound.chance || (sound.requires_light && state.luminance == 0) {
+                    continue;
+                }
+                heard.push((
+                    sound.sound_id,
+                    sample.position,
+                    lerp(&sound.volume, sample.volume),
+                    lerp(&sound.pitch, sample.pitch),
+                ));
+            }
+        }
+
+        for (sound_id, position, volume, pitch) in heard {
+            let center = Vector3::new(
+                f64::from(position.0.x) + 0.5,
+                f64::from(position.0.y) + 0.5,
+                f64::from(position.0.z) + 0.5,
+            );
+            self.play_sound_in_range(sound_id, SoundCategory::Blocks, &center, volume, pitch)
+                .await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_core::math::vector3::Vector3;
+    use pumpkin_world::{block::block_registry::get_block, is_in_world};
+
+    use super::{lerp, AmbientSounds, Sample};
+
+    #[test]
+    fn vanilla_sounds() {
+        let sounds = AmbientSounds::default();
+        let campfire = get_block("campfire").unwrap();
+        assert!(sounds.0[&campfire.id].requires_light);
+        assert!(sounds
+            .0
+            .contains_key(&get_block("nether_portal").unwrap().id));
+        assert!(!sounds.0.contains_key(&get_block("stone").unwrap().id));
+    }
+
+    #[test]
+    fn lerp_range() {
+        assert!((lerp(&(0.6..=1.3), 0.0) - 0.6).abs() < 1e-6);
+        assert!((lerp(&(0.6..=1.3), 1.0) - 1.3).abs() < 1e-6);
+    }
+
+    #[test]
+    fn samples_inside_the_world() {
+        let mut rng = rand::thread_rng();
+        for y in [-64, 319] {
+            for _ in 0..100 {
+                if let Some(sample) = Sample::random(Vector3::new(0, y, 0), &mut rng) {
+                    assert!(is_in_world(sample.position.0.y));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use std::{collections::HashSet, sync::LazyLock};
+
+use pumpkin_config::ADVANCED_CONFIG;
+use pumpkin_core::math::{position::WorldPosition, vector3::Vector3};
+use pumpkin_macros::block_state;
+use pumpkin_protocol::client::play::CBlockUpdate;
+use pumpkin_registry::DimensionType;
+use pumpkin_world::{block::block_registry::get_block, chunk::ChunkData, WORLD_LOWEST_Y};
+
+use super::World;
+
+/// Every state of the blocks to hide, see [`pumpkin_config::AntiXrayConfig::hidden_blocks`]
+static HIDDEN_STATES: LazyLock<HashSet<u16>> = LazyLock::new(|| {
+    let mut states = HashSet::new();
+    for name in &ADVANCED_CONFIG.anti_xray.hidden_blocks {
+        match get_block(name) {
+            Some(block) => states.extend(block.states.iter().map(|state| state.id)),
+            None => log::warn!("Anti x-ray can't hide the unknown block {name}"),
+        }
+    }
+    states
+});
+
+/// The block hidden blocks are sent as, the most common block around them
+fn filler(dimension: DimensionType, layer: usize) -> u16 {
+    match dimension {
+        DimensionType::TheNether => block_state!("netherrack").state_id,
+        DimensionType::TheEnd => block_state!("end_stone").state_id,
+        // Deepslate replaces stone below y 0
+        DimensionType::Overworld | DimensionType::OverworldCaves
+            if layer < WORLD_LOWEST_Y.unsigned_abs() as usize =>
+        {
+            block_state!("deepslate").state_id
+        }
+        DimensionType::Overworld | DimensionType::OverworldCaves => block_state!("stone").state_id,
+    }
+}
+
+/// The chunk as it is sent to players, with the blocks nobody can see replaced by the filler.
+/// `None` if nothing is hidden, then the chunk can be sent as it is
+pub(super) fn obfuscate(chunk: &ChunkData, dimension: DimensionType) -> Option<ChunkData> {
+    let blocks = chunk.blocks.obfuscated(
+        |state_id| HIDDEN_STATES.contains(&state_id),
+        |layer| filler(dimension, layer),
+    )?;
+    Some(ChunkData {
+        blocks,
+        position: chunk.position,
+    })
+}
+
+impl World {
+    /// The chunk as the players of this world get it, `None` if it can be sent as it is.
+    /// Every chunk sent to players has to go through this, otherwise they see the hidden blocks
+    pub(super) fn obfuscate_chunk(&self, chunk: &ChunkData) -> Option<ChunkData> {
+        if self.config.anti_xray {
+            obfuscate(chunk, self.dimension_type)
+        } else {
+            None
+        }
+    }
+
+    /// Sends the real state of the hidden blocks around the position, as players may see them now
+    pub(super) async fn reveal_hidden_neighbors(&self, position: WorldPosition) {
+        let offsets = [
+            Vector3::new(1, 0, 0),
+            Vector3::new(-1, 0, 0),
+            Vector3::new(0, 1, 0),
+            Vector3::new(0, -1, 0),
+            Vector3::new(0, 0, 1),
+            Vector3::new(0, 0, -1),
+        ];
+        for offset in offsets {
+            let neighbor = WorldPosition(position.0.add(&offset));
+            let Ok(state_id) = self.get_block_state_id(neighbor).await else {
+                continue;
+            };
+            if HIDDEN_STATES.contains(&state_id) {
+                let (chunk, _) = neighbor.chunk_and_chunk_relative_position();
+                self.broadcast_to_chunk_watchers(
+                    chunk,
+                    &CBlockUpdate::new(&neighbor, i32::from(state_id).into()),
+                )
+                .await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_macros::block_state;
+    use pumpkin_registry::DimensionType;
+
+    use super::filler;
+
+    #[test]
+    fn fillers() {
+        assert_eq!(
+            filler(DimensionType::Overworld, 0),
+            block_state!("deepslate").state_id
+        );
+        assert_eq!(
+            filler(DimensionType::Overworld, 64),
+            block_state!("stone").state_id
+        );
+        assert_eq!(
+            filler(DimensionType::TheNether, 0),
+            block_state!("netherrack").state_id
+        );
+    }
+}
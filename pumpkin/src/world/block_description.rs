@@ -0,0 +1,39 @@
+use pumpkin_core::math::position::WorldPosition;
+use pumpkin_world::block::block_registry::get_state_index;
+
+use super::World;
+
+/// Everything about a block in the world, see [`World::describe_block`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockDescription {
+    /// e.g. `minecraft:oak_stairs`
+    pub name: String,
+    pub state_id: u16,
+    /// The value of every property of the state, e.g. `("facing", "north")`
+    pub properties: Vec<(String, String)>,
+    pub has_block_entity: bool,
+}
+
+impl World {
+    /// Describes the block at the position, for debugging and commands like `/data get block`.
+    ///
+    /// Returns `None` if the position is outside of the world or its chunk is not loaded
+    pub async fn describe_block(&self, position: WorldPosition) -> Option<BlockDescription> {
+        let (chunk, _) = position.chunk_and_chunk_relative_position();
+        if !self.level.is_chunk_loaded(&chunk) {
+            return None;
+        }
+        let (block, state) = self.get_block_and_block_state(position).await.ok()?;
+        let state_index = get_state_index(state.id)?;
+        Some(BlockDescription {
+            name: format!("minecraft:{}", block.name),
+            state_id: state.id,
+            properties: block
+                .state_properties(state_index as usize)
+                .into_iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+            has_block_entity: state.block_entity_type.is_some(),
+        })
+    }
+}
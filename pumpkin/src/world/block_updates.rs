@@ -0,0 +1,134 @@
+use std::collections::HashMap;
+
+use pumpkin_config::ADVANCED_CONFIG;
+use pumpkin_core::math::{position::WorldPosition, vector2::Vector2};
+use pumpkin_protocol::client::play::CBlockUpdate;
+
+use super::World;
+
+/// How many blocks changed in every chunk this tick, see [`World::send_block_update`]
+#[derive(Default)]
+pub struct BlockUpdates(HashMap<Vector2<i32>, u32>);
+
+impl BlockUpdates {
+    /// Counts the change, returns false once the chunk changed too much to send the block on its own.
+    /// `max` 0 disables the limit
+    fn count(&mut self, chunk: Vector2<i32>, max: u32) -> bool {
+        let changes = self.0.entry(chunk).or_default();
+        *changes += 1;
+        max == 0 || *changes <= max
+    }
+
+    /// The chunks which changed too much this tick, and starts counting again
+    fn take_overflowing(&mut self, max: u32) -> Vec<Vector2<i32>> {
+        self.0
+            .drain()
+            .filter(|(_, changes)| max != 0 && *changes > max)
+            .map(|(chunk, _)| chunk)
+            .collect()
+    }
+}
+
+impl World {
+    /// Sends the changed block to the players watching its chunk. Once more blocks of the chunk change in one tick
+    /// than [`pumpkin_config::ChunkGenerationConfig::max_block_updates_per_tick`], the whole chunk gets sent again at
+    /// the end of the tick instead, e.g. when a feature populates a chunk which was already sent
+    pub(super) async fn send_block_update(
+        &self,
+        chunk: Vector2<i32>,
+        position: &WorldPosition,
+        block_state_id: u16,
+    ) {
+        let max = ADVANCED_CONFIG.chunk_generation.max_block_updates_per_tick;
+        if !self.block_updates.lock().await.count(chunk, max) {
+            return;
+        }
+        self.broadcast_to_chunk_watchers(
+            chunk,
+            &CBlockUpdate::new(position, i32::from(block_state_id).into()),
+        )
+        .await;
+    }
+
+    /// Sends the chunks which changed too much this tick again
+    pub(super) async fn tick_block_updates(&self) {
+        let max = ADVANCED_CONFIG.chunk_generation.max_block_updates_per_tick;
+        let chunks = self.block_updates.lock().await.take_overflowing(max);
+        for chunk in chunks {
+            log::debug!(
+                "Resending chunk {} {} after many block changes",
+                chunk.x,
+                chunk.z
+            );
+            self.resend_chunk(chunk).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_config::ADVANCED_CONFIG;
+    use pumpkin_core::math::{position::WorldPosition, vector2::Vector2, vector3::Vector3};
+    use pumpkin_macros::block_state;
+    use pumpkin_world::coordinates::ChunkRelativeBlockCoordinates;
+
+    use crate::world::{world_config::WorldConfig, World};
+
+    use super::BlockUpdates;
+
+    #[test]
+    fn resend_after_too_many_changes() {
+        let mut updates = BlockUpdates::default();
+        let (busy, quiet) = (Vector2::new(0, 0), Vector2::new(1, 0));
+        assert!(updates.count(busy, 2));
+        assert!(updates.count(busy, 2));
+        assert!(!updates.count(busy, 2));
+        assert!(updates.count(quiet, 2));
+
+        assert_eq!(updates.take_overflowing(2), vec![busy]);
+        // Every tick starts over
+        assert!(updates.take_overflowing(2).is_empty());
+        assert!(updates.count(busy, 2));
+    }
+
+    #[tokio::test]
+    async fn resent_chunks_stay_obfuscated() {
+        let world = World::test_world();
+        let config = WorldConfig {
+            anti_xray: true,
+            ..world.config
+        };
+        let world = world.with_config(config);
+        let stone = block_state!("stone").state_id;
+        // More changes than get sent on their own, with an ore inside the stone
+        for x in 6..=10 {
+            for y in 99..=101 {
+                for z in 6..=10 {
+                    world
+                        .set_block_state(WorldPosition(Vector3::new(x, y, z)), stone)
+                        .await;
+                }
+            }
+        }
+        let ore = WorldPosition(Vector3::new(8, 100, 8));
+        world
+            .set_block_state(ore, block_state!("diamond_ore").state_id)
+            .await;
+        let max = ADVANCED_CONFIG.chunk_generation.max_block_updates_per_tick;
+        assert_eq!(
+            world.block_updates.lock().await.take_overflowing(max),
+            vec![Vector2::new(0, 0)]
+        );
+
+        // The chunk gets resent the way players get every chunk, without the ore
+        let chunk = world.receive_chunk(Vector2::new(0, 0)).await.unwrap();
+        let chunk = chunk.read().await;
+        let sent = world.obfuscate_chunk(&chunk).expect("The ore is hidden");
+        let (_, relative) = ore.chunk_and_chunk_relative_position();
+        assert_eq!(
+            sent.blocks
+                .get_block(ChunkRelativeBlockCoordinates::from(relative)),
+            Some(stone)
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use crate::entity::player::Player;
+use crate::world::World;
 use pumpkin_core::text::TextComponent;
 use pumpkin_protocol::client::play::{BosseventAction, CBossEvent};
 use uuid::Uuid;
@@ -116,3 +117,73 @@ impl Player {
         self.client.send_packet(&packet).await;
     }
 }
+
+/// Extension of the world to show a single boss bar to all of its players, e.g. for event timers
+impl World {
+    /// Shows the boss bar to all players in the world, replacing the currently shown one
+    pub async fn show_custom_bossbar(&self, bossbar: Bossbar) {
+        let mut current = self.custom_bossbar.lock().await;
+        if let Some(old) = current.take() {
+            self.broadcast_packet_all(&CBossEvent::new(&old.uuid, BosseventAction::Remove))
+                .await;
+        }
+        let boss_action = BosseventAction::Add {
+            title: bossbar.title.clone(),
+            health: bossbar.health,
+            color: (bossbar.color.clone() as u8).into(),
+            division: (bossbar.division.clone() as u8).into(),
+            flags: bossbar.flags.clone() as u8,
+        };
+        self.broadcast_packet_all(&CBossEvent::new(&bossbar.uuid, boss_action))
+            .await;
+        *current = Some(bossbar);
+    }
+
+    /// Updates the shown boss bar, only the parts which changed are sent to the players.
+    /// Does nothing if no boss bar is shown
+    pub async fn update_custom_bossbar(
+        &self,
+        progress: f32,
+        title: TextComponent,
+        color: BossbarColor,
+    ) {
+        let mut current = self.custom_bossbar.lock().await;
+        let Some(bossbar) = current.as_mut() else {
+            return;
+        };
+        let progress = progress.clamp(0.0, 1.0);
+        if (bossbar.health - progress).abs() > f32::EPSILON {
+            bossbar.health = progress;
+            self.broadcast_packet_all(&CBossEvent::new(
+                &bossbar.uuid,
+                BosseventAction::UpdateHealth(progress),
+            ))
+            .await;
+        }
+        if bossbar.title != title {
+            bossbar.title = title.clone();
+            self.broadcast_packet_all(&CBossEvent::new(
+                &bossbar.uuid,
+                BosseventAction::UpdateTile(title),
+            ))
+            .await;
+        }
+        if bossbar.color != color {
+            bossbar.color = color;
+            let boss_action = BosseventAction::UpdateStyle {
+                color: (bossbar.color.clone() as u8).into(),
+                dividers: (bossbar.division.clone() as u8).into(),
+            };
+            self.broadcast_packet_all(&CBossEvent::new(&bossbar.uuid, boss_action))
+                .await;
+        }
+    }
+
+    /// Removes the shown boss bar from all players
+    pub async fn hide_custom_bossbar(&self) {
+        if let Some(bossbar) = self.custom_bossbar.lock().await.take() {
+            self.broadcast_packet_all(&CBossEvent::new(&bossbar.uuid, BosseventAction::Remove))
+                .await;
+        }
+    }
+}
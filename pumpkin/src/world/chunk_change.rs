@@ -0,0 +1,37 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_core::math::vector2::Vector2;
+
+use crate::entity::player::Player;
+
+use super::World;
+
+#[async_trait]
+pub trait PlayerChunkChangeHandler: Send + Sync {
+    /// Called once every time a player crosses into another chunk, moving within a chunk does not call it
+    async fn on_chunk_change(
+        &self,
+        player: &Arc<Player>,
+        old_chunk: Vector2<i32>,
+        new_chunk: Vector2<i32>,
+    );
+}
+
+impl World {
+    /// Registers a handler which gets called whenever a player in this world crosses into another chunk
+    pub async fn on_player_chunk_change(&self, handler: Arc<dyn PlayerChunkChangeHandler>) {
+        self.chunk_change_handlers.write().await.push(handler);
+    }
+
+    pub(super) async fn player_chunk_changed(
+        &self,
+        player: &Arc<Player>,
+        old_chunk: Vector2<i32>,
+        new_chunk: Vector2<i32>,
+    ) {
+        for handler in self.chunk_change_handlers.read().await.iter() {
+            handler.on_chunk_change(player, old_chunk, new_chunk).await;
+        }
+    }
+}
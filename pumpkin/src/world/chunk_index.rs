@@ -0,0 +1,83 @@
+use std::collections::{HashMap, HashSet};
+
+use pumpkin_core::math::vector2::Vector2;
+use uuid::Uuid;
+
+/// Keeps track of which players are in which chunk, so we don't have to scan all players to find the ones in a chunk
+#[derive(Default)]
+pub struct PlayerChunkIndex {
+    chunks: HashMap<Vector2<i32>, HashSet<Uuid>>,
+    positions: HashMap<Uuid, Vector2<i32>>,
+}
+
+impl PlayerChunkIndex {
+    /// Moves the player into the chunk, adding them if they are not indexed yet.
+    /// Returns the chunk they left, `None` if they stayed in the same chunk or were not indexed yet
+    pub fn update(&mut self, uuid: Uuid, chunk_pos: Vector2<i32>) -> Option<Vector2<i32>> {
+        let old_chunk = self.positions.insert(uuid, chunk_pos);
+        if let Some(old_chunk) = old_chunk {
+            if old_chunk == chunk_pos {
+                return None;
+            }
+            self.remove_from_chunk(uuid, old_chunk);
+        }
+        self.chunks.entry(chunk_pos).or_default().insert(uuid);
+        old_chunk
+    }
+
+    pub fn remove(&mut self, uuid: &Uuid) {
+        if let Some(chunk_pos) = self.positions.remove(uuid) {
+            self.remove_from_chunk(*uuid, chunk_pos);
+        }
+    }
+
+    /// The players in the chunk
+    pub fn get(&self, chunk_pos: &Vector2<i32>) -> impl Iterator<Item = &Uuid> {
+        self.chunks.get(chunk_pos).into_iter().flatten()
+    }
+
+    /// All chunks with at least one player in them
+    pub fn occupied_chunks(&self) -> impl Iterator<Item = (&Vector2<i32>, &HashSet<Uuid>)> {
+        self.chunks.iter()
+    }
+
+    fn remove_from_chunk(&mut self, uuid: Uuid, chunk_pos: Vector2<i32>) {
+        if let Some(players) = self.chunks.get_mut(&chunk_pos) {
+            players.remove(&uuid);
+            // Don't keep empty chunks around
+            if players.is_empty() {
+                self.chunks.remove(&chunk_pos);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_core::math::vector2::Vector2;
+    use uuid::Uuid;
+
+    use super::PlayerChunkIndex;
+
+    #[test]
+    fn membership_after_movement() {
+        let mut index = PlayerChunkIndex::default();
+        let player = Uuid::new_v4();
+        let other = Uuid::new_v4();
+        let start = Vector2::new(0, 0);
+        let end = Vector2::new(1, -1);
+
+        assert_eq!(index.update(player, start), None);
+        index.update(other, start);
+        assert_eq!(index.get(&start).count(), 2);
+
+        assert_eq!(index.update(player, start), None);
+        assert_eq!(index.update(player, end), Some(start));
+        assert_eq!(index.get(&start).collect::<Vec<_>>(), vec![&other]);
+        assert_eq!(index.get(&end).collect::<Vec<_>>(), vec![&player]);
+
+        index.remove(&other);
+        assert_eq!(index.get(&start).count(), 0);
+        assert_eq!(index.occupied_chunks().count(), 1);
+    }
+}
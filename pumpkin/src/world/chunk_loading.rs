@@ -0,0 +1,149 @@
+use std::collections::{HashMap, HashSet};
+
+use pumpkin_config::ADVANCED_CONFIG;
+use pumpkin_core::math::vector2::Vector2;
+use pumpkin_entity::EntityId;
+
+use super::World;
+
+/// The entities keeping the chunk they are in loaded, with that chunk, see [`World::set_entity_chunk_loading`]
+#[derive(Default)]
+pub struct ChunkLoadingEntities(HashMap<EntityId, Vector2<i32>>);
+
+impl ChunkLoadingEntities {
+    /// Returns `false` if there are already `max` other entities loading chunks
+    fn start(&mut self, entity_id: EntityId, chunk: Vector2<i32>, max: usize) -> bool {
+        if !self.0.contains_key(&entity_id) && self.0.len() >= max {
+            return false;
+        }
+        self.0.entry(entity_id).or_insert(chunk);
+        true
+    }
+
+    /// Returns the chunk the entity kept loaded
+    fn stop(&mut self, entity_id: EntityId) -> Option<Vector2<i32>> {
+        self.0.remove(&entity_id)
+    }
+
+    /// Returns the chunk the entity kept loaded before, if the entity moved into another chunk
+    fn moved(&mut self, entity_id: EntityId, chunk: Vector2<i32>) -> Option<Vector2<i32>> {
+        let current = self.0.get_mut(&entity_id)?;
+        if *current == chunk {
+            return None;
+        }
+        Some(std::mem::replace(current, chunk))
+    }
+}
+
+impl World {
+    /// Keeps the chunk the entity is in loaded, following the entity as it moves, like a Vanilla chunk loader.
+    /// Meant for mobs which have to keep going without players around, e.g. a patrolling boss.
+    ///
+    /// Returns `false` if the entity is not in this world, or if enabling it would exceed the configured limit of chunk
+    /// loading entities, since every one of them keeps a chunk in memory
+    pub async fn set_entity_chunk_loading(&self, entity_id: EntityId, enabled: bool) -> bool {
+        if !enabled {
+            let chunk = self.chunk_loading_entities.lock().await.stop(entity_id);
+            if let Some(chunk) = chunk {
+                self.stop_loading_chunk(chunk);
+            }
+            return true;
+        }
+        let Some(chunk) = self.entity_chunk(entity_id).await else {
+            return false;
+        };
+        let max = ADVANCED_CONFIG.chunk_cache.max_chunk_loading_entities;
+        {
+            let mut entities = self.chunk_loading_entities.lock().await;
+            if entities.0.contains_key(&entity_id) {
+                return true;
+            }
+            if !entities.start(entity_id, chunk, max) {
+                return false;
+            }
+        }
+        self.start_loading_chunk(chunk);
+        true
+    }
+
+    /// The entities keeping their chunk loaded, they keep ticking no matter how far away players are
+    pub(super) async fn chunk_loading_entity_ids(&self) -> HashSet<EntityId> {
+        self.chunk_loading_entities
+            .lock()
+            .await
+            .0
+            .keys()
+            .copied()
+            .collect()
+    }
+
+    async fn entity_chunk(&self, entity_id: EntityId) -> Option<Vector2<i32>> {
+        if let Some(player) = self.get_player_by_entityid(entity_id).await {
+            return Some(player.living_entity.entity.chunk_pos.load());
+        }
+        self.get_living_entity_by_entityid(entity_id)
+            .await
+            .map(|living_entity| living_entity.entity.chunk_pos.load())
+    }
+
+    fn start_loading_chunk(&self, chunk: Vector2<i32>) {
+        self.level.mark_chunk_as_newly_watched(chunk);
+        let mut receiver = self.receive_chunks(vec![chunk]);
+        tokio::spawn(async move { while receiver.recv().await.is_some() {} });
+    }
+
+    fn stop_loading_chunk(&self, chunk: Vector2<i32>) {
+        if self.level.mark_chunk_as_not_watched(chunk) {
+            self.level.schedule_unload(&[chunk]);
+        }
+    }
+
+    /// Moves the loaded chunk along with the entities, and stops loading chunks for entities which are gone
+    pub(super) async fn tick_chunk_loading_entities(&self) {
+        for entity_id in self.chunk_loading_entity_ids().await {
+            let Some(chunk) = self.entity_chunk(entity_id).await else {
+                let chunk = self.chunk_loading_entities.lock().await.stop(entity_id);
+                if let Some(chunk) = chunk {
+                    self.stop_loading_chunk(chunk);
+                }
+                continue;
+            };
+            let previous = self
+                .chunk_loading_entities
+                .lock()
+                .await
+                .moved(entity_id, chunk);
+            if let Some(previous) = previous {
+                // Load the new chunk first, so chunks shared with others never drop to no watchers in between
+                self.start_loading_chunk(chunk);
+                self.stop_loading_chunk(previous);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_core::math::vector2::Vector2;
+
+    use super::ChunkLoadingEntities;
+
+    #[test]
+    fn limit_and_movement() {
+        let mut entities = ChunkLoadingEntities::default();
+        assert!(entities.start(1, Vector2::new(0, 0), 1));
+        // Already loading, doesn't count twice
+        assert!(entities.start(1, Vector2::new(0, 0), 1));
+        assert!(!entities.start(2, Vector2::new(5, 5), 1));
+
+        assert_eq!(entities.moved(1, Vector2::new(0, 0)), None);
+        assert_eq!(
+            entities.moved(1, Vector2::new(1, 0)),
+            Some(Vector2::new(0, 0))
+        );
+        assert_eq!(entities.moved(2, Vector2::new(1, 0)), None);
+
+        assert_eq!(entities.stop(1), Some(Vector2::new(1, 0)));
+        assert!(entities.start(2, Vector2::new(5, 5), 1));
+    }
+}
@@ -0,0 +1,101 @@
+use std::time::{Duration, Instant};
+
+use crossbeam::atomic::AtomicCell;
+
+/// Where the world gets the current time from, see [`super::World::with_clock`].
+///
+/// The game itself counts ticks, the clock decides how long ticks seem to take and times what
+/// happens in real time, like moving world borders and removing dead mobs
+pub trait Clock: Send + Sync {
+    fn now(&self) -> Instant;
+}
+
+/// The real time
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// A clock which only moves when told to, so tests don't depend on how fast they run
+pub struct MockClock {
+    now: AtomicCell<Instant>,
+}
+
+impl MockClock {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            now: AtomicCell::new(Instant::now()),
+        }
+    }
+
+    pub fn advance(&self, duration: Duration) {
+        self.now.store(self.now.load() + duration);
+    }
+}
+
+impl Default for MockClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> Instant {
+        self.now.load()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{sync::Arc, time::Duration};
+
+    use pumpkin_core::math::vector3::Vector3;
+    use pumpkin_entity::entity_type::EntityType;
+
+    use crate::{server::Server, world::World};
+
+    use super::MockClock;
+
+    #[tokio::test]
+    async fn ticks_without_real_time() {
+        let server = Server::test_server();
+        let world = World::test_world()
+            .with_clock(Arc::new(MockClock::new()))
+            .with_random_seed(42);
+        for _ in 0..20 {
+            world.tick_once(&server).await;
+        }
+        assert_eq!(world.level_time.lock().await.world_age, 20);
+        // The clock didn't move, so the ticks took no time
+        assert_eq!(
+            world
+                .last_tick_timings()
+                .await
+                .map(|timings| timings.total()),
+            Some(Duration::ZERO)
+        );
+    }
+
+    #[tokio::test]
+    async fn dead_mobs_removed() {
+        let server = Server::test_server();
+        let clock = Arc::new(MockClock::new());
+        let world = Arc::new(World::test_world().with_clock(clock.clone()));
+        let (mob, uuid) = server
+            .add_mob_entity(EntityType::Zombie, Vector3::new(0.0, 100.0, 0.0), &world)
+            .await;
+
+        world.remove_mob_entity(mob.living_entity.clone()).await;
+        // Dead mobs are not ticked anymore, only their death animation is left
+        assert!(!world.current_living_mobs.lock().await.contains_key(&uuid));
+        assert_eq!(world.dying_mobs.lock().await.len(), 1);
+
+        clock.advance(Duration::from_secs(2));
+        world.tick_once(&server).await;
+        assert!(world.dying_mobs.lock().await.is_empty());
+    }
+}
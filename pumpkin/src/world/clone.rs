@@ -0,0 +1,119 @@
+use std::collections::HashMap;
+
+use pumpkin_core::math::{position::WorldPosition, vector3::Vector3};
+use pumpkin_macros::block_state;
+use pumpkin_world::block::block_registry::state_is_air;
+
+use super::World;
+
+/// The lowest and highest block y of the world
+const WORLD_Y_BOUNDS: (i32, i32) = (-64, 319);
+
+/// Which blocks of the source region get copied
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MaskMode {
+    /// Copy every block
+    Replace,
+    /// Don't copy air, so the destination keeps its blocks there
+    Masked,
+}
+
+/// What happens with the source region
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CloneFlag {
+    /// Keep the source region as it is
+    Normal,
+    /// Replace the source region with air, which is overwritten by the destination where both overlap
+    Move,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CloneMode {
+    pub mask: MaskMode,
+    pub flag: CloneFlag,
+}
+
+impl World {
+    /// Copies all blocks between the two corners of `from`, so the lowest corner ends up at `dest`, like the `/clone` command.
+    ///
+    /// The whole source region is read before anything gets written, so source and destination may overlap.
+    /// Blocks outside of the world's height are skipped. Returns how many blocks were placed at the destination
+    pub async fn clone_region(
+        &self,
+        from: (WorldPosition, WorldPosition),
+        dest: WorldPosition,
+        mode: CloneMode,
+    ) -> usize {
+        let (min_y, max_y) = WORLD_Y_BOUNDS;
+        let (a, b) = (from.0 .0, from.1 .0);
+        let min = Vector3::new(a.x.min(b.x), a.y.min(b.y).max(min_y), a.z.min(b.z));
+        let max = Vector3::new(a.x.max(b.x), a.y.max(b.y).min(max_y), a.z.max(b.z));
+        // The unclamped lowest corner ends up at the destination
+        let offset = dest.0.sub(&Vector3::new(min.x, a.y.min(b.y), min.z));
+
+        let air = block_state!("air").state_id;
+        let mut source = Vec::new();
+        let mut changes = HashMap::new();
+        for x in min.x..=max.x {
+            for y in min.y..=max.y {
+                for z in min.z..=max.z {
+                    let position = WorldPosition(Vector3::new(x, y, z));
+                    let Ok(state_id) = self.get_block_state_id(position).await else {
+                        continue;
+                    };
+                    source.push((position, state_id));
+                    if mode.flag == CloneFlag::Move {
+                        changes.insert(position, air);
+                    }
+                }
+            }
+        }
+
+        // TODO: Copy block entities once we have them
+        let mut affected = 0;
+        for (position, state_id) in source {
+            let is_air = state_is_air(state_id);
+            if mode.mask == MaskMode::Masked && is_air {
+                continue;
+            }
+            let destination = position.0.add(&offset);
+            if destination.y < min_y || destination.y > max_y {
+                continue;
+            }
+            changes.insert(WorldPosition(destination), state_id);
+            affected += 1;
+        }
+
+        self.set_block_states(&changes).await;
+        affected
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_core::math::{position::WorldPosition, vector3::Vector3};
+    use pumpkin_macros::block_state;
+
+    use super::{CloneFlag, CloneMode, MaskMode};
+    use crate::world::World;
+
+    #[tokio::test]
+    async fn move_block_into_other_chunk() {
+        let world = World::test_world();
+        let stone = block_state!("stone").state_id;
+        let from = WorldPosition(Vector3::new(0, 0, 0));
+        let dest = WorldPosition(Vector3::new(20, 0, 20));
+        world.set_block_state(from, stone).await;
+
+        let mode = CloneMode {
+            mask: MaskMode::Replace,
+            flag: CloneFlag::Move,
+        };
+        assert_eq!(world.clone_region((from, from), dest, mode).await, 1);
+        assert_eq!(world.get_block_state_id(dest).await.unwrap(), stone);
+        assert_eq!(
+            world.get_block_state_id(from).await.unwrap(),
+            block_state!("air").state_id
+        );
+    }
+}
@@ -0,0 +1,82 @@
+use std::collections::HashMap;
+
+use pumpkin_core::math::{position::WorldPosition, vector3::Vector3};
+use pumpkin_entity::entity_type::EntityType;
+
+use super::World;
+
+/// Whether the position is in one of the blocks between the two corners, the corners included
+fn region_contains(min: Vector3<i32>, max: Vector3<i32>, position: Vector3<f64>) -> bool {
+    let block = Vector3::new(
+        position.x.floor() as i32,
+        position.y.floor() as i32,
+        position.z.floor() as i32,
+    );
+    (min.x..=max.x).contains(&block.x)
+        && (min.y..=max.y).contains(&block.y)
+        && (min.z..=max.z).contains(&block.z)
+}
+
+impl World {
+    /// Counts the players, mobs, experience orbs and projectiles in the blocks between the two corners by their type,
+    /// e.g. to enforce mob caps or to find out which entities cause lag
+    pub async fn count_entities(
+        &self,
+        from: WorldPosition,
+        to: WorldPosition,
+    ) -> HashMap<EntityType, usize> {
+        let (a, b) = (from.0, to.0);
+        let min = Vector3::new(a.x.min(b.x), a.y.min(b.y), a.z.min(b.z));
+        let max = Vector3::new(a.x.max(b.x), a.y.max(b.y), a.z.max(b.z));
+        self.count_entities_matching(|position| region_contains(min, max, position))
+            .await
+    }
+
+    /// Counts every entity of the world by their type, see [`World::count_entities`]
+    pub async fn count_all_entities(&self) -> HashMap<EntityType, usize> {
+        self.count_entities_matching(|_| true).await
+    }
+
+    async fn count_entities_matching(
+        &self,
+        matches: impl Fn(Vector3<f64>) -> bool,
+    ) -> HashMap<EntityType, usize> {
+        let mut counts = HashMap::new();
+        let mut count = |entity_type: EntityType, position: Vector3<f64>| {
+            if matches(position) {
+                *counts.entry(entity_type).or_insert(0) += 1;
+            }
+        };
+        for player in self.current_players.lock().await.values() {
+            count(EntityType::Player, player.living_entity.entity.pos.load());
+        }
+        for mob in self.current_living_mobs.lock().await.values() {
+            let entity = &mob.living_entity.entity;
+            count(entity.entity_type.clone(), entity.pos.load());
+        }
+        for position in self.experience_orb_positions().await {
+            count(EntityType::ExperienceOrb, position);
+        }
+        for (entity_type, position) in self.projectile_positions().await {
+            count(entity_type, position);
+        }
+        counts
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_core::math::vector3::Vector3;
+
+    use super::region_contains;
+
+    #[test]
+    fn positions_in_region() {
+        let (min, max) = (Vector3::new(-2, 0, 0), Vector3::new(3, 10, 3));
+        assert!(region_contains(min, max, Vector3::new(-1.5, 0.0, 0.2)));
+        // Anywhere in the highest block still counts
+        assert!(region_contains(min, max, Vector3::new(3.9, 10.5, 3.99)));
+        assert!(!region_contains(min, max, Vector3::new(-2.1, 5.0, 1.0)));
+        assert!(!region_contains(min, max, Vector3::new(0.0, 11.0, 1.0)));
+    }
+}
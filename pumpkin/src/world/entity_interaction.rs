@@ -0,0 +1,118 @@
+use std::sync::Arc;
+
+use pumpkin_core::text::TextComponent;
+use pumpkin_entity::EntityId;
+
+use crate::{
+    block::block_manager::BlockActionResult,
+    entity::{
+        interaction::{EntityInteraction, InteractionTarget},
+        player::Player,
+    },
+    log_context::{player_debug, player_error, player_warn},
+    server::Server,
+};
+
+use super::World;
+
+impl World {
+    /// Handles a player clicking on an entity.
+    ///
+    /// The registered handler of the entity type is called first, if it does not consume the interaction the default behaviour runs
+    pub async fn interact_entity(
+        self: &Arc<Self>,
+        player: &Player,
+        entity_id: EntityId,
+        interaction: EntityInteraction,
+        server: &Server,
+    ) {
+        let target = if let Some(target) = self.get_player_by_entityid(entity_id).await {
+            InteractionTarget::Player(target)
+        } else if let Some(target) = self.get_living_entity_by_entityid(entity_id).await {
+            InteractionTarget::Living(target)
+        } else {
+            // TODO: block entities should be checked here (signs)
+            player_error!(
+                player,
+                "Interacted with entity id {} which was not found",
+                entity_id
+            );
+            player
+                .kick(TextComponent::text("Interacted with invalid entity id"))
+                .await;
+            return;
+        };
+
+        if entity_id == player.entity_id() {
+            // this can't be triggered from a non-modded client.
+            player
+                .kick(TextComponent::text("You can't interact with yourself"))
+                .await;
+            return;
+        }
+
+        if !player.can_interact_with_entity(target.entity(), 0.0) {
+            player_warn!(
+                player,
+                "Tried to interact with entity {} out of reach",
+                entity_id
+            );
+            return;
+        }
+
+        if let BlockActionResult::Consume = server
+            .entity_interaction_manager
+            .on_interact(player, &target, interaction, server)
+            .await
+        {
+            return;
+        }
+
+        match interaction {
+            EntityInteraction::Attack => self.attack_entity(player, &target, server).await,
+            EntityInteraction::Interact(_) | EntityInteraction::InteractAt(..) => {
+                if let InteractionTarget::Living(living) = &target {
+                    if self.use_lead(player, living.entity_id()).await {
+                        return;
+                    }
+                }
+                // Mounting is left out, there are no vehicles or passengers to mount yet.
+                // Entity types which do something on a right click register a handler instead
+                player_debug!(player, "Interacted with entity {}", entity_id);
+            }
+        }
+    }
+
+    /// The default behaviour when a player attacks an entity
+    async fn attack_entity(
+        self: &Arc<Self>,
+        player: &Player,
+        target: &InteractionTarget,
+        server: &Server,
+    ) {
+        if !self.config.pvp {
+            return;
+        }
+        match target {
+            InteractionTarget::Player(victim) => {
+                if victim.living_entity.health.load() <= 0.0 {
+                    // you can trigger this from a non-modded / innocent client client,
+                    // so we shouldn't kick the player
+                    return;
+                }
+                if !self.allows_pvp(player, victim).await {
+                    return;
+                }
+                player.attack(victim).await;
+            }
+            InteractionTarget::Living(victim) => {
+                if victim.health.load() <= 0.0 {
+                    return;
+                }
+                victim.kill().await;
+                self.drop_mob_experience(server, victim).await;
+                self.remove_mob_entity(victim.clone()).await;
+            }
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use std::collections::HashMap;
+
+use pumpkin_config::ADVANCED_CONFIG;
+use pumpkin_core::text::{color::NamedColor, TextComponent};
+use pumpkin_entity::EntityId;
+use uuid::Uuid;
+
+use crate::entity::player::Player;
+
+use super::World;
+
+/// The player who spawned each entity and how many entities every player spawned, see [`World::may_spawn_entity_for`]
+#[derive(Default)]
+pub struct PlayerEntities {
+    owners: HashMap<EntityId, Uuid>,
+    counts: HashMap<Uuid, u32>,
+}
+
+impl PlayerEntities {
+    fn count(&self, owner: &Uuid) -> u32 {
+        self.counts.get(owner).copied().unwrap_or(0)
+    }
+
+    fn attribute(&mut self, owner: Uuid, entity_id: EntityId) {
+        if let Some(previous) = self.owners.insert(entity_id, owner) {
+            self.decrement(&previous);
+        }
+        *self.counts.entry(owner).or_default() += 1;
+    }
+
+    fn forget(&mut self, entity_id: EntityId) {
+        if let Some(owner) = self.owners.remove(&entity_id) {
+            self.decrement(&owner);
+        }
+    }
+
+    fn decrement(&mut self, owner: &Uuid) {
+        if let Some(count) = self.counts.get_mut(owner) {
+            *count -= 1;
+            // Don't keep players without entities around
+            if *count == 0 {
+                self.counts.remove(owner);
+            }
+        }
+    }
+}
+
+impl World {
+    /// Whether the player may spawn another entity, tells them if they reached the limit of the config.
+    /// Spawning with a spawn egg or shooting a projectile checks this, see [`World::attribute_entity`]
+    pub async fn may_spawn_entity_for(&self, player: &Player) -> bool {
+        let config = &ADVANCED_CONFIG.entity_limit;
+        if !config.enabled {
+            return true;
+        }
+        let count = self
+            .player_entities
+            .lock()
+            .await
+            .count(&player.gameprofile.id);
+        if count < config.max_entities_per_player {
+            return true;
+        }
+        player
+            .send_system_message(
+                &TextComponent::text(format!(
+                    "You can't spawn more than {} entities",
+                    config.max_entities_per_player
+                ))
+                .color_named(NamedColor::Red),
+            )
+            .await;
+        false
+    }
+
+    /// Counts the entity towards the entities the player spawned, until it gets removed
+    pub async fn attribute_entity(&self, player: &Player, entity_id: EntityId) {
+        self.player_entities
+            .lock()
+            .await
+            .attribute(player.gameprofile.id, entity_id);
+    }
+
+    /// How many of the entities in the world the player spawned
+    pub async fn player_entity_count(&self, player: &Player) -> u32 {
+        self.player_entities
+            .lock()
+            .await
+            .count(&player.gameprofile.id)
+    }
+
+    /// Stops counting the removed entity for the player who spawned it
+    pub(super) async fn forget_entity_owner(&self, entity_id: EntityId) {
+        self.player_entities.lock().await.forget(entity_id);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use uuid::Uuid;
+
+    use super::PlayerEntities;
+
+    #[test]
+    fn counts_per_player() {
+        let mut entities = PlayerEntities::default();
+        let (alice, bob) = (Uuid::new_v4(), Uuid::new_v4());
+        entities.attribute(alice, 1);
+        entities.attribute(alice, 2);
+        entities.attribute(bob, 3);
+        assert_eq!(entities.count(&alice), 2);
+
+        entities.forget(1);
+        // Nobody spawned it, nothing changes
+        entities.forget(4);
+        assert_eq!(entities.count(&alice), 1);
+        assert_eq!(entities.count(&bob), 1);
+
+        entities.forget(2);
+        assert_eq!(entities.count(&alice), 0);
+        assert!(!entities.counts.contains_key(&alice));
+    }
+}
@@ -0,0 +1,161 @@
+use std::{collections::HashMap, sync::Arc};
+
+use pumpkin_config::{ADVANCED_CONFIG, BASIC_CONFIG};
+use pumpkin_core::{
+    math::{vector2::Vector2, vector3::Vector3},
+    GameMode,
+};
+use pumpkin_protocol::{client::play::CEntityVelocity, codec::var_int::VarInt};
+
+use crate::entity::{mob::MobEntity, player::Player, Entity};
+
+use super::World;
+
+/// Something that can be pushed around by other entities
+enum Pushable {
+    Player(Arc<Player>),
+    Mob(Arc<MobEntity>),
+}
+
+impl Pushable {
+    fn entity(&self) -> &Entity {
+        match self {
+            Self::Player(player) => &player.living_entity.entity,
+            Self::Mob(mob) => &mob.living_entity.entity,
+        }
+    }
+
+    /// Adds the push to the velocity of the entity, so knockback and other pushes are kept, and tells the clients.
+    /// The client moves the player itself, so only the player's own client is told
+    async fn push(&self, world: &World, x: f64, z: f64) {
+        let push = Vector3::new(x, 0.0, z);
+        match self {
+            Self::Player(player) => player.apply_velocity(push).await,
+            Self::Mob(mob) => {
+                // TODO: Slow mobs down again once they move by their velocity
+                let entity = &mob.living_entity.entity;
+                let velocity = entity.set_velocity(entity.velocity.load().add(&push));
+                world
+                    .broadcast_packet_all(&CEntityVelocity::new(
+                        &VarInt(entity.entity_id),
+                        velocity.x,
+                        velocity.y,
+                        velocity.z,
+                    ))
+                    .await;
+            }
+        }
+    }
+}
+
+/// Calculates how much `entity` should be pushed away from `other`, following vanilla's `Entity#pushAwayFrom`.
+/// The returned push is applied negated to `entity` and as is to `other`
+fn push_vector(entity: &Entity, other: &Entity) -> Option<(f64, f64)> {
+    let pos = entity.pos.load();
+    let other_pos = other.pos.load();
+    let mut x = other_pos.x - pos.x;
+    let mut z = other_pos.z - pos.z;
+    let max = x.abs().max(z.abs());
+    if max < 0.01 {
+        return None;
+    }
+    let distance = max.sqrt();
+    x /= distance;
+    z /= distance;
+    let scale = (1.0 / distance).min(1.0) * ADVANCED_CONFIG.collision.push_strength;
+    Some((x * scale, z * scale))
+}
+
+/// Pushes apart all entities within the simulation distance whose hitboxes overlap.
+///
+/// Entities are bucketed by chunk, so we only have to check entities in the same or neighbouring chunks
+pub async fn tick_entity_push(world: &World) {
+    if !ADVANCED_CONFIG.collision.enabled {
+        return;
+    }
+
+    let players: Vec<Arc<Player>> = world
+        .current_players
+        .lock()
+        .await
+        .values()
+        .cloned()
+        .collect();
+    let mobs: Vec<Arc<MobEntity>> = world
+        .current_living_mobs
+        .lock()
+        .await
+        .values()
+        .cloned()
+        .collect();
+
+    let simulation_distance = i32::from(BASIC_CONFIG.simulation_distance.get());
+    let player_chunks: Vec<Vector2<i32>> = players
+        .iter()
+        .map(|player| player.living_entity.entity.chunk_pos.load())
+        .collect();
+    let is_simulated = |chunk: Vector2<i32>| {
+        player_chunks.iter().any(|player_chunk| {
+            (player_chunk.x - chunk.x).abs() <= simulation_distance
+                && (player_chunk.z - chunk.z).abs() <= simulation_distance
+        })
+    };
+
+    // TODO: Respect the scoreboard team collisionRule once we have teams
+    let pushables: Vec<Pushable> = players
+        .into_iter()
+        .filter(|player| player.gamemode.load() != GameMode::Spectator)
+        .map(Pushable::Player)
+        .chain(mobs.into_iter().map(Pushable::Mob))
+        .filter(|pushable| is_simulated(pushable.entity().chunk_pos.load()))
+        .collect();
+
+    let mut chunks: HashMap<Vector2<i32>, Vec<usize>> = HashMap::new();
+    for (i, pushable) in pushables.iter().enumerate() {
+        chunks
+            .entry(pushable.entity().chunk_pos.load())
+            .or_default()
+            .push(i);
+    }
+
+    // Only entities which overlap another one get pushed
+    let mut pushes: Vec<Option<(f64, f64)>> = vec![None; pushables.len()];
+    for (i, pushable) in pushables.iter().enumerate() {
+        let entity = pushable.entity();
+        let chunk = entity.chunk_pos.load();
+        let bounding_box = entity.bounding_box.load();
+        for x in -1..=1 {
+            for z in -1..=1 {
+                let Some(others) = chunks.get(&Vector2::new(chunk.x + x, chunk.z + z)) else {
+                    continue;
+                };
+                // Only handle each pair once
+                for &j in others.iter().filter(|&&j| j > i) {
+                    let other = pushables[j].entity();
+                    if !bounding_box.intersects(&other.bounding_box.load()) {
+                        continue;
+                    }
+                    if let Some((push_x, push_z)) = push_vector(entity, other) {
+                        let (x, z) = pushes[i].get_or_insert((0.0, 0.0));
+                        *x -= push_x;
+                        *z -= push_z;
+                        let (x, z) = pushes[j].get_or_insert((0.0, 0.0));
+                        *x += push_x;
+                        *z += push_z;
+                    }
+                }
+            }
+        }
+    }
+
+    // Cap the total push, so large crowds don't launch entities
+    let max_push = ADVANCED_CONFIG.collision.max_push;
+    for (pushable, push) in pushables.iter().zip(pushes) {
+        let Some((x, z)) = push else {
+            continue;
+        };
+        let x = x.clamp(-max_push, max_push);
+        let z = z.clamp(-max_push, max_push);
+        pushable.push(world, x, z).await;
+    }
+}
@@ -0,0 +1,274 @@
+use std::sync::Arc;
+
+use pumpkin_core::{
+    math::{position::WorldPosition, vector3::Vector3},
+    GameMode,
+};
+use pumpkin_entity::EntityId;
+use pumpkin_macros::sound;
+use pumpkin_protocol::{
+    client::play::{CRemoveEntities, CSpawnExperienceOrb, CTakeItemEntity, CUpdateEntityPos},
+    codec::var_int::VarInt,
+    SoundCategory,
+};
+
+use pumpkin_world::block::block_registry::Block;
+use rand::thread_rng;
+
+use crate::{
+    entity::{
+        experience::{block_experience, mob_experience},
+        living::LivingEntity,
+        player::Player,
+    },
+    server::Server,
+};
+
+use super::World;
+
+/// Orbs fly towards players closer than this, like Vanilla
+const FOLLOW_RANGE: f64 = 8.0;
+/// Players collect orbs closer than this
+const PICKUP_DISTANCE: f64 = 1.0;
+/// New orbs closer than this to an existing orb are merged into it
+const MERGE_DISTANCE: f64 = 0.5;
+/// Orbs nobody collected disappear after 5 minutes, like Vanilla
+const DESPAWN_TICKS: u32 = 6000;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct ExperienceOrb {
+    entity_id: EntityId,
+    position: Vector3<f64>,
+    velocity: Vector3<f64>,
+    amount: i32,
+    age: u32,
+}
+
+impl ExperienceOrb {
+    fn spawn_packet(&self) -> CSpawnExperienceOrb {
+        let count = i16::try_from(self.amount).unwrap_or(i16::MAX);
+        CSpawnExperienceOrb::new(self.entity_id.into(), self.position, count)
+    }
+
+    /// Accelerates the orb towards the target like Vanilla, faster the closer it is
+    fn follow(&mut self, target: Vector3<f64>) {
+        let direction = target.sub(&self.position);
+        let closeness = 1.0 - direction.length() / FOLLOW_RANGE;
+        if closeness > 0.0 {
+            self.velocity += direction.normalize() * (closeness * closeness * 0.1);
+        }
+        self.position += self.velocity;
+        self.velocity = self.velocity * 0.98;
+    }
+}
+
+/// All experience orbs of a world which nobody collected yet
+#[derive(Default)]
+pub struct ExperienceOrbs(Vec<ExperienceOrb>);
+
+impl ExperienceOrbs {
+    /// Adds the amount to an orb close to the position, returns the merged orb or `None` if there is none
+    fn merge(&mut self, position: Vector3<f64>, amount: i32) -> Option<ExperienceOrb> {
+        let orb = self.0.iter_mut().find(|orb| {
+            orb.position.squared_distance_to_vec(position) <= MERGE_DISTANCE * MERGE_DISTANCE
+        })?;
+        orb.amount = orb.amount.saturating_add(amount);
+        Some(*orb)
+    }
+}
+
+/// The point orbs fly towards, the middle of the player's body
+fn orb_target(player: &Player) -> Vector3<f64> {
+    let entity = &player.living_entity.entity;
+    entity.pos.load().add(&Vector3::new(
+        0.0,
+        f64::from(entity.standing_eye_height) / 2.0,
+        0.0,
+    ))
+}
+
+impl World {
+    /// Spawns an experience orb worth `amount` points, or adds them to an orb close by.
+    /// Does nothing if the world doesn't drop experience or the amount is not positive
+    pub async fn spawn_experience_orb(&self, server: &Server, pos: Vector3<f64>, amount: i32) {
+        if !self.config.drop_experience || amount <= 0 {
+            return;
+        }
+        let merged = self.experience_orbs.lock().await.merge(pos, amount);
+        if let Some(orb) = merged {
+            // The client only reads the amount when spawning the orb, so spawn it again to update its size
+            self.broadcast_packet_all(&CRemoveEntities::new(&[orb.entity_id.into()]))
+                .await;
+            self.broadcast_packet_all(&orb.spawn_packet()).await;
+            return;
+        }
+        let orb = ExperienceOrb {
+            entity_id: server.new_entity_id(),
+            position: pos,
+            velocity: Vector3::new(0.0, 0.0, 0.0),
+            amount,
+            age: 0,
+        };
+        self.experience_orbs.lock().await.0.push(orb);
+        self.broadcast_packet_all(&orb.spawn_packet()).await;
+    }
+
+    /// Drops the experience of a block a player broke, e.g. an ore
+    pub async fn drop_block_experience(
+        &self,
+        server: &Server,
+        position: WorldPosition,
+        block: &Block,
+    ) {
+        let amount = block_experience(&block.name, &mut thread_rng());
+        let center = Vector3::new(
+            f64::from(position.0.x) + 0.5,
+            f64::from(position.0.y) + 0.5,
+            f64::from(position.0.z) + 0.5,
+        );
+        self.spawn_experience_orb(server, center, amount).await;
+    }
+
+    /// Drops the experience of a mob a player killed
+    pub async fn drop_mob_experience(&self, server: &Server, mob: &LivingEntity) {
+        let amount = mob_experience(&mob.entity.entity_type, &mut thread_rng());
+        self.spawn_experience_orb(server, mob.entity.pos.load(), amount)
+            .await;
+    }
+
+    /// Shows a player who joined the world all experience orbs
+    pub async fn send_experience_orbs(&self, player: &Player) {
+        for orb in &self.experience_orbs.lock().await.0 {
+            player.client.send_packet(&orb.spawn_packet()).await;
+        }
+    }
+
+    /// Where the orbs are, see [`World::count_entities`]
+    pub(super) async fn experience_orb_positions(&self) -> Vec<Vector3<f64>> {
+        self.experience_orbs
+            .lock()
+            .await
+            .0
+            .iter()
+            .map(|orb| orb.position)
+            .collect()
+    }
+
+    /// Moves orbs towards the closest player, lets players collect them and removes old ones
+    pub(super) async fn tick_experience_orbs(&self) {
+        let players: Vec<Arc<Player>> = self
+            .current_players
+            .lock()
+            .await
+            .values()
+            .filter(|player| {
+                player.gamemode.load() != GameMode::Spectator
+                    && player.living_entity.health.load() > 0.0
+            })
+            .cloned()
+            .collect();
+
+        let mut moved = Vec::new();
+        let mut collected = Vec::new();
+        let mut removed: Vec<VarInt> = Vec::new();
+        self.experience_orbs.lock().await.0.retain_mut(|orb| {
+            orb.age += 1;
+            if orb.age >= DESPAWN_TICKS {
+                removed.push(orb.entity_id.into());
+                return false;
+            }
+            let closest = players
+                .iter()
+                .map(|player| (player, orb_target(player)))
+                .filter(|(_, target)| {
+                    target.squared_distance_to_vec(orb.position) <= FOLLOW_RANGE * FOLLOW_RANGE
+                })
+                .min_by(|(_, a), (_, b)| {
+                    a.squared_distance_to_vec(orb.position)
+                        .total_cmp(&b.squared_distance_to_vec(orb.position))
+                });
+            let Some((player, target)) = closest else {
+                return true;
+            };
+            if target.squared_distance_to_vec(orb.position) <= PICKUP_DISTANCE * PICKUP_DISTANCE {
+                collected.push((*orb, player.clone()));
+                removed.push(orb.entity_id.into());
+                return false;
+            }
+            let old_position = orb.position;
+            orb.follow(target);
+            moved.push((orb.entity_id, old_position, orb.position));
+            true
+        });
+
+        for (entity_id, old, new) in moved {
+            self.broadcast_packet_all(&CUpdateEntityPos::new(
+                entity_id.into(),
+                Vector3::new(
+                    new.x.mul_add(4096.0, -(old.x * 4096.0)) as i16,
+                    new.y.mul_add(4096.0, -(old.y * 4096.0)) as i16,
+                    new.z.mul_add(4096.0, -(old.z * 4096.0)) as i16,
+                ),
+                false,
+            ))
+            .await;
+        }
+        for (orb, player) in collected {
+            self.broadcast_packet_all(&CTakeItemEntity::new(
+                orb.entity_id.into(),
+                player.entity_id().into(),
+                1.into(),
+            ))
+            .await;
+            player.add_experience(orb.amount).await;
+            self.play_sound(
+                sound!("entity.experience_orb.pickup"),
+                SoundCategory::Players,
+                &player.living_entity.entity.pos.load(),
+            )
+            .await;
+        }
+        if !removed.is_empty() {
+            self.broadcast_packet_all(&CRemoveEntities::new(&removed))
+                .await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_core::math::vector3::Vector3;
+
+    use super::{ExperienceOrb, ExperienceOrbs};
+
+    #[test]
+    fn close_orbs_merge() {
+        let mut orbs = ExperienceOrbs::default();
+        orbs.0.push(ExperienceOrb {
+            entity_id: 1,
+            position: Vector3::new(0.5, 64.5, 0.5),
+            velocity: Vector3::new(0.0, 0.0, 0.0),
+            amount: 3,
+            age: 0,
+        });
+
+        let merged = orbs.merge(Vector3::new(0.7, 64.5, 0.5), 4).unwrap();
+        assert_eq!(merged.entity_id, 1);
+        assert_eq!(merged.amount, 7);
+        assert!(orbs.merge(Vector3::new(5.5, 64.5, 0.5), 2).is_none());
+    }
+
+    #[test]
+    fn orb_follows_target() {
+        let mut orb = ExperienceOrb {
+            entity_id: 1,
+            position: Vector3::new(0.0, 64.0, 0.0),
+            velocity: Vector3::new(0.0, 0.0, 0.0),
+            amount: 1,
+            age: 0,
+        };
+        orb.follow(Vector3::new(4.0, 64.0, 0.0));
+        assert!(orb.position.x > 0.0);
+        assert!(orb.position.z.abs() < f64::EPSILON);
+    }
+}
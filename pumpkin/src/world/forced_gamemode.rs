@@ -0,0 +1,50 @@
+use pumpkin_core::{permission::PermissionLvl, GameMode};
+
+use crate::entity::player::Player;
+
+use super::World;
+
+/// Operators with this permission level keep their gamemode in worlds with a forced gamemode
+const BYPASS_PERMISSION: PermissionLvl = PermissionLvl::Two;
+
+impl World {
+    /// The gamemode the world forces on the player, `None` if they keep their own
+    #[must_use]
+    pub fn forced_gamemode(&self, player: &Player) -> Option<GameMode> {
+        self.config
+            .forced_gamemode
+            .filter(|_| player.permission_lvl.load() < BYPASS_PERMISSION)
+    }
+
+    /// Switches a player who entered the world to its forced gamemode.
+    /// The gamemode they had before is remembered, so [`World::restore_gamemode`] can give it back
+    pub async fn enforce_gamemode(&self, player: &Player) {
+        let Some(forced) = self.forced_gamemode(player) else {
+            return;
+        };
+        let current = player.gamemode.load();
+        if current == forced {
+            return;
+        }
+        if self.config.restore_gamemode {
+            // Keep the gamemode from before the first forced world when going from one to the next
+            let _ = player
+                .gamemode_before_forced
+                .compare_exchange(None, Some(current));
+        }
+        player.set_gamemode(forced).await;
+    }
+
+    /// Gives a player who left the world the gamemode they had before it was forced
+    pub async fn restore_gamemode(&self, player: &Player) {
+        if self.config.forced_gamemode.is_none() {
+            return;
+        }
+        let Some(previous) = player.gamemode_before_forced.take() else {
+            return;
+        };
+        if player.gamemode.load() != previous {
+            player.set_gamemode(previous).await;
+        }
+    }
+}
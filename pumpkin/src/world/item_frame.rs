@@ -0,0 +1,221 @@
+use std::{collections::HashMap, sync::Arc};
+
+use pumpkin_core::math::position::WorldPosition;
+use pumpkin_entity::{entity_type::EntityType, EntityId};
+use pumpkin_protocol::{
+    client::play::{
+        CMapData, CRemoveEntities, CSetEntityMetadata, CSpawnEntity, Metadata, MAP_SIZE,
+    },
+    codec::{slot::Slot, var_int::VarInt},
+};
+use pumpkin_world::{block::BlockFace, item::ItemStack};
+use uuid::Uuid;
+
+use crate::{entity::player::Player, server::Server};
+
+use super::World;
+
+/// The metadata index of the displayed item
+const ITEM_INDEX: u8 = 8;
+/// The metadata index of the rotation of the displayed item
+const ROTATION_INDEX: u8 = 9;
+/// Items in frames can be turned in 8 steps of 45 degrees
+const ROTATIONS: u8 = 8;
+
+/// An item frame hanging on a block, see [`World::spawn_item_frame`]
+struct ItemFrame {
+    uuid: Uuid,
+    /// The block the frame is in, not the one it hangs on
+    position: WorldPosition,
+    /// The direction the frame faces, away from the block it hangs on
+    facing: BlockFace,
+    item: Option<ItemStack>,
+    rotation: u8,
+}
+
+impl ItemFrame {
+    fn spawn_packet(&self, entity_id: EntityId) -> CSpawnEntity {
+        let position = self.position.0;
+        CSpawnEntity::new(
+            entity_id.into(),
+            self.uuid,
+            (EntityType::ItemFrame as i32).into(),
+            f64::from(position.x) + 0.5,
+            f64::from(position.y) + 0.5,
+            f64::from(position.z) + 0.5,
+            0.0,
+            0.0,
+            0.0,
+            // The client hangs the frame by the direction in the data
+            (self.facing as i32).into(),
+            0.0,
+            0.0,
+            0.0,
+        )
+    }
+
+    fn item_packet(&self, entity_id: EntityId) -> CSetEntityMetadata<Slot> {
+        CSetEntityMetadata::new(
+            entity_id.into(),
+            Metadata::new(ITEM_INDEX, VarInt(7), Slot::from(&self.item)),
+        )
+    }
+
+    fn rotation_packet(&self, entity_id: EntityId) -> CSetEntityMetadata<VarInt> {
+        CSetEntityMetadata::new(
+            entity_id.into(),
+            Metadata::new(ROTATION_INDEX, VarInt(1), VarInt(i32::from(self.rotation))),
+        )
+    }
+}
+
+/// The rotation after turning the item one step further, like clicking on a frame
+const fn next_rotation(rotation: u8) -> u8 {
+    (rotation + 1) % ROTATIONS
+}
+
+/// All item frames of a world and the images of the maps they can show
+#[derive(Default)]
+pub struct ItemFrames {
+    frames: HashMap<EntityId, ItemFrame>,
+    /// The colors of every pixel of the maps by their id, see [`World::set_map_colors`]
+    maps: HashMap<i32, Arc<[u8]>>,
+}
+
+impl World {
+    /// Hangs an item frame showing the item into the block, facing away from the block it hangs on, and shows it to
+    /// all players
+    pub async fn spawn_item_frame(
+        &self,
+        server: &Server,
+        position: WorldPosition,
+        facing: BlockFace,
+        item: Option<ItemStack>,
+    ) -> EntityId {
+        let entity_id = server.new_entity_id();
+        let frame = ItemFrame {
+            uuid: Uuid::new_v4(),
+            position,
+            facing,
+            item,
+            rotation: 0,
+        };
+        self.broadcast_packet_all(&frame.spawn_packet(entity_id))
+            .await;
+        if frame.item.is_some() {
+            self.broadcast_packet_all(&frame.item_packet(entity_id))
+                .await;
+        }
+        self.item_frames
+            .lock()
+            .await
+            .frames
+            .insert(entity_id, frame);
+        entity_id
+    }
+
+    /// Puts the item into the frame or takes the item out with `None`, returns `false` if there is no such frame
+    pub async fn set_item_frame_item(&self, entity_id: EntityId, item: Option<ItemStack>) -> bool {
+        let packet = {
+            let mut item_frames = self.item_frames.lock().await;
+            let Some(frame) = item_frames.frames.get_mut(&entity_id) else {
+                return false;
+            };
+            frame.item = item;
+            // A new item starts upright again
+            frame.rotation = 0;
+            frame.item_packet(entity_id)
+        };
+        self.broadcast_packet_all(&packet).await;
+        true
+    }
+
+    /// Turns the item in the frame by 45 degrees, returns `false` if there is no such frame
+    pub async fn rotate_item_frame(&self, entity_id: EntityId) -> bool {
+        let packet = {
+            let mut item_frames = self.item_frames.lock().await;
+            let Some(frame) = item_frames.frames.get_mut(&entity_id) else {
+                return false;
+            };
+            frame.rotation = next_rotation(frame.rotation);
+            frame.rotation_packet(entity_id)
+        };
+        self.broadcast_packet_all(&packet).await;
+        true
+    }
+
+    /// Removes the frame together with its item, returns the item it showed
+    pub async fn remove_item_frame(&self, entity_id: EntityId) -> Option<ItemStack> {
+        let frame = self.item_frames.lock().await.frames.remove(&entity_id)?;
+        self.broadcast_packet_all(&CRemoveEntities::new(&[entity_id.into()]))
+            .await;
+        frame.item
+    }
+
+    /// Sets the image of the map with the id and shows it to all players, `colors` holds the color of every pixel
+    /// row by row. The image never changes on its own, the map is only decoration.
+    ///
+    /// TODO: Map items can't carry their map id yet, as items are sent without components, so frames don't know which
+    /// map to show
+    pub async fn set_map_colors(&self, map_id: i32, mut colors: Vec<u8>) {
+        colors.resize(MAP_SIZE * MAP_SIZE, 0);
+        let colors: Arc<[u8]> = colors.into();
+        self.item_frames
+            .lock()
+            .await
+            .maps
+            .insert(map_id, colors.clone());
+        self.broadcast_packet_all(&CMapData::new(
+            VarInt(map_id),
+            0,
+            true,
+            Some(colors.as_ref()),
+        ))
+        .await;
+    }
+
+    /// Shows all item frames and maps to a player who just joined
+    pub async fn send_item_frames(&self, player: &Player) {
+        let item_frames = self.item_frames.lock().await;
+        for (map_id, colors) in &item_frames.maps {
+            player
+                .client
+                .send_packet(&CMapData::new(
+                    VarInt(*map_id),
+                    0,
+                    true,
+                    Some(colors.as_ref()),
+                ))
+                .await;
+        }
+        for (entity_id, frame) in &item_frames.frames {
+            player
+                .client
+                .send_packet(&frame.spawn_packet(*entity_id))
+                .await;
+            if frame.item.is_some() {
+                player
+                    .client
+                    .send_packet(&frame.item_packet(*entity_id))
+                    .await;
+            }
+            if frame.rotation != 0 {
+                player
+                    .client
+                    .send_packet(&frame.rotation_packet(*entity_id))
+                    .await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::next_rotation;
+
+    #[test]
+    fn rotation_wraps() {
+        assert_eq!(next_rotation(0), 1);
+        assert_eq!(next_rotation(7), 0);
+    }
+}
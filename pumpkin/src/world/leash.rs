@@ -0,0 +1,322 @@
+use std::collections::HashMap;
+
+use pumpkin_core::math::{position::WorldPosition, vector3::Vector3};
+use pumpkin_entity::{entity_type::EntityType, EntityId};
+use pumpkin_protocol::{
+    client::play::{CRemoveEntities, CSetEntityLink, CSpawnEntity, CUpdateEntityPos},
+    codec::var_int::VarInt,
+};
+use pumpkin_world::item::item_registry::get_item;
+use uuid::Uuid;
+
+use crate::{entity::player::Player, server::Server};
+
+use super::World;
+
+/// Leashed entities further away from their holder than this get pulled back, like Vanilla
+const ELASTIC_DISTANCE: f64 = 6.0;
+/// Leashes break when the entity is further away from its holder than this, like Vanilla
+const BREAK_DISTANCE: f64 = 10.0;
+
+/// A knot on a fence, which can hold any number of leashes
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct LeashKnot {
+    entity_id: EntityId,
+    uuid: Uuid,
+    fence: WorldPosition,
+}
+
+impl LeashKnot {
+    /// Knots hang in the middle of the fence post
+    fn position(&self) -> Vector3<f64> {
+        let fence = self.fence.0;
+        Vector3::new(
+            f64::from(fence.x) + 0.5,
+            f64::from(fence.y) + 0.5,
+            f64::from(fence.z) + 0.5,
+        )
+    }
+
+    fn spawn_packet(&self) -> CSpawnEntity {
+        let pos = self.position();
+        CSpawnEntity::new(
+            self.entity_id.into(),
+            self.uuid,
+            (EntityType::LeashKnot as i32).into(),
+            pos.x,
+            pos.y,
+            pos.z,
+            0.0,
+            0.0,
+            0.0,
+            0.into(),
+            0.0,
+            0.0,
+            0.0,
+        )
+    }
+}
+
+/// Which entity holds the leash of which, and the knots holding leashes on fences
+#[derive(Default)]
+pub struct Leashes {
+    /// The holder of every leashed entity
+    holders: HashMap<EntityId, EntityId>,
+    knots: Vec<LeashKnot>,
+}
+
+impl Leashes {
+    /// Leashes the entity to the holder or unleashes it with `None`, returns the previous holder
+    fn set(&mut self, entity_id: EntityId, holder_id: Option<EntityId>) -> Option<EntityId> {
+        match holder_id {
+            Some(holder_id) => self.holders.insert(entity_id, holder_id),
+            None => self.holders.remove(&entity_id),
+        }
+    }
+
+    /// The entities whose leash the holder holds
+    fn held_by(&self, holder_id: EntityId) -> Vec<EntityId> {
+        self.holders
+            .iter()
+            .filter(|(_, holder)| **holder == holder_id)
+            .map(|(entity_id, _)| *entity_id)
+            .collect()
+    }
+
+    /// Removes and returns the knots which don't hold any leash anymore
+    fn remove_unused_knots(&mut self) -> Vec<LeashKnot> {
+        let (unused, used) = std::mem::take(&mut self.knots)
+            .into_iter()
+            .partition(|knot| {
+                !self
+                    .holders
+                    .values()
+                    .any(|holder| *holder == knot.entity_id)
+            });
+        self.knots = used;
+        unused
+    }
+}
+
+impl World {
+    /// Leashes the entity to the holder or removes its leash with `None`, and shows it to all players
+    pub async fn set_leash(&self, entity_id: EntityId, holder_id: Option<EntityId>) {
+        let unused_knots = {
+            let mut leashes = self.leashes.lock().await;
+            if leashes.set(entity_id, holder_id) == holder_id {
+                return;
+            }
+            leashes.remove_unused_knots()
+        };
+        self.broadcast_packet_all(&CSetEntityLink::new(entity_id, holder_id))
+            .await;
+        if !unused_knots.is_empty() {
+            let ids: Vec<VarInt> = unused_knots
+                .iter()
+                .map(|knot| knot.entity_id.into())
+                .collect();
+            self.broadcast_packet_all(&CRemoveEntities::new(&ids)).await;
+        }
+    }
+
+    /// Ties the leash of the entity to a knot on the fence, the knot is created if there is none yet.
+    /// Returns `false` if the block is no fence
+    pub async fn tie_leash_to_fence(
+        &self,
+        server: &Server,
+        entity_id: EntityId,
+        fence: WorldPosition,
+    ) -> bool {
+        let is_fence = self
+            .get_block(fence)
+            .await
+            .is_ok_and(|block| block.name.ends_with("_fence"));
+        if !is_fence {
+            return false;
+        }
+
+        let (knot, created) = {
+            let mut leashes = self.leashes.lock().await;
+            let existing = leashes
+                .knots
+                .iter()
+                .find(|knot| knot.fence == fence)
+                .copied();
+            let created = existing.is_none();
+            let knot = existing.unwrap_or_else(|| {
+                let knot = LeashKnot {
+                    entity_id: server.new_entity_id(),
+                    uuid: Uuid::new_v4(),
+                    fence,
+                };
+                leashes.knots.push(knot);
+                knot
+            });
+            (knot, created)
+        };
+        if created {
+            self.broadcast_packet_all(&knot.spawn_packet()).await;
+        }
+        self.set_leash(entity_id, Some(knot.entity_id)).await;
+        true
+    }
+
+    /// Ties all leashes the holder holds to the fence.
+    /// Returns `false` if they hold none or the block is no fence
+    pub async fn tie_held_leashes_to_fence(
+        &self,
+        server: &Server,
+        holder_id: EntityId,
+        fence: WorldPosition,
+    ) -> bool {
+        let held = self.leashes.lock().await.held_by(holder_id);
+        if held.is_empty() {
+            return false;
+        }
+        for entity_id in held {
+            if !self.tie_leash_to_fence(server, entity_id, fence).await {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Leashes the mob to a player holding a lead, or takes the leash off if the player already holds it.
+    /// Returns `false` if nothing happened
+    pub async fn use_lead(&self, player: &Player, entity_id: EntityId) -> bool {
+        let holder_id = self.leashes.lock().await.holders.get(&entity_id).copied();
+        if holder_id == Some(player.entity_id()) {
+            self.set_leash(entity_id, None).await;
+            return true;
+        }
+        let holds_lead = player
+            .inventory()
+            .lock()
+            .await
+            .held_item()
+            .is_some_and(|stack| get_item("lead").is_some_and(|lead| lead.id == stack.item_id));
+        if !holds_lead {
+            return false;
+        }
+        // TODO: Use up the lead once using items takes them from the inventory
+        self.set_leash(entity_id, Some(player.entity_id())).await;
+        true
+    }
+
+    /// Removes all leashes the entity holds, e.g. because the player holding them left
+    pub async fn drop_leashes_held_by(&self, holder_id: EntityId) {
+        let held = self.leashes.lock().await.held_by(holder_id);
+        for entity_id in held {
+            self.set_leash(entity_id, None).await;
+        }
+    }
+
+    /// Shows a player who joined the world all knots and leashes
+    pub async fn send_leashes(&self, player: &Player) {
+        let leashes = self.leashes.lock().await;
+        for knot in &leashes.knots {
+            player.client.send_packet(&knot.spawn_packet()).await;
+        }
+        for (entity_id, holder_id) in &leashes.holders {
+            player
+                .client
+                .send_packet(&CSetEntityLink::new(*entity_id, Some(*holder_id)))
+                .await;
+        }
+    }
+
+    async fn leash_holder_position(&self, holder_id: EntityId) -> Option<Vector3<f64>> {
+        let knot = self
+            .leashes
+            .lock()
+            .await
+            .knots
+            .iter()
+            .find(|knot| knot.entity_id == holder_id)
+            .copied();
+        if let Some(knot) = knot {
+            return Some(knot.position());
+        }
+        if let Some(player) = self.get_player_by_entityid(holder_id).await {
+            return Some(player.living_entity.entity.pos.load());
+        }
+        self.get_living_entity_by_entityid(holder_id)
+            .await
+            .map(|living| living.entity.pos.load())
+    }
+
+    /// Pulls leashed entities towards their holder and breaks leashes which got too long
+    pub(super) async fn tick_leashes(&self) {
+        let holders = self.leashes.lock().await.holders.clone();
+        for (entity_id, holder_id) in holders {
+            // Only mobs can be leashed
+            let Some(leashed) = self.get_living_entity_by_entityid(entity_id).await else {
+                self.set_leash(entity_id, None).await;
+                continue;
+            };
+            let Some(holder_pos) = self.leash_holder_position(holder_id).await else {
+                self.set_leash(entity_id, None).await;
+                continue;
+            };
+
+            let pos = leashed.entity.pos.load();
+            let distance = pos.squared_distance_to_vec(holder_pos).sqrt();
+            if distance > BREAK_DISTANCE {
+                // TODO: Drop a lead once we have item entities
+                self.set_leash(entity_id, None).await;
+            } else if distance > ELASTIC_DISTANCE {
+                // Same pull as Vanilla, stronger the more the leash points along an axis
+                let direction = holder_pos.sub(&pos) * (1.0 / distance);
+                let pull = |axis: f64| (axis * axis * 0.4).copysign(axis);
+                let new_pos = pos.add(&Vector3::new(
+                    pull(direction.x),
+                    pull(direction.y),
+                    pull(direction.z),
+                ));
+                leashed.set_pos(new_pos);
+                self.broadcast_packet_all(&CUpdateEntityPos::new(
+                    entity_id.into(),
+                    Vector3::new(
+                        new_pos.x.mul_add(4096.0, -(pos.x * 4096.0)) as i16,
+                        new_pos.y.mul_add(4096.0, -(pos.y * 4096.0)) as i16,
+                        new_pos.z.mul_add(4096.0, -(pos.z * 4096.0)) as i16,
+                    ),
+                    leashed
+                        .entity
+                        .on_ground
+                        .load(std::sync::atomic::Ordering::Relaxed),
+                ))
+                .await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_core::math::{position::WorldPosition, vector3::Vector3};
+    use uuid::Uuid;
+
+    use super::{LeashKnot, Leashes};
+
+    #[test]
+    fn unused_knots_are_removed() {
+        let mut leashes = Leashes::default();
+        let knot = LeashKnot {
+            entity_id: 100,
+            uuid: Uuid::new_v4(),
+            fence: WorldPosition(Vector3::new(0, 64, 0)),
+        };
+        leashes.knots.push(knot);
+
+        assert_eq!(leashes.set(1, Some(100)), None);
+        assert_eq!(leashes.set(2, Some(100)), None);
+        assert_eq!(leashes.set(3, Some(7)), None);
+        assert_eq!(leashes.held_by(7), vec![3]);
+
+        assert_eq!(leashes.set(1, None), Some(100));
+        assert!(leashes.remove_unused_knots().is_empty());
+        assert_eq!(leashes.set(2, Some(7)), Some(100));
+        assert_eq!(leashes.remove_unused_knots(), vec![knot]);
+    }
+}
@@ -8,6 +8,7 @@ pub struct LevelTime {
     pub rain_time: i64,
 }
 
+#[derive(Clone, Copy)]
 pub enum DayTime {
     Day = 1000,
     Night = 13000,
@@ -44,7 +45,7 @@ impl LevelTime {
     }
 
     pub fn add_time(&mut self, time: i64) {
-        self.time_of_day += time;
+        self.time_of_day = self.time_of_day.saturating_add(time);
     }
 
     pub fn set_time(&mut self, time: i64) {
@@ -53,7 +54,7 @@ impl LevelTime {
 
     #[must_use]
     pub const fn query_daytime(&self) -> i64 {
-        self.time_of_day % 24000
+        self.time_of_day.rem_euclid(24000)
     }
 
     #[must_use]
@@ -63,6 +64,24 @@ impl LevelTime {
 
     #[must_use]
     pub const fn query_day(&self) -> i64 {
-        self.time_of_day / 24000
+        self.time_of_day.div_euclid(24000)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LevelTime;
+
+    #[test]
+    fn daytime_wraps() {
+        let mut time = LevelTime::new();
+        time.set_time(23_999);
+        time.add_time(2);
+        assert_eq!(time.query_daytime(), 1);
+        assert_eq!(time.query_day(), 1);
+
+        time.add_time(-2);
+        assert_eq!(time.query_daytime(), 23_999);
+        assert_eq!(time.query_day(), 0);
     }
 }
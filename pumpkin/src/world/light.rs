@@ -0,0 +1,287 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use pumpkin_config::ADVANCED_CONFIG;
+use pumpkin_core::math::{position::WorldPosition, vector2::Vector2, vector3::Vector3};
+use pumpkin_world::{
+    block::block_registry::{state_luminance, state_opacity},
+    WORLD_HEIGHT, WORLD_LOWEST_Y,
+};
+
+use super::{snapshot::RegionSnapshot, World};
+
+/// Light fades by at least one level per block, so changing a block changes the light at most this far away
+const LIGHT_RANGE: i32 = 15;
+/// How many blocks get their light spread again per tick at most, the others wait for the next ticks
+const MAX_LIGHT_UPDATES_PER_TICK: usize = 64;
+
+const NEIGHBORS: [Vector3<i32>; 6] = [
+    Vector3::new(1, 0, 0),
+    Vector3::new(-1, 0, 0),
+    Vector3::new(0, 1, 0),
+    Vector3::new(0, -1, 0),
+    Vector3::new(0, 0, 1),
+    Vector3::new(0, 0, -1),
+];
+
+/// The light level of every block lit by a block emitting light, see [`World::block_light`]
+#[derive(Default)]
+pub struct BlockLight {
+    levels: HashMap<WorldPosition, u8>,
+    /// Blocks which changed since their light was spread, see [`World::tick_block_light`]
+    pending: HashSet<WorldPosition>,
+}
+
+/// Whether the light around a block has to be spread again after it changed
+pub(super) fn changes_light(old_state_id: u16, new_state_id: u16) -> bool {
+    state_luminance(old_state_id) != state_luminance(new_state_id)
+        || state_opacity(old_state_id) != state_opacity(new_state_id)
+}
+
+/// Spreads the light around a block which changed again, the light of a block depends on its state in the snapshot.
+/// Like Vanilla, light fades by one level per block or by the opacity of the block it enters if that is higher.
+///
+/// First takes away all light which could have come through the block, then spreads the light of the blocks
+/// emitting light and of the lit blocks around the darkened area into it again
+fn relight(
+    levels: &mut HashMap<WorldPosition, u8>,
+    snapshot: &RegionSnapshot,
+    position: WorldPosition,
+) {
+    let mut darken = VecDeque::new();
+    let mut darkened = vec![position];
+    let mut spread = VecDeque::new();
+    if let Some(level) = levels.remove(&position) {
+        darken.push_back((position, level));
+    }
+    while let Some((position, level)) = darken.pop_front() {
+        for offset in &NEIGHBORS {
+            let neighbor = WorldPosition(position.0.add(offset));
+            let Some(&neighbor_level) = levels.get(&neighbor) else {
+                continue;
+            };
+            if neighbor_level < level {
+                levels.remove(&neighbor);
+                darken.push_back((neighbor, neighbor_level));
+                darkened.push(neighbor);
+            } else {
+                // Lit by something else
+                spread.push_back(neighbor);
+            }
+        }
+    }
+
+    for position in darkened {
+        let luminance = snapshot
+            .get_block_state_id(position)
+            .map_or(0, state_luminance);
+        if luminance > 0 {
+            levels.insert(position, luminance);
+            spread.push_back(position);
+        }
+    }
+    while let Some(position) = spread.pop_front() {
+        let Some(&level) = levels.get(&position) else {
+            continue;
+        };
+        for offset in &NEIGHBORS {
+            let neighbor = WorldPosition(position.0.add(offset));
+            let Some(state_id) = snapshot.get_block_state_id(neighbor) else {
+                continue;
+            };
+            let neighbor_level = level.saturating_sub(state_opacity(state_id).max(1));
+            if neighbor_level > levels.get(&neighbor).copied().unwrap_or(0) {
+                levels.insert(neighbor, neighbor_level);
+                spread.push_back(neighbor);
+            }
+        }
+    }
+}
+
+impl World {
+    /// The light level at the position coming from blocks which emit light, from `0` to `15`.
+    ///
+    /// Light is spread again in the tick after a block changed, so blocks around light sources which were loaded from
+    /// disk or generated count as dark until a block near them changes
+    pub async fn block_light(&self, position: WorldPosition) -> u8 {
+        self.block_light_levels
+            .lock()
+            .await
+            .levels
+            .get(&position)
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Whether nothing above the position blocks the sky, e.g. for rain falling on it or snow forming.
+    /// Positions above the world always see the sky, blocks letting light through like glass don't block it
+    pub async fn is_sky_visible(&self, position: WorldPosition) -> bool {
+        let y = position.0.y - i32::from(WORLD_LOWEST_Y);
+        if y >= WORLD_HEIGHT as i32 {
+            return true;
+        }
+        let (chunk_coordinate, relative) = position.chunk_and_chunk_relative_position();
+        let Ok(chunk) = self.receive_chunk(chunk_coordinate).await else {
+            return false;
+        };
+        let sky_height = chunk
+            .read()
+            .await
+            .blocks
+            .sky_height(relative.x as u8, relative.z as u8);
+        y >= sky_height as i32
+    }
+
+    /// Spreads the light around the block again during the next ticks, e.g. after a furnace there was lit
+    pub(super) async fn queue_light_update(&self, position: WorldPosition) {
+        if !ADVANCED_CONFIG.lighting.block_light {
+            return;
+        }
+        self.block_light_levels
+            .lock()
+            .await
+            .pending
+            .insert(position);
+    }
+
+    /// Spreads the light around the blocks which changed since the last tick, see [`MAX_LIGHT_UPDATES_PER_TICK`]
+    pub(super) async fn tick_block_light(&self) {
+        let positions: Vec<WorldPosition> = {
+            let mut light = self.block_light_levels.lock().await;
+            let positions: Vec<WorldPosition> = light
+                .pending
+                .iter()
+                .take(MAX_LIGHT_UPDATES_PER_TICK)
+                .copied()
+                .collect();
+            for position in &positions {
+                light.pending.remove(position);
+            }
+            positions
+        };
+        let range = Vector3::new(LIGHT_RANGE, LIGHT_RANGE, LIGHT_RANGE);
+        for position in positions {
+            let snapshot = match self
+                .snapshot_blocks(
+                    WorldPosition(position.0.sub(&range)),
+                    WorldPosition(position.0.add(&range)),
+                )
+                .await
+            {
+                Ok(snapshot) => snapshot,
+                Err(err) => {
+                    log::error!("Failed to relight {position}: {err}");
+                    continue;
+                }
+            };
+            relight(
+                &mut self.block_light_levels.lock().await.levels,
+                &snapshot,
+                position,
+            );
+        }
+    }
+
+    /// Forgets the light in the chunks, e.g. once they got unloaded
+    pub(super) async fn forget_block_light(&self, chunks: &[Vector2<i32>]) {
+        if chunks.is_empty() {
+            return;
+        }
+        let chunks: HashSet<&Vector2<i32>> = chunks.iter().collect();
+        let mut light = self.block_light_levels.lock().await;
+        let in_chunks = |position: &WorldPosition| {
+            chunks.contains(&position.chunk_and_chunk_relative_position().0)
+        };
+        light.levels.retain(|position, _| !in_chunks(position));
+        light.pending.retain(|position| !in_chunks(position));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_core::math::{position::WorldPosition, vector2::Vector2, vector3::Vector3};
+    use pumpkin_macros::block_state;
+    use pumpkin_world::block::block_registry::get_block;
+
+    use crate::world::World;
+
+    #[tokio::test]
+    async fn toggle_redstone_lamp() {
+        let world = World::test_world();
+        let lamp = get_block("redstone_lamp").unwrap();
+        let unlit = lamp.default_state_id;
+        let lit = lamp
+            .states
+            .iter()
+            .find(|state| state.luminance > 0)
+            .unwrap()
+            .id;
+        let position = WorldPosition(Vector3::new(8, 200, 8));
+        let two_away = WorldPosition(Vector3::new(10, 200, 8));
+
+        world.set_block_state(position, unlit).await;
+        world.tick_block_light().await;
+        assert_eq!(world.block_light(two_away).await, 0);
+
+        world.set_block_state(position, lit).await;
+        // Spread with the tick
+        assert_eq!(world.block_light(position).await, 0);
+        world.tick_block_light().await;
+        assert_eq!(world.block_light(position).await, 15);
+        assert_eq!(world.block_light(two_away).await, 13);
+
+        world.set_block_state(position, unlit).await;
+        world.tick_block_light().await;
+        assert_eq!(world.block_light(two_away).await, 0);
+    }
+
+    #[tokio::test]
+    async fn other_lights_stay() {
+        let world = World::test_world();
+        let glowstone = block_state!("glowstone").state_id;
+        let air = block_state!("air").state_id;
+        let first = WorldPosition(Vector3::new(8, 200, 8));
+        let second = WorldPosition(Vector3::new(12, 200, 8));
+        let between = WorldPosition(Vector3::new(10, 200, 8));
+        world.set_block_state(first, glowstone).await;
+        world.set_block_state(second, glowstone).await;
+        world.tick_block_light().await;
+        assert_eq!(world.block_light(between).await, 13);
+
+        world.set_block_state(first, air).await;
+        world.tick_block_light().await;
+        assert_eq!(world.block_light(first).await, 11);
+        assert_eq!(world.block_light(between).await, 13);
+    }
+
+    #[tokio::test]
+    async fn blocked_by_stone() {
+        let world = World::test_world();
+        let position = WorldPosition(Vector3::new(8, 200, 8));
+        let wall = WorldPosition(Vector3::new(9, 200, 8));
+        let behind = WorldPosition(Vector3::new(10, 200, 8));
+        world
+            .set_block_state(position, block_state!("glowstone").state_id)
+            .await;
+        world.tick_block_light().await;
+        assert_eq!(world.block_light(behind).await, 13);
+
+        world
+            .set_block_state(wall, block_state!("stone").state_id)
+            .await;
+        world.tick_block_light().await;
+        // Around the stone instead of through it
+        assert_eq!(world.block_light(behind).await, 11);
+    }
+
+    #[tokio::test]
+    async fn forgotten_with_chunk() {
+        let world = World::test_world();
+        let position = WorldPosition(Vector3::new(8, 200, 8));
+        world
+            .set_block_state(position, block_state!("glowstone").state_id)
+            .await;
+        world.tick_block_light().await;
+        world.forget_block_light(&[Vector2::new(0, 0)]).await;
+        assert_eq!(world.block_light(position).await, 0);
+    }
+}
@@ -0,0 +1,245 @@
+use std::sync::atomic::Ordering;
+
+use pumpkin_config::ADVANCED_CONFIG;
+use pumpkin_core::{
+    math::{position::WorldPosition, vector2::Vector2, vector3::Vector3},
+    Difficulty, GameMode,
+};
+use pumpkin_entity::entity_type::EntityType;
+use pumpkin_macros::{block_state, sound};
+use pumpkin_protocol::{
+    client::play::{CGameEvent, CSpawnEntity, GameEvent},
+    SoundCategory,
+};
+use pumpkin_registry::DimensionType;
+use rand::Rng;
+use uuid::Uuid;
+
+use crate::{
+    entity::{death::DeathCause, player::Player},
+    server::Server,
+};
+
+use super::World;
+
+/// Like Vanilla, every chunk near a player gets struck once in 100000 ticks on average during a thunderstorm
+const STRIKE_CHANCE: u32 = 100_000;
+/// Entities within this many blocks of a strike get hit, like Vanilla
+const HIT_RADIUS: f64 = 3.0;
+/// Entities up to this many blocks above a strike get hit, like Vanilla
+const HIT_HEIGHT: f64 = 6.0;
+/// How many fires spread around the struck block, like Vanilla
+const EXTRA_FIRES: usize = 4;
+/// The damage type of lightning in the damage type registry
+const LIGHTNING_DAMAGE_TYPE: u8 = 25;
+
+impl World {
+    /// Starts or ends a thunderstorm, during which lightning strikes near players, see [`World::strike_lightning`].
+    /// Only worlds with weather, like the Overworld, have thunderstorms
+    pub async fn set_thundering(&self, thundering: bool) {
+        if self.thundering.swap(thundering, Ordering::Relaxed) == thundering {
+            return;
+        }
+        // TODO: Rain on its own, once there is a weather cycle
+        let (event, level) = if thundering {
+            (GameEvent::BeginRaining, 1.0)
+        } else {
+            (GameEvent::EndRaining, 0.0)
+        };
+        self.broadcast_packet_all(&CGameEvent::new(event, 0.0))
+            .await;
+        self.broadcast_packet_all(&CGameEvent::new(GameEvent::RainLevelChange, level))
+            .await;
+        self.broadcast_packet_all(&CGameEvent::new(GameEvent::ThunderLevelChange, level))
+            .await;
+    }
+
+    pub fn is_thundering(&self) -> bool {
+        self.thundering.load(Ordering::Relaxed)
+    }
+
+    /// Shows a player who just joined the thunderstorm going on
+    pub(super) async fn send_thunderstorm(&self, player: &Player) {
+        if !self.is_thundering() {
+            return;
+        }
+        player
+            .client
+            .send_packet(&CGameEvent::new(GameEvent::BeginRaining, 0.0))
+            .await;
+        player
+            .client
+            .send_packet(&CGameEvent::new(GameEvent::RainLevelChange, 1.0))
+            .await;
+        player
+            .client
+            .send_packet(&CGameEvent::new(GameEvent::ThunderLevelChange, 1.0))
+            .await;
+    }
+
+    /// Strikes the block with lightning: everyone sees the bolt and hears the thunder, entities close to it get hurt
+    /// and it sets the block on fire, see [`pumpkin_config::LightningConfig`]
+    pub async fn strike_lightning(&self, server: &Server, position: WorldPosition) {
+        let center = Vector3::new(
+            f64::from(position.0.x) + 0.5,
+            f64::from(position.0.y),
+            f64::from(position.0.z) + 0.5,
+        );
+        // The client removes the bolt by itself once its flash is over
+        self.broadcast_packet_all(&CSpawnEntity::new(
+            server.new_entity_id().into(),
+            Uuid::new_v4(),
+            (EntityType::LightningBolt as i32).into(),
+            center.x,
+            center.y,
+            center.z,
+            0.0,
+            0.0,
+            0.0,
+            0.into(),
+            0.0,
+            0.0,
+            0.0,
+        ))
+        .await;
+
+        let (thunder_pitch, impact_pitch) = {
+            let mut rng = self.random.lock().await;
+            (rng.gen_range(0.8..1.0), rng.gen_range(0.5..0.7))
+        };
+        self.play_sound_in_range(
+            sound!("entity.lightning_bolt.thunder"),
+            SoundCategory::Weather,
+            &center,
+            10_000.0,
+            thunder_pitch,
+        )
+        .await;
+        self.play_sound_in_range(
+            sound!("entity.lightning_bolt.impact"),
+            SoundCategory::Weather,
+            &center,
+            2.0,
+            impact_pitch,
+        )
+        .await;
+
+        let config = &ADVANCED_CONFIG.lightning;
+        if config.fire
+            && matches!(
+                self.config.difficulty,
+                Difficulty::Normal | Difficulty::Hard
+            )
+        {
+            self.ignite(position).await;
+            for _ in 0..EXTRA_FIRES {
+                let offset = {
+                    let mut rng = self.random.lock().await;
+                    Vector3::new(
+                        rng.gen_range(-1..=1),
+                        rng.gen_range(-1..=1),
+                        rng.gen_range(-1..=1),
+                    )
+                };
+                self.ignite(WorldPosition(position.0.add(&offset))).await;
+            }
+        }
+        self.damage_struck_entities(center, config.damage).await;
+    }
+
+    /// Places fire at the position if it is empty and the block below can burn or hold it
+    async fn ignite(&self, position: WorldPosition) {
+        if !self
+            .get_block_state(position)
+            .await
+            .is_ok_and(|state| state.air)
+        {
+            return;
+        }
+        let below = WorldPosition(position.0.add(&Vector3::new(0, -1, 0)));
+        let Ok(below) = self.get_block_state(below).await else {
+            return;
+        };
+        if below.burnable || below.is_surface_solid() {
+            self.set_block_state(position, block_state!("fire").state_id)
+                .await;
+        }
+    }
+
+    async fn damage_struck_entities(&self, center: Vector3<f64>, damage: f32) {
+        let is_hit = |pos: Vector3<f64>| {
+            (pos.x - center.x).abs() <= HIT_RADIUS
+                && (pos.z - center.z).abs() <= HIT_RADIUS
+                && pos.y >= center.y - HIT_RADIUS
+                && pos.y <= center.y + HIT_HEIGHT
+        };
+        let players: Vec<_> = self
+            .current_players
+            .lock()
+            .await
+            .values()
+            .filter(|player| {
+                !matches!(
+                    player.gamemode.load(),
+                    GameMode::Creative | GameMode::Spectator
+                ) && is_hit(player.living_entity.entity.pos.load())
+            })
+            .cloned()
+            .collect();
+        for player in players {
+            if player
+                .living_entity
+                .damage(damage, LIGHTNING_DAMAGE_TYPE)
+                .await
+            {
+                player.on_death(DeathCause::Lightning).await;
+            }
+        }
+        let mobs: Vec<_> = self
+            .current_living_mobs
+            .lock()
+            .await
+            .values()
+            .filter(|mob| {
+                mob.living_entity.health.load() > 0.0 && is_hit(mob.living_entity.entity.pos.load())
+            })
+            .cloned()
+            .collect();
+        for mob in mobs {
+            let living = &mob.living_entity;
+            if living.damage(damage, LIGHTNING_DAMAGE_TYPE).await {
+                living
+                    .entity
+                    .world()
+                    .remove_mob_entity(living.clone())
+                    .await;
+            }
+        }
+    }
+
+    /// Strikes random blocks in the chunks near players during a thunderstorm
+    pub(super) async fn tick_lightning(&self, server: &Server) {
+        if !ADVANCED_CONFIG.lightning.enabled
+            || !self.is_thundering()
+            || self.dimension_type != DimensionType::Overworld
+        {
+            return;
+        }
+        for chunk in self.ticked_chunks().await {
+            let column = {
+                let mut rng = self.random.lock().await;
+                if rng.gen_range(0..STRIKE_CHANCE) != 0 {
+                    continue;
+                }
+                Vector2::new(
+                    chunk.x * 16 + rng.gen_range(0..16),
+                    chunk.z * 16 + rng.gen_range(0..16),
+                )
+            };
+            // Lightning strikes the block above the highest one, where the rain lands
+            let y = self.get_top_block(column).await + 1;
+            self.strike_lightning(server, WorldPosition(Vector3::new(column.x, y, column.z)))
+                .await;
+        }
+    }
+}
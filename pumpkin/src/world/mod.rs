@@ -1,63 +1,130 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{atomic::AtomicBool, Arc},
+    time::{Duration, Instant},
+};
 
+use bytes::BytesMut;
+
+pub mod active_chunks;
+mod ambient_sound;
+mod anti_xray;
+mod block_updates;
+pub mod chunk_change;
+mod chunk_index;
+mod chunk_loading;
+pub mod clock;
+mod entity_count;
+mod entity_interaction;
+mod entity_limit;
+mod entity_push;
+mod experience_orb;
+mod forced_gamemode;
+mod item_frame;
+mod leash;
 pub mod level_time;
+mod light;
+mod lightning;
+mod pathfinding;
 pub mod player_chunker;
-
+mod player_list;
+pub mod portal;
+pub mod projectile;
+mod ranged_bossbar;
+mod redstone;
+pub mod region;
+pub mod replace_chunk;
+pub mod schematic;
+pub mod snapshot;
+mod snow;
+mod spawn;
+mod spawner;
+pub mod tick_timings;
+pub mod world_config;
+
+use crate::entity::mob::activation::{is_active, ActivationCategory};
+use crate::log_context::{player_debug, player_info};
 use crate::{
+    block::mining::{self, ToolType},
     command::client_cmd_suggestions,
     entity::{living::LivingEntity, mob::MobEntity, player::Player, Entity},
     error::PumpkinError,
     server::Server,
 };
+use bossbar::Bossbar;
+use chunk_change::PlayerChunkChangeHandler;
+use chunk_index::PlayerChunkIndex;
+use clock::{Clock, SystemClock};
 use level_time::LevelTime;
-use pumpkin_config::BasicConfiguration;
+use player_chunker::{ChunkSelector, DefaultChunkSelector};
+use pumpkin_config::{BasicConfiguration, ADVANCED_CONFIG, BASIC_CONFIG};
 use pumpkin_core::math::vector2::Vector2;
 use pumpkin_core::math::{position::WorldPosition, vector3::Vector3};
 use pumpkin_core::text::{color::NamedColor, TextComponent};
+use pumpkin_core::GameMode;
 use pumpkin_entity::{entity_type::EntityType, pose::EntityPose, EntityId};
+use pumpkin_macros::particle;
 use pumpkin_protocol::{
-    client::play::CLevelEvent,
-    codec::{identifier::Identifier, var_int::VarInt},
+    client::play::{Animation, CEntityAnimation, CLevelEvent, CPlayDisconnect},
+    codec::{identifier::Identifier, var_int::VarInt, Codec},
+    packet_encoder::serialize_packet,
 };
 use pumpkin_protocol::{
-    client::play::{CBlockUpdate, CRespawn, CSoundEffect, CWorldEvent},
+    client::play::{CBlockUpdate, CMultiBlockUpdate, CParticle, CRespawn, CSoundEffect},
     SoundCategory,
 };
 use pumpkin_protocol::{
     client::play::{
         CChunkData, CGameEvent, CLogin, CPlayerInfoUpdate, CRemoveEntities, CRemovePlayerInfo,
-        CSetEntityMetadata, CSpawnEntity, GameEvent, Metadata, PlayerAction,
+        CSetEntityMetadata, CSpawnEntity, GameEvent, Metadata,
     },
     ClientPacket,
 };
 use pumpkin_registry::DimensionType;
+use pumpkin_world::biome::Biome;
 use pumpkin_world::chunk::ChunkData;
 use pumpkin_world::level::Level;
 use pumpkin_world::{
-    block::block_registry::{
-        get_block_and_state_by_state_id, get_block_by_state_id, get_state_by_state_id,
+    block::{
+        block_registry::{
+            get_block_and_state_by_state_id, get_block_by_state_id, get_state_by_state_id,
+            state_opacity,
+        },
+        BlockSoundGroup,
     },
-    coordinates::ChunkRelativeBlockCoordinates,
+    coordinates::{ChunkRelativeBlockCoordinates, XZBlockCoordinates},
 };
-use rand::{thread_rng, Rng};
+use rand::{rngs::StdRng, thread_rng, Rng, SeedableRng};
 use scoreboard::Scoreboard;
+pub use spawn::SpawnPoint;
 use thiserror::Error;
+use tick_timings::{TickTimingHistory, TickTimings};
+use tokio::sync::{mpsc, RwLock};
 use tokio::sync::{mpsc::Receiver, Mutex};
-use tokio::{
-    runtime::Handle,
-    sync::{mpsc, RwLock},
-};
+use tokio::task::JoinHandle;
+use world_config::WorldConfig;
 use worldborder::Worldborder;
 
+pub mod block_description;
 pub mod bossbar;
+pub mod clone;
 pub mod custom_bossbar;
 pub mod scoreboard;
 pub mod worldborder;
 
+/// How many loaded chunks can wait to be sent, the others wait in a backlog until the channel has room
+const CHUNK_CHANNEL_SIZE: usize = 64;
+/// How long dead mobs stay around to show their death animation before they get removed
+const DEATH_ANIMATION: Duration = Duration::from_millis(2000);
+
 #[derive(Debug, Error)]
 pub enum GetBlockError {
     BlockOutOfWorldBounds,
     InvalidBlockId,
+    /// The chunk of the block took too long to load
+    Timeout,
+    /// The chunk of the block could neither be loaded nor generated
+    ChunkUnavailable,
 }
 
 impl std::fmt::Display for GetBlockError {
@@ -80,6 +147,11 @@ impl PumpkinError for GetBlockError {
     }
 }
 
+/// A chunk could neither be loaded nor generated, e.g. because its region file is corrupted
+#[derive(Debug, Error)]
+#[error("Chunk {0:?} could not be loaded or generated")]
+pub struct ChunkUnavailable(pub Vector2<i32>);
+
 /// Represents a Minecraft world, containing entities, players, and the underlying level data.
 ///
 /// Each dimension (Overworld, Nether, End) typically has its own `World`.
@@ -104,12 +176,70 @@ pub struct World {
     pub level_time: Mutex<LevelTime>,
     /// The type of dimension the world is in
     pub dimension_type: DimensionType,
+    /// The positions of the nether portal blocks in all loaded chunks, used to link portals
+    pub portals: Mutex<HashSet<WorldPosition>>,
+    /// The boss bar shown to all players in the world, see [`World::show_custom_bossbar`]
+    pub custom_bossbar: Mutex<Option<Bossbar>>,
+    /// Decides which chunks get sent to players and in which order
+    pub chunk_selector: Arc<dyn ChunkSelector>,
+    /// Settings overriding the server configuration for this world
+    pub config: WorldConfig,
+    /// Random numbers seeded by the world seed, so results are reproducible for the same world,
+    /// see [`World::with_random_seed`]
+    pub random: Mutex<StdRng>,
+    /// The real time, measures how long ticks take and times real time delays, see [`World::with_clock`]
+    clock: Arc<dyn Clock>,
+    /// The chunk every player is in, see [`World::players_in_chunk`]
+    player_chunks: Mutex<PlayerChunkIndex>,
+    /// How long the last ticks took, see [`World::last_tick_timings`]
+    tick_timings: Mutex<TickTimingHistory>,
+    /// The world spawn, `None` until a new world needs it, see [`World::default_spawn`]
+    default_spawn: Mutex<Option<SpawnPoint>>,
+    /// Who holds the leash of which entity, see [`World::set_leash`]
+    leashes: Mutex<leash::Leashes>,
+    /// The experience orbs nobody collected yet, see [`World::spawn_experience_orb`]
+    experience_orbs: Mutex<experience_orb::ExperienceOrbs>,
+    /// The light spread by blocks emitting light, see [`World::block_light`]
+    block_light_levels: Mutex<light::BlockLight>,
+    /// The sounds blocks play now and then, see [`World::set_ambient_sound`]
+    ambient_sounds: RwLock<ambient_sound::AmbientSounds>,
+    /// Boss bars shown to the players close to them, see [`World::add_ranged_bossbar`]
+    ranged_bossbars: Mutex<ranged_bossbar::RangedBossbars>,
+    /// Arrows and other projectiles, see [`World::spawn_projectile`]
+    projectiles: Mutex<projectile::Projectiles>,
+    /// The entities mob spawners spawn, see [`World::set_spawner_entity`]
+    spawners: Mutex<spawner::Spawners>,
+    /// Item frames and the images of maps, see [`World::spawn_item_frame`]
+    item_frames: Mutex<item_frame::ItemFrames>,
+    /// The entities keeping their chunk loaded, see [`World::set_entity_chunk_loading`]
+    chunk_loading_entities: Mutex<chunk_loading::ChunkLoadingEntities>,
+    /// The chunks with something going on, see [`World::set_chunk_activity`]
+    active_chunks: Mutex<active_chunks::ActiveChunks>,
+    /// Who spawned which entity, see [`World::may_spawn_entity_for`]
+    player_entities: Mutex<entity_limit::PlayerEntities>,
+    /// Called when a player crosses into another chunk, see [`World::on_player_chunk_change`]
+    chunk_change_handlers: RwLock<Vec<Arc<dyn PlayerChunkChangeHandler>>>,
+    /// Whether system messages are only logged, see [`World::set_broadcast_suppressed`]
+    broadcast_suppressed: AtomicBool,
+    /// How many blocks changed in every chunk this tick, see [`World::send_block_update`]
+    block_updates: Mutex<block_updates::BlockUpdates>,
+    /// Areas denying PvP, building or mob spawning, see [`World::define_region`]
+    regions: RwLock<region::Regions>,
+    /// Whether there is a thunderstorm, see [`World::set_thundering`]
+    thundering: AtomicBool,
+    /// Dead mobs still showing their death animation and when they get removed, see [`World::remove_mob_entity`]
+    dying_mobs: Mutex<Vec<(Instant, Arc<LivingEntity>)>>,
     // TODO: entities
 }
 
 impl World {
     #[must_use]
     pub fn load(level: Level, dimension_type: DimensionType) -> Self {
+        let seed = level.seed.0;
+        let default_spawn = level.spawn().map(|(position, angle)| SpawnPoint {
+            position: WorldPosition(position),
+            angle,
+        });
         Self {
             level: Arc::new(level),
             current_players: Arc::new(Mutex::new(HashMap::new())),
@@ -118,25 +248,112 @@ impl World {
             worldborder: Mutex::new(Worldborder::new(0.0, 0.0, 29_999_984.0, 0, 0, 0)),
             level_time: Mutex::new(LevelTime::new()),
             dimension_type,
+            portals: Mutex::new(HashSet::new()),
+            custom_bossbar: Mutex::new(None),
+            chunk_selector: Arc::new(DefaultChunkSelector),
+            config: WorldConfig {
+                respawn_dimension: WorldConfig::respawn_dimension_of(dimension_type),
+                ..WorldConfig::from(&*BASIC_CONFIG)
+            },
+            random: Mutex::new(StdRng::seed_from_u64(seed)),
+            clock: Arc::new(SystemClock),
+            player_chunks: Mutex::new(PlayerChunkIndex::default()),
+            tick_timings: Mutex::new(TickTimingHistory::default()),
+            default_spawn: Mutex::new(default_spawn),
+            leashes: Mutex::new(leash::Leashes::default()),
+            experience_orbs: Mutex::new(experience_orb::ExperienceOrbs::default()),
+            block_light_levels: Mutex::new(light::BlockLight::default()),
+            ambient_sounds: RwLock::new(ambient_sound::AmbientSounds::default()),
+            ranged_bossbars: Mutex::new(ranged_bossbar::RangedBossbars::default()),
+            projectiles: Mutex::new(projectile::Projectiles::default()),
+            spawners: Mutex::new(spawner::Spawners::default()),
+            item_frames: Mutex::new(item_frame::ItemFrames::default()),
+            chunk_loading_entities: Mutex::new(chunk_loading::ChunkLoadingEntities::default()),
+            active_chunks: Mutex::new(active_chunks::ActiveChunks::default()),
+            player_entities: Mutex::new(entity_limit::PlayerEntities::default()),
+            chunk_change_handlers: RwLock::new(Vec::new()),
+            broadcast_suppressed: AtomicBool::new(false),
+            block_updates: Mutex::new(block_updates::BlockUpdates::default()),
+            regions: RwLock::new(region::Regions::default()),
+            thundering: AtomicBool::new(false),
+            dying_mobs: Mutex::new(Vec::new()),
         }
     }
 
+    /// Replaces the default chunk selection, e.g. to load chunks in a different shape
+    #[must_use]
+    pub fn with_chunk_selector(mut self, chunk_selector: Arc<dyn ChunkSelector>) -> Self {
+        self.chunk_selector = chunk_selector;
+        self
+    }
+
+    /// Overrides the server configuration for this world
+    #[must_use]
+    pub const fn with_config(mut self, config: WorldConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Seeds the random numbers of the world with another seed than the world seed, e.g. to make tests reproducible
+    #[must_use]
+    pub fn with_random_seed(mut self, seed: u64) -> Self {
+        self.random = Mutex::new(StdRng::seed_from_u64(seed));
+        self
+    }
+
+    /// Replaces the real time tick timings and real time delays are measured with, e.g. with a [`clock::MockClock`] in tests
+    #[must_use]
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// A superflat overworld which only lives in memory, see [`Level::in_memory`].
+    ///
+    /// No player can join it, so broadcasts reach nobody
+    #[cfg(test)]
+    pub fn test_world() -> Self {
+        Self::load(Level::in_memory(), DimensionType::Overworld)
+    }
+
     pub async fn save(&self) {
+        // Decide on the spawn of a new world, so it doesn't move after a restart
+        self.default_spawn().await;
         self.level.save().await;
     }
 
+    /// Saves the world in the background while keeping all chunks loaded, used for periodic saving.
+    ///
+    /// Chunks can't be written yet, so this only saves the world info
+    pub async fn autosave(&self) {
+        self.default_spawn().await;
+        let level = self.level.clone();
+        tokio::task::spawn_blocking(move || {
+            let start = std::time::Instant::now();
+            level.save_info();
+            log::info!("Autosaved world info in {}ms", start.elapsed().as_millis());
+        });
+    }
+
     /// Broadcasts a packet to all connected players within the world.
     ///
-    /// Sends the specified packet to every player currently logged in to the world.
+    /// Sends the specified packet to every player currently logged in to the world. Players in the other worlds
+    /// don't get it, see [`Server::broadcast_packet_all`] to reach every player on the server.
     ///
     /// **Note:** This function acquires a lock on the `current_players` map, ensuring thread safety.
     pub async fn broadcast_packet_all<P>(&self, packet: &P)
     where
         P: ClientPacket,
     {
+        // Only serialize once, no matter how many players there are
+        self.broadcast_raw(&serialize_packet(packet)).await;
+    }
+
+    /// Sends a packet serialized by [`serialize_packet`] to every player in the world
+    pub async fn broadcast_raw(&self, data: &[u8]) {
         let current_players = self.current_players.lock().await;
         for player in current_players.values() {
-            player.client.send_packet(packet).await;
+            player.client.send_serialized(data).await;
         }
     }
 
@@ -149,12 +366,184 @@ impl World {
     where
         P: ClientPacket,
     {
+        let data = serialize_packet(packet);
         let current_players = self.current_players.lock().await;
         for (_, player) in current_players.iter().filter(|c| !except.contains(c.0)) {
+            player.client.send_serialized(&data).await;
+        }
+    }
+
+    /// Broadcasts a packet to all players within the world whose view includes the given chunk.
+    pub async fn broadcast_to_chunk_watchers<P>(&self, chunk_pos: Vector2<i32>, packet: &P)
+    where
+        P: ClientPacket,
+    {
+        for player in self.players_watching_chunk(chunk_pos).await {
             player.client.send_packet(packet).await;
         }
     }
 
+    /// Plays the animation of the entity for every player who can see it.
+    /// The entity itself is left out, a player's own client already animates their arm
+    pub async fn send_entity_animation(&self, entity_id: EntityId, animation: Animation) {
+        let mut chunk_pos = self
+            .get_player_by_entityid(entity_id)
+            .await
+            .map(|player| player.living_entity.entity.chunk_pos.load());
+        if chunk_pos.is_none() {
+            chunk_pos = self
+                .get_living_entity_by_entityid(entity_id)
+                .await
+                .map(|living_entity| living_entity.entity.chunk_pos.load());
+        }
+        let Some(chunk_pos) = chunk_pos else {
+            return;
+        };
+        let data = serialize_packet(&CEntityAnimation::new(VarInt(entity_id), animation as u8));
+        for player in self.players_watching_chunk(chunk_pos).await {
+            if player.entity_id() != entity_id {
+                player.client.send_serialized(&data).await;
+            }
+        }
+    }
+
+    /// Whether the block is inside of the world border, while the border moves this is where it is right now
+    pub async fn is_within_border(&self, position: WorldPosition) -> bool {
+        self.worldborder
+            .lock()
+            .await
+            .contains_block(&position, self.clock.now())
+    }
+
+    /// Stops or resumes sending join, leave and other system messages to players, e.g. during maintenance.
+    /// Suppressed messages are still logged to the console and messages for operators still get sent,
+    /// see [`Server::broadcast_op_message`].
+    ///
+    /// This only affects chat and system text, gameplay packets are sent as usual
+    pub fn set_broadcast_suppressed(&self, suppressed: bool) {
+        self.broadcast_suppressed
+            .store(suppressed, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    /// Whether system messages are currently not sent to players, see [`World::set_broadcast_suppressed`]
+    pub fn is_broadcast_suppressed(&self) -> bool {
+        self.broadcast_suppressed
+            .load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    /// Logs the message to the console and sends it to every player in the world,
+    /// unless broadcasts are suppressed with [`World::set_broadcast_suppressed`]
+    pub async fn broadcast_system_message(&self, message: &TextComponent) {
+        log::info!("{}", message.to_pretty_console());
+        if self.is_broadcast_suppressed() {
+            return;
+        }
+        for player in self.current_players.lock().await.values() {
+            player.send_system_message(message).await;
+        }
+    }
+
+    /// Returns all players who are currently in the chunk
+    pub async fn players_in_chunk(&self, chunk_pos: Vector2<i32>) -> Vec<Arc<Player>> {
+        let uuids: Vec<uuid::Uuid> = self
+            .player_chunks
+            .lock()
+            .await
+            .get(&chunk_pos)
+            .copied()
+            .collect();
+        let current_players = self.current_players.lock().await;
+        uuids
+            .iter()
+            .filter_map(|uuid| current_players.get(uuid).cloned())
+            .collect()
+    }
+
+    /// Returns all players whose view includes the chunk
+    pub async fn players_watching_chunk(&self, chunk_pos: Vector2<i32>) -> Vec<Arc<Player>> {
+        // Nobody can see further than the world's view distance, so only players in these chunks can watch it
+        let view_distance = i32::from(self.config.view_distance.get());
+        let uuids: Vec<uuid::Uuid> = self
+            .player_chunks
+            .lock()
+            .await
+            .occupied_chunks()
+            .filter(|(chunk, _)| {
+                (chunk.x - chunk_pos.x).abs() <= view_distance
+                    && (chunk.z - chunk_pos.z).abs() <= view_distance
+            })
+            .flat_map(|(_, players)| players.iter().copied())
+            .collect();
+        let current_players = self.current_players.lock().await;
+        uuids
+            .iter()
+            .filter_map(|uuid| current_players.get(uuid))
+            .filter(|player| {
+                let watched = player.watched_section.load();
+                self.chunk_selector
+                    .is_selected(watched.center, watched.view_distance, chunk_pos)
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Checks if the player is allowed to be in the air without falling, so flight checks must not punish them.
+    ///
+    /// This is the case for players who are allowed to fly, are gliding with an elytra or just got teleported
+    pub async fn is_flight_exempt(&self, player: &Player) -> bool {
+        // The player is still switching worlds
+        if !self
+            .current_players
+            .lock()
+            .await
+            .contains_key(&player.gameprofile.id)
+        {
+            return true;
+        }
+        if matches!(
+            player.gamemode.load(),
+            GameMode::Creative | GameMode::Spectator
+        ) {
+            return true;
+        }
+        // Floating in place while waiting for the chunk, see `Player::hold_until_chunk_sent`
+        if player.held_for_chunk.load().is_some()
+            || player
+                .living_entity
+                .entity
+                .no_gravity
+                .load(std::sync::atomic::Ordering::Relaxed)
+        {
+            return true;
+        }
+        let abilities = player.abilities.lock().await;
+        if abilities.allow_flying || abilities.flying {
+            return true;
+        }
+        drop(abilities);
+        let entity = &player.living_entity.entity;
+        if entity
+            .fall_flying
+            .load(std::sync::atomic::Ordering::Relaxed)
+        {
+            return true;
+        }
+        // TODO: Exempt players with levitation or slow falling and players riding a vehicle once we have effects and vehicles
+        player
+            .ticks_since_teleport
+            .load(std::sync::atomic::Ordering::Relaxed)
+            < ADVANCED_CONFIG.movement.teleport_grace_ticks
+    }
+
+    /// Moves the player to their current chunk in the chunk index, called whenever the player moves
+    /// Keeps track of the chunk the player is in, returns the chunk they left if they crossed into another one
+    pub async fn update_player_chunk(&self, player: &Player) -> Option<Vector2<i32>> {
+        self.player_chunks.lock().await.update(
+            player.gameprofile.id,
+            player.living_entity.entity.chunk_pos.load(),
+        )
+    }
+
     pub async fn play_sound(
         &self,
         sound_id: u16,
@@ -176,6 +565,33 @@ impl World {
         .await;
     }
 
+    /// Plays the sound only to the players close enough to hear it.
+    /// Like Vanilla, sounds reach 16 blocks, louder sounds reach further
+    pub async fn play_sound_in_range(
+        &self,
+        sound_id: u16,
+        category: SoundCategory,
+        position: &Vector3<f64>,
+        volume: f32,
+        pitch: f32,
+    ) {
+        let range = 16.0 * f64::from(volume.max(1.0));
+        let packet = CSoundEffect::new(
+            VarInt(i32::from(sound_id)),
+            None,
+            category,
+            position.x,
+            position.y,
+            position.z,
+            volume,
+            pitch,
+            thread_rng().gen::<f64>(),
+        );
+        for player in self.get_nearby_players(*position, range).await.values() {
+            player.client.send_packet(&packet).await;
+        }
+    }
+
     pub async fn play_block_sound(&self, sound_id: u16, position: WorldPosition) {
         let new_vec = Vector3::new(
             f64::from(position.0.x) + 0.5,
@@ -196,32 +612,161 @@ impl World {
             .await;
     }
 
-    pub async fn tick(&self) {
+    /// Sets the time of day and sends it to all players right away instead of waiting for the next
+    /// time sync
+    pub async fn set_time(&self, ticks: u64) {
+        let mut level_time = self.level_time.lock().await;
+        level_time.set_time(ticks.try_into().unwrap_or(i64::MAX));
+        level_time.send_time(self).await;
+    }
+
+    /// Adds to the time of day and sends it to all players right away instead of waiting for the
+    /// next time sync
+    pub async fn add_time(&self, delta: i64) {
+        let mut level_time = self.level_time.lock().await;
+        level_time.add_time(delta);
+        level_time.send_time(self).await;
+    }
+
+    /// Advances the world by exactly one tick. The server calls this every tick, tests can call it
+    /// as often as they need to, see [`World::with_clock`] and [`World::with_random_seed`]
+    pub async fn tick_once(&self, server: &Server) {
+        let start = self.clock.now();
         // world ticks
-        {
+        let autosave = {
             let mut level_time = self.level_time.lock().await;
             level_time.tick_time();
             if level_time.world_age % 20 == 0 {
                 level_time.send_time(self).await;
             }
+            let autosave_interval = BASIC_CONFIG.autosave_interval_ticks as i64;
+            autosave_interval != 0 && level_time.world_age % autosave_interval == 0
+        };
+        if autosave {
+            self.autosave().await;
         }
+        let time_end = self.clock.now();
         // player ticks
-        for player in self.current_players.lock().await.values() {
+        let mut player_positions = Vec::new();
+        // Players may get kicked while ticking, which needs the players
+        let players: Vec<Arc<Player>> = self
+            .current_players
+            .lock()
+            .await
+            .values()
+            .cloned()
+            .collect();
+        for player in &players {
+            player.rate_limiter.tick();
             player.tick().await;
+            player_positions.push(player.living_entity.entity.pos.load());
+        }
+        // Don't wait for the client to ask, it does so too but it may take a while
+        if self.config.immediate_respawn {
+            for player in &players {
+                server.respawn_dead_player(player).await;
+            }
         }
+        let players_end = self.clock.now();
         // entites tick
-        for entity in self.current_living_mobs.lock().await.values() {
-            entity.tick().await;
+        let activation = &ADVANCED_CONFIG.entity_activation;
+        let chunk_loaders = self.chunk_loading_entity_ids().await;
+        for mob in self.current_living_mobs.lock().await.values() {
+            let entity = &mob.living_entity.entity;
+            if activation.enabled && !chunk_loaders.contains(&entity.entity_id) {
+                let range =
+                    ActivationCategory::from_entity_type(&entity.entity_type).range(activation);
+                if !is_active(entity.pos.load(), range, &player_positions) {
+                    // Too far away from every player to matter, skip the AI
+                    continue;
+                }
+            }
+            mob.tick().await;
+        }
+        self.tick_leashes().await;
+        self.tick_experience_orbs().await;
+        self.tick_projectiles().await;
+        self.tick_ambient_sounds().await;
+        self.tick_ranged_bossbars().await;
+        self.tick_chunk_loading_entities().await;
+        let unloaded = self.level.tick_chunk_cache();
+        self.forget_block_light(&unloaded).await;
+        self.forget_portals(&unloaded).await;
+        let loaded = self.level.take_loaded_chunks();
+        self.index_portals(&loaded).await;
+        self.tick_portals(server, &players).await;
+        // block ticks
+        let blocks_start = self.clock.now();
+        self.tick_active_chunks().await;
+        self.tick_block_updates().await;
+        self.tick_block_light().await;
+        self.tick_snow().await;
+        self.tick_lightning(server).await;
+        let blocks_end = self.clock.now();
+        // the rest of the entity ticks
+        self.tick_dying_mobs().await;
+        entity_push::tick_entity_push(self).await;
+        // Send all metadata changes of this tick at once.
+        // Flushing broadcasts to the players, which needs their lock, so it can't be held meanwhile
+        let players: Vec<Arc<Player>> = self
+            .current_players
+            .lock()
+            .await
+            .values()
+            .cloned()
+            .collect();
+        for player in players {
+            player.living_entity.entity.flush_metadata().await;
+        }
+        let mobs: Vec<Arc<MobEntity>> = self
+            .current_living_mobs
+            .lock()
+            .await
+            .values()
+            .cloned()
+            .collect();
+        for mob in mobs {
+            mob.living_entity.entity.flush_metadata().await;
+        }
+
+        let timings = TickTimings {
+            time: time_end - start,
+            players: players_end - time_end,
+            entities: (blocks_start - players_end) + (self.clock.now() - blocks_end),
+            blocks: blocks_end - blocks_start,
+        };
+        let threshold = ADVANCED_CONFIG.logging.slow_tick_threshold_ms;
+        if threshold != 0 && timings.total().as_millis() > u128::from(threshold) {
+            log::warn!(
+                "World {} tick took {}ms (time: {}ms, players: {}ms, entities: {}ms, blocks: {}ms)",
+                self.dimension_type.name(),
+                timings.total().as_millis(),
+                timings.time.as_millis(),
+                timings.players.as_millis(),
+                timings.entities.as_millis(),
+                timings.blocks.as_millis()
+            );
         }
+        self.tick_timings.lock().await.push(timings);
     }
 
-    /// Gets the y position of the first non air block from the top down
+    /// How long the phases of the last tick took, `None` if the world did not tick yet
+    pub async fn last_tick_timings(&self) -> Option<TickTimings> {
+        self.tick_timings.lock().await.last()
+    }
+
+    /// The average time the phases took over the last ticks
+    pub async fn average_tick_timings(&self) -> TickTimings {
+        self.tick_timings.lock().await.average()
+    }
+
+    /// The y of the highest block entities can stand on in the column, so fluids and plants are skipped.
+    /// Returns the top of the world if there is no such block
     pub async fn get_top_block(&self, position: Vector2<i32>) -> i32 {
         for y in (-64..=319).rev() {
             let pos = WorldPosition(Vector3::new(position.x, y, position.z));
-            let block = self.get_block_state(pos).await;
-            if let Ok(block) = block {
-                if block.air {
+            if let Ok(state) = self.get_block_state(pos).await {
+                if !state.is_surface_solid() {
                     continue;
                 }
             }
@@ -230,6 +775,14 @@ impl World {
         319
     }
 
+    /// The biome at the position, see [`Level::biome`]
+    pub fn get_biome(&self, position: WorldPosition) -> Biome {
+        self.level.biome(XZBlockCoordinates {
+            x: position.0.x,
+            z: position.0.z,
+        })
+    }
+
     #[expect(clippy::too_many_lines)]
     pub async fn spawn_player(
         &self,
@@ -243,11 +796,7 @@ impl World {
         // This code follows the vanilla packet order
         let entity_id = player.entity_id();
         let gamemode = player.gamemode.load();
-        log::debug!(
-            "spawning player {}, entity id {}",
-            player.gameprofile.name,
-            entity_id
-        );
+        player_debug!(player, "Spawning player with entity id {}", entity_id);
 
         // login packet for our new player
         player
@@ -257,58 +806,62 @@ impl World {
                 base_config.hardcore,
                 &dimensions,
                 base_config.max_players.into(),
-                base_config.view_distance.get().into(), //  TODO: view distance
+                self.config.view_distance.get().into(),
                 base_config.simulation_distance.get().into(), // TODO: sim view dinstance
-                false,
-                true,
+                player
+                    .reduced_debug_info
+                    .load(std::sync::atomic::Ordering::Relaxed),
+                !self.config.immediate_respawn,
                 false,
                 (self.dimension_type as u8).into(),
                 self.dimension_type.name(),
                 0, // seed
                 gamemode as u8,
-                base_config.default_gamemode as i8,
-                false,
+                self.config.default_gamemode as i8,
                 false,
-                None,
-                0.into(),
+                self.level.is_flat(),
+                None, // death location
+                player
+                    .portal_cooldown
+                    .load(std::sync::atomic::Ordering::Relaxed)
+                    .into(), // portal cooldown
                 0.into(),
                 false,
             ))
             .await;
         // permissions, i. e. the commands a player may use
+        player
+            .permission_lvl
+            .store(server.permission_level(&player.gameprofile.id).await);
+        player.send_difficulty(self).await;
         player.send_permission_lvl_update().await;
         client_cmd_suggestions::send_c_commands_packet(&player, &server.command_dispatcher).await;
         // teleport
-        let mut position = Vector3::new(10.0, 120.0, 10.0);
-        let yaw = 10.0;
-        let pitch = 10.0;
-
-        let top = self
-            .get_top_block(Vector2::new(position.x as i32, position.z as i32))
-            .await;
-        position.y = f64::from(top + 1);
+        // TODO: Use the saved position of returning players, we don't save player data yet so everyone counts as a new player
+        let position = self.spawn_position().await;
+        let yaw = self.default_spawn().await.angle;
+        let pitch = 0.0;
 
-        log::debug!("Sending player teleport to {}", player.gameprofile.name);
+        player_debug!(player, "Sending player teleport");
         player.request_teleport(position, yaw, pitch).await;
+        player.hold_until_chunk_sent().await;
 
         player.living_entity.last_pos.store(position);
 
-        let gameprofile = &player.gameprofile;
+        player_debug!(player, "Sending inventory");
+        player.load_inventory().await;
+
+        // Don't show a player who already left to everyone else
+        if Self::spawn_aborted(&player) {
+            return;
+        }
+
         // first send info update to our new player, So he can see his Skin
         // also send his info to everyone else
-        log::debug!("Broadcasting player info for {}", player.gameprofile.name);
+        player_debug!(player, "Broadcasting player info");
         self.broadcast_packet_all(&CPlayerInfoUpdate::new(
-            0x01 | 0x08,
-            &[pumpkin_protocol::client::play::Player {
-                uuid: gameprofile.id,
-                actions: vec![
-                    PlayerAction::AddPlayer {
-                        name: &gameprofile.name,
-                        properties: &gameprofile.properties,
-                    },
-                    PlayerAction::UpdateListed(true),
-                ],
-            }],
+            player_list::LIST_ENTRY_ACTIONS,
+            &[player_list::list_entry(&player)],
         ))
         .await;
         player.update_client_information().await;
@@ -321,28 +874,21 @@ impl World {
                 .iter()
                 .filter(|(c, _)| **c != player.gameprofile.id)
             {
-                let gameprofile = &playerr.gameprofile;
-                entries.push(pumpkin_protocol::client::play::Player {
-                    uuid: gameprofile.id,
-                    actions: vec![
-                        PlayerAction::AddPlayer {
-                            name: &gameprofile.name,
-                            properties: &gameprofile.properties,
-                        },
-                        PlayerAction::UpdateListed(true),
-                    ],
-                });
+                entries.push(player_list::list_entry(playerr));
             }
-            log::debug!("Sending player info to {}", player.gameprofile.name);
+            player_debug!(player, "Sending player info");
             player
                 .client
-                .send_packet(&CPlayerInfoUpdate::new(0x01 | 0x08, &entries))
+                .send_packet(&CPlayerInfoUpdate::new(
+                    player_list::LIST_ENTRY_ACTIONS,
+                    &entries,
+                ))
                 .await;
         };
 
         let gameprofile = &player.gameprofile;
 
-        log::debug!("Broadcasting player spawn for {}", player.gameprofile.name);
+        player_debug!(player, "Broadcasting player spawn");
         // spawn player for every client
         self.broadcast_packet_except(
             &[player.gameprofile.id],
@@ -376,7 +922,7 @@ impl World {
             let entity = &existing_player.living_entity.entity;
             let pos = entity.pos.load();
             let gameprofile = &existing_player.gameprofile;
-            log::debug!("Sending player entities to {}", player.gameprofile.name);
+            player_debug!(player, "Sending player entities");
             player
                 .client
                 .send_packet(&CSpawnEntity::new(
@@ -395,7 +941,38 @@ impl World {
                     0.0,
                 ))
                 .await;
+            entity.send_custom_name(&player.client).await;
+            entity.send_pose(&player.client).await;
         }
+        // spawn mobs for our client
+        for mob in self.current_living_mobs.lock().await.values() {
+            let entity = &mob.living_entity.entity;
+            let pos = entity.pos.load();
+            player
+                .client
+                .send_packet(&CSpawnEntity::new(
+                    entity.entity_id.into(),
+                    entity.entity_uuid,
+                    (entity.entity_type.clone() as i32).into(),
+                    pos.x,
+                    pos.y,
+                    pos.z,
+                    entity.pitch.load(),
+                    entity.yaw.load(),
+                    entity.head_yaw.load(),
+                    0.into(),
+                    0.0,
+                    0.0,
+                    0.0,
+                ))
+                .await;
+            entity.send_custom_name(&player.client).await;
+            entity.send_pose(&player.client).await;
+        }
+        self.send_leashes(&player).await;
+        self.send_experience_orbs(&player).await;
+        self.send_projectiles(&player).await;
+        self.send_item_frames(&player).await;
         // entity meta data
         // set skin parts
         if let Some(config) = player.client.config.lock().await.as_ref() {
@@ -403,16 +980,20 @@ impl World {
                 entity_id.into(),
                 Metadata::new(17, VarInt(0), config.skin_parts),
             );
-            log::debug!("Broadcasting skin for {}", player.gameprofile.name);
+            player_debug!(player, "Broadcasting skin");
             self.broadcast_packet_all(&packet).await;
         }
 
+        player_debug!(player, "Sending compass target");
+        player.send_compass_target().await;
+
         // Start waiting for level chunks, Sets the "Loading Terrain" screen
-        log::debug!("Sending waiting chunks to {}", player.gameprofile.name);
+        player_debug!(player, "Sending waiting chunks");
         player
             .client
             .send_packet(&CGameEvent::new(GameEvent::StartWaitingChunks, 0.0))
             .await;
+        self.send_thunderstorm(&player).await;
 
         self.worldborder
             .lock()
@@ -423,19 +1004,46 @@ impl World {
         // Sends initial time
         player.send_time(self).await;
 
-        // Spawn in initial chunks
-        player_chunker::player_join(&player).await;
+        player.send_resource_pack().await;
+
+        if Self::spawn_aborted(&player) {
+            return;
+        }
+
+        // Spawn in initial chunks. There is no packet telling the client they are done, it closes the
+        // "Loading Terrain" screen on its own once it has the chunk it is in
+        let chunks_sent = player_chunker::player_join(&player).await;
+        let timeout = ADVANCED_CONFIG.chunk_generation.join_chunks_timeout_ms;
+        if let Some(chunks_sent) = chunks_sent.filter(|_| timeout != 0) {
+            match tokio::time::timeout(Duration::from_millis(timeout), chunks_sent).await {
+                Ok(_) => player_debug!(player, "Initial chunks sent"),
+                Err(_) => player_debug!(player, "Stopped waiting for the initial chunks"),
+            }
+        }
 
-        // if let Some(bossbars) = self..lock().await.get_player_bars(&player.gameprofile.id) {
-        //     for bossbar in bossbars {
-        //         player.send_bossbar(bossbar).await;
-        //     }
-        // }
+        // Send the active boss bars
+        if let Some(bossbar) = self.custom_bossbar.lock().await.as_ref() {
+            player.send_bossbar(bossbar).await;
+        }
+        for bossbar in server.bossbars.lock().await.custom_bossbars.values() {
+            if bossbar.visible && bossbar.player.contains(&player.gameprofile.id) {
+                player.send_bossbar(&bossbar.bossbar_data).await;
+            }
+        }
     }
 
-    pub async fn respawn_player(&self, player: &Arc<Player>, alive: bool) {
+    /// Respawns the player in this world, `death_dimension` is the dimension they died in.
+    ///
+    /// They arrive at `destination`, or at the world spawn if there is none
+    pub async fn respawn_player(
+        &self,
+        player: &Arc<Player>,
+        death_dimension: DimensionType,
+        alive: bool,
+        destination: Option<Vector3<f64>>,
+    ) {
         let last_pos = player.living_entity.last_pos.load();
-        let death_dimension = player.world().dimension_type.name();
+        let death_dimension = death_dimension.name();
         let death_location = WorldPosition(Vector3::new(
             last_pos.x.round() as i32,
             last_pos.y.round() as i32,
@@ -444,8 +1052,6 @@ impl World {
 
         let data_kept = u8::from(alive);
 
-        // TODO: switch world in player entity to new world
-
         player
             .client
             .send_packet(&CRespawn::new(
@@ -455,35 +1061,48 @@ impl World {
                 player.gamemode.load() as u8,
                 player.gamemode.load() as i8,
                 false,
-                false,
+                self.level.is_flat(),
                 Some((death_dimension, death_location)),
-                0.into(),
+                player
+                    .portal_cooldown
+                    .load(std::sync::atomic::Ordering::Relaxed)
+                    .into(), // portal cooldown
                 0.into(),
                 data_kept,
             ))
             .await;
 
-        log::debug!("Sending player abilities to {}", player.gameprofile.name);
+        player_debug!(player, "Sending player abilities");
         player.send_abilities_update().await;
 
         player.send_permission_lvl_update().await;
+        // The respawn packet does not contain them, so they have to be sent again
+        player.send_reduced_debug_info().await;
+        player
+            .client
+            .send_packet(&CGameEvent::new(
+                GameEvent::EnabledRespawnScreen,
+                f32::from(u8::from(self.config.immediate_respawn)),
+            ))
+            .await;
 
         // teleport
-        let mut position = Vector3::new(10.0, 120.0, 10.0);
-        let yaw = 10.0;
-        let pitch = 10.0;
-
-        let top = self
-            .get_top_block(Vector2::new(position.x as i32, position.z as i32))
-            .await;
-        position.y = f64::from(top + 1);
+        // TODO: Respawn at the position of the respawn point once beds set it, only its angle is used for now
+        let position = match destination {
+            Some(destination) => destination,
+            None => self.spawn_position().await,
+        };
+        let yaw = player.spawn_point().await.angle;
+        let pitch = 0.0;
 
-        log::debug!("Sending player teleport to {}", player.gameprofile.name);
+        player_debug!(player, "Sending player teleport");
         player.request_teleport(position, yaw, pitch).await;
 
         player.living_entity.last_pos.store(position);
 
-        // TODO: difficulty, exp bar, status effect
+        player.send_difficulty(self).await;
+
+        // TODO: exp bar, status effect
 
         self.worldborder
             .lock()
@@ -491,12 +1110,17 @@ impl World {
             .init_client(&player.client)
             .await;
 
-        // TODO: world spawn (compass stuff)
+        player.send_compass_target().await;
 
         player
             .client
             .send_packet(&CGameEvent::new(GameEvent::StartWaitingChunks, 0.0))
             .await;
+        self.send_thunderstorm(player).await;
+
+        if Self::spawn_aborted(player) {
+            return;
+        }
 
         let entity = &player.living_entity.entity;
         let entity_id = entity.entity_id;
@@ -530,42 +1154,68 @@ impl World {
         self.broadcast_packet_all(&entity_metadata_packet).await;
         // update commands
 
-        player.set_health(20.0, 20, 20.0).await;
+        if alive {
+            // The client forgets them with the old player entity
+            player
+                .set_health(
+                    player.living_entity.health.load(),
+                    player.food.load(std::sync::atomic::Ordering::Relaxed),
+                    player.food_saturation.load(),
+                )
+                .await;
+        } else {
+            player.set_health(20.0, 20, 20.0).await;
+        }
+    }
+
+    /// Checks if the player disconnected while getting spawned, in which case we stop spawning them.
+    /// The connection task removes the player from the world once it notices the closed connection
+    fn spawn_aborted(player: &Player) -> bool {
+        let closed = player
+            .client
+            .closed
+            .load(std::sync::atomic::Ordering::Relaxed);
+        if closed {
+            player_debug!(player, "Disconnected while being spawned, aborting");
+        }
+        closed
     }
 
     /// IMPORTANT: Chunks have to be non-empty
+    /// Sends the chunks to the player in the background, the returned task finishes once all of them are sent.
+    /// `None` if the connection is closed already
     fn spawn_world_chunks(
         &self,
         player: Arc<Player>,
         chunks: Vec<Vector2<i32>>,
-        center_chunk: Vector2<i32>,
-    ) {
+    ) -> Option<JoinHandle<()>> {
         if player
             .client
             .closed
             .load(std::sync::atomic::Ordering::Relaxed)
         {
-            log::info!("The connection has closed before world chunks were spawned",);
-            return;
+            player_info!(
+                player,
+                "The connection has closed before world chunks were spawned"
+            );
+            return None;
         }
         #[cfg(debug_assertions)]
         let inst = std::time::Instant::now();
 
-        // Sort such that the first chunks are closest to the center
-        let mut chunks = chunks;
-        chunks.sort_unstable_by_key(|pos| {
-            let rel_x = pos.x - center_chunk.x;
-            let rel_z = pos.z - center_chunk.z;
-            rel_x * rel_x + rel_z * rel_z
-        });
-
+        // The chunks are already ordered by the chunk selector
         let mut receiver = self.receive_chunks(chunks);
         let level = self.level.clone();
+        let anti_xray = self.config.anti_xray.then_some(self.dimension_type);
 
-        tokio::spawn(async move {
+        Some(tokio::spawn(async move {
             while let Some(chunk_data) = receiver.recv().await {
                 let chunk_data = chunk_data.read().await;
-                let packet = CChunkData(&chunk_data);
+                // Players get a copy with the hidden blocks replaced, the real chunk stays the same.
+                // This is `World::obfuscate_chunk`, which the task can't call without the world
+                let obfuscated =
+                    anti_xray.and_then(|dimension| anti_xray::obfuscate(&chunk_data, dimension));
+                let packet = CChunkData(obfuscated.as_ref().unwrap_or(&*chunk_data));
                 #[cfg(debug_assertions)]
                 if chunk_data.position == (0, 0).into() {
                     let mut test = bytes::BytesMut::new();
@@ -584,7 +1234,7 @@ impl World {
                         "Received chunk {:?}, but it is no longer watched... cleaning",
                         &chunk_data.position
                     );
-                    level.clean_chunk(&chunk_data.position);
+                    level.schedule_unload(&[chunk_data.position]);
                     continue;
                 }
 
@@ -594,12 +1244,13 @@ impl World {
                     .load(std::sync::atomic::Ordering::Relaxed)
                 {
                     player.client.send_packet(&packet).await;
+                    player.on_chunk_sent(chunk_data.position).await;
                 }
             }
 
             #[cfg(debug_assertions)]
-            log::debug!("chunks sent after {}ms ", inst.elapsed().as_millis(),);
-        });
+            player_debug!(player, "Chunks sent after {}ms", inst.elapsed().as_millis());
+        }))
     }
 
     /// Gets a Player by entity id
@@ -623,6 +1274,49 @@ impl World {
         None
     }
 
+    /// Sets or clears the custom name shown above a player or mob and sends it to all players who can see it
+    pub async fn set_entity_custom_name(
+        &self,
+        entity_id: EntityId,
+        name: Option<TextComponent>,
+        visible: bool,
+    ) {
+        if let Some(player) = self.get_player_by_entityid(entity_id).await {
+            player
+                .living_entity
+                .entity
+                .set_custom_name(name, visible)
+                .await;
+        } else if let Some(living_entity) = self.get_living_entity_by_entityid(entity_id).await {
+            living_entity.entity.set_custom_name(name, visible).await;
+        }
+    }
+
+    /// Lets the player or mob float instead of falling, e.g. for holograms and stationary NPCs
+    pub async fn set_no_gravity(&self, entity_id: EntityId, no_gravity: bool) {
+        if let Some(player) = self.get_player_by_entityid(entity_id).await {
+            player.living_entity.entity.set_no_gravity(no_gravity).await;
+        } else if let Some(living_entity) = self.get_living_entity_by_entityid(entity_id).await {
+            living_entity.entity.set_no_gravity(no_gravity).await;
+        }
+    }
+
+    /// Makes the player or mob immune to all damage, or lets it take damage again
+    pub async fn set_invulnerable(&self, entity_id: EntityId, invulnerable: bool) {
+        if let Some(player) = self.get_player_by_entityid(entity_id).await {
+            player
+                .living_entity
+                .entity
+                .invulnerable
+                .store(invulnerable, std::sync::atomic::Ordering::Relaxed);
+        } else if let Some(living_entity) = self.get_living_entity_by_entityid(entity_id).await {
+            living_entity
+                .entity
+                .invulnerable
+                .store(invulnerable, std::sync::atomic::Ordering::Relaxed);
+        }
+    }
+
     /// Gets a Player by username
     pub async fn get_player_by_name(&self, name: &str) -> Option<Arc<Player>> {
         for player in self.current_players.lock().await.values() {
@@ -737,17 +1431,17 @@ impl World {
     /// * `uuid`: The unique UUID of the player to add.
     /// * `player`: An `Arc<Player>` reference to the player object.
     pub async fn add_player(&self, uuid: uuid::Uuid, player: Arc<Player>) {
-        let mut current_players = self.current_players.lock().await;
-        current_players.insert(uuid, player.clone());
+        self.update_player_chunk(&player).await;
+        self.current_players
+            .lock()
+            .await
+            .insert(uuid, player.clone());
 
         // Handle join message
         // TODO: Config
         let msg_txt = format!("{} joined the game.", player.gameprofile.name.as_str());
         let msg_comp = TextComponent::text(msg_txt).color_named(NamedColor::Yellow);
-        for player in current_players.values() {
-            player.send_system_message(&msg_comp).await;
-        }
-        log::info!("{}", msg_comp.to_pretty_console());
+        self.broadcast_system_message(&msg_comp).await;
     }
 
     /// Removes a player from the world and broadcasts a disconnect message if enabled.
@@ -768,28 +1462,56 @@ impl World {
     ///
     /// - This function assumes `broadcast_packet_expect` and `remove_entity` are defined elsewhere.
     /// - The disconnect message sending is currently optional. Consider making it a configurable option.
+    /// - Removing a player which is not in the world does nothing, this happens when a disconnect and a kick race each other.
     pub async fn remove_player(&self, player: &Player) {
-        self.current_players
-            .lock()
-            .await
-            .remove(&player.gameprofile.id)
-            .unwrap();
         let uuid = player.gameprofile.id;
+        if self.current_players.lock().await.remove(&uuid).is_none() {
+            player_debug!(player, "Tried to remove player which is not in the world");
+            return;
+        }
+        player.save_inventory().await;
+        self.player_chunks.lock().await.remove(&uuid);
         self.broadcast_packet_except(
             &[player.gameprofile.id],
             &CRemovePlayerInfo::new(1.into(), &[uuid]),
         )
         .await;
         self.remove_entity(&player.living_entity.entity).await;
+        self.drop_leashes_held_by(player.entity_id()).await;
 
         // Send disconnect message / quit message to players in the same world
         // TODO: Config
         let disconn_msg_txt = format!("{} left the game.", player.gameprofile.name.as_str());
         let disconn_msg_cmp = TextComponent::text(disconn_msg_txt).color_named(NamedColor::Yellow);
-        for player in self.current_players.lock().await.values() {
-            player.send_system_message(&disconn_msg_cmp).await;
+        self.broadcast_system_message(&disconn_msg_cmp).await;
+    }
+
+    /// Disconnects the player with the reason shown on their disconnect screen, which can have colors and formatting.
+    /// All kicks go through here, so the player always gets removed and their data saved right away
+    pub async fn disconnect_player(&self, player: &Player, reason: TextComponent) {
+        if player
+            .client
+            .closed
+            .load(std::sync::atomic::Ordering::Relaxed)
+        {
+            player_debug!(player, "Tried to kick but connection is closed!");
+            return;
         }
-        log::info!("{}", disconn_msg_cmp.to_pretty_console());
+
+        player
+            .client
+            .try_send_packet(&CPlayDisconnect::new(&reason))
+            .await
+            .unwrap_or_else(|_| player.client.close());
+        player_info!(
+            player,
+            "Kicked (client id {}) for {}",
+            player.client.id,
+            reason.to_pretty_console()
+        );
+        player.client.close();
+        // The connection loop removes the player again once it notices the connection is closed, which does nothing then
+        self.remove_player(player).await;
     }
 
     /// Adds a living entity to the world.
@@ -806,96 +1528,313 @@ impl World {
         current_living_entities.insert(uuid, living_entity);
     }
 
-    pub async fn remove_mob_entity(self: Arc<Self>, living_entity: Arc<LivingEntity>) {
-        let mut current_living_entities = self.current_living_mobs.lock().await.clone();
-        current_living_entities.remove(&living_entity.entity.entity_uuid);
+    /// Removes a dead mob once its death animation played, measured with the clock of the world
+    pub async fn remove_mob_entity(&self, living_entity: Arc<LivingEntity>) {
+        self.current_living_mobs
+            .lock()
+            .await
+            .remove(&living_entity.entity.entity_uuid);
         // TODO: does this work with collisions?
-        living_entity.entity.set_pose(EntityPose::Dying).await;
-        tokio::spawn(async move {
-            tokio::time::sleep(tokio::time::Duration::from_millis(2000)).await;
+        living_entity.entity.set_pose(EntityPose::Dying);
+        // It does not get ticked anymore, so nothing else would send the pose
+        living_entity.entity.flush_metadata().await;
+        self.dying_mobs
+            .lock()
+            .await
+            .push((self.clock.now() + DEATH_ANIMATION, living_entity));
+    }
+
+    /// Removes the dead mobs whose death animation is over
+    async fn tick_dying_mobs(&self) {
+        let now = self.clock.now();
+        let removed: Vec<Arc<LivingEntity>> = {
+            let mut dying_mobs = self.dying_mobs.lock().await;
+            let (removed, dying): (Vec<_>, Vec<_>) = dying_mobs
+                .drain(..)
+                .partition(|(remove_at, _)| *remove_at <= now);
+            *dying_mobs = dying;
+            removed.into_iter().map(|(_, entity)| entity).collect()
+        };
+        for living_entity in removed {
             self.remove_entity(&living_entity.entity).await;
-        });
+        }
     }
 
     pub async fn remove_entity(&self, entity: &Entity) {
+        self.forget_entity_owner(entity.entity_id).await;
         self.broadcast_packet_all(&CRemoveEntities::new(&[entity.entity_id.into()]))
             .await;
     }
 
-    /// Sets a block
+    /// Sets a block, returns the block state it replaced.
+    ///
+    /// Nothing changes if the chunk of the block is unavailable, the block is treated as air then
     pub async fn set_block_state(&self, position: WorldPosition, block_state_id: u16) -> u16 {
         let (chunk_coordinate, relative_coordinates) = position.chunk_and_chunk_relative_position();
 
         // Since we divide by 16 remnant can never exceed u8
         let relative = ChunkRelativeBlockCoordinates::from(relative_coordinates);
 
-        let chunk = self.receive_chunk(chunk_coordinate).await;
+        let chunk = match self.receive_chunk(chunk_coordinate).await {
+            Ok(chunk) => chunk,
+            Err(err) => {
+                log::error!("Failed to set the block at {:?}: {}", position, err);
+                return 0;
+            }
+        };
         let replaced_block_state_id = chunk
             .write()
             .await
             .blocks
             .set_block(relative, block_state_id);
 
-        self.broadcast_packet_all(&CBlockUpdate::new(
-            &position,
-            i32::from(block_state_id).into(),
-        ))
-        .await;
+        if portal::is_portal(block_state_id) {
+            self.portals.lock().await.insert(position);
+        } else if portal::is_portal(replaced_block_state_id) {
+            self.portals.lock().await.remove(&position);
+        }
+
+        self.send_block_update(chunk_coordinate, &position, block_state_id)
+            .await;
+
+        if self.config.anti_xray && state_opacity(block_state_id) < 15 {
+            self.reveal_hidden_neighbors(position).await;
+        }
+
+        if light::changes_light(replaced_block_state_id, block_state_id) {
+            self.queue_light_update(position).await;
+        }
 
         replaced_block_state_id
     }
 
-    // Stream the chunks (don't collect them and then do stuff with them)
-    /// Important: must be called from an async function (or changed to accept a tokio runtime
-    /// handle)
+    /// Sets many blocks at once, sending a single update per chunk section instead of one per block
+    pub async fn set_block_states(&self, blocks: &HashMap<WorldPosition, u16>) {
+        let mut chunks: HashMap<Vector2<i32>, Vec<(WorldPosition, u16)>> = HashMap::new();
+        for (&position, &block_state_id) in blocks {
+            let (chunk_coordinate, _) = position.chunk_and_chunk_relative_position();
+            chunks
+                .entry(chunk_coordinate)
+                .or_default()
+                .push((position, block_state_id));
+        }
+
+        for (chunk_coordinate, blocks) in chunks {
+            let chunk = match self.receive_chunk(chunk_coordinate).await {
+                Ok(chunk) => chunk,
+                Err(err) => {
+                    log::error!("Skipping {} block changes: {}", blocks.len(), err);
+                    continue;
+                }
+            };
+            let mut sections: HashMap<i32, Vec<(Vector3<u8>, u16)>> = HashMap::new();
+            // Hidden blocks next to these may be seen now
+            let mut exposed = Vec::new();
+            let mut relit = Vec::new();
+            {
+                let mut chunk = chunk.write().await;
+                let mut portals = self.portals.lock().await;
+                for (position, block_state_id) in blocks {
+                    let (_, relative) = position.chunk_and_chunk_relative_position();
+                    let replaced_block_state_id = chunk.blocks.set_block(
+                        ChunkRelativeBlockCoordinates::from(relative),
+                        block_state_id,
+                    );
+                    if portal::is_portal(block_state_id) {
+                        portals.insert(position);
+                    } else if portal::is_portal(replaced_block_state_id) {
+                        portals.remove(&position);
+                    }
+                    if self.config.anti_xray && state_opacity(block_state_id) < 15 {
+                        exposed.push(position);
+                    }
+                    if light::changes_light(replaced_block_state_id, block_state_id) {
+                        relit.push(position);
+                    }
+
+                    let (section_y, y) = (relative.y.div_euclid(16), relative.y.rem_euclid(16));
+                    sections.entry(section_y).or_default().push((
+                        Vector3::new(relative.x as u8, y as u8, relative.z as u8),
+                        block_state_id,
+                    ));
+                }
+            }
+
+            for (section_y, blocks) in sections {
+                self.broadcast_to_chunk_watchers(
+                    chunk_coordinate,
+                    &CMultiBlockUpdate::new(
+                        Vector3::new(chunk_coordinate.x, section_y, chunk_coordinate.z),
+                        &blocks,
+                    ),
+                )
+                .await;
+            }
+            for position in exposed {
+                self.reveal_hidden_neighbors(position).await;
+            }
+            for position in relit {
+                self.queue_light_update(position).await;
+            }
+        }
+    }
+
+    /// Shows a block to a single player without changing the world.
+    ///
+    /// **Note:** Since the server state is unchanged, the client reverts the block once it reloads the chunk
+    /// or the real block gets updated. Use [`World::reset_fake_block`] to revert it manually.
+    pub async fn send_fake_block(
+        &self,
+        player: &Player,
+        position: WorldPosition,
+        block_state_id: u16,
+    ) {
+        // The block would otherwise show up in whatever world the player is currently in
+        if !self
+            .current_players
+            .lock()
+            .await
+            .contains_key(&player.gameprofile.id)
+        {
+            return;
+        }
+        player
+            .client
+            .send_packet(&CBlockUpdate::new(
+                &position,
+                i32::from(block_state_id).into(),
+            ))
+            .await;
+    }
+
+    /// Sends the real block at the position to the player again, undoing [`World::send_fake_block`]
+    pub async fn reset_fake_block(
+        &self,
+        player: &Player,
+        position: WorldPosition,
+    ) -> Result<(), GetBlockError> {
+        let block_state_id = self.get_block_state_id(position).await?;
+        self.send_fake_block(player, position, block_state_id).await;
+        Ok(())
+    }
+
+    /// Streams the chunks roughly in the order they are loaded.
+    ///
+    /// Chunks which don't fit into the channel wait in a task of their own, so a slow receiver never holds up
+    /// the chunk loading of other players
     pub fn receive_chunks(&self, chunks: Vec<Vector2<i32>>) -> Receiver<Arc<RwLock<ChunkData>>> {
-        let (sender, receive) = mpsc::channel(chunks.len());
+        let (sender, receive) = mpsc::channel(CHUNK_CHANNEL_SIZE);
         // Put this in another thread so we aren't blocking on it
         let level = self.level.clone();
-        let rt = Handle::current();
+        let runtime = tokio::runtime::Handle::current();
         rayon::spawn(move || {
-            level.fetch_chunks(&chunks, sender, &rt);
+            let backlog = level.fetch_chunks(&chunks, &sender);
+            if backlog.is_empty() {
+                return;
+            }
+            runtime.spawn(async move {
+                for chunk in backlog {
+                    if sender.send(chunk).await.is_err() {
+                        // Nobody is waiting for the chunks anymore
+                        break;
+                    }
+                }
+            });
         });
         receive
     }
 
-    pub async fn receive_chunk(&self, chunk_pos: Vector2<i32>) -> Arc<RwLock<ChunkData>> {
+    /// Loads or generates the chunk, fails if neither worked
+    pub async fn receive_chunk(
+        &self,
+        chunk_pos: Vector2<i32>,
+    ) -> Result<Arc<RwLock<ChunkData>>, ChunkUnavailable> {
         let mut receiver = self.receive_chunks(vec![chunk_pos]);
-        let chunk = receiver
-            .recv()
-            .await
-            .expect("Channel closed for unknown reason");
+        let chunk = receiver.recv().await.ok_or(ChunkUnavailable(chunk_pos))?;
 
         if !self.level.is_chunk_watched(&chunk_pos) {
             log::trace!(
                 "Received chunk {:?}, but it is not watched... cleaning",
                 chunk_pos
             );
-            self.level.clean_chunk(&chunk_pos);
+            self.level.schedule_unload(&[chunk_pos]);
         }
 
-        chunk
+        Ok(chunk)
     }
 
     pub async fn break_block(&self, position: WorldPosition, cause: Option<&Player>) {
         let broken_block_state_id = self.set_block_state(position, 0).await;
 
-        let particles_packet =
-            CWorldEvent::new(2001, &position, broken_block_state_id.into(), false);
+        let sound_group = get_block_by_state_id(broken_block_state_id)
+            .map_or(BlockSoundGroup::Stone, |block| block.sound_group);
+        let center = Vector3::new(
+            f64::from(position.0.x) + 0.5,
+            f64::from(position.0.y) + 0.5,
+            f64::from(position.0.z) + 0.5,
+        );
+
+        // The block particle takes the broken block state as its data
+        let mut particle_data = BytesMut::new();
+        VarInt(i32::from(broken_block_state_id)).encode(&mut particle_data);
+        let particles_packet = CParticle::new(
+            false,
+            center,
+            Vector3::new(0.25, 0.25, 0.25),
+            0.0,
+            64,
+            VarInt(i32::from(particle!("block"))),
+            &particle_data,
+        );
+        let sound_packet = CSoundEffect::new(
+            VarInt(i32::from(sound_group.break_sound())),
+            None,
+            SoundCategory::Blocks,
+            center.x,
+            center.y,
+            center.z,
+            (sound_group.volume() + 1.0) / 2.0,
+            0.8,
+            thread_rng().gen::<f64>(),
+        );
 
-        match cause {
-            Some(player) => {
-                self.broadcast_packet_except(&[player.gameprofile.id], &particles_packet)
-                    .await;
-            }
-            None => self.broadcast_packet_all(&particles_packet).await,
-        }
+        // The client which broke the block already plays the effects itself
+        let except = cause.map_or_else(Vec::new, |player| vec![player.gameprofile.id]);
+        self.broadcast_packet_except(&except, &particles_packet)
+            .await;
+        self.broadcast_packet_except(&except, &sound_packet).await;
     }
 
+    /// Like [`World::get_block_state_id_timeout`], with the timeout from the config
     pub async fn get_block_state_id(&self, position: WorldPosition) -> Result<u16, GetBlockError> {
-        let (chunk, relative) = position.chunk_and_chunk_relative_position();
+        let timeout = tokio::time::Duration::from_millis(
+            ADVANCED_CONFIG.chunk_generation.block_lookup_timeout_ms,
+        );
+        self.get_block_state_id_timeout(position, timeout).await
+    }
+
+    /// Gets the block state at the position, waiting at most `timeout` for its chunk to be loaded or generated
+    pub async fn get_block_state_id_timeout(
+        &self,
+        position: WorldPosition,
+        timeout: tokio::time::Duration,
+    ) -> Result<u16, GetBlockError> {
+        let (chunk_coordinate, relative) = position.chunk_and_chunk_relative_position();
         let relative = ChunkRelativeBlockCoordinates::from(relative);
-        let chunk = self.receive_chunk(chunk).await;
+        let Ok(chunk) = tokio::time::timeout(timeout, self.receive_chunk(chunk_coordinate)).await
+        else {
+            log::warn!(
+                "Timed out after {:?} waiting for chunk {:?} to get the block at {:?}, is chunk generation stuck?",
+                timeout,
+                chunk_coordinate,
+                position
+            );
+            return Err(GetBlockError::Timeout);
+        };
+        let chunk = chunk.map_err(|err| {
+            log::error!("Failed to get the block at {:?}: {}", position, err);
+            GetBlockError::ChunkUnavailable
+        })?;
         let chunk: tokio::sync::RwLockReadGuard<ChunkData> = chunk.read().await;
 
         let Some(id) = chunk.blocks.get_block(relative) else {
@@ -909,7 +1848,7 @@ impl World {
     pub async fn get_block(
         &self,
         position: WorldPosition,
-    ) -> Result<&pumpkin_world::block::block_registry::Block, GetBlockError> {
+    ) -> Result<Arc<pumpkin_world::block::block_registry::Block>, GetBlockError> {
         let id = self.get_block_state_id(position).await?;
         get_block_by_state_id(id).ok_or(GetBlockError::InvalidBlockId)
     }
@@ -918,7 +1857,7 @@ impl World {
     pub async fn get_block_state(
         &self,
         position: WorldPosition,
-    ) -> Result<&pumpkin_world::block::block_registry::State, GetBlockError> {
+    ) -> Result<pumpkin_world::block::block_registry::State, GetBlockError> {
         let id = self.get_block_state_id(position).await?;
         get_state_by_state_id(id).ok_or(GetBlockError::InvalidBlockId)
     }
@@ -929,12 +1868,24 @@ impl World {
         position: WorldPosition,
     ) -> Result<
         (
-            &pumpkin_world::block::block_registry::Block,
-            &pumpkin_world::block::block_registry::State,
+            Arc<pumpkin_world::block::block_registry::Block>,
+            pumpkin_world::block::block_registry::State,
         ),
         GetBlockError,
     > {
         let id = self.get_block_state_id(position).await?;
         get_block_and_state_by_state_id(id).ok_or(GetBlockError::InvalidBlockId)
     }
+
+    /// How long the block state takes to mine, see [`mining::block_hardness`]
+    #[must_use]
+    pub fn block_hardness(state_id: u16) -> f32 {
+        mining::block_hardness(state_id)
+    }
+
+    /// The kind of tool the block state needs to drop something, see [`mining::block_requires_tool`]
+    #[must_use]
+    pub fn block_requires_tool(state_id: u16) -> Option<ToolType> {
+        mining::block_requires_tool(state_id)
+    }
 }
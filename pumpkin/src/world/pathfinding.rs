@@ -0,0 +1,308 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+    sync::Arc,
+};
+
+use pumpkin_core::math::{position::WorldPosition, vector2::Vector2, vector3::Vector3};
+use pumpkin_world::{
+    block::block_registry::registry, chunk::ChunkData, coordinates::ChunkRelativeBlockCoordinates,
+    is_in_world,
+};
+use tokio::sync::RwLock;
+
+use super::World;
+
+/// How many blocks a mob walks down in one step at most, like Vanilla mobs avoid longer falls
+const MAX_DROP: i32 = 3;
+/// The sides a mob can walk to from a block
+const DIRECTIONS: [(i32, i32); 4] = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+
+/// What standing in or on a block means for a walking mob
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum BlockKind {
+    /// Nothing to collide with, like air or tall grass
+    Passable,
+    /// Something to stand on
+    Solid,
+    /// Lava and fire, which mobs avoid
+    Dangerous,
+}
+
+fn block_kind(state_id: Option<u16>) -> BlockKind {
+    let registry = registry();
+    let Some((block, state)) = state_id.and_then(|state_id| registry.block_and_state(state_id))
+    else {
+        // Blocks outside of the world or in chunks which are not loaded count as walls
+        return BlockKind::Solid;
+    };
+    if matches!(
+        block.name.as_str(),
+        "lava" | "fire" | "soul_fire" | "magma_block"
+    ) {
+        BlockKind::Dangerous
+    } else if state.collision_shapes.is_empty() {
+        BlockKind::Passable
+    } else {
+        BlockKind::Solid
+    }
+}
+
+/// The blocks a walking mob can step to from `from`, for each side the one it would end up standing in
+fn neighbours<F>(from: WorldPosition, kind: &mut F) -> Vec<WorldPosition>
+where
+    F: FnMut(WorldPosition) -> BlockKind,
+{
+    let at = |x: i32, y: i32, z: i32| WorldPosition(Vector3::new(x, y, z));
+    let can_stand_in = |kind: &mut F, position: WorldPosition| {
+        let (x, y, z) = (position.0.x, position.0.y, position.0.z);
+        kind(at(x, y - 1, z)) == BlockKind::Solid
+            && kind(position) == BlockKind::Passable
+            && kind(at(x, y + 1, z)) == BlockKind::Passable
+    };
+    let (x, y, z) = (from.0.x, from.0.y, from.0.z);
+    let mut neighbours = Vec::new();
+    for (dx, dz) in DIRECTIONS {
+        let (nx, nz) = (x + dx, z + dz);
+        if can_stand_in(kind, at(nx, y, nz)) {
+            neighbours.push(at(nx, y, nz));
+            continue;
+        }
+        // Jumping up a block needs room above the head
+        if kind(at(x, y + 2, z)) == BlockKind::Passable && can_stand_in(kind, at(nx, y + 1, nz)) {
+            neighbours.push(at(nx, y + 1, nz));
+            continue;
+        }
+        // Walking down, the mob falls until it lands
+        if kind(at(nx, y, nz)) != BlockKind::Passable
+            || kind(at(nx, y + 1, nz)) != BlockKind::Passable
+        {
+            continue;
+        }
+        for drop in 1..=MAX_DROP {
+            let below = at(nx, y - drop, nz);
+            match kind(below) {
+                BlockKind::Passable => {
+                    if can_stand_in(kind, below) {
+                        neighbours.push(below);
+                        break;
+                    }
+                }
+                BlockKind::Solid | BlockKind::Dangerous => break,
+            }
+        }
+    }
+    neighbours
+}
+
+/// The block as something the queue can sort, blocks don't have an order
+const fn key(position: WorldPosition) -> [i32; 3] {
+    [position.0.x, position.0.y, position.0.z]
+}
+
+/// The A* heuristic, every step moves one block sideways so this never guesses too long
+const fn distance(from: &WorldPosition, to: &WorldPosition) -> u32 {
+    from.0.x.abs_diff(to.0.x) + from.0.z.abs_diff(to.0.z)
+}
+
+impl World {
+    /// The block for the path search, only looking into loaded chunks. The chunks looked into are kept in `chunks`
+    async fn path_block_kind(
+        &self,
+        chunks: &mut HashMap<Vector2<i32>, Option<Arc<RwLock<ChunkData>>>>,
+        position: WorldPosition,
+    ) -> BlockKind {
+        if !is_in_world(position.0.y) {
+            return block_kind(None);
+        }
+        let (chunk, relative) = position.chunk_and_chunk_relative_position();
+        let Some(chunk) = chunks
+            .entry(chunk)
+            .or_insert_with(|| self.level.get_loaded_chunk(&chunk))
+        else {
+            return block_kind(None);
+        };
+        let state_id = chunk
+            .read()
+            .await
+            .blocks
+            .get_block(ChunkRelativeBlockCoordinates::from(relative));
+        block_kind(state_id)
+    }
+
+    /// Finds a way for a walking mob from one block to another with A*, the blocks are the ones the mob stands in.
+    /// Mobs need solid ground and two blocks of room, can jump up one block and fall down a few, and avoid lava and fire.
+    ///
+    /// Chunks which are not loaded are never loaded for the search, their blocks count as walls.
+    /// Gives up after looking at `max_nodes` blocks, returns `None` if there is no way.
+    /// Otherwise the blocks to walk through, from `from` to `to` both included
+    pub async fn find_path(
+        &self,
+        from: WorldPosition,
+        to: WorldPosition,
+        max_nodes: usize,
+    ) -> Option<Vec<WorldPosition>> {
+        // The search looks at the same blocks many times, so they are only looked up once
+        let mut blocks: HashMap<WorldPosition, BlockKind> = HashMap::new();
+        let mut chunks = HashMap::new();
+        let mut came_from: HashMap<WorldPosition, WorldPosition> = HashMap::new();
+        let mut cost: HashMap<WorldPosition, u32> = HashMap::from([(from, 0)]);
+        let mut open = BinaryHeap::from([Reverse((distance(&from, &to), 0, key(from)))]);
+        let mut visited = 0;
+
+        while let Some(Reverse((_, current_cost, [x, y, z]))) = open.pop() {
+            let current = WorldPosition(Vector3::new(x, y, z));
+            if current == to {
+                let mut path = vec![current];
+                let mut position = current;
+                while let Some(previous) = came_from.get(&position) {
+                    path.push(*previous);
+                    position = *previous;
+                }
+                path.reverse();
+                return Some(path);
+            }
+            if cost.get(&current).is_some_and(|cost| *cost < current_cost) {
+                // Found a cheaper way to this block since it was queued
+                continue;
+            }
+            visited += 1;
+            if visited > max_nodes {
+                return None;
+            }
+
+            // Look up the blocks around first, as the neighbour check itself can't wait for chunks
+            for dy in -(MAX_DROP + 1)..=2 {
+                for (dx, dz) in DIRECTIONS.into_iter().chain([(0, 0)]) {
+                    let position = WorldPosition(current.0.add(&Vector3::new(dx, dy, dz)));
+                    if !blocks.contains_key(&position) {
+                        let kind = self.path_block_kind(&mut chunks, position).await;
+                        blocks.insert(position, kind);
+                    }
+                }
+            }
+            let next = neighbours(current, &mut |position| {
+                blocks.get(&position).copied().unwrap_or(BlockKind::Solid)
+            });
+            for neighbour in next {
+                let neighbour_cost = current_cost + 1;
+                if cost
+                    .get(&neighbour)
+                    .is_some_and(|cost| *cost <= neighbour_cost)
+                {
+                    continue;
+                }
+                cost.insert(neighbour, neighbour_cost);
+                came_from.insert(neighbour, current);
+                open.push(Reverse((
+                    neighbour_cost + distance(&neighbour, &to),
+                    neighbour_cost,
+                    key(neighbour),
+                )));
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_core::math::{position::WorldPosition, vector2::Vector2, vector3::Vector3};
+    use pumpkin_macros::block_state;
+
+    use crate::world::World;
+
+    fn at(x: i32, y: i32, z: i32) -> WorldPosition {
+        WorldPosition(Vector3::new(x, y, z))
+    }
+
+    /// A stone floor at y 199 in the air, so mobs stand at y 200
+    async fn floor_world() -> World {
+        let world = World::test_world();
+        for x in -1..=8 {
+            for z in -4..=4 {
+                world
+                    .set_block_state(at(x, 199, z), block_state!("stone").state_id)
+                    .await;
+            }
+        }
+        world
+    }
+
+    #[tokio::test]
+    async fn walks_around_walls() {
+        let world = floor_world().await;
+        let straight = world.find_path(at(0, 200, 0), at(6, 200, 0), 1000).await;
+        assert_eq!(straight.map(|path| path.len()), Some(7));
+
+        // A wall two blocks high, with a gap at the edge
+        for z in -4..=3 {
+            for y in 200..=201 {
+                world
+                    .set_block_state(at(3, y, z), block_state!("stone").state_id)
+                    .await;
+            }
+        }
+        let path = world
+            .find_path(at(0, 200, 0), at(6, 200, 0), 1000)
+            .await
+            .expect("The gap is reachable");
+        assert!(path.first() == Some(&at(0, 200, 0)));
+        assert!(path.last() == Some(&at(6, 200, 0)));
+        assert!(path.contains(&at(3, 200, 4)));
+        // Not enough nodes to find the way around
+        assert!(world
+            .find_path(at(0, 200, 0), at(6, 200, 0), 5)
+            .await
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn steps_up_and_avoids_lava() {
+        let world = floor_world().await;
+        // A one block step is jumped on
+        world
+            .set_block_state(at(3, 200, 0), block_state!("stone").state_id)
+            .await;
+        let path = world
+            .find_path(at(0, 200, 0), at(6, 200, 0), 1000)
+            .await
+            .expect("The step can be jumped on");
+        assert!(path.contains(&at(3, 201, 0)));
+
+        // Lava on the floor is walked around
+        world
+            .set_block_state(at(3, 199, 1), block_state!("lava").state_id)
+            .await;
+        let path = world
+            .find_path(at(0, 200, 1), at(6, 200, 1), 1000)
+            .await
+            .expect("The lava can be walked around");
+        assert!(!path.contains(&at(3, 200, 1)));
+    }
+
+    #[tokio::test]
+    async fn only_loaded_chunks_inside_the_world() {
+        let world = floor_world().await;
+        // The floor goes up to the chunk border, the chunk behind it is not loaded and counts as a wall
+        for x in 9..=15 {
+            world
+                .set_block_state(at(x, 199, 0), block_state!("stone").state_id)
+                .await;
+        }
+        assert!(world
+            .find_path(at(0, 200, 0), at(20, 200, 0), 1000)
+            .await
+            .is_none());
+        assert!(world.level.get_loaded_chunk(&Vector2::new(1, 0)).is_none());
+
+        // Blocks below the world count as walls
+        world
+            .set_block_state(at(0, -64, 0), block_state!("air").state_id)
+            .await;
+        assert!(world
+            .find_path(at(0, -64, 0), at(1, -64, 0), 1000)
+            .await
+            .is_none());
+    }
+}
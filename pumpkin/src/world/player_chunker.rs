@@ -1,26 +1,78 @@
-use std::{num::NonZeroU8, sync::Arc};
+use std::{collections::HashSet, num::NonZeroU8, sync::Arc};
 
-use pumpkin_config::BASIC_CONFIG;
 use pumpkin_core::{
-    math::{get_section_cord, position::WorldPosition, vector3::Vector3},
+    math::{get_section_cord, position::WorldPosition, vector2::Vector2, vector3::Vector3},
     GameMode,
 };
 use pumpkin_protocol::client::play::{CCenterChunk, CUnloadChunk};
 use pumpkin_world::cylindrical_chunk_iterator::Cylindrical;
+use tokio::task::JoinHandle;
+
+use crate::entity::{death::DeathCause, player::Player};
+use crate::log_context::player_debug;
+
+/// Decides which chunks get loaded around a player and in which order they get sent.
+///
+/// Set it for a world with [`super::World::with_chunk_selector`]
+pub trait ChunkSelector: Send + Sync {
+    /// Returns all chunks which should be loaded around the center, the first chunks get sent first
+    fn select_chunks(&self, center: Vector2<i32>, view_distance: NonZeroU8) -> Vec<Vector2<i32>>;
+
+    /// Whether the chunk is one of the chunks selected around the center
+    fn is_selected(
+        &self,
+        center: Vector2<i32>,
+        view_distance: NonZeroU8,
+        chunk: Vector2<i32>,
+    ) -> bool {
+        self.select_chunks(center, view_distance).contains(&chunk)
+    }
+}
 
-use crate::entity::player::Player;
+/// Loads all chunks within a cylinder around the player, nearest chunks first
+pub struct DefaultChunkSelector;
+
+impl ChunkSelector for DefaultChunkSelector {
+    fn select_chunks(&self, center: Vector2<i32>, view_distance: NonZeroU8) -> Vec<Vector2<i32>> {
+        let mut chunks = Cylindrical::new(center, view_distance).all_chunks_within();
+        // Sort such that the first chunks are closest to the center
+        chunks.sort_unstable_by_key(|pos| {
+            let rel_x = pos.x - center.x;
+            let rel_z = pos.z - center.z;
+            rel_x * rel_x + rel_z * rel_z
+        });
+        chunks
+    }
 
+    fn is_selected(
+        &self,
+        center: Vector2<i32>,
+        view_distance: NonZeroU8,
+        chunk: Vector2<i32>,
+    ) -> bool {
+        Cylindrical::new(center, view_distance).is_within_distance(chunk.x, chunk.z)
+    }
+}
+
+/// The view distance the client asked for, at least 2 like Vanilla and at most the view distance of the server
+fn effective_view_distance(requested: NonZeroU8, max: NonZeroU8) -> NonZeroU8 {
+    let min = NonZeroU8::new(2).unwrap_or(NonZeroU8::MIN).min(max);
+    requested.clamp(min, max)
+}
+
+/// The view distance the chunks around the player get sent in, from the client settings of the player
 pub async fn get_view_distance(player: &Player) -> NonZeroU8 {
-    player.config.lock().await.view_distance.clamp(
-        unsafe { NonZeroU8::new_unchecked(2) },
-        BASIC_CONFIG.view_distance,
+    effective_view_distance(
+        player.config.lock().await.view_distance,
+        player.world().config.view_distance,
     )
 }
 
-pub async fn player_join(player: &Arc<Player>) {
+/// Sends the chunks around a player who just joined, the returned task finishes once they are sent
+pub async fn player_join(player: &Arc<Player>) -> Option<JoinHandle<()>> {
     let chunk_pos = player.living_entity.entity.chunk_pos.load();
 
-    log::debug!("Sending center chunk to {}", player.gameprofile.name);
+    player_debug!(player, "Sending center chunk");
     player
         .client
         .send_packet(&CCenterChunk {
@@ -29,25 +81,37 @@ pub async fn player_join(player: &Arc<Player>) {
         })
         .await;
     let view_distance = get_view_distance(player).await;
-    log::debug!(
-        "Player {} ({}) joined with view distance: {}",
-        player.gameprofile.name,
-        player.client.id,
-        view_distance
+    player_debug!(
+        player,
+        "Joined with view distance {} (client id {})",
+        view_distance,
+        player.client.id
     );
 
-    update_position(player).await;
+    update_position(player).await
 }
 
-pub async fn update_position(player: &Arc<Player>) {
+/// Sends the chunks the player can see now and unloads the ones they can't see anymore.
+/// The returned task finishes once the new chunks are sent, `None` if no chunks get sent
+pub async fn update_position(player: &Arc<Player>) -> Option<JoinHandle<()>> {
     if !player.abilities.lock().await.flying {
-        player
+        let killed = player
             .living_entity
             .update_fall_distance(player.gamemode.load() == GameMode::Creative)
             .await;
+        if killed {
+            player.on_death(DeathCause::Fall).await;
+        }
     }
 
     let entity = &player.living_entity.entity;
+    // The player could change worlds meanwhile, all chunks have to be of the same world
+    let world = entity.world();
+    if let Some(old_chunk) = world.update_player_chunk(player).await {
+        world
+            .player_chunk_changed(player, old_chunk, entity.chunk_pos.load())
+            .await;
+    }
 
     let view_distance = get_view_distance(player).await;
     let new_chunk_center = entity.chunk_pos.load();
@@ -64,33 +128,31 @@ pub async fn update_position(player: &Arc<Player>) {
             })
             .await;
 
-        let mut loading_chunks = Vec::new();
-        let mut unloading_chunks = Vec::new();
-        Cylindrical::for_each_changed_chunk(
-            old_cylindrical,
-            new_cylindrical,
-            |chunk_pos| {
-                loading_chunks.push(chunk_pos);
-            },
-            |chunk_pos| {
-                unloading_chunks.push(chunk_pos);
-            },
-        );
+        let selector = &world.chunk_selector;
+        let old_chunks =
+            selector.select_chunks(old_cylindrical.center, old_cylindrical.view_distance);
+        let new_chunks = selector.select_chunks(new_chunk_center, view_distance);
+        let old_set: HashSet<Vector2<i32>> = old_chunks.iter().copied().collect();
+        let new_set: HashSet<Vector2<i32>> = new_chunks.iter().copied().collect();
+
+        // Keep the order of the selector
+        let loading_chunks: Vec<Vector2<i32>> = new_chunks
+            .into_iter()
+            .filter(|chunk_pos| !old_set.contains(chunk_pos))
+            .collect();
+        let unloading_chunks: Vec<Vector2<i32>> = old_chunks
+            .into_iter()
+            .filter(|chunk_pos| !new_set.contains(chunk_pos))
+            .collect();
 
         // Make sure the watched section and the chunk watcher updates are async atomic. We want to
         // ensure what we unload when the player disconnects is correct
-        entity
-            .world
-            .level
-            .mark_chunks_as_newly_watched(&loading_chunks);
-        let chunks_to_clean = entity
-            .world
-            .level
-            .mark_chunks_as_not_watched(&unloading_chunks);
+        world.level.mark_chunks_as_newly_watched(&loading_chunks);
+        let chunks_to_clean = world.level.mark_chunks_as_not_watched(&unloading_chunks);
         player.watched_section.store(new_cylindrical);
 
         if !chunks_to_clean.is_empty() {
-            entity.world.level.clean_chunks(&chunks_to_clean);
+            world.level.schedule_unload(&chunks_to_clean);
 
             // This can take a little if we are sending a bunch of packets, queue it up :p
             let client = player.client.clone();
@@ -108,11 +170,10 @@ pub async fn update_position(player: &Arc<Player>) {
         }
 
         if !loading_chunks.is_empty() {
-            entity
-                .world
-                .spawn_world_chunks(player.clone(), loading_chunks, new_chunk_center);
+            return world.spawn_world_chunks(player.clone(), loading_chunks);
         }
     }
+    None
 }
 
 #[must_use]
@@ -124,3 +185,50 @@ pub const fn chunk_section_from_pos(block_pos: &WorldPosition) -> Vector3<i32> {
         get_section_cord(block_pos.z),
     )
 }
+
+#[cfg(test)]
+mod test {
+    use std::num::NonZeroU8;
+
+    use pumpkin_core::math::vector2::Vector2;
+
+    use super::{effective_view_distance, ChunkSelector, DefaultChunkSelector};
+
+    #[test]
+    fn clamped_to_the_server() {
+        let distance = |value| NonZeroU8::new(value).unwrap();
+        assert_eq!(
+            effective_view_distance(distance(6), distance(10)),
+            distance(6)
+        );
+        assert_eq!(
+            effective_view_distance(distance(32), distance(10)),
+            distance(10)
+        );
+        assert_eq!(
+            effective_view_distance(distance(1), distance(10)),
+            distance(2)
+        );
+        // A server view distance below the minimum still wins
+        assert_eq!(
+            effective_view_distance(distance(8), distance(1)),
+            distance(1)
+        );
+    }
+
+    #[test]
+    fn selected_chunks() {
+        let center = Vector2::new(3, -2);
+        let view_distance = NonZeroU8::new(4).unwrap();
+        let selected = DefaultChunkSelector.select_chunks(center, view_distance);
+        for x in -8..=14 {
+            for z in -13..=9 {
+                let chunk = Vector2::new(x, z);
+                assert_eq!(
+                    DefaultChunkSelector.is_selected(center, view_distance, chunk),
+                    selected.contains(&chunk)
+                );
+            }
+        }
+    }
+}
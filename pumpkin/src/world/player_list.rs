@@ -0,0 +1,65 @@
+use std::sync::atomic::Ordering;
+
+use pumpkin_protocol::client::play::{CPlayerInfoUpdate, PlayerAction};
+use uuid::Uuid;
+
+use crate::entity::player::Player;
+
+use super::World;
+
+/// The player info actions of a full tab list entry: adding the player, whether they are listed and their order
+pub(super) const LIST_ENTRY_ACTIONS: i8 = 0x01 | 0x08 | 0x40;
+const LISTED_ACTION: i8 = 0x08;
+const LIST_ORDER_ACTION: i8 = 0x40;
+
+/// The tab list entry of the player, sent with [`LIST_ENTRY_ACTIONS`]
+pub(super) fn list_entry(player: &Player) -> pumpkin_protocol::client::play::Player<'_> {
+    let gameprofile = &player.gameprofile;
+    pumpkin_protocol::client::play::Player {
+        uuid: gameprofile.id,
+        actions: vec![
+            PlayerAction::AddPlayer {
+                name: &gameprofile.name,
+                properties: &gameprofile.properties,
+            },
+            PlayerAction::UpdateListed(player.listed.load(Ordering::Relaxed)),
+            PlayerAction::UpdateListOrder(player.list_order.load(Ordering::Relaxed).into()),
+        ],
+    }
+}
+
+impl World {
+    /// Shows or hides the player in the tab list of everyone, e.g. for vanished staff.
+    /// The player stays in the world and keeps their skin, they are only missing from the list
+    pub async fn set_listed(&self, uuid: Uuid, listed: bool) {
+        let Some(player) = self.get_player_by_uuid(uuid).await else {
+            return;
+        };
+        player.listed.store(listed, Ordering::Relaxed);
+        self.broadcast_packet_all(&CPlayerInfoUpdate::new(
+            LISTED_ACTION,
+            &[pumpkin_protocol::client::play::Player {
+                uuid,
+                actions: vec![PlayerAction::UpdateListed(listed)],
+            }],
+        ))
+        .await;
+    }
+
+    /// Moves the player in the tab list, players with a higher order come first, e.g. to sort players by rank.
+    /// Players with the same order are sorted by name
+    pub async fn set_list_order(&self, uuid: Uuid, order: i32) {
+        let Some(player) = self.get_player_by_uuid(uuid).await else {
+            return;
+        };
+        player.list_order.store(order, Ordering::Relaxed);
+        self.broadcast_packet_all(&CPlayerInfoUpdate::new(
+            LIST_ORDER_ACTION,
+            &[pumpkin_protocol::client::play::Player {
+                uuid,
+                actions: vec![PlayerAction::UpdateListOrder(order.into())],
+            }],
+        ))
+        .await;
+    }
+}
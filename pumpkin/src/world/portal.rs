@@ -0,0 +1,279 @@
+use std::{collections::HashSet, sync::Arc};
+
+use pumpkin_core::math::{position::WorldPosition, vector2::Vector2, vector3::Vector3};
+use pumpkin_macros::block_state;
+use pumpkin_registry::DimensionType;
+use pumpkin_world::{
+    block::block_registry::{get_block, get_block_by_state_id},
+    chunk::ChunkBlocks,
+    WORLD_LOWEST_Y,
+};
+
+use crate::{entity::player::Player, server::Server};
+
+use super::World;
+
+/// Destinations are kept this far inside the maximum world border, like Vanilla does
+const MAX_HORIZONTAL_POSITION: f64 = 29_999_984.0 - 16.0;
+
+/// Scales a position for travelling from one dimension to another, e.g. Overworld coordinates get divided by 8 in the Nether.
+///
+/// The result is clamped so it always stays inside the world
+#[must_use]
+pub fn scale_portal_position(
+    position: Vector3<f64>,
+    from: DimensionType,
+    to: DimensionType,
+) -> WorldPosition {
+    let scale = from.coordinate_scale() / to.coordinate_scale();
+    let x = (position.x * scale).clamp(-MAX_HORIZONTAL_POSITION, MAX_HORIZONTAL_POSITION);
+    let z = (position.z * scale).clamp(-MAX_HORIZONTAL_POSITION, MAX_HORIZONTAL_POSITION);
+    let (min_y, max_y) = portal_y_bounds(to);
+    WorldPosition(Vector3::new(
+        x.floor() as i32,
+        (position.y.floor() as i32).clamp(min_y, max_y),
+        z.floor() as i32,
+    ))
+}
+
+/// The lowest and highest y a portal (including its frame) can be placed at in the dimension
+const fn portal_y_bounds(dimension: DimensionType) -> (i32, i32) {
+    match dimension {
+        // Stay out of the bedrock floor and ceiling
+        DimensionType::TheNether => (5, 118),
+        DimensionType::TheEnd => (1, 250),
+        DimensionType::Overworld | DimensionType::OverworldCaves => (-63, 314),
+    }
+}
+
+/// How far around the destination we look for an existing portal, Vanilla searches 128 blocks in the Overworld and 16 in the Nether
+fn portal_search_radius(dimension: DimensionType) -> i32 {
+    (128.0 / dimension.coordinate_scale()) as i32
+}
+
+/// Checks if the block state is a nether portal block, regardless of its axis
+pub(super) fn is_portal(state_id: u16) -> bool {
+    get_block_by_state_id(state_id)
+        .is_some_and(|block| block.id == block_state!("nether_portal").block_id)
+}
+
+/// The positions of all nether portal blocks in the blocks of the chunk
+pub(super) fn portals_in_chunk(
+    chunk_pos: Vector2<i32>,
+    blocks: &ChunkBlocks,
+) -> Vec<WorldPosition> {
+    let Some(portal) = get_block("nether_portal") else {
+        return Vec::new();
+    };
+    blocks
+        .iter_blocks()
+        .enumerate()
+        .filter(|(_, state_id)| portal.states.iter().any(|state| state.id == *state_id))
+        .map(|(index, _)| {
+            // The blocks are ordered by y, then z, then x
+            let (x, z, y) = (index % 16, (index / 16) % 16, index / 256);
+            WorldPosition(Vector3::new(
+                chunk_pos.x * 16 + x as i32,
+                y as i32 + i32::from(WORLD_LOWEST_Y),
+                chunk_pos.z * 16 + z as i32,
+            ))
+        })
+        .collect()
+}
+
+impl World {
+    /// Adds the portals in the loaded chunks to [`World::portals`], e.g. once the chunks got loaded
+    pub(super) async fn index_portals(&self, chunks: &[Vector2<i32>]) {
+        for chunk_pos in chunks {
+            let Some(chunk) = self.level.get_loaded_chunk(chunk_pos) else {
+                continue;
+            };
+            let found = portals_in_chunk(*chunk_pos, &chunk.read().await.blocks);
+            if !found.is_empty() {
+                self.portals.lock().await.extend(found);
+            }
+        }
+    }
+
+    /// Removes the portals in the unloaded chunks from [`World::portals`], they may be gone once the chunks get loaded again
+    pub(super) async fn forget_portals(&self, chunks: &[Vector2<i32>]) {
+        if chunks.is_empty() {
+            return;
+        }
+        let chunks: HashSet<&Vector2<i32>> = chunks.iter().collect();
+        self.portals
+            .lock()
+            .await
+            .retain(|portal| !chunks.contains(&portal.chunk_and_chunk_relative_position().0));
+    }
+
+    /// Sends the players standing in a nether portal through it, unless they are on portal cooldown.
+    ///
+    /// TODO: Survival players have to stand in the portal for 80 ticks first in Vanilla
+    pub(super) async fn tick_portals(&self, server: &Server, players: &[Arc<Player>]) {
+        for player in players {
+            if player.is_on_portal_cooldown() {
+                continue;
+            }
+            let pos = player.living_entity.entity.block_pos.load();
+            if self.get_block_state_id(pos).await.is_ok_and(is_portal) {
+                server.worlds.travel_through_portal(player).await;
+            }
+        }
+    }
+
+    /// Finds the closest portal near the scaled destination or builds a new one if there is none.
+    /// Only portals in loaded chunks are found, loading all chunks in the search radius would hold up the tick.
+    /// Returns the position of the lowest portal block, which is where the player should arrive
+    pub async fn find_or_create_portal(&self, scaled_pos: WorldPosition) -> WorldPosition {
+        let radius = portal_search_radius(self.dimension_type);
+        let target = scaled_pos.0;
+        let closest = self
+            .portals
+            .lock()
+            .await
+            .iter()
+            .filter(|portal| {
+                (portal.0.x - target.x).abs() <= radius && (portal.0.z - target.z).abs() <= radius
+            })
+            // Prefer the closest and then the lowest portal block
+            .min_by_key(|portal| {
+                let x = i64::from(portal.0.x - target.x);
+                let y = i64::from(portal.0.y - target.y);
+                let z = i64::from(portal.0.z - target.z);
+                (x * x + y * y + z * z, portal.0.y)
+            })
+            .copied();
+        if let Some(portal) = closest {
+            return portal;
+        }
+
+        self.create_portal(scaled_pos).await
+    }
+
+    /// Builds a 4x5 obsidian frame along the x axis filled with portal blocks, with a small platform on both sides
+    async fn create_portal(&self, scaled_pos: WorldPosition) -> WorldPosition {
+        let (min_y, max_y) = portal_y_bounds(self.dimension_type);
+        let target = scaled_pos.0;
+        let y = if self.dimension_type == DimensionType::TheNether {
+            // The top block would be the bedrock ceiling
+            target.y
+        } else {
+            self.get_top_block(Vector2::new(target.x, target.z)).await + 1
+        }
+        .clamp(min_y, max_y);
+
+        let obsidian = block_state!("obsidian").state_id;
+        let portal = block_state!("nether_portal").state_id;
+        let air = block_state!("air").state_id;
+
+        for x in -1..=2 {
+            for dy in -1..=3 {
+                for z in -1..=1 {
+                    let pos = WorldPosition(Vector3::new(target.x + x, y + dy, target.z + z));
+                    let frame = x == -1 || x == 2 || dy == -1 || dy == 3;
+                    let state = if z != 0 {
+                        // Platform to stand on in front of and behind the portal
+                        if dy == -1 {
+                            obsidian
+                        } else {
+                            air
+                        }
+                    } else if frame {
+                        obsidian
+                    } else {
+                        portal
+                    };
+                    self.set_block_state(pos, state).await;
+                }
+            }
+        }
+
+        WorldPosition(Vector3::new(target.x, y, target.z))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_core::math::{position::WorldPosition, vector2::Vector2, vector3::Vector3};
+    use pumpkin_macros::block_state;
+    use pumpkin_registry::DimensionType;
+    use pumpkin_world::{chunk::ChunkBlocks, coordinates::ChunkRelativeBlockCoordinates};
+
+    use crate::world::World;
+
+    use super::{portals_in_chunk, scale_portal_position, MAX_HORIZONTAL_POSITION};
+
+    #[test]
+    fn scaled_between_dimensions() {
+        let position = Vector3::new(100.5, 70.0, -20.0);
+        assert!(
+            scale_portal_position(position, DimensionType::Overworld, DimensionType::TheNether)
+                == WorldPosition(Vector3::new(12, 70, -3))
+        );
+        assert!(
+            scale_portal_position(position, DimensionType::TheNether, DimensionType::Overworld)
+                == WorldPosition(Vector3::new(804, 70, -160))
+        );
+        assert!(
+            scale_portal_position(position, DimensionType::Overworld, DimensionType::TheEnd)
+                == WorldPosition(Vector3::new(100, 70, -20))
+        );
+    }
+
+    #[test]
+    fn scaled_inside_the_world() {
+        // Out of the bedrock of the Nether
+        let high = Vector3::new(0.0, 200.0, 0.0);
+        assert_eq!(
+            scale_portal_position(high, DimensionType::Overworld, DimensionType::TheNether)
+                .0
+                .y,
+            118
+        );
+        let low = Vector3::new(0.0, -50.0, 0.0);
+        assert_eq!(
+            scale_portal_position(low, DimensionType::Overworld, DimensionType::TheNether)
+                .0
+                .y,
+            5
+        );
+        // Inside of the world border
+        let far = Vector3::new(29_000_000.0, 64.0, -29_000_000.0);
+        let scaled = scale_portal_position(far, DimensionType::TheNether, DimensionType::Overworld);
+        assert_eq!(scaled.0.x, MAX_HORIZONTAL_POSITION as i32);
+        assert_eq!(scaled.0.z, -(MAX_HORIZONTAL_POSITION as i32));
+    }
+
+    #[test]
+    fn found_in_chunk() {
+        let mut blocks = ChunkBlocks::default();
+        blocks.set_block(
+            ChunkRelativeBlockCoordinates::from(Vector3::new(3, 70, 9)),
+            block_state!("nether_portal").state_id,
+        );
+        blocks.set_block(
+            ChunkRelativeBlockCoordinates::from(Vector3::new(4, 70, 9)),
+            block_state!("obsidian").state_id,
+        );
+        assert!(
+            portals_in_chunk(Vector2::new(-1, 2), &blocks)
+                == vec![WorldPosition(Vector3::new(-13, 70, 41))]
+        );
+    }
+
+    #[tokio::test]
+    async fn only_loaded_chunks_are_searched() {
+        let world = World::test_world();
+        let created = world
+            .find_or_create_portal(WorldPosition(Vector3::new(4, 70, 4)))
+            .await;
+        // Only the chunk the portal got built in
+        assert_eq!(world.level.loaded_chunk_count(), 1);
+        assert!(world.portals.lock().await.contains(&created));
+        assert!(world.find_or_create_portal(created).await == created);
+
+        world.forget_portals(&[Vector2::new(0, 0)]).await;
+        assert!(world.portals.lock().await.is_empty());
+    }
+}
@@ -0,0 +1,591 @@
+use std::sync::Arc;
+
+use pumpkin_core::{
+    math::{boundingbox::BoundingBox, position::WorldPosition, vector3::Vector3},
+    GameMode,
+};
+use pumpkin_entity::{entity_type::EntityType, EntityId};
+use pumpkin_macros::sound;
+use pumpkin_protocol::{
+    client::play::{
+        CEntityVelocity, CRemoveEntities, CSpawnEntity, CTeleportEntity, CUpdateEntityPos,
+    },
+    codec::var_int::VarInt,
+    SoundCategory,
+};
+use pumpkin_world::{
+    block::block_registry::state_is_solid, coordinates::ChunkRelativeBlockCoordinates, is_in_world,
+    WORLD_LOWEST_Y,
+};
+use uuid::Uuid;
+
+use crate::{
+    entity::{death::DeathCause, mob::MobEntity, player::Player},
+    server::Server,
+};
+
+use super::World;
+
+/// Projectiles lose this much of their speed every tick, like Vanilla
+const DRAG: f64 = 0.99;
+/// Projectiles fall this much faster every tick, like Vanilla
+const GRAVITY: f64 = 0.05;
+/// Entities are hit a bit outside of their bounding box, like Vanilla
+const HIT_MARGIN: f64 = 0.3;
+/// The shooter can't hit themself for this many ticks, so projectiles don't hit them right after leaving their body
+const SHOOTER_GRACE_TICKS: u32 = 5;
+/// Arrows stuck in a block disappear after 1 minute, like Vanilla
+const STUCK_DESPAWN_TICKS: u32 = 1200;
+/// The damage type of arrows in the damage type registry
+const ARROW_DAMAGE_TYPE: u8 = 0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProjectileKind {
+    Arrow,
+}
+
+impl ProjectileKind {
+    const fn entity_type(self) -> EntityType {
+        match self {
+            Self::Arrow => EntityType::Arrow,
+        }
+    }
+}
+
+/// A projectile flying through the world or stuck in a block, see [`World::spawn_projectile`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Projectile {
+    pub entity_id: EntityId,
+    uuid: Uuid,
+    pub kind: ProjectileKind,
+    /// The entity which shot the projectile, `None` if it was shot by e.g. a dispenser
+    pub shooter: Option<EntityId>,
+    pub position: Vector3<f64>,
+    pub velocity: Vector3<f64>,
+    /// Ticks since it was shot, or since it got stuck in a block
+    age: u32,
+    stuck: bool,
+}
+
+impl Projectile {
+    fn spawn_packet(&self) -> CSpawnEntity {
+        // The client uses the data to know who shot the projectile, 0 means nobody
+        let shooter = self.shooter.map_or(0, |shooter| shooter + 1);
+        CSpawnEntity::new(
+            self.entity_id.into(),
+            self.uuid,
+            (self.kind.entity_type() as i32).into(),
+            self.position.x,
+            self.position.y,
+            self.position.z,
+            0.0,
+            0.0,
+            0.0,
+            shooter.into(),
+            self.velocity.x as f32,
+            self.velocity.y as f32,
+            self.velocity.z as f32,
+        )
+    }
+
+    /// Moves the projectile along its velocity, which slows down and falls like in Vanilla
+    fn fly(&mut self) {
+        self.position += self.velocity;
+        self.velocity = self.velocity * DRAG;
+        self.velocity.y -= GRAVITY;
+    }
+}
+
+/// The change of position in a [`CUpdateEntityPos`], in 1/4096 of a block.
+/// `None` if the projectile moved 8 blocks or more along an axis, which doesn't fit
+fn position_delta(old: Vector3<f64>, new: Vector3<f64>) -> Option<Vector3<i16>> {
+    let delta = |old: f64, new: f64| {
+        let delta = new.mul_add(4096.0, -(old * 4096.0)).round();
+        (f64::from(i16::MIN)..=f64::from(i16::MAX))
+            .contains(&delta)
+            .then_some(delta as i16)
+    };
+    Some(Vector3::new(
+        delta(old.x, new.x)?,
+        delta(old.y, new.y)?,
+        delta(old.z, new.z)?,
+    ))
+}
+
+/// What a projectile hit
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ProjectileImpact {
+    Block(WorldPosition),
+    Entity(EntityId),
+}
+
+/// All projectiles of a world
+#[derive(Default)]
+pub struct Projectiles(Vec<Projectile>);
+
+/// An entity a projectile can hit
+enum Target {
+    Player(Arc<Player>),
+    Mob(Arc<MobEntity>),
+}
+
+impl Target {
+    fn entity_id(&self) -> EntityId {
+        match self {
+            Self::Player(player) => player.entity_id(),
+            Self::Mob(mob) => mob.living_entity.entity_id(),
+        }
+    }
+
+    fn hit_box(&self) -> BoundingBox {
+        let entity = match self {
+            Self::Player(player) => &player.living_entity.entity,
+            Self::Mob(mob) => &mob.living_entity.entity,
+        };
+        let pos = entity.pos.load();
+        let mut hit_box =
+            BoundingBox::new_from_pos(pos.x, pos.y, pos.z, &entity.bounding_box_size.load());
+        hit_box.min_x -= HIT_MARGIN;
+        hit_box.min_y -= HIT_MARGIN;
+        hit_box.min_z -= HIT_MARGIN;
+        hit_box.max_x += HIT_MARGIN;
+        hit_box.max_y += HIT_MARGIN;
+        hit_box.max_z += HIT_MARGIN;
+        hit_box
+    }
+}
+
+/// How far along the segment from `from` to `to` it enters the box, between `0.0` and `1.0`.
+/// `None` if the segment misses the box
+fn segment_box_entry(from: Vector3<f64>, to: Vector3<f64>, hit_box: &BoundingBox) -> Option<f64> {
+    let delta = to.sub(&from);
+    let mut enter: f64 = 0.0;
+    let mut exit: f64 = 1.0;
+    for (start, direction, min, max) in [
+        (from.x, delta.x, hit_box.min_x, hit_box.max_x),
+        (from.y, delta.y, hit_box.min_y, hit_box.max_y),
+        (from.z, delta.z, hit_box.min_z, hit_box.max_z),
+    ] {
+        if direction.abs() < f64::EPSILON {
+            if start < min || start > max {
+                return None;
+            }
+            continue;
+        }
+        let (a, b) = ((min - start) / direction, (max - start) / direction);
+        enter = enter.max(a.min(b));
+        exit = exit.min(a.max(b));
+        if enter > exit {
+            return None;
+        }
+    }
+    Some(enter)
+}
+
+/// Every block the segment from `from` to `to` passes through, in the order it passes them
+fn blocks_along(from: Vector3<f64>, to: Vector3<f64>) -> Vec<WorldPosition> {
+    let start = [from.x, from.y, from.z];
+    let delta = [to.x - from.x, to.y - from.y, to.z - from.z];
+    let mut block = start.map(|axis| axis.floor() as i32);
+    let end = [to.x, to.y, to.z].map(|axis| axis.floor() as i32);
+
+    // How far along the segment the next block border of every axis is, and how far apart the borders are
+    let mut next_border: [f64; 3] = std::array::from_fn(|axis| {
+        if delta[axis] > 0.0 {
+            (f64::from(block[axis] + 1) - start[axis]) / delta[axis]
+        } else if delta[axis] < 0.0 {
+            (f64::from(block[axis]) - start[axis]) / delta[axis]
+        } else {
+            f64::INFINITY
+        }
+    });
+    let border_distance = delta.map(|axis| 1.0 / axis.abs());
+
+    let mut blocks = vec![WorldPosition(Vector3::new(block[0], block[1], block[2]))];
+    while block != end {
+        let axis = (0..3)
+            .min_by(|a, b| next_border[*a].total_cmp(&next_border[*b]))
+            .unwrap_or(0);
+        if next_border[axis] > 1.0 {
+            break;
+        }
+        block[axis] += if delta[axis] > 0.0 { 1 } else { -1 };
+        next_border[axis] += border_distance[axis];
+        blocks.push(WorldPosition(Vector3::new(block[0], block[1], block[2])));
+    }
+    blocks
+}
+
+impl World {
+    /// Shoots a projectile from the position and shows it to all players, it flies and falls every tick until
+    /// it hits a block or an entity. `None` if the shooter is a player who can't spawn more entities
+    pub async fn spawn_projectile(
+        &self,
+        server: &Server,
+        shooter: Option<EntityId>,
+        kind: ProjectileKind,
+        pos: Vector3<f64>,
+        velocity: Vector3<f64>,
+    ) -> Option<EntityId> {
+        let player = match shooter {
+            Some(shooter) => self.get_player_by_entityid(shooter).await,
+            None => None,
+        };
+        if let Some(player) = &player {
+            if !self.may_spawn_entity_for(player).await {
+                return None;
+            }
+        }
+        let projectile = Projectile {
+            entity_id: server.new_entity_id(),
+            uuid: Uuid::new_v4(),
+            kind,
+            shooter,
+            position: pos,
+            velocity,
+            age: 0,
+            stuck: false,
+        };
+        if let Some(player) = &player {
+            self.attribute_entity(player, projectile.entity_id).await;
+        }
+        self.projectiles.lock().await.0.push(projectile);
+        self.broadcast_packet_all(&projectile.spawn_packet()).await;
+        let entity_id = projectile.entity_id.into();
+        self.broadcast_packet_all(&CEntityVelocity::new(
+            &entity_id, velocity.x, velocity.y, velocity.z,
+        ))
+        .await;
+        Some(projectile.entity_id)
+    }
+
+    /// Shows a player who joined the world all projectiles
+    pub async fn send_projectiles(&self, player: &Player) {
+        for projectile in &self.projectiles.lock().await.0 {
+            player.client.send_packet(&projectile.spawn_packet()).await;
+        }
+    }
+
+    /// The type of every projectile and where it is, see [`World::count_entities`]
+    pub(super) async fn projectile_positions(&self) -> Vec<(EntityType, Vector3<f64>)> {
+        self.projectiles
+            .lock()
+            .await
+            .0
+            .iter()
+            .map(|projectile| (projectile.kind.entity_type(), projectile.position))
+            .collect()
+    }
+
+    /// Whether entities collide with the block, `None` if its chunk is not loaded. There are no blocks outside of the world
+    async fn is_solid_loaded(&self, position: WorldPosition) -> Option<bool> {
+        if !is_in_world(position.0.y) {
+            return Some(false);
+        }
+        let (chunk, relative) = position.chunk_and_chunk_relative_position();
+        let chunk = self.level.get_loaded_chunk(&chunk)?;
+        let chunk = chunk.read().await;
+        Some(
+            chunk
+                .blocks
+                .get_block(ChunkRelativeBlockCoordinates::from(relative))
+                .is_some_and(state_is_solid),
+        )
+    }
+
+    /// Moves all flying projectiles, lets them hit blocks and entities in their way and removes old ones.
+    /// Projectiles flying into chunks which are not loaded are removed, they would not be ticked there in Vanilla either
+    pub(super) async fn tick_projectiles(&self) {
+        let mut targets: Vec<Target> = self
+            .current_players
+            .lock()
+            .await
+            .values()
+            .filter(|player| {
+                player.gamemode.load() != GameMode::Spectator
+                    && player.living_entity.health.load() > 0.0
+            })
+            .cloned()
+            .map(Target::Player)
+            .collect();
+        targets.extend(
+            self.current_living_mobs
+                .lock()
+                .await
+                .values()
+                .filter(|mob| mob.living_entity.health.load() > 0.0)
+                .cloned()
+                .map(Target::Mob),
+        );
+
+        let mut moved = Vec::new();
+        let mut impacts = Vec::new();
+        let mut removed: Vec<VarInt> = Vec::new();
+        {
+            let mut projectiles = self.projectiles.lock().await;
+            let mut kept = Vec::with_capacity(projectiles.0.len());
+            for mut projectile in std::mem::take(&mut projectiles.0) {
+                projectile.age += 1;
+                if projectile.stuck {
+                    if projectile.age >= STUCK_DESPAWN_TICKS {
+                        removed.push(projectile.entity_id.into());
+                    } else {
+                        kept.push(projectile);
+                    }
+                    continue;
+                }
+
+                let from = projectile.position;
+                let to = from.add(&projectile.velocity);
+                let mut block_hit = None;
+                let mut unloaded = false;
+                for position in blocks_along(from, to) {
+                    match self.is_solid_loaded(position).await {
+                        Some(true) => {
+                            let along =
+                                segment_box_entry(from, to, &BoundingBox::from_block(&position))
+                                    .unwrap_or(0.0);
+                            block_hit = Some((along, position));
+                            break;
+                        }
+                        Some(false) => {}
+                        None => {
+                            unloaded = true;
+                            break;
+                        }
+                    }
+                }
+                let entity_hit = targets
+                    .iter()
+                    .filter(|target| {
+                        Some(target.entity_id()) != projectile.shooter
+                            || projectile.age > SHOOTER_GRACE_TICKS
+                    })
+                    .filter_map(|target| {
+                        segment_box_entry(from, to, &target.hit_box())
+                            .map(|along| (along, target.entity_id()))
+                    })
+                    .min_by(|(a, _), (b, _)| a.total_cmp(b));
+
+                match (block_hit, entity_hit) {
+                    (block, Some((along, entity_id)))
+                        if block.is_none_or(|(block_along, _)| along < block_along) =>
+                    {
+                        impacts.push((projectile, ProjectileImpact::Entity(entity_id)));
+                        removed.push(projectile.entity_id.into());
+                    }
+                    (Some((along, position)), _) => {
+                        projectile.position = from.add(&(projectile.velocity * along));
+                        projectile.velocity = Vector3::new(0.0, 0.0, 0.0);
+                        projectile.stuck = true;
+                        projectile.age = 0;
+                        moved.push((
+                            projectile.entity_id,
+                            from,
+                            projectile.position,
+                            projectile.velocity,
+                        ));
+                        impacts.push((projectile, ProjectileImpact::Block(position)));
+                        kept.push(projectile);
+                    }
+                    _ if unloaded || to.y < f64::from(WORLD_LOWEST_Y) - 64.0 => {
+                        removed.push(projectile.entity_id.into());
+                    }
+                    _ => {
+                        projectile.fly();
+                        moved.push((
+                            projectile.entity_id,
+                            from,
+                            projectile.position,
+                            projectile.velocity,
+                        ));
+                        kept.push(projectile);
+                    }
+                }
+            }
+            projectiles.0 = kept;
+        }
+
+        for (entity_id, old, new, velocity) in moved {
+            if let Some(delta) = position_delta(old, new) {
+                self.broadcast_packet_all(&CUpdateEntityPos::new(entity_id.into(), delta, false))
+                    .await;
+            } else {
+                // Too far for a relative move, which can happen with fast projectiles
+                self.broadcast_packet_all(&CTeleportEntity::new(
+                    entity_id.into(),
+                    new,
+                    velocity,
+                    0.0,
+                    0.0,
+                    &[],
+                    false,
+                ))
+                .await;
+            }
+        }
+        if !removed.is_empty() {
+            for entity_id in &removed {
+                self.forget_entity_owner(entity_id.0).await;
+            }
+            self.broadcast_packet_all(&CRemoveEntities::new(&removed))
+                .await;
+        }
+        for (projectile, impact) in impacts {
+            self.on_projectile_impact(&projectile, impact, &targets)
+                .await;
+        }
+    }
+
+    /// What happens when a projectile hits something
+    async fn on_projectile_impact(
+        &self,
+        projectile: &Projectile,
+        impact: ProjectileImpact,
+        targets: &[Target],
+    ) {
+        match (projectile.kind, impact) {
+            (ProjectileKind::Arrow, ProjectileImpact::Block(_)) => {
+                self.play_sound(
+                    sound!("entity.arrow.hit"),
+                    SoundCategory::Neutral,
+                    &projectile.position,
+                )
+                .await;
+            }
+            (ProjectileKind::Arrow, ProjectileImpact::Entity(entity_id)) => {
+                let Some(target) = targets
+                    .iter()
+                    .find(|target| target.entity_id() == entity_id)
+                else {
+                    return;
+                };
+                // Faster arrows hurt more, like Vanilla
+                let damage = (projectile.velocity.length() * 2.0).ceil() as f32;
+                self.damage_entity(target, damage, projectile.shooter).await;
+                self.play_sound(
+                    sound!("entity.arrow.hit"),
+                    SoundCategory::Neutral,
+                    &projectile.position,
+                )
+                .await;
+            }
+        }
+    }
+
+    /// Damages the entity a projectile hit, shown as arrow damage
+    async fn damage_entity(&self, target: &Target, damage: f32, shooter: Option<EntityId>) {
+        match target {
+            Target::Player(player) => {
+                if player.gamemode.load() == GameMode::Creative {
+                    return;
+                }
+                let shooter = match shooter {
+                    Some(shooter) => self.get_player_by_entityid(shooter).await,
+                    None => None,
+                };
+                if let Some(shooter) = &shooter {
+                    if !self.allows_pvp(shooter, player).await {
+                        return;
+                    }
+                }
+                if player.living_entity.damage(damage, ARROW_DAMAGE_TYPE).await {
+                    let cause = shooter.as_ref().map_or(DeathCause::Generic, |shooter| {
+                        DeathCause::Arrow(&shooter.gameprofile.name)
+                    });
+                    player.on_death(cause).await;
+                }
+            }
+            Target::Mob(mob) => {
+                let living = &mob.living_entity;
+                if living.damage(damage, ARROW_DAMAGE_TYPE).await {
+                    // TODO: Drop experience once the world knows the server
+                    living
+                        .entity
+                        .world()
+                        .remove_mob_entity(living.clone())
+                        .await;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_core::math::{boundingbox::BoundingBox, position::WorldPosition, vector3::Vector3};
+
+    use crate::world::World;
+
+    use super::{blocks_along, position_delta, segment_box_entry};
+
+    #[test]
+    fn segment_enters_box() {
+        let hit_box = BoundingBox::new(Vector3::new(1.0, 0.0, 0.0), Vector3::new(2.0, 1.0, 1.0));
+        let along = segment_box_entry(
+            Vector3::new(0.0, 0.5, 0.5),
+            Vector3::new(4.0, 0.5, 0.5),
+            &hit_box,
+        )
+        .unwrap();
+        assert!((along - 0.25).abs() < 1e-9);
+        assert!(segment_box_entry(
+            Vector3::new(0.0, 2.0, 0.5),
+            Vector3::new(4.0, 2.0, 0.5),
+            &hit_box
+        )
+        .is_none());
+        // Stops right before the box
+        assert!(segment_box_entry(
+            Vector3::new(0.0, 0.5, 0.5),
+            Vector3::new(0.9, 0.5, 0.5),
+            &hit_box
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn blocks_in_order() {
+        let blocks = blocks_along(Vector3::new(0.5, 64.5, 0.5), Vector3::new(2.5, 63.5, 0.5));
+        assert!(
+            blocks
+                == [
+                    WorldPosition(Vector3::new(0, 64, 0)),
+                    WorldPosition(Vector3::new(1, 64, 0)),
+                    WorldPosition(Vector3::new(1, 63, 0)),
+                    WorldPosition(Vector3::new(2, 63, 0)),
+                ]
+        );
+        assert!(
+            blocks_along(Vector3::new(0.5, 64.5, 0.5), Vector3::new(0.6, 64.5, 0.5))
+                == [WorldPosition(Vector3::new(0, 64, 0))]
+        );
+    }
+
+    #[test]
+    fn delta_only_for_short_moves() {
+        let old = Vector3::new(0.0, 64.0, 0.0);
+        assert_eq!(
+            position_delta(old, Vector3::new(1.5, 63.0, -7.9)),
+            Some(Vector3::new(6144, -4096, -32358))
+        );
+        assert!(position_delta(old, Vector3::new(8.0, 64.0, 0.0)).is_none());
+        assert!(position_delta(old, Vector3::new(0.0, 0.0, 0.0)).is_none());
+    }
+
+    #[tokio::test]
+    async fn nothing_solid_outside_of_the_world() {
+        let world = World::test_world();
+        world
+            .set_block_state(WorldPosition(Vector3::new(0, 0, 0)), 0)
+            .await;
+        for y in [-65, -200, 320, 400] {
+            assert_eq!(
+                world
+                    .is_solid_loaded(WorldPosition(Vector3::new(0, y, 0)))
+                    .await,
+                Some(false)
+            );
+        }
+    }
+}
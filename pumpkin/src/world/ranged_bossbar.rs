@@ -0,0 +1,210 @@
+use std::collections::{HashMap, HashSet};
+
+use pumpkin_core::math::vector3::Vector3;
+use pumpkin_protocol::client::play::{BosseventAction, CBossEvent};
+use uuid::Uuid;
+
+use super::{bossbar::Bossbar, World};
+use crate::entity::player::Player;
+
+/// A boss bar only shown to the players within a radius around its anchor, like the bars of the Ender Dragon and the Wither
+struct RangedBossbar {
+    bossbar: Bossbar,
+    anchor: Vector3<f64>,
+    radius: f64,
+    /// The players currently seeing the bar
+    viewers: HashSet<Uuid>,
+}
+
+/// All boss bars shown by distance, see [`World::add_ranged_bossbar`]
+#[derive(Default)]
+pub struct RangedBossbars(HashMap<Uuid, RangedBossbar>);
+
+/// The players who start seeing a bar and the ones who stop seeing it, given who sees it now and who is in range
+fn visibility_changes(viewers: &HashSet<Uuid>, in_range: &HashSet<Uuid>) -> (Vec<Uuid>, Vec<Uuid>) {
+    let shown = in_range.difference(viewers).copied().collect();
+    let hidden = viewers.difference(in_range).copied().collect();
+    (shown, hidden)
+}
+
+impl World {
+    /// Shows the boss bar to every player within the radius around the anchor, players see it as soon as they come close
+    /// and stop seeing it when they leave, see [`World::move_ranged_bossbar`]
+    pub async fn add_ranged_bossbar(&self, bossbar: Bossbar, anchor: Vector3<f64>, radius: f64) {
+        let uuid = bossbar.uuid;
+        self.ranged_bossbars.lock().await.0.insert(
+            uuid,
+            RangedBossbar {
+                bossbar,
+                anchor,
+                radius,
+                viewers: HashSet::new(),
+            },
+        );
+        self.update_ranged_bossbar_viewers(&uuid).await;
+    }
+
+    /// Moves the anchor of the boss bar, e.g. because the boss moved
+    pub async fn move_ranged_bossbar(&self, uuid: &Uuid, anchor: Vector3<f64>) {
+        if let Some(bar) = self.ranged_bossbars.lock().await.0.get_mut(uuid) {
+            bar.anchor = anchor;
+        }
+    }
+
+    /// Updates the health of the boss bar for everyone seeing it
+    pub async fn update_ranged_bossbar_health(&self, uuid: &Uuid, health: f32) {
+        let viewers = {
+            let mut bars = self.ranged_bossbars.lock().await;
+            let Some(bar) = bars.0.get_mut(uuid) else {
+                return;
+            };
+            bar.bossbar.health = health;
+            bar.viewers.clone()
+        };
+        for viewer in viewers {
+            if let Some(player) = self.get_player_by_uuid(viewer).await {
+                player.update_bossbar_health(uuid, health).await;
+            }
+        }
+    }
+
+    /// Removes the boss bar from everyone seeing it
+    pub async fn remove_ranged_bossbar(&self, uuid: &Uuid) {
+        let Some(bar) = self.ranged_bossbars.lock().await.0.remove(uuid) else {
+            return;
+        };
+        for viewer in bar.viewers {
+            if let Some(player) = self.get_player_by_uuid(viewer).await {
+                player.remove_bossbar(*uuid).await;
+            }
+        }
+    }
+
+    /// Hides all boss bars the player sees, used when the player leaves the world for another one,
+    /// as the bars of this world can't reach them there anymore
+    pub async fn hide_ranged_bossbars(&self, player: &Player) {
+        let uuid = player.gameprofile.id;
+        let seen: Vec<Uuid> = self
+            .ranged_bossbars
+            .lock()
+            .await
+            .0
+            .iter_mut()
+            .filter_map(|(bar_uuid, bar)| bar.viewers.remove(&uuid).then_some(*bar_uuid))
+            .collect();
+        for bar_uuid in seen {
+            player.remove_bossbar(bar_uuid).await;
+        }
+    }
+
+    /// Shows the boss bar to players who came into range and hides it from the ones who left
+    async fn update_ranged_bossbar_viewers(&self, uuid: &Uuid) {
+        let Some((anchor, radius)) = self
+            .ranged_bossbars
+            .lock()
+            .await
+            .0
+            .get(uuid)
+            .map(|bar| (bar.anchor, bar.radius))
+        else {
+            return;
+        };
+        let nearby = self.get_nearby_players(anchor, radius).await;
+        let in_range: HashSet<Uuid> = nearby.keys().copied().collect();
+
+        let (bossbar, shown, hidden) = {
+            let mut bars = self.ranged_bossbars.lock().await;
+            let Some(bar) = bars.0.get_mut(uuid) else {
+                return;
+            };
+            let (shown, hidden) = visibility_changes(&bar.viewers, &in_range);
+            bar.viewers.clone_from(&in_range);
+            (bar.bossbar.clone(), shown, hidden)
+        };
+        for viewer in shown {
+            if let Some(player) = nearby.get(&viewer) {
+                player.send_bossbar(&bossbar).await;
+            }
+        }
+        // Players who went to another world got the removal from `hide_ranged_bossbars` already
+        let remove = CBossEvent::new(uuid, BosseventAction::Remove);
+        for viewer in hidden {
+            if let Some(player) = self.get_player_by_uuid(viewer).await {
+                player.client.send_packet(&remove).await;
+            }
+        }
+    }
+
+    pub(super) async fn tick_ranged_bossbars(&self) {
+        let uuids: Vec<Uuid> = self
+            .ranged_bossbars
+            .lock()
+            .await
+            .0
+            .keys()
+            .copied()
+            .collect();
+        for uuid in uuids {
+            self.update_ranged_bossbar_viewers(&uuid).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::HashSet, sync::Arc};
+
+    use pumpkin_core::{math::vector3::Vector3, text::TextComponent, GameMode};
+    use tokio::net::{TcpListener, TcpStream};
+    use uuid::Uuid;
+
+    use super::visibility_changes;
+    use crate::{
+        entity::player::Player,
+        net::Client,
+        world::{bossbar::Bossbar, World},
+    };
+
+    #[test]
+    fn players_entering_and_leaving() {
+        let (stays, leaves, enters) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let viewers = HashSet::from([stays, leaves]);
+        let in_range = HashSet::from([stays, enters]);
+
+        let (shown, hidden) = visibility_changes(&viewers, &in_range);
+        assert_eq!(shown, vec![enters]);
+        assert_eq!(hidden, vec![leaves]);
+        assert_eq!(
+            visibility_changes(&in_range, &in_range),
+            (Vec::new(), Vec::new())
+        );
+    }
+
+    #[tokio::test]
+    async fn hidden_when_leaving_world() {
+        let world = Arc::new(World::test_world());
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let _connection = TcpStream::connect(listener.local_addr().unwrap())
+            .await
+            .unwrap();
+        let (stream, address) = listener.accept().await.unwrap();
+        let client = Arc::new(Client::new(stream, address, 0));
+        let player = Arc::new(Player::new(client, world.clone(), 1, GameMode::Survival).await);
+        let uuid = player.gameprofile.id;
+        world.add_player(uuid, player.clone()).await;
+
+        let bossbar = Bossbar::new(TextComponent::text("Boss"));
+        let bar_uuid = bossbar.uuid;
+        world
+            .add_ranged_bossbar(bossbar, Vector3::new(0.0, 0.0, 0.0), 1_000.0)
+            .await;
+        assert!(world.ranged_bossbars.lock().await.0[&bar_uuid]
+            .viewers
+            .contains(&uuid));
+
+        world.hide_ranged_bossbars(&player).await;
+        assert!(world.ranged_bossbars.lock().await.0[&bar_uuid]
+            .viewers
+            .is_empty());
+    }
+}
@@ -0,0 +1,280 @@
+use pumpkin_core::math::position::WorldPosition;
+use pumpkin_world::{
+    block::{
+        block_registry::{
+            get_block_and_state_by_state_id, get_state_index, state_is_solid, state_opacity,
+        },
+        BlockFace,
+    },
+    is_in_world,
+};
+
+use super::World;
+
+/// The power a block sends into one of its neighbors. Weak power only reaches the neighbor, strong power also goes
+/// through it if the neighbor is a conductor, like a lever powering the block it is placed on
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct EmittedPower {
+    weak: u8,
+    strong: u8,
+}
+
+impl EmittedPower {
+    const NONE: Self = Self { weak: 0, strong: 0 };
+
+    const fn weak(power: u8) -> Self {
+        Self {
+            weak: power,
+            strong: 0,
+        }
+    }
+
+    const fn strong(power: u8) -> Self {
+        Self {
+            weak: power,
+            strong: power,
+        }
+    }
+}
+
+fn property<'a>(properties: &[(&str, &'a str)], name: &str) -> Option<&'a str> {
+    properties
+        .iter()
+        .find(|(property, _)| *property == name)
+        .map(|(_, value)| *value)
+}
+
+fn horizontal_face(name: &str) -> Option<BlockFace> {
+    match name {
+        "north" => Some(BlockFace::North),
+        "south" => Some(BlockFace::South),
+        "west" => Some(BlockFace::West),
+        "east" => Some(BlockFace::East),
+        _ => None,
+    }
+}
+
+/// The neighbor levers and buttons are placed on, from their `face` and `facing`
+fn attached_face(properties: &[(&str, &str)]) -> Option<BlockFace> {
+    match property(properties, "face")? {
+        "floor" => Some(BlockFace::Bottom),
+        "ceiling" => Some(BlockFace::Top),
+        _ => horizontal_face(property(properties, "facing")?).map(BlockFace::opposite),
+    }
+}
+
+/// The power the block in the state sends into its neighbor in the direction
+fn emitted_power(
+    block_name: &str,
+    properties: &[(&str, &str)],
+    direction: BlockFace,
+) -> EmittedPower {
+    let powered = property(properties, "powered") == Some("true");
+    let lit = property(properties, "lit") == Some("true");
+    let power = property(properties, "power").and_then(|power| power.parse::<u8>().ok());
+    match block_name {
+        "redstone_block" => EmittedPower::weak(15),
+        "lever" if powered => match attached_face(properties) {
+            Some(attached) if attached == direction => EmittedPower::strong(15),
+            _ => EmittedPower::weak(15),
+        },
+        name if name.ends_with("_button") && powered => match attached_face(properties) {
+            Some(attached) if attached == direction => EmittedPower::strong(15),
+            _ => EmittedPower::weak(15),
+        },
+        name if name.ends_with("_pressure_plate") => {
+            let power = power.unwrap_or(if powered { 15 } else { 0 });
+            if direction == BlockFace::Bottom {
+                EmittedPower::strong(power)
+            } else {
+                EmittedPower::weak(power)
+            }
+        }
+        // Torches don't power the block they are placed on, it turns them off instead
+        "redstone_torch" | "redstone_wall_torch" if lit => {
+            let attached = property(properties, "facing")
+                .and_then(horizontal_face)
+                .map_or(BlockFace::Bottom, BlockFace::opposite);
+            match direction {
+                BlockFace::Top => EmittedPower::strong(15),
+                direction if direction == attached => EmittedPower::NONE,
+                _ => EmittedPower::weak(15),
+            }
+        }
+        // TODO: Wire going up the side of a block, and wire powering wire with one less once it propagates
+        "redstone_wire" => {
+            let power = power.unwrap_or(0);
+            let connected = match direction {
+                BlockFace::Bottom => true,
+                BlockFace::Top => false,
+                BlockFace::North => property(properties, "north") != Some("none"),
+                BlockFace::South => property(properties, "south") != Some("none"),
+                BlockFace::West => property(properties, "west") != Some("none"),
+                BlockFace::East => property(properties, "east") != Some("none"),
+            };
+            if connected {
+                EmittedPower::strong(power)
+            } else {
+                EmittedPower::NONE
+            }
+        }
+        _ => EmittedPower::NONE,
+    }
+}
+
+fn state_emitted_power(state_id: u16, direction: BlockFace) -> EmittedPower {
+    let (Some((block, _)), Some(index)) = (
+        get_block_and_state_by_state_id(state_id),
+        get_state_index(state_id),
+    ) else {
+        return EmittedPower::NONE;
+    };
+    emitted_power(
+        &block.name,
+        &block.state_properties(usize::from(index)),
+        direction,
+    )
+}
+
+/// Whether strong power goes through the state, full solid blocks conduct and glass or slabs don't
+fn is_conductor(state_id: u16) -> bool {
+    state_is_solid(state_id) && state_opacity(state_id) == 15
+}
+
+impl World {
+    /// The power level between `0` and `15` the block receives from its neighbors: the power sources next to it, like
+    /// redstone blocks, levers, buttons and wire, and the conductors next to it which get strongly powered themselves.
+    ///
+    /// Only direct power is taken into account, wire reports the power level of its state
+    pub async fn get_redstone_power(&self, position: WorldPosition) -> u8 {
+        let mut power = 0;
+        for face in BlockFace::ALL {
+            let neighbor = WorldPosition(position.0.add(&face.to_offset()));
+            // Nothing powers from outside of the world
+            if !is_in_world(neighbor.0.y) {
+                continue;
+            }
+            let Ok(state_id) = self.get_block_state_id(neighbor).await else {
+                continue;
+            };
+            let received = if is_conductor(state_id) {
+                self.get_strong_redstone_power(neighbor).await
+            } else {
+                state_emitted_power(state_id, face.opposite()).weak
+            };
+            power = power.max(received);
+            if power == 15 {
+                break;
+            }
+        }
+        power
+    }
+
+    /// The strongest strong power the block receives from its neighbors, which is what a conductor passes on
+    pub async fn get_strong_redstone_power(&self, position: WorldPosition) -> u8 {
+        let mut power = 0;
+        for face in BlockFace::ALL {
+            let neighbor = WorldPosition(position.0.add(&face.to_offset()));
+            if !is_in_world(neighbor.0.y) {
+                continue;
+            }
+            if let Ok(state_id) = self.get_block_state_id(neighbor).await {
+                power = power.max(state_emitted_power(state_id, face.opposite()).strong);
+            }
+        }
+        power
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_core::math::{position::WorldPosition, vector3::Vector3};
+    use pumpkin_macros::block_state;
+    use pumpkin_world::block::BlockFace;
+
+    use super::{emitted_power, EmittedPower};
+    use crate::world::World;
+
+    #[test]
+    fn lever_on_a_wall() {
+        let properties = [("face", "wall"), ("facing", "north"), ("powered", "true")];
+        // Facing north, so it is placed on the block south of it
+        assert_eq!(
+            emitted_power("lever", &properties, BlockFace::South),
+            EmittedPower::strong(15)
+        );
+        assert_eq!(
+            emitted_power("lever", &properties, BlockFace::Top),
+            EmittedPower::weak(15)
+        );
+        let off = [("face", "wall"), ("facing", "north"), ("powered", "false")];
+        assert_eq!(
+            emitted_power("lever", &off, BlockFace::South),
+            EmittedPower::NONE
+        );
+    }
+
+    #[test]
+    fn wire_and_torch() {
+        let wire = [
+            ("east", "side"),
+            ("north", "none"),
+            ("power", "7"),
+            ("south", "none"),
+            ("west", "none"),
+        ];
+        assert_eq!(
+            emitted_power("redstone_wire", &wire, BlockFace::East),
+            EmittedPower::strong(7)
+        );
+        assert_eq!(
+            emitted_power("redstone_wire", &wire, BlockFace::North),
+            EmittedPower::NONE
+        );
+        let torch = [("lit", "true")];
+        assert_eq!(
+            emitted_power("redstone_torch", &torch, BlockFace::Bottom),
+            EmittedPower::NONE
+        );
+        assert_eq!(
+            emitted_power("redstone_torch", &torch, BlockFace::Top),
+            EmittedPower::strong(15)
+        );
+    }
+
+    #[tokio::test]
+    async fn powered_through_a_block() {
+        let world = World::test_world();
+        let redstone_block = block_state!("redstone_block").state_id;
+        let stone = block_state!("stone").state_id;
+        let position = WorldPosition(Vector3::new(0, 200, 0));
+        assert_eq!(world.get_redstone_power(position).await, 0);
+
+        world
+            .set_block_state(WorldPosition(Vector3::new(1, 200, 0)), redstone_block)
+            .await;
+        assert_eq!(world.get_redstone_power(position).await, 15);
+
+        // Redstone blocks only power weakly, so the power doesn't go through stone
+        let behind = WorldPosition(Vector3::new(-1, 200, 0));
+        world.set_block_state(position, stone).await;
+        assert_eq!(world.get_redstone_power(behind).await, 0);
+    }
+
+    #[tokio::test]
+    async fn edges_of_the_world() {
+        let world = World::test_world();
+        let redstone_block = block_state!("redstone_block").state_id;
+        for y in [-64, 319] {
+            world
+                .set_block_state(WorldPosition(Vector3::new(1, y, 0)), redstone_block)
+                .await;
+            assert_eq!(
+                world
+                    .get_redstone_power(WorldPosition(Vector3::new(0, y, 0)))
+                    .await,
+                15
+            );
+        }
+    }
+}
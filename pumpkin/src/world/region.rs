@@ -0,0 +1,203 @@
+use std::collections::HashMap;
+
+use pumpkin_core::{
+    math::{position::WorldPosition, vector3::Vector3},
+    permission::PermissionLvl,
+};
+
+use crate::entity::player::Player;
+
+use super::World;
+
+/// Operators with this permission level can build in regions which deny building, like Vanilla's spawn protection
+const BUILD_BYPASS_PERMISSION: PermissionLvl = PermissionLvl::One;
+
+/// Something a region can deny, see [`World::define_region`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RegionAction {
+    /// Players hurting each other
+    Pvp,
+    /// Players breaking and placing blocks
+    Build,
+    /// Mobs spawning, e.g. from spawn eggs
+    MobSpawning,
+}
+
+/// What happens inside a region, `true` allows it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RegionFlags {
+    pub pvp: bool,
+    pub build: bool,
+    pub mob_spawning: bool,
+}
+
+impl RegionFlags {
+    /// Everything is allowed, like outside of all regions
+    pub const ALLOW_ALL: Self = Self {
+        pvp: true,
+        build: true,
+        mob_spawning: true,
+    };
+    /// Nothing is allowed, e.g. for the spawn or shops
+    pub const SAFE: Self = Self {
+        pvp: false,
+        build: false,
+        mob_spawning: false,
+    };
+
+    #[must_use]
+    pub const fn allows(self, action: RegionAction) -> bool {
+        match action {
+            RegionAction::Pvp => self.pvp,
+            RegionAction::Build => self.build,
+            RegionAction::MobSpawning => self.mob_spawning,
+        }
+    }
+}
+
+impl Default for RegionFlags {
+    fn default() -> Self {
+        Self::ALLOW_ALL
+    }
+}
+
+/// The blocks between two corners, both included
+struct Region {
+    min: Vector3<i32>,
+    max: Vector3<i32>,
+    flags: RegionFlags,
+}
+
+impl Region {
+    fn new(from: WorldPosition, to: WorldPosition, flags: RegionFlags) -> Self {
+        let (from, to) = (from.0, to.0);
+        Self {
+            min: Vector3::new(from.x.min(to.x), from.y.min(to.y), from.z.min(to.z)),
+            max: Vector3::new(from.x.max(to.x), from.y.max(to.y), from.z.max(to.z)),
+            flags,
+        }
+    }
+
+    fn contains(&self, position: &WorldPosition) -> bool {
+        let position = position.0;
+        (self.min.x..=self.max.x).contains(&position.x)
+            && (self.min.y..=self.max.y).contains(&position.y)
+            && (self.min.z..=self.max.z).contains(&position.z)
+    }
+}
+
+/// The regions of a world by their names, see [`World::define_region`]
+#[derive(Default)]
+pub struct Regions(HashMap<String, Region>);
+
+impl Regions {
+    /// Where regions overlap, the action has to be allowed by all of them
+    fn allows(&self, position: &WorldPosition, action: RegionAction) -> bool {
+        self.0
+            .values()
+            .filter(|region| region.contains(position))
+            .all(|region| region.flags.allows(action))
+    }
+}
+
+/// The block the entity position is in
+fn block_at(position: Vector3<f64>) -> WorldPosition {
+    WorldPosition(Vector3::new(
+        position.x.floor() as i32,
+        position.y.floor() as i32,
+        position.z.floor() as i32,
+    ))
+}
+
+impl World {
+    /// Defines the region of the blocks between the corners, replacing the region with the same name.
+    /// The flags decide what may happen inside of it, where regions overlap the most restrictive one wins
+    pub async fn define_region(
+        &self,
+        name: impl Into<String>,
+        from: WorldPosition,
+        to: WorldPosition,
+        flags: RegionFlags,
+    ) {
+        self.regions
+            .write()
+            .await
+            .0
+            .insert(name.into(), Region::new(from, to, flags));
+    }
+
+    /// Returns `false` if there was no region with the name
+    pub async fn remove_region(&self, name: &str) -> bool {
+        self.regions.write().await.0.remove(name).is_some()
+    }
+
+    /// Whether no region at the block denies the action
+    pub async fn region_allows(&self, position: &WorldPosition, action: RegionAction) -> bool {
+        self.regions.read().await.allows(position, action)
+    }
+
+    /// Whether the regions at the positions of both players allow them to hurt each other
+    pub async fn allows_pvp(&self, attacker: &Player, victim: &Player) -> bool {
+        let regions = self.regions.read().await;
+        [attacker, victim].into_iter().all(|player| {
+            regions.allows(
+                &block_at(player.living_entity.entity.pos.load()),
+                RegionAction::Pvp,
+            )
+        })
+    }
+
+    /// Whether the player may break or place the block, operators can build everywhere
+    pub async fn may_build_at(&self, player: &Player, position: &WorldPosition) -> bool {
+        player.permission_lvl.load() >= BUILD_BYPASS_PERMISSION
+            || self.region_allows(position, RegionAction::Build).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_core::math::{position::WorldPosition, vector3::Vector3};
+
+    use super::{Region, RegionAction, RegionFlags, Regions};
+
+    fn position(x: i32, y: i32, z: i32) -> WorldPosition {
+        WorldPosition(Vector3::new(x, y, z))
+    }
+
+    #[test]
+    fn most_restrictive_region_wins() {
+        let mut regions = Regions::default();
+        // The corners can be given in any order
+        regions.0.insert(
+            "spawn".to_string(),
+            Region::new(
+                position(10, 100, 10),
+                position(-10, 0, -10),
+                RegionFlags {
+                    pvp: false,
+                    ..RegionFlags::ALLOW_ALL
+                },
+            ),
+        );
+        regions.0.insert(
+            "shop".to_string(),
+            Region::new(
+                position(0, 0, 0),
+                position(20, 100, 20),
+                RegionFlags {
+                    build: false,
+                    ..RegionFlags::ALLOW_ALL
+                },
+            ),
+        );
+
+        assert!(!regions.allows(&position(-5, 50, -5), RegionAction::Pvp));
+        assert!(regions.allows(&position(-5, 50, -5), RegionAction::Build));
+        // Both regions
+        assert!(!regions.allows(&position(10, 50, 10), RegionAction::Pvp));
+        assert!(!regions.allows(&position(10, 50, 10), RegionAction::Build));
+        assert!(regions.allows(&position(10, 50, 10), RegionAction::MobSpawning));
+        // Outside of all regions
+        assert!(regions.allows(&position(30, 50, 30), RegionAction::Pvp));
+    }
+}
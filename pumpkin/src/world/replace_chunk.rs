@@ -0,0 +1,75 @@
+use pumpkin_core::math::vector2::Vector2;
+use pumpkin_protocol::client::play::CChunkData;
+use pumpkin_world::{block::block_registry::registry, chunk::ChunkBlocks};
+use thiserror::Error;
+
+use super::{portal, ChunkUnavailable, World};
+
+#[derive(Error, Debug)]
+pub enum ReplaceChunkError {
+    #[error("Block state id {0} does not exist")]
+    InvalidBlockState(u16),
+    #[error(transparent)]
+    ChunkUnavailable(#[from] ChunkUnavailable),
+}
+
+impl World {
+    /// Replaces all blocks of the chunk at once and sends the new chunk to everyone watching it.
+    ///
+    /// This is a lot faster than setting every block on its own, e.g. for pasting or undoing big edits.
+    /// Returns the old blocks, so they can be put back later
+    pub async fn replace_chunk_blocks(
+        &self,
+        chunk_pos: Vector2<i32>,
+        mut new_blocks: ChunkBlocks,
+    ) -> Result<ChunkBlocks, ReplaceChunkError> {
+        // The size of the blocks is always the size of a chunk, so only the states have to be checked
+        let registry = registry();
+        if let Some(invalid) = new_blocks
+            .iter_blocks()
+            .find(|state_id| registry.block_and_state(*state_id).is_none())
+        {
+            return Err(ReplaceChunkError::InvalidBlockState(invalid));
+        }
+        new_blocks.heightmap = new_blocks.calculate_heightmap();
+        let chunk = self.receive_chunk(chunk_pos).await?;
+
+        {
+            let mut portals = self.portals.lock().await;
+            portals.retain(|position| position.chunk_and_chunk_relative_position().0 != chunk_pos);
+            portals.extend(portal::portals_in_chunk(chunk_pos, &new_blocks));
+        }
+
+        let old_blocks = std::mem::replace(&mut chunk.write().await.blocks, new_blocks);
+
+        self.resend_chunk(chunk_pos).await;
+        Ok(old_blocks)
+    }
+
+    /// Sends the whole chunk again to every player watching it, e.g. after many blocks in it changed
+    pub async fn resend_chunk(&self, chunk_pos: Vector2<i32>) {
+        let chunk = match self.receive_chunk(chunk_pos).await {
+            Ok(chunk) => chunk,
+            Err(err) => {
+                log::error!("Failed to resend chunk: {}", err);
+                return;
+            }
+        };
+        let chunk = chunk.read().await;
+        let obfuscated = self.obfuscate_chunk(&chunk);
+        self.broadcast_to_chunk_watchers(
+            chunk_pos,
+            &CChunkData(obfuscated.as_ref().unwrap_or(&chunk)),
+        )
+        .await;
+    }
+
+    /// Sends every loaded chunk to the players watching it again, returns how many chunks were loaded
+    pub async fn resend_loaded_chunks(&self) -> usize {
+        let chunks = self.level.loaded_chunk_positions();
+        for chunk_pos in &chunks {
+            self.resend_chunk(*chunk_pos).await;
+        }
+        chunks.len()
+    }
+}
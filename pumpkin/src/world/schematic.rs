@@ -0,0 +1,360 @@
+use std::collections::HashMap;
+
+use bytes::BytesMut;
+use pumpkin_core::math::{position::WorldPosition, vector2::Vector2, vector3::Vector3};
+use pumpkin_macros::block_state;
+use pumpkin_nbt::{deserializer::from_bytes, serializer::to_bytes, IntArray};
+use pumpkin_world::{
+    block::block_registry::{get_block, get_block_by_state_id, get_state_index},
+    coordinates::ChunkRelativeBlockCoordinates,
+    is_in_world, WORLD_LOWEST_Y, WORLD_MAX_Y,
+};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::World;
+
+/// The current version of the schematic format
+const SCHEMATIC_VERSION: i32 = 1;
+/// The most blocks a schematic may have, a cube of 256 blocks. Exporting keeps all blocks in memory at once
+const MAX_VOLUME: i64 = 256 * 256 * 256;
+
+/// A copy of a region of blocks, see [`World::export_region`].
+///
+/// Schematics are stored as a named NBT compound `Schematic` with these fields:
+/// - `Version` (Int): the format version, currently 1
+/// - `Width`, `Height`, `Length` (Int): the size along x, y and z
+/// - `Palette` (List of Compounds): every block state used, with the block `Name` (e.g. `minecraft:stone`)
+///   and `State` (Int), the index of the state in the block's list of states
+/// - `Blocks` (Int Array): the palette index of every block, ordered by y, then z, then x.
+///   The index of a block is `(y * Length + z) * Width + x`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "PascalCase")]
+pub struct Schematic {
+    pub version: i32,
+    pub width: i32,
+    pub height: i32,
+    pub length: i32,
+    #[serde(default)]
+    pub palette: Vec<PaletteEntry>,
+    #[serde(with = "IntArray")]
+    pub blocks: Vec<i32>,
+    // TODO: Store block entities once we have them
+}
+
+#[derive(Error, Debug)]
+pub enum SchematicError {
+    #[error("A schematic of {0} x {1} x {2} blocks is negative or too large")]
+    InvalidSize(i64, i64, i64),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "PascalCase")]
+pub struct PaletteEntry {
+    pub name: String,
+    pub state: i32,
+}
+
+impl PaletteEntry {
+    fn from_state_id(state_id: u16) -> Option<Self> {
+        let block = get_block_by_state_id(state_id)?;
+        let state = get_state_index(state_id)?;
+        Some(Self {
+            name: format!("minecraft:{}", block.name),
+            state: i32::from(state),
+        })
+    }
+
+    /// Returns `None` if the block does not exist in this version
+    fn to_state_id(&self) -> Option<u16> {
+        let block = get_block(&self.name)?;
+        let state = block.states.get(usize::try_from(self.state).ok()?)?;
+        Some(state.id)
+    }
+}
+
+/// How many blocks a schematic of the size has, at most [`MAX_VOLUME`]
+fn volume(width: i64, height: i64, length: i64) -> Result<i32, SchematicError> {
+    if width < 0 || height < 0 || length < 0 {
+        return Err(SchematicError::InvalidSize(width, height, length));
+    }
+    width
+        .checked_mul(height)
+        .and_then(|area| area.checked_mul(length))
+        .filter(|volume| *volume <= MAX_VOLUME)
+        .and_then(|volume| i32::try_from(volume).ok())
+        .ok_or(SchematicError::InvalidSize(width, height, length))
+}
+
+impl Schematic {
+    /// Only valid once [`volume`] accepted the size
+    const fn index(&self, x: i32, y: i32, z: i32) -> usize {
+        ((y * self.length + z) * self.width + x) as usize
+    }
+
+    /// Writes the schematic as NBT
+    pub fn to_bytes(&self) -> Result<BytesMut, pumpkin_nbt::Error> {
+        to_bytes(self, "Schematic".to_string())
+    }
+
+    /// Reads a schematic written by [`Schematic::to_bytes`]
+    pub fn from_bytes(bytes: &mut BytesMut) -> Result<Self, pumpkin_nbt::Error> {
+        from_bytes(bytes)
+    }
+}
+
+impl World {
+    /// Copies all blocks between the two corners into a [`Schematic`], blocks outside of the world are air.
+    ///
+    /// The region is read one chunk at a time, so only a single chunk is locked while reading
+    pub async fn export_region(
+        &self,
+        from: WorldPosition,
+        to: WorldPosition,
+    ) -> Result<Schematic, SchematicError> {
+        let (a, b) = (from.0, to.0);
+        let min = Vector3::new(a.x.min(b.x), a.y.min(b.y), a.z.min(b.z));
+        let max = Vector3::new(a.x.max(b.x), a.y.max(b.y), a.z.max(b.z));
+        // The sizes of huge regions don't fit into an i32
+        let side = |min: i32, max: i32| i64::from(max) - i64::from(min) + 1;
+        let (width, height, length) = (side(min.x, max.x), side(min.y, max.y), side(min.z, max.z));
+        let volume = volume(width, height, length)?;
+
+        // Every side is at most as large as the volume, so it fits into an i32 too
+        let mut schematic = Schematic {
+            version: SCHEMATIC_VERSION,
+            width: width as i32,
+            height: height as i32,
+            length: length as i32,
+            palette: Vec::new(),
+            blocks: vec![0; volume as usize],
+        };
+
+        let air = PaletteEntry::from_state_id(block_state!("air").state_id)
+            .expect("Air is always a valid block");
+        let mut palette = HashMap::from([(air.clone(), 0)]);
+        schematic.palette.push(air);
+
+        let (min_chunk, _) = WorldPosition(min).chunk_and_chunk_relative_position();
+        let (max_chunk, _) = WorldPosition(max).chunk_and_chunk_relative_position();
+        for chunk_x in min_chunk.x..=max_chunk.x {
+            for chunk_z in min_chunk.z..=max_chunk.z {
+                let Ok(chunk) = self.receive_chunk(Vector2::new(chunk_x, chunk_z)).await else {
+                    // Keep the chunk as air, so the rest of the region can still be exported
+                    log::error!("Chunk {chunk_x} {chunk_z} is unavailable, exporting it as air");
+                    continue;
+                };
+                let chunk = chunk.read().await;
+                // Only the part of the region inside of this chunk
+                let (start_x, end_x) = ((chunk_x * 16).max(min.x), (chunk_x * 16 + 15).min(max.x));
+                let (start_z, end_z) = ((chunk_z * 16).max(min.z), (chunk_z * 16 + 15).min(max.z));
+                // Only the part inside of the world, the rest stays air
+                let (start_y, end_y) = (
+                    min.y.max(i32::from(WORLD_LOWEST_Y)),
+                    max.y.min(i32::from(WORLD_MAX_Y) - 1),
+                );
+                for x in start_x..=end_x {
+                    for z in start_z..=end_z {
+                        for y in start_y..=end_y {
+                            let position = WorldPosition(Vector3::new(x, y, z));
+                            let (_, relative) = position.chunk_and_chunk_relative_position();
+                            let Some(entry) = chunk
+                                .blocks
+                                .get_block(ChunkRelativeBlockCoordinates::from(relative))
+                                .and_then(PaletteEntry::from_state_id)
+                            else {
+                                // Not a known block state, keep it as air
+                                continue;
+                            };
+                            let next_index = palette.len() as i32;
+                            let palette_index =
+                                *palette.entry(entry.clone()).or_insert_with(|| {
+                                    schematic.palette.push(entry);
+                                    next_index
+                                });
+                            let index = schematic.index(x - min.x, y - min.y, z - min.z);
+                            schematic.blocks[index] = palette_index;
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(schematic)
+    }
+
+    /// Places the schematic with its lowest corner at `origin`, returns how many blocks were placed.
+    ///
+    /// Blocks which don't exist in this version or would be outside of the world are skipped
+    pub async fn import_schematic(
+        &self,
+        origin: WorldPosition,
+        schematic: &Schematic,
+    ) -> Result<usize, SchematicError> {
+        volume(
+            schematic.width.into(),
+            schematic.height.into(),
+            schematic.length.into(),
+        )?;
+        let palette: Vec<Option<u16>> = schematic
+            .palette
+            .iter()
+            .map(PaletteEntry::to_state_id)
+            .collect();
+
+        let mut blocks = HashMap::new();
+        for y in 0..schematic.height {
+            for z in 0..schematic.length {
+                for x in 0..schematic.width {
+                    let Some(&palette_index) = schematic.blocks.get(schematic.index(x, y, z))
+                    else {
+                        continue;
+                    };
+                    let Some(Some(state_id)) = usize::try_from(palette_index)
+                        .ok()
+                        .and_then(|palette_index| palette.get(palette_index))
+                    else {
+                        continue;
+                    };
+                    let position = WorldPosition(origin.0.add(&Vector3::new(x, y, z)));
+                    if !is_in_world(position.0.y) {
+                        continue;
+                    }
+                    blocks.insert(position, *state_id);
+                }
+            }
+        }
+
+        let placed = blocks.len();
+        self.set_block_states(&blocks).await;
+        Ok(placed)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_core::math::{position::WorldPosition, vector3::Vector3};
+    use pumpkin_macros::block_state;
+
+    use crate::world::World;
+
+    use super::{Schematic, SchematicError, SCHEMATIC_VERSION};
+
+    #[tokio::test]
+    async fn nbt_round_trip() {
+        let world = World::test_world();
+        let stone = block_state!("stone").state_id;
+        let glowstone = block_state!("glowstone").state_id;
+        world
+            .set_block_state(WorldPosition(Vector3::new(1, 200, 2)), stone)
+            .await;
+        world
+            .set_block_state(WorldPosition(Vector3::new(3, 201, 2)), glowstone)
+            .await;
+        let schematic = world
+            .export_region(
+                WorldPosition(Vector3::new(0, 200, 0)),
+                WorldPosition(Vector3::new(3, 201, 2)),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            (schematic.width, schematic.height, schematic.length),
+            (4, 2, 3)
+        );
+
+        let mut bytes = schematic.to_bytes().unwrap();
+        let read = Schematic::from_bytes(&mut bytes).unwrap();
+        assert_eq!(read, schematic);
+
+        let origin = WorldPosition(Vector3::new(20, 210, 20));
+        assert_eq!(world.import_schematic(origin, &read).await.unwrap(), 24);
+        assert_eq!(
+            world
+                .get_block_state_id(WorldPosition(Vector3::new(21, 210, 22)))
+                .await
+                .unwrap(),
+            stone
+        );
+        assert_eq!(
+            world
+                .get_block_state_id(WorldPosition(Vector3::new(23, 211, 22)))
+                .await
+                .unwrap(),
+            glowstone
+        );
+    }
+
+    #[tokio::test]
+    async fn too_large() {
+        let world = World::test_world();
+        let huge = Schematic {
+            version: SCHEMATIC_VERSION,
+            width: 100_000,
+            height: 384,
+            length: 100_000,
+            palette: Vec::new(),
+            blocks: Vec::new(),
+        };
+        assert!(matches!(
+            world
+                .import_schematic(WorldPosition(Vector3::new(0, 0, 0)), &huge)
+                .await,
+            Err(SchematicError::InvalidSize(..))
+        ));
+        assert!(matches!(
+            world
+                .export_region(
+                    WorldPosition(Vector3::new(i32::MIN, 0, 0)),
+                    WorldPosition(Vector3::new(i32::MAX, 0, 0)),
+                )
+                .await,
+            Err(SchematicError::InvalidSize(..))
+        ));
+        // Fits into an i32, but is too much to keep in memory
+        assert!(matches!(
+            world
+                .export_region(
+                    WorldPosition(Vector3::new(0, -64, 0)),
+                    WorldPosition(Vector3::new(999, 319, 999)),
+                )
+                .await,
+            Err(SchematicError::InvalidSize(..))
+        ));
+    }
+
+    #[tokio::test]
+    async fn outside_of_the_world() {
+        let world = World::test_world();
+        let stone = block_state!("stone").state_id;
+        world
+            .set_block_state(WorldPosition(Vector3::new(0, 319, 0)), stone)
+            .await;
+        let schematic = world
+            .export_region(
+                WorldPosition(Vector3::new(0, 318, 0)),
+                WorldPosition(Vector3::new(0, 321, 0)),
+            )
+            .await
+            .unwrap();
+        assert_eq!(schematic.blocks.len(), 4);
+        assert_ne!(schematic.blocks[1], 0);
+        assert_eq!(schematic.blocks[2..], [0, 0]);
+
+        // Only the two blocks below the top of the world are placed
+        assert_eq!(
+            world
+                .import_schematic(WorldPosition(Vector3::new(5, 318, 5)), &schematic)
+                .await
+                .unwrap(),
+            2
+        );
+        assert!(world
+            .export_region(
+                WorldPosition(Vector3::new(0, -70, 0)),
+                WorldPosition(Vector3::new(0, -60, 0)),
+            )
+            .await
+            .is_ok());
+    }
+}
@@ -0,0 +1,277 @@
+use pumpkin_core::math::{position::WorldPosition, vector2::Vector2, vector3::Vector3};
+use pumpkin_world::{
+    block::block_registry::registry, coordinates::ChunkRelativeBlockCoordinates, WORLD_LOWEST_Y,
+    WORLD_MAX_Y,
+};
+use thiserror::Error;
+
+use super::World;
+
+#[derive(Error, Debug)]
+pub enum SnapshotError {
+    #[error("The region of {0} x {1} x {2} blocks is too large to copy")]
+    TooLarge(i64, i64, i64),
+}
+
+/// A copy of the blocks of a region, see [`World::snapshot_region`].
+///
+/// This is a point-in-time copy: it owns all of its data and never changes, no matter what happens in the world afterwards
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RegionSnapshot {
+    /// The lowest corner of the region
+    pub min: Vector3<i32>,
+    /// The highest corner of the region
+    pub max: Vector3<i32>,
+    /// The block state of every block, ordered by y, then z, then x
+    blocks: Vec<u16>,
+    /// The y above the highest non air block of every column, ordered by z, then x.
+    /// The whole column counts, not only the part inside of the region
+    surface: Vec<i32>,
+    // TODO: Copy biomes once chunks store them
+}
+
+impl RegionSnapshot {
+    const fn size(&self) -> Vector3<i32> {
+        Vector3::new(
+            self.max.x - self.min.x + 1,
+            self.max.y - self.min.y + 1,
+            self.max.z - self.min.z + 1,
+        )
+    }
+
+    fn contains_column(&self, x: i32, z: i32) -> bool {
+        (self.min.x..=self.max.x).contains(&x) && (self.min.z..=self.max.z).contains(&z)
+    }
+
+    const fn column_index(&self, x: i32, z: i32) -> usize {
+        ((z - self.min.z) * self.size().x + (x - self.min.x)) as usize
+    }
+
+    const fn block_index(&self, position: Vector3<i32>) -> usize {
+        let size = self.size();
+        (((position.y - self.min.y) * size.z + (position.z - self.min.z)) * size.x
+            + (position.x - self.min.x)) as usize
+    }
+
+    /// The block state at the position when the snapshot was taken, `None` if the position is outside of the region
+    #[must_use]
+    pub fn get_block_state_id(&self, position: WorldPosition) -> Option<u16> {
+        let position = position.0;
+        if !self.contains_column(position.x, position.z)
+            || !(self.min.y..=self.max.y).contains(&position.y)
+        {
+            return None;
+        }
+        self.blocks.get(self.block_index(position)).copied()
+    }
+
+    /// Every block of the region with its state when the snapshot was taken
+    pub fn iter_blocks(&self) -> impl Iterator<Item = (WorldPosition, u16)> + '_ {
+        let size = self.size();
+        self.blocks
+            .iter()
+            .enumerate()
+            .map(move |(index, state_id)| {
+                let index = index as i32;
+                let offset = Vector3::new(
+                    index % size.x,
+                    index / (size.x * size.z),
+                    (index / size.x) % size.z,
+                );
+                (WorldPosition(self.min.add(&offset)), *state_id)
+            })
+    }
+
+    /// The y above the highest non air block of the column, the bottom of the world if the column is empty.
+    /// `None` if the column is outside of the region
+    #[must_use]
+    pub fn surface_height(&self, x: i32, z: i32) -> Option<i32> {
+        if !self.contains_column(x, z) {
+            return None;
+        }
+        self.surface.get(self.column_index(x, z)).copied()
+    }
+}
+
+impl World {
+    /// Copies the blocks between the two corners and the surface height of their columns into a [`RegionSnapshot`],
+    /// which can be processed without touching the world again, e.g. to render a map.
+    ///
+    /// Every chunk is only locked while its part of the region is copied, so the snapshot doesn't hold up ticking.
+    /// Chunks get copied one after another, so changes made while taking the snapshot may show up in some chunks only
+    pub async fn snapshot_region(
+        &self,
+        from: WorldPosition,
+        to: WorldPosition,
+    ) -> Result<RegionSnapshot, SnapshotError> {
+        self.copy_region(from, to, true).await
+    }
+
+    /// Copies only the blocks between the two corners, like [`World::snapshot_region`] but without looking at the
+    /// whole height of every column. The surface height of every column is `None`
+    pub(super) async fn snapshot_blocks(
+        &self,
+        from: WorldPosition,
+        to: WorldPosition,
+    ) -> Result<RegionSnapshot, SnapshotError> {
+        self.copy_region(from, to, false).await
+    }
+
+    async fn copy_region(
+        &self,
+        from: WorldPosition,
+        to: WorldPosition,
+        with_surface: bool,
+    ) -> Result<RegionSnapshot, SnapshotError> {
+        let (a, b) = (from.0, to.0);
+        let min = Vector3::new(a.x.min(b.x), a.y.min(b.y), a.z.min(b.z));
+        let max = Vector3::new(a.x.max(b.x), a.y.max(b.y), a.z.max(b.z));
+        // The sizes of huge regions don't fit into an i32, and the blocks are indexed with one
+        let side = |min: i32, max: i32| i64::from(max) - i64::from(min) + 1;
+        let (size_x, size_y, size_z) = (side(min.x, max.x), side(min.y, max.y), side(min.z, max.z));
+        let volume = size_x
+            .checked_mul(size_y)
+            .and_then(|area| area.checked_mul(size_z))
+            .and_then(|volume| i32::try_from(volume).ok())
+            .ok_or(SnapshotError::TooLarge(size_x, size_y, size_z))?;
+
+        let mut snapshot = RegionSnapshot {
+            min,
+            max,
+            blocks: Vec::new(),
+            surface: Vec::new(),
+        };
+        let size = snapshot.size();
+        // Outside of the world, keep it as air
+        snapshot.blocks = vec![0; volume as usize];
+        if with_surface {
+            // Never larger than the volume
+            snapshot.surface = vec![i32::from(WORLD_LOWEST_Y); (size.x * size.z) as usize];
+        }
+
+        let registry = registry();
+        let (min_chunk, _) = WorldPosition(min).chunk_and_chunk_relative_position();
+        let (max_chunk, _) = WorldPosition(max).chunk_and_chunk_relative_position();
+        for chunk_x in min_chunk.x..=max_chunk.x {
+            for chunk_z in min_chunk.z..=max_chunk.z {
+                let Ok(chunk) = self.receive_chunk(Vector2::new(chunk_x, chunk_z)).await else {
+                    log::error!(
+                        "Chunk {chunk_x} {chunk_z} is unavailable, it stays air in the snapshot"
+                    );
+                    continue;
+                };
+                let chunk = chunk.read().await;
+                // Only the part of the region inside of this chunk
+                let (start_x, end_x) = ((chunk_x * 16).max(min.x), (chunk_x * 16 + 15).min(max.x));
+                let (start_z, end_z) = ((chunk_z * 16).max(min.z), (chunk_z * 16 + 15).min(max.z));
+                let (start_y, end_y) = (
+                    min.y.max(i32::from(WORLD_LOWEST_Y)),
+                    max.y.min(i32::from(WORLD_MAX_Y) - 1),
+                );
+                for x in start_x..=end_x {
+                    for z in start_z..=end_z {
+                        let block_at = |y| {
+                            let (_, relative) = WorldPosition(Vector3::new(x, y, z))
+                                .chunk_and_chunk_relative_position();
+                            chunk
+                                .blocks
+                                .get_block(ChunkRelativeBlockCoordinates::from(relative))
+                        };
+                        for y in start_y..=end_y {
+                            if let Some(state_id) = block_at(y) {
+                                let index = snapshot.block_index(Vector3::new(x, y, z));
+                                snapshot.blocks[index] = state_id;
+                            }
+                        }
+                        if !with_surface {
+                            continue;
+                        }
+                        let highest = (i32::from(WORLD_LOWEST_Y)..i32::from(WORLD_MAX_Y))
+                            .rev()
+                            .find(|y| {
+                                block_at(*y)
+                                    .and_then(|state_id| registry.block_and_state(state_id))
+                                    .is_some_and(|(_, state)| !state.air)
+                            });
+                        if let Some(highest) = highest {
+                            let index = snapshot.column_index(x, z);
+                            snapshot.surface[index] = highest + 1;
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(snapshot)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_core::math::{position::WorldPosition, vector3::Vector3};
+    use pumpkin_macros::block_state;
+
+    use crate::world::World;
+
+    use super::SnapshotError;
+
+    #[tokio::test]
+    async fn snapshot_is_a_copy() {
+        let world = World::test_world();
+        let stone = block_state!("stone").state_id;
+        let air = block_state!("air").state_id;
+        let position = WorldPosition(Vector3::new(17, 200, 3));
+        world.set_block_state(position, stone).await;
+
+        let snapshot = world
+            .snapshot_region(
+                WorldPosition(Vector3::new(10, 199, 0)),
+                WorldPosition(Vector3::new(20, 201, 5)),
+            )
+            .await
+            .unwrap();
+        world.set_block_state(position, air).await;
+
+        assert_eq!(snapshot.get_block_state_id(position), Some(stone));
+        assert_eq!(
+            snapshot.get_block_state_id(WorldPosition(Vector3::new(17, 201, 3))),
+            Some(air)
+        );
+        assert_eq!(snapshot.surface_height(17, 3), Some(201));
+        assert_eq!(snapshot.surface_height(30, 3), None);
+    }
+
+    #[tokio::test]
+    async fn too_large() {
+        let world = World::test_world();
+        let result = world
+            .snapshot_region(
+                WorldPosition(Vector3::new(i32::MIN, 0, i32::MIN)),
+                WorldPosition(Vector3::new(i32::MAX, 10, i32::MAX)),
+            )
+            .await;
+        assert!(matches!(result, Err(SnapshotError::TooLarge(..))));
+    }
+
+    #[tokio::test]
+    async fn outside_of_the_world_is_air() {
+        let world = World::test_world();
+        let stone = block_state!("stone").state_id;
+        let top = WorldPosition(Vector3::new(0, 319, 0));
+        world.set_block_state(top, stone).await;
+
+        let snapshot = world
+            .snapshot_region(
+                WorldPosition(Vector3::new(0, -70, 0)),
+                WorldPosition(Vector3::new(1, 325, 1)),
+            )
+            .await
+            .unwrap();
+        assert_eq!(snapshot.get_block_state_id(top), Some(stone));
+        assert_eq!(
+            snapshot.get_block_state_id(WorldPosition(Vector3::new(0, 322, 0))),
+            Some(block_state!("air").state_id)
+        );
+        assert_eq!(snapshot.surface_height(0, 0), Some(320));
+    }
+}
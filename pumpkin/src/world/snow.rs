@@ -0,0 +1,172 @@
+use pumpkin_config::ADVANCED_CONFIG;
+use pumpkin_core::math::{position::WorldPosition, vector2::Vector2, vector3::Vector3};
+use pumpkin_macros::block_state;
+use pumpkin_registry::DimensionType;
+use pumpkin_world::{
+    block::block_registry::{
+        get_block, get_block_and_state_by_state_id, get_state_index, registry,
+    },
+    coordinates::ChunkRelativeBlockCoordinates,
+    WORLD_LOWEST_Y, WORLD_MAX_Y,
+};
+use rand::Rng;
+
+use super::World;
+
+/// Like Vanilla, every chunk near a player gets a random column checked every 16 ticks on average
+const CHECK_CHANCE: u32 = 16;
+/// Biomes colder than this have snowfall instead of rain and their water freezes, like Vanilla
+const FREEZING_TEMPERATURE: f32 = 0.15;
+/// Snow and ice don't form at this block light level or brighter, like Vanilla
+const MELTING_LIGHT: u8 = 10;
+/// Whether a generator picks biomes cold enough for snow and ice. None does yet, every generator only
+/// picks Plains, so snow and ice can't form anywhere and the snow pass is skipped.
+// TODO: Remove once a generator picks cold biomes
+const HAS_COLD_BIOMES: bool = false;
+
+/// The temperature of the biome at the height, like Vanilla it gets colder above y 80
+#[allow(clippy::cast_precision_loss)]
+fn temperature_at(biome_temperature: f32, y: i32) -> f32 {
+    if y > 80 {
+        biome_temperature - (y - 80) as f32 * 0.05 / 40.0
+    } else {
+        biome_temperature
+    }
+}
+
+impl World {
+    /// The highest block in the column which is not air and its state, `None` if the chunk isn't loaded
+    async fn surface_block(&self, column: Vector2<i32>) -> Option<(WorldPosition, u16)> {
+        let position = WorldPosition(Vector3::new(column.x, 0, column.z));
+        let (chunk_coordinate, relative) = position.chunk_and_chunk_relative_position();
+        let chunk = self.level.get_loaded_chunk(&chunk_coordinate)?;
+        let chunk = chunk.read().await;
+        let registry = registry();
+        for y in (i32::from(WORLD_LOWEST_Y)..i32::from(WORLD_MAX_Y)).rev() {
+            let coordinates =
+                ChunkRelativeBlockCoordinates::from(Vector3::new(relative.x, y, relative.z));
+            let Some(state_id) = chunk.blocks.get_block(coordinates) else {
+                continue;
+            };
+            if registry
+                .block_and_state(state_id)
+                .is_some_and(|(_, state)| !state.air)
+            {
+                return Some((WorldPosition(Vector3::new(column.x, y, column.z)), state_id));
+            }
+        }
+        None
+    }
+
+    /// Whether it's cold and dark enough for snow or ice to form at the position and it is under the open sky
+    async fn can_freeze(&self, position: WorldPosition) -> bool {
+        temperature_at(self.get_biome(position).temperature(), position.0.y) < FREEZING_TEMPERATURE
+            && self.block_light(position).await < MELTING_LIGHT
+            && self.is_sky_visible(position).await
+    }
+
+    /// Freezes the water at the top of the column, or lets snow fall on it during snowfall
+    async fn freeze_column(&self, column: Vector2<i32>) {
+        let Some((surface, state_id)) = self.surface_block(column).await else {
+            return;
+        };
+        let Some((block, state)) = get_block_and_state_by_state_id(state_id) else {
+            return;
+        };
+        let above = WorldPosition(surface.0.add(&Vector3::new(0, 1, 0)));
+        // Only still water freezes, the first state is the source block
+        if block.name == "water" && state_id == block.default_state_id {
+            if self.can_freeze(above).await {
+                self.set_block_state(surface, block_state!("ice").state_id)
+                    .await;
+            }
+            return;
+        }
+
+        // TODO: Also snow during plain rain once there is a weather cycle
+        if !self.is_thundering() {
+            return;
+        }
+        let Some(snow) = get_block("snow") else {
+            return;
+        };
+        let max_layers = usize::from(ADVANCED_CONFIG.snow.max_layers).min(snow.states.len());
+        if block.name == "snow" {
+            // The states are ordered by the amount of layers
+            let layers = get_state_index(state_id).map_or(0, usize::from) + 1;
+            if layers < max_layers && self.can_freeze(surface).await {
+                self.set_block_state(surface, snow.states[layers].id).await;
+            }
+            return;
+        }
+        let holds_snow = !matches!(block.name.as_str(), "ice" | "packed_ice" | "barrier")
+            && (block.name.ends_with("_leaves") || state.is_surface_solid());
+        if max_layers > 0 && holds_snow && self.can_freeze(above).await {
+            self.set_block_state(above, snow.default_state_id).await;
+        }
+    }
+
+    /// Lets snow fall and water freeze in random columns of the chunks near players in cold biomes.
+    /// Like Vanilla, water freezes in any weather while snow only falls during snowfall
+    pub(super) async fn tick_snow(&self) {
+        if !HAS_COLD_BIOMES
+            || !ADVANCED_CONFIG.snow.enabled
+            || self.dimension_type != DimensionType::Overworld
+        {
+            return;
+        }
+        for chunk in self.ticked_chunks().await {
+            let column = {
+                let mut rng = self.random.lock().await;
+                if rng.gen_range(0..CHECK_CHANCE) != 0 {
+                    continue;
+                }
+                Vector2::new(
+                    chunk.x * 16 + rng.gen_range(0..16),
+                    chunk.z * 16 + rng.gen_range(0..16),
+                )
+            };
+            self.freeze_column(column).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_core::math::{position::WorldPosition, vector2::Vector2, vector3::Vector3};
+    use pumpkin_macros::block_state;
+
+    use super::temperature_at;
+    use crate::world::World;
+
+    #[test]
+    fn colder_up_high() {
+        assert!((temperature_at(0.8, 64) - 0.8).abs() < f32::EPSILON);
+        assert!((temperature_at(0.8, 80) - 0.8).abs() < f32::EPSILON);
+        // 0.05 colder every 40 blocks
+        assert!((temperature_at(0.8, 120) - 0.75).abs() < 1e-6);
+    }
+
+    #[tokio::test]
+    async fn nothing_freezes_in_plains() {
+        let world = World::test_world();
+        let column = Vector2::new(8, 8);
+        let surface = WorldPosition(Vector3::new(8, 200, 8));
+        let above = WorldPosition(Vector3::new(8, 201, 8));
+        let water = block_state!("water").state_id;
+        world.set_block_state(surface, water).await;
+        world.freeze_column(column).await;
+        assert_eq!(world.get_block_state_id(surface).await.unwrap(), water);
+
+        // Not even during a thunderstorm, Plains are too warm for snow
+        let stone = block_state!("stone").state_id;
+        world.set_block_state(surface, stone).await;
+        world.set_thundering(true).await;
+        world.freeze_column(column).await;
+        assert_eq!(world.get_block_state_id(surface).await.unwrap(), stone);
+        assert_eq!(
+            world.get_block_state_id(above).await.unwrap(),
+            block_state!("air").state_id
+        );
+    }
+}
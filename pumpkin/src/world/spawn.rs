@@ -0,0 +1,268 @@
+use pumpkin_core::math::{position::WorldPosition, vector2::Vector2, vector3::Vector3};
+use pumpkin_protocol::client::play::CSetDefaultSpawnPosition;
+use pumpkin_world::block::block_registry::{get_block_by_state_id, get_state_index};
+use rand::Rng;
+
+use super::World;
+
+/// How many random positions we try before falling back to the exact spawn point
+const SPAWN_ATTEMPTS: u32 = 16;
+
+/// Whether the state is or contains a fluid, including waterlogged blocks and plants which only grow in water
+fn is_fluid(state_id: u16) -> bool {
+    let Some(block) = get_block_by_state_id(state_id) else {
+        return false;
+    };
+    if matches!(
+        block.name.as_str(),
+        "water" | "lava" | "bubble_column" | "kelp" | "kelp_plant" | "seagrass" | "tall_seagrass"
+    ) {
+        return true;
+    }
+    get_state_index(state_id).is_some_and(|index| {
+        block
+            .state_properties(usize::from(index))
+            .contains(&("waterlogged", "true"))
+    })
+}
+
+/// Picks a random column offset within the spawn radius
+fn scatter_offset(random: &mut impl Rng, radius: u32) -> Vector2<i32> {
+    let radius = radius as i32;
+    Vector2::new(
+        random.gen_range(-radius..=radius),
+        random.gen_range(-radius..=radius),
+    )
+}
+
+/// Picks a random column in a random chunk around the center, `None` if the column is not between the two radii.
+///
+/// The chunk is picked first, so every chunk in range is as likely to be picked, no matter how far away it is
+fn pick_spawn_column(
+    random: &mut impl Rng,
+    center: Vector3<f64>,
+    min_radius: f64,
+    max_radius: f64,
+) -> Option<Vector2<i32>> {
+    let center_chunk = Vector2::new(center.x.floor() as i32 >> 4, center.z.floor() as i32 >> 4);
+    let chunk_radius = (max_radius.ceil() as i32 >> 4) + 1;
+    let chunk_x = center_chunk.x + random.gen_range(-chunk_radius..=chunk_radius);
+    let chunk_z = center_chunk.z + random.gen_range(-chunk_radius..=chunk_radius);
+    let column = Vector2::new(
+        (chunk_x << 4) + random.gen_range(0..16),
+        (chunk_z << 4) + random.gen_range(0..16),
+    );
+    let dx = f64::from(column.x) + 0.5 - center.x;
+    let dz = f64::from(column.z) + 0.5 - center.z;
+    let distance_squared = dx.mul_add(dx, dz * dz);
+    (min_radius * min_radius..=max_radius * max_radius)
+        .contains(&distance_squared)
+        .then_some(column)
+}
+
+/// Where players spawn and compasses point to, the world spawn (see [`World::set_default_spawn`]) or a player's bed
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpawnPoint {
+    pub position: WorldPosition,
+    /// The yaw players look at when spawning
+    pub angle: f32,
+}
+
+impl World {
+    /// The world spawn, which is saved with the world. A new world starts with the spawn point of the config,
+    /// on top of the highest block there once it is first needed
+    pub async fn default_spawn(&self) -> SpawnPoint {
+        if let Some(spawn) = *self.default_spawn.lock().await {
+            return spawn;
+        }
+        let spawn_point = self.config.spawn_point;
+        let y = self.get_top_block(spawn_point).await + 1;
+        let spawn = SpawnPoint {
+            position: WorldPosition(Vector3::new(spawn_point.x, y, spawn_point.z)),
+            angle: self.config.spawn_angle,
+        };
+        let mut default_spawn = self.default_spawn.lock().await;
+        if let Some(spawn) = *default_spawn {
+            return spawn;
+        }
+        *default_spawn = Some(spawn);
+        self.level.set_spawn(spawn.position.0, spawn.angle);
+        spawn
+    }
+
+    /// Moves the world spawn and points the compasses of all players without a respawn point there
+    pub async fn set_default_spawn(&self, position: WorldPosition, angle: f32) {
+        *self.default_spawn.lock().await = Some(SpawnPoint { position, angle });
+        self.level.set_spawn(position.0, angle);
+        let packet = CSetDefaultSpawnPosition::new(position, angle);
+        for player in self.current_players.lock().await.values() {
+            if player.respawn_point.load().is_none() {
+                player.client.send_packet(&packet).await;
+            }
+        }
+    }
+
+    /// The chunks around the world spawn which are kept loaded, see [`World::force_load_spawn_chunks`]
+    #[must_use]
+    pub fn spawn_chunks(&self) -> Vec<Vector2<i32>> {
+        let radius = i32::from(self.config.spawn_chunk_radius);
+        if radius == 0 {
+            return Vec::new();
+        }
+        let center = Vector2::new(
+            self.config.spawn_point.x >> 4,
+            self.config.spawn_point.z >> 4,
+        );
+        let mut chunks = Vec::new();
+        for x in -radius..=radius {
+            for z in -radius..=radius {
+                chunks.push(Vector2::new(center.x + x, center.z + z));
+            }
+        }
+        chunks
+    }
+
+    /// Loads the spawn chunks and keeps them loaded even when no player is watching them.
+    ///
+    /// The chunks count as watched forever, so they are never cleaned up
+    pub fn force_load_spawn_chunks(&self) {
+        let chunks = self.spawn_chunks();
+        if chunks.is_empty() {
+            return;
+        }
+        self.level.mark_chunks_as_newly_watched(&chunks);
+        let mut receiver = self.receive_chunks(chunks);
+        tokio::spawn(async move { while receiver.recv().await.is_some() {} });
+    }
+
+    /// Returns the y a player can safely stand at in the column,
+    /// or `None` if there is no solid surface or the player would be inside of a fluid
+    pub async fn get_safe_spawn_y(&self, column: Vector2<i32>) -> Option<i32> {
+        let top = self.get_top_block(column).await;
+        let surface = self
+            .get_block_state(WorldPosition(Vector3::new(column.x, top, column.z)))
+            .await
+            .ok()?;
+        if !surface.is_surface_solid() {
+            return None;
+        }
+        // Only fluids and plants can be above the surface, plants don't hurt but fluids drown the player
+        for y in top + 1..=top + 2 {
+            let state_id = self
+                .get_block_state_id(WorldPosition(Vector3::new(column.x, y, column.z)))
+                .await
+                .ok()?;
+            if is_fluid(state_id) {
+                return None;
+            }
+        }
+        Some(top + 1)
+    }
+
+    /// A random position mobs could spawn at, standing on the ground between the two radii around the player.
+    ///
+    /// Uses the world random, so the positions are the same for the same seed. Returns `None` if the picked column is
+    /// out of range, not loaded or has no safe ground, so callers try again instead of loading chunks to spawn mobs
+    pub async fn random_spawn_position_near(
+        &self,
+        player_pos: Vector3<f64>,
+        min_radius: f64,
+        max_radius: f64,
+    ) -> Option<WorldPosition> {
+        let column = pick_spawn_column(
+            &mut *self.random.lock().await,
+            player_pos,
+            min_radius,
+            max_radius,
+        )?;
+        let chunk = Vector2::new(column.x >> 4, column.z >> 4);
+        self.level.get_loaded_chunk(&chunk)?;
+        let y = self.get_safe_spawn_y(column).await?;
+        Some(WorldPosition(Vector3::new(column.x, y, column.z)))
+    }
+
+    /// Where players spawn, a random safe position within the spawn radius around the world spawn.
+    /// Falls back to the top of the spawn point if there is no safe position
+    pub async fn spawn_position(&self) -> Vector3<f64> {
+        let spawn = self.default_spawn().await.position.0;
+        let spawn_point = Vector2::new(spawn.x, spawn.z);
+        let radius = self.config.spawn_radius;
+        if radius > 0 {
+            for _ in 0..SPAWN_ATTEMPTS {
+                let offset = scatter_offset(&mut *self.random.lock().await, radius);
+                let column = Vector2::new(spawn_point.x + offset.x, spawn_point.z + offset.z);
+                if let Some(y) = self.get_safe_spawn_y(column).await {
+                    // Center the player on the block
+                    return Vector3::new(
+                        f64::from(column.x) + 0.5,
+                        f64::from(y),
+                        f64::from(column.z) + 0.5,
+                    );
+                }
+            }
+        }
+
+        let top = self.get_top_block(spawn_point).await;
+        Vector3::new(
+            f64::from(spawn_point.x),
+            f64::from(top + 1),
+            f64::from(spawn_point.z),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use pumpkin_core::math::{position::WorldPosition, vector3::Vector3};
+
+    use crate::world::World;
+
+    use super::{pick_spawn_column, scatter_offset};
+
+    #[test]
+    fn scatter_is_deterministic_and_within_radius() {
+        let mut first = StdRng::seed_from_u64(42);
+        let mut second = StdRng::seed_from_u64(42);
+        for _ in 0..100 {
+            let offset = scatter_offset(&mut first, 10);
+            assert!(offset.x.abs() <= 10 && offset.z.abs() <= 10);
+            assert_eq!(offset, scatter_offset(&mut second, 10));
+        }
+    }
+
+    #[test]
+    fn spawn_columns_are_within_radii() {
+        let mut first = StdRng::seed_from_u64(7);
+        let mut second = StdRng::seed_from_u64(7);
+        let center = Vector3::new(100.5, 64.0, -40.5);
+        let mut picked = 0;
+        for _ in 0..1000 {
+            let column = pick_spawn_column(&mut first, center, 24.0, 128.0);
+            assert_eq!(column, pick_spawn_column(&mut second, center, 24.0, 128.0));
+            if let Some(column) = column {
+                let dx = f64::from(column.x) + 0.5 - center.x;
+                let dz = f64::from(column.z) + 0.5 - center.z;
+                let distance = dx.hypot(dz);
+                assert!((24.0..=128.0).contains(&distance));
+                picked += 1;
+            }
+        }
+        assert!(picked > 0);
+    }
+
+    #[tokio::test]
+    async fn spawn_is_saved_with_the_level() {
+        let world = World::test_world();
+        assert_eq!(world.level.spawn(), None);
+
+        // A new world decides on its spawn once
+        let spawn = world.default_spawn().await;
+        assert_eq!(world.level.spawn(), Some((spawn.position.0, spawn.angle)));
+
+        let position = WorldPosition(Vector3::new(3, 80, -7));
+        world.set_default_spawn(position, 45.0).await;
+        assert_eq!(world.level.spawn(), Some((position.0, 45.0)));
+    }
+}
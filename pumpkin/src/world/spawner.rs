@@ -0,0 +1,69 @@
+use std::collections::HashMap;
+
+use pumpkin_core::math::position::WorldPosition;
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+use pumpkin_protocol::client::play::CBlockEntityData;
+use pumpkin_world::block::block_registry::{get_block, get_state_by_state_id};
+
+use super::World;
+
+/// The entity every mob spawner spawns, by the position of the spawner.
+// TODO: Save them and send them with the chunk once chunks store block entities, until then only players
+// who are online when the entity gets set see it inside of the spawner
+#[derive(Default)]
+pub struct Spawners(HashMap<WorldPosition, String>);
+
+/// The block entity data telling the client which entity spins inside of the spawner
+fn spawner_nbt(entity_name: &str) -> NbtCompound {
+    let mut entity = NbtCompound::new();
+    entity.put(
+        "id".to_string(),
+        format!("minecraft:{entity_name}").as_str(),
+    );
+    let mut spawn_data = NbtCompound::new();
+    spawn_data.put("entity".to_string(), NbtTag::Compound(entity));
+    let mut nbt = NbtCompound::new();
+    nbt.put("SpawnData".to_string(), NbtTag::Compound(spawn_data));
+    nbt
+}
+
+/// The block entity type of mob spawners
+fn spawner_block_entity_type() -> Option<u32> {
+    let spawner = get_block("spawner")?;
+    get_state_by_state_id(spawner.default_state_id)?.block_entity_type
+}
+
+impl World {
+    /// Makes the mob spawner spawn the entity, e.g. because a player used a spawn egg on it.
+    /// Returns `false` if the block is no spawner
+    pub async fn set_spawner_entity(&self, position: WorldPosition, entity_name: &str) -> bool {
+        let Some(block_entity_type) = spawner_block_entity_type() else {
+            return false;
+        };
+        let is_spawner = self
+            .get_block(position)
+            .await
+            .is_ok_and(|block| block.name == "spawner");
+        if !is_spawner {
+            return false;
+        }
+        self.spawners
+            .lock()
+            .await
+            .0
+            .insert(position, entity_name.to_string());
+        let nbt = spawner_nbt(entity_name);
+        self.broadcast_packet_all(&CBlockEntityData::new(
+            position,
+            block_entity_type.into(),
+            &nbt,
+        ))
+        .await;
+        true
+    }
+
+    /// The entity the mob spawner spawns, `None` if none was set
+    pub async fn spawner_entity(&self, position: WorldPosition) -> Option<String> {
+        self.spawners.lock().await.0.get(&position).cloned()
+    }
+}
@@ -0,0 +1,108 @@
+use std::{collections::VecDeque, ops::Add, time::Duration};
+
+/// Over how many ticks the rolling averages are computed
+const HISTORY_LENGTH: usize = 100;
+
+/// How long each phase of a world tick took
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TickTimings {
+    /// Advancing the time, including autosaving
+    pub time: Duration,
+    pub players: Duration,
+    /// Ticking mobs and pushing entities
+    pub entities: Duration,
+    /// Random ticks, scheduled block updates, block light, snow and lightning
+    pub blocks: Duration,
+}
+
+impl TickTimings {
+    #[must_use]
+    pub fn total(&self) -> Duration {
+        self.time + self.players + self.entities + self.blocks
+    }
+}
+
+impl Add for TickTimings {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            time: self.time + rhs.time,
+            players: self.players + rhs.players,
+            entities: self.entities + rhs.entities,
+            blocks: self.blocks + rhs.blocks,
+        }
+    }
+}
+
+/// The timings of the last ticks, see [`super::World::last_tick_timings`]
+#[derive(Default)]
+pub struct TickTimingHistory {
+    ticks: VecDeque<TickTimings>,
+}
+
+impl TickTimingHistory {
+    pub fn push(&mut self, timings: TickTimings) {
+        if self.ticks.len() == HISTORY_LENGTH {
+            self.ticks.pop_front();
+        }
+        self.ticks.push_back(timings);
+    }
+
+    #[must_use]
+    pub fn last(&self) -> Option<TickTimings> {
+        self.ticks.back().copied()
+    }
+
+    /// The average of every phase over the last ticks
+    #[must_use]
+    pub fn average(&self) -> TickTimings {
+        if self.ticks.is_empty() {
+            return TickTimings::default();
+        }
+        let sum = self
+            .ticks
+            .iter()
+            .fold(TickTimings::default(), |sum, timings| sum + *timings);
+        let count = self.ticks.len() as u32;
+        TickTimings {
+            time: sum.time / count,
+            players: sum.players / count,
+            entities: sum.entities / count,
+            blocks: sum.blocks / count,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::{TickTimingHistory, TickTimings, HISTORY_LENGTH};
+
+    fn timings(millis: u64) -> TickTimings {
+        TickTimings {
+            time: Duration::from_millis(millis),
+            players: Duration::from_millis(millis * 2),
+            entities: Duration::ZERO,
+            blocks: Duration::from_millis(millis * 3),
+        }
+    }
+
+    #[test]
+    fn rolling_average() {
+        let mut history = TickTimingHistory::default();
+        assert_eq!(history.average(), TickTimings::default());
+
+        history.push(timings(10));
+        history.push(timings(20));
+        assert_eq!(history.last(), Some(timings(20)));
+        assert_eq!(history.average(), timings(15));
+
+        // Old ticks get dropped
+        for _ in 0..HISTORY_LENGTH {
+            history.push(timings(4));
+        }
+        assert_eq!(history.average(), timings(4));
+    }
+}
@@ -0,0 +1,80 @@
+use std::num::NonZeroU8;
+
+use pumpkin_config::{BasicConfiguration, ADVANCED_CONFIG};
+use pumpkin_core::{math::vector2::Vector2, Difficulty, GameMode};
+use pumpkin_registry::DimensionType;
+
+/// Settings which can be different for every world, e.g. to have a creative and a survival world on the same server.
+///
+/// By default everything is taken from the server's [`BasicConfiguration`]
+#[derive(Clone, Copy, Debug)]
+pub struct WorldConfig {
+    /// The gamemode new players get when joining the world
+    pub default_gamemode: GameMode,
+    /// The gamemode every player gets when entering the world, `None` to keep their own.
+    /// Operators with permission level 2 or higher keep their gamemode
+    pub forced_gamemode: Option<GameMode>,
+    /// Whether players get their gamemode back when leaving a world with a forced gamemode
+    pub restore_gamemode: bool,
+    /// Can players attack each other?
+    pub pvp: bool,
+    pub difficulty: Difficulty,
+    /// The maximum view distance for players in the world
+    pub view_distance: NonZeroU8,
+    /// The block column players spawn in, they get placed on top of the highest block
+    pub spawn_point: Vector2<i32>,
+    /// The yaw new players look at when spawning
+    pub spawn_angle: f32,
+    /// How many blocks around the spawn point new players get scattered
+    pub spawn_radius: u32,
+    /// How many chunks around the spawn point are kept loaded, `0` to not keep any loaded
+    pub spawn_chunk_radius: u8,
+    /// Whether the debug screen of players hides coordinates and other details
+    pub reduced_debug_info: bool,
+    /// Whether mined ores and killed mobs drop experience orbs
+    pub drop_experience: bool,
+    /// Whether players who die respawn right away without a death screen, see [`pumpkin_config::DeathConfig::immediate_respawn`]
+    pub immediate_respawn: bool,
+    /// The dimension players who die in the world respawn in, `None` to respawn in this world.
+    /// See [`WorldConfig::respawn_dimension_of`] for the Vanilla default
+    pub respawn_dimension: Option<DimensionType>,
+    /// Whether blocks hidden in stone are sent as stone, see [`pumpkin_config::AntiXrayConfig`]
+    pub anti_xray: bool,
+}
+
+impl WorldConfig {
+    /// Like Vanilla, players dying in the Nether or the End respawn in the Overworld
+    #[must_use]
+    pub const fn respawn_dimension_of(dimension: DimensionType) -> Option<DimensionType> {
+        match dimension {
+            DimensionType::TheNether | DimensionType::TheEnd => Some(DimensionType::Overworld),
+            DimensionType::Overworld | DimensionType::OverworldCaves => None,
+        }
+    }
+}
+
+impl From<&BasicConfiguration> for WorldConfig {
+    fn from(config: &BasicConfiguration) -> Self {
+        let default_gamemode = match config.default_gamemode {
+            GameMode::Undefined => GameMode::Survival,
+            gamemode => gamemode,
+        };
+        Self {
+            default_gamemode,
+            forced_gamemode: config.force_gamemode.then_some(default_gamemode),
+            restore_gamemode: true,
+            pvp: ADVANCED_CONFIG.pvp.enabled,
+            difficulty: config.default_difficulty,
+            view_distance: config.view_distance,
+            spawn_point: Vector2::new(10, 10),
+            spawn_angle: 0.0,
+            spawn_radius: config.spawn_radius,
+            spawn_chunk_radius: config.spawn_chunk_radius,
+            reduced_debug_info: config.reduced_debug_info,
+            drop_experience: ADVANCED_CONFIG.experience.drop_orbs,
+            immediate_respawn: ADVANCED_CONFIG.death.immediate_respawn,
+            respawn_dimension: None,
+            anti_xray: ADVANCED_CONFIG.anti_xray.enabled,
+        }
+    }
+}
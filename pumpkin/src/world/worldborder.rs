@@ -1,3 +1,6 @@
+use std::time::{Duration, Instant};
+
+use pumpkin_core::math::position::WorldPosition;
 use pumpkin_protocol::client::play::{
     CInitializeWorldBorder, CSetBorderCenter, CSetBorderLerpSize, CSetBorderSize,
     CSetBorderWarningDelay, CSetBorderWarningDistance,
@@ -18,6 +21,8 @@ pub struct Worldborder {
     pub warning_time: i32,
     pub damage_per_block: f32,
     pub buffer: f32,
+    /// When the diameter started moving from `old_diameter` to `new_diameter` by the clock of the world, `None` if it is not moving
+    pub lerp_start: Option<Instant>,
 }
 
 impl Worldborder {
@@ -41,9 +46,47 @@ impl Worldborder {
             warning_time,
             damage_per_block: 0.0,
             buffer: 0.0,
+            lerp_start: None,
         }
     }
 
+    /// The diameter after moving towards `new_diameter` for the given time, `speed` is the duration of the whole move in milliseconds
+    #[allow(clippy::cast_precision_loss)]
+    fn diameter_after(&self, elapsed: Duration) -> f64 {
+        if self.speed <= 0 {
+            return self.new_diameter;
+        }
+        let progress = (elapsed.as_secs_f64() * 1000.0 / self.speed as f64).min(1.0);
+        (self.new_diameter - self.old_diameter).mul_add(progress, self.old_diameter)
+    }
+
+    /// The diameter at the time `now` of the clock of the world, somewhere between `old_diameter` and `new_diameter`
+    /// while the border moves
+    #[must_use]
+    pub fn current_diameter(&self, now: Instant) -> f64 {
+        self.lerp_start.map_or(self.new_diameter, |start| {
+            self.diameter_after(now.saturating_duration_since(start))
+        })
+    }
+
+    /// Whether the block is inside of the border at its current diameter, like Vanilla a block only
+    /// partly inside counts as inside
+    #[must_use]
+    pub fn contains_block(&self, position: &WorldPosition, now: Instant) -> bool {
+        let radius = self.current_diameter(now) / 2.0;
+        let limit = f64::from(self.portal_teleport_boundary);
+        let (min_x, max_x) = (
+            (self.center_x - radius).max(-limit),
+            (self.center_x + radius).min(limit),
+        );
+        let (min_z, max_z) = (
+            (self.center_z - radius).max(-limit),
+            (self.center_z + radius).min(limit),
+        );
+        let (x, z) = (f64::from(position.0.x), f64::from(position.0.z));
+        x + 1.0 > min_x && x < max_x && z + 1.0 > min_z && z < max_z
+    }
+
     pub async fn init_client(&self, client: &Client) {
         client
             .send_packet(&CInitializeWorldBorder::new(
@@ -69,8 +112,12 @@ impl Worldborder {
     }
 
     pub async fn set_diameter(&mut self, world: &World, diameter: f64, speed: Option<i64>) {
-        self.old_diameter = self.new_diameter;
+        // Moving again while still moving starts from where the border is right now
+        let now = world.clock.now();
+        self.old_diameter = self.current_diameter(now);
         self.new_diameter = diameter;
+        self.speed = speed.unwrap_or(0);
+        self.lerp_start = speed.map(|_| now);
 
         match speed {
             Some(speed) => {
@@ -111,3 +158,49 @@ impl Worldborder {
             .await;
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::{
+        sync::Arc,
+        time::{Duration, Instant},
+    };
+
+    use pumpkin_core::math::{position::WorldPosition, vector3::Vector3};
+
+    use crate::world::{
+        clock::{Clock, MockClock},
+        World,
+    };
+
+    use super::Worldborder;
+
+    #[test]
+    fn blocks_inside_border() {
+        let border = Worldborder::new(0.5, 0.5, 10.0, 0, 0, 0);
+        let now = Instant::now();
+        assert!(border.contains_block(&WorldPosition(Vector3::new(0, 64, 0)), now));
+        // Only partly inside, the border is at 5.5
+        assert!(border.contains_block(&WorldPosition(Vector3::new(5, 64, -4)), now));
+        assert!(!border.contains_block(&WorldPosition(Vector3::new(6, 64, 0)), now));
+        assert!(!border.contains_block(&WorldPosition(Vector3::new(0, 64, -6)), now));
+    }
+
+    #[tokio::test]
+    async fn moving_diameter() {
+        let clock = Arc::new(MockClock::new());
+        let world = World::test_world().with_clock(clock.clone());
+        let mut border = Worldborder::new(0.0, 0.0, 10.0, 0, 0, 0);
+        border.set_diameter(&world, 20.0, Some(1000)).await;
+        assert_eq!(border.speed, 1000);
+        clock.advance(Duration::from_millis(500));
+        assert!((border.current_diameter(clock.now()) - 15.0).abs() < 1e-9);
+        clock.advance(Duration::from_secs(5));
+        assert!((border.current_diameter(clock.now()) - 20.0).abs() < 1e-9);
+
+        // Without a speed the border jumps to the new diameter
+        border.set_diameter(&world, 30.0, None).await;
+        assert_eq!(border.speed, 0);
+        assert!((border.current_diameter(clock.now()) - 30.0).abs() < 1e-9);
+    }
+}